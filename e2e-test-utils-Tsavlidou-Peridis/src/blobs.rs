@@ -0,0 +1,33 @@
+// Import necessary modules and components
+use alloy_consensus::{BlobTransactionSidecar, EnvKzgSettings, SidecarBuilder, SimpleCoder};
+use alloy_eips::eip4844::FIELD_ELEMENTS_PER_BLOB;
+use rand::RngCore;
+
+/// Number of data bytes the [`SimpleCoder`] stores in a field element
+const BYTES_PER_CODED_FIELD_ELEMENT: usize = 31;
+
+/// Returns the KZG settings of the trusted setup
+///
+/// The trusted setup is only loaded on first use.
+pub fn kzg_settings() -> &'static EnvKzgSettings {
+    static SETTINGS: EnvKzgSettings = EnvKzgSettings::Default;
+    &SETTINGS
+}
+
+/// Builds a sidecar that encodes the given data, with the commitments and proofs of the blobs
+pub fn blob_sidecar(data: &[u8]) -> eyre::Result<BlobTransactionSidecar> {
+    let builder = SidecarBuilder::<SimpleCoder>::from_slice(data);
+    Ok(builder.build_with_settings(kzg_settings().get())?)
+}
+
+/// Builds a sidecar with `num_blobs` blobs of random data
+pub fn random_blob_sidecar(num_blobs: usize) -> eyre::Result<BlobTransactionSidecar> {
+    // The first field element encodes the length of the data, all others are filled with data
+    let field_elements = (num_blobs * FIELD_ELEMENTS_PER_BLOB as usize).saturating_sub(1);
+    let mut data = vec![0u8; field_elements * BYTES_PER_CODED_FIELD_ELEMENT];
+    rand::thread_rng().fill_bytes(&mut data);
+
+    let sidecar = blob_sidecar(&data)?;
+    debug_assert_eq!(sidecar.blobs.len(), num_blobs.max(1));
+    Ok(sidecar)
+}
@@ -0,0 +1,124 @@
+// Import necessary modules and components
+use jsonrpsee::http_client::{transport::HttpBackend, HttpClient}; // HTTP client for engine API
+use reth::{
+    api::EngineTypes, // Engine types of the node
+    rpc::{
+        api::EngineApiClient, // Client for the engine API
+        types::{
+            engine::{ForkchoiceState, ForkchoiceUpdated, PayloadAttributes}, // Engine API types
+            Withdrawal,
+        },
+    },
+};
+use reth_ethereum_forks::EthereumHardforks; // Activation of the ethereum hardforks
+use reth_payload_builder::PayloadId;
+use reth_primitives::{Address, B256};
+use reth_rpc_layer::AuthClientService;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Authenticated engine API client that builds requests for the hardfork that is active at the
+/// timestamp of the payload
+pub struct EngineApiTestClient<E, C> {
+    client: HttpClient<AuthClientService<HttpBackend>>, // Authenticated engine API client
+    chain_spec: Arc<C>,                                 // Activation of the hardforks
+    _marker: PhantomData<E>,                            // Engine types of the node
+}
+
+impl<E, C> EngineApiTestClient<E, C>
+where
+    E: EngineTypes<PayloadAttributes = PayloadAttributes> + 'static,
+    C: EthereumHardforks,
+{
+    /// Creates a new client, e.g. with the `engine_api_client` of a test node
+    pub fn new(client: HttpClient<AuthClientService<HttpBackend>>, chain_spec: Arc<C>) -> Self {
+        Self { client, chain_spec, _marker: PhantomData }
+    }
+
+    /// Returns the authenticated engine API client
+    pub fn client(&self) -> &HttpClient<AuthClientService<HttpBackend>> {
+        &self.client
+    }
+
+    /// Builds payload attributes that are valid at the given timestamp
+    ///
+    /// Withdrawals are only set after Shanghai, and the parent beacon block root only after
+    /// Cancun.
+    pub fn payload_attributes(
+        &self,
+        timestamp: u64,
+        suggested_fee_recipient: Address,
+        withdrawals: Vec<Withdrawal>,
+    ) -> PayloadAttributes {
+        PayloadAttributes {
+            timestamp,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient,
+            withdrawals: self
+                .chain_spec
+                .is_shanghai_active_at_timestamp(timestamp)
+                .then_some(withdrawals),
+            parent_beacon_block_root: self
+                .chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+        }
+    }
+
+    /// Sends a forkchoice update with the version of the hardfork that is active at the given
+    /// timestamp
+    ///
+    /// The timestamp is the one of the payload attributes, or the one of the new head if no payload
+    /// is built. `engine_forkchoiceUpdatedV3` is used after Cancun, `engine_forkchoiceUpdatedV2`
+    /// after Shanghai and `engine_forkchoiceUpdatedV1` before.
+    pub async fn fork_choice_updated(
+        &self,
+        state: ForkchoiceState,
+        timestamp: u64,
+        attributes: Option<PayloadAttributes>,
+    ) -> eyre::Result<ForkchoiceUpdated> {
+        if let Some(attributes) = &attributes {
+            eyre::ensure!(
+                attributes.timestamp == timestamp,
+                "payload attributes timestamp {} does not match {timestamp}",
+                attributes.timestamp
+            );
+        }
+
+        let client = &self.client;
+        let updated = if self.chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            EngineApiClient::<E>::fork_choice_updated_v3(client, state, attributes).await?
+        } else if self.chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+            EngineApiClient::<E>::fork_choice_updated_v2(client, state, attributes).await?
+        } else {
+            EngineApiClient::<E>::fork_choice_updated_v1(client, state, attributes).await?
+        };
+        Ok(updated)
+    }
+
+    /// Starts building a payload on top of the given head, returning the id of the payload
+    pub async fn build_payload(
+        &self,
+        head: B256,
+        timestamp: u64,
+        suggested_fee_recipient: Address,
+    ) -> eyre::Result<PayloadId> {
+        let state = ForkchoiceState {
+            head_block_hash: head,
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let attributes = self.payload_attributes(timestamp, suggested_fee_recipient, vec![]);
+        let updated = self.fork_choice_updated(state, timestamp, Some(attributes)).await?;
+        updated
+            .payload_id
+            .ok_or_else(|| eyre::eyre!("no payload started: {:?}", updated.payload_status))
+    }
+
+    /// Retrieves a v3 payload from the engine API
+    pub async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> eyre::Result<E::ExecutionPayloadV3> {
+        Ok(EngineApiClient::<E>::get_payload_v3(&self.client, payload_id).await?)
+    }
+}
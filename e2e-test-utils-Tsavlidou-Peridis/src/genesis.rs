@@ -0,0 +1,26 @@
+// Import necessary modules and components
+use crate::wallet::Wallet;
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_signer::Signer;
+use reth_primitives::{Address, U256};
+use std::collections::BTreeMap;
+
+/// Returns a genesis alloc that prefunds each account of the wallet with the given balance
+pub fn genesis_alloc(wallet: &Wallet, balance: U256) -> BTreeMap<Address, GenesisAccount> {
+    wallet
+        .gen()
+        .into_iter()
+        .map(|signer| (signer.address(), GenesisAccount::default().with_balance(balance)))
+        .collect()
+}
+
+/// Returns the genesis alloc of [`genesis_alloc`] as JSON, which can be used as the `alloc` field
+/// of a genesis file
+pub fn genesis_alloc_json(wallet: &Wallet, balance: U256) -> serde_json::Value {
+    serde_json::to_value(genesis_alloc(wallet, balance)).expect("genesis alloc is serializable")
+}
+
+/// Adds the accounts of the wallet with the given balance to the alloc of the genesis
+pub fn funded_genesis(genesis: Genesis, wallet: &Wallet, balance: U256) -> Genesis {
+    genesis.extend_accounts(genesis_alloc(wallet, balance))
+}
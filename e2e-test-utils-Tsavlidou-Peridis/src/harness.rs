@@ -0,0 +1,107 @@
+// Import necessary modules and components
+use crate::{
+    genesis::funded_genesis, rpc::RpcTestContext, setup, wallet::Wallet, Adapter, NodeHelperType,
+    TmpNodeAdapter,
+};
+use reth::tasks::TaskManager; // Task manager to handle async tasks
+use reth_chainspec::{ChainSpec, ChainSpecBuilder}; // Chain specification for the blockchain
+use reth_node_builder::Node; // Node type trait
+use reth_primitives::U256; // 256-bit unsigned integers for balances
+use std::sync::Arc; // Arc for thread-safe reference counting
+
+/// A network of interconnected test nodes, each with its own funded wallet.
+///
+/// Every node runs with its own temporary datadir and unused ports, and is peered with the
+/// previous node (and the last node with the first one), see [`setup`].
+pub struct NetworkHarness<N>
+where
+    N: Default + Node<TmpNodeAdapter<N>>,
+{
+    nodes: Vec<NodeHelperType<N>>, // The launched nodes
+    wallets: Vec<Wallet>,          // The wallet of each node
+    _tasks: TaskManager,           // Keeps the tasks of the nodes alive
+}
+
+impl<N> NetworkHarness<N>
+where
+    N: Default + Node<TmpNodeAdapter<N>>,
+{
+    /// Launches `num_nodes` interconnected nodes.
+    ///
+    /// The wallet of node `idx` derives its accounts from `m/44'/60'/{idx}'/0/` of the test
+    /// mnemonic, and its first account is prefunded with `balance` in the genesis.
+    pub async fn launch(
+        num_nodes: usize,           // Number of nodes to launch
+        chain_spec: Arc<ChainSpec>, // Chain specification, its genesis alloc is extended
+        is_dev: bool,               // Development mode flag
+        balance: U256,              // Balance of the account of each node
+    ) -> eyre::Result<Self> {
+        let chain_id = chain_spec.chain().into();
+        let wallets = (0..num_nodes)
+            .map(|idx| {
+                Wallet::default()
+                    .with_chain_id(chain_id)
+                    .with_derivation_path(format!("m/44'/60'/{idx}'/0/"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        // Prefund the accounts of all wallets
+        let genesis = wallets.iter().fold(chain_spec.genesis().clone(), |genesis, wallet| {
+            funded_genesis(genesis, wallet, balance)
+        });
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&chain_spec).genesis(genesis).build());
+
+        let (nodes, tasks, _) = setup::<N>(num_nodes, chain_spec, is_dev).await?;
+        Ok(Self { nodes, wallets, _tasks: tasks })
+    }
+
+    /// Returns the number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the harness has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns all nodes
+    pub fn nodes(&self) -> &[NodeHelperType<N>] {
+        &self.nodes
+    }
+
+    /// Returns all nodes mutably
+    pub fn nodes_mut(&mut self) -> &mut [NodeHelperType<N>] {
+        &mut self.nodes
+    }
+
+    /// Returns the node with the given index
+    pub fn node(&mut self, idx: usize) -> &mut NodeHelperType<N> {
+        &mut self.nodes[idx]
+    }
+
+    /// Returns the RPC handle of the node with the given index
+    pub fn rpc(&mut self, idx: usize) -> &mut RpcTestContext<Adapter<N>> {
+        &mut self.nodes[idx].rpc
+    }
+
+    /// Returns the wallet of the node with the given index
+    pub fn wallet(&mut self, idx: usize) -> &mut Wallet {
+        &mut self.wallets[idx]
+    }
+
+    /// Connects every node to every other node it is not peered with yet
+    pub async fn connect_all(&mut self) {
+        let len = self.nodes.len();
+        for i in 0..len {
+            // `setup` already connected each node to its neighbours
+            for j in (i + 2)..len {
+                if i == 0 && j + 1 == len {
+                    continue
+                }
+                let (left, right) = self.nodes.split_at_mut(j);
+                left[i].connect(&mut right[0]).await;
+            }
+        }
+    }
+}
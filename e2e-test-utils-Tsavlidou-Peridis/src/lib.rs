@@ -5,23 +5,32 @@ use reth::{
     builder::{NodeBuilder, NodeConfig, NodeHandle}, // Components to build a node
     tasks::TaskManager, // Task manager to handle async tasks
 };
-use reth_chainspec::ChainSpec; // Chain specification for the blockchain
+use reth_chainspec::{ChainSpec, ChainSpecBuilder}; // Chain specification for the blockchain
 use reth_db::{test_utils::TempDatabase, DatabaseEnv}; // Database components
 use reth_node_builder::{
     components::NodeComponentsBuilder, FullNodeTypesAdapter, Node, NodeAdapter, RethFullAdapter,
 }; // Node builder components
+use reth_primitives::U256; // 256-bit unsigned integers for balances
 use reth_provider::providers::BlockchainProvider; // Blockchain provider
 use std::sync::Arc; // Arc for thread-safe reference counting
 use tracing::{span, Level}; // Tracing for logging
 use wallet::Wallet; // Wallet module
 
 // Define modules for organizing code
+pub mod blobs;          // Module for blob sidecar fixtures
+pub mod genesis;        // Module for funded genesis allocs
+pub mod harness;        // Module for multi-node networks
+pub mod miner;          // Module for mining blocks in dev mode
 pub mod node;           // Module for test nodes
+pub mod reorg;          // Module for reorg orchestration
+pub mod scenario;       // Module for end-to-end test scenarios
+pub mod spammer;        // Module for load tests
 pub mod transaction;    // Module for transaction operations
 pub mod wallet;         // Module for wallet operations
 mod payload;            // Module for payload operations
 mod network;            // Module for network operations
 mod engine_api;         // Module for engine API operations
+pub mod engine_client;  // Module for the engine API test client
 mod rpc;                // Module for RPC operations
 mod traits;             // Module for helper traits
 
@@ -84,6 +93,25 @@ where
     Ok((nodes, tasks, Wallet::default().with_chain_id(chain_spec.chain().into())))
 }
 
+// Function to set up test nodes on a chain that prefunds the accounts of the wallet
+pub async fn setup_funded<N>(
+    num_nodes: usize,                  // Number of nodes to create
+    chain_spec: Arc<ChainSpec>,        // Chain specification, its genesis alloc is extended
+    is_dev: bool,                      // Development mode flag
+    wallet: Wallet,                    // Wallet whose accounts are prefunded
+    balance: U256,                     // Balance of each prefunded account
+) -> eyre::Result<(Vec<NodeHelperType<N>>, TaskManager, Wallet)>
+where
+    N: Default + Node<TmpNodeAdapter<N>>, // Constraints for node type
+{
+    // Add the accounts of the wallet to the genesis of the chain spec
+    let genesis = genesis::funded_genesis(chain_spec.genesis().clone(), &wallet, balance);
+    let chain_spec = Arc::new(ChainSpecBuilder::from(&chain_spec).genesis(genesis).build());
+
+    let (nodes, tasks, _) = setup::<N>(num_nodes, chain_spec.clone(), is_dev).await?;
+    Ok((nodes, tasks, wallet.with_chain_id(chain_spec.chain().into())))
+}
+
 // Type aliases for convenience
 type TmpDB = Arc<TempDatabase<DatabaseEnv>>; // Alias for a temporary database
 type TmpNodeAdapter<N> = FullNodeTypesAdapter<N, TmpDB, BlockchainProvider<TmpDB>>; // Alias for a temporary node adapter
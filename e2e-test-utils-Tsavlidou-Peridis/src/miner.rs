@@ -0,0 +1,72 @@
+// Import necessary modules and components
+use crate::{node::NodeTestContext, wallet::Wallet};
+use alloy_network::eip2718::Encodable2718;
+use alloy_signer::Signer;
+use reth::api::FullNodeComponents;
+use reth_primitives::{BlockNumber, B256, U256};
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+/// Mines blocks on demand against a node in dev mode
+///
+/// The node must be launched in dev mode without a block time, see [`setup`](crate::setup), so
+/// that it seals a block as soon as a transaction arrives. The producer mines a block by
+/// submitting a transfer from its wallet and waiting for the block to become canonical.
+pub struct DevBlockProducer {
+    wallet: Wallet, // Wallet that signs the transfers, must be funded
+}
+
+impl DevBlockProducer {
+    /// Creates a new block producer that sends its transfers from the given wallet
+    pub fn new(wallet: Wallet) -> Self {
+        Self { wallet }
+    }
+
+    /// Mines a single block, returning its number and hash
+    pub async fn mine_block<Node: FullNodeComponents>(
+        &self,
+        node: &mut NodeTestContext<Node>,
+    ) -> eyre::Result<(BlockNumber, B256)> {
+        // A transfer to itself triggers the auto-seal miner
+        let tx = self.wallet.eip1559_tx(self.wallet.inner.address(), U256::ZERO).await;
+        let tx_hash = node.rpc.inject_tx(tx.encoded_2718().into()).await?;
+
+        // Wait for the block that includes the transfer
+        while let Some(notification) = node.engine_api.canonical_stream.next().await {
+            let tip = notification.tip();
+            if tip.body.iter().any(|tx| tx.hash() == tx_hash) {
+                return Ok((tip.number, tip.hash()))
+            }
+        }
+        Err(eyre::eyre!("canonical state stream closed"))
+    }
+
+    /// Mines `n` blocks back to back, returning their numbers and hashes
+    pub async fn mine_blocks<Node: FullNodeComponents>(
+        &self,
+        node: &mut NodeTestContext<Node>,
+        n: usize,
+    ) -> eyre::Result<Vec<(BlockNumber, B256)>> {
+        let mut blocks = Vec::with_capacity(n);
+        for _ in 0..n {
+            blocks.push(self.mine_block(node).await?);
+        }
+        Ok(blocks)
+    }
+
+    /// Mines `n` blocks, one every `interval`, returning their numbers and hashes
+    pub async fn mine_blocks_every<Node: FullNodeComponents>(
+        &self,
+        node: &mut NodeTestContext<Node>,
+        n: usize,
+        interval: Duration,
+    ) -> eyre::Result<Vec<(BlockNumber, B256)>> {
+        let mut interval = tokio::time::interval(interval);
+        let mut blocks = Vec::with_capacity(n);
+        for _ in 0..n {
+            interval.tick().await;
+            blocks.push(self.mine_block(node).await?);
+        }
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,158 @@
+// Import necessary modules and components
+use crate::{node::NodeTestContext, traits::PayloadEnvelopeExt};
+use futures_util::Future;
+use reth::{
+    api::{BuiltPayload, EngineTypes, FullNodeComponents},
+    payload::PayloadTypes,
+    providers::{BlockReader, CanonStateNotification},
+    rpc::types::engine::PayloadStatusEnum,
+};
+use reth_primitives::{BlockNumber, Bytes, B256};
+use std::pin::Pin;
+use tokio_stream::StreamExt;
+
+/// The blocks of a reorg triggered by [`ReorgTestContext::reorg`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgOutcome {
+    pub reverted: Vec<(BlockNumber, B256)>, // Blocks of the canonical chain that were reverted
+    pub new_chain: Vec<(BlockNumber, B256)>, // Blocks of the fork that became canonical
+}
+
+impl ReorgOutcome {
+    /// Returns the number of reverted blocks
+    pub fn depth(&self) -> usize {
+        self.reverted.len()
+    }
+
+    /// Returns the new tip of the chain
+    pub fn new_tip(&self) -> (BlockNumber, B256) {
+        *self.new_chain.last().expect("fork chain is not empty")
+    }
+
+    /// Asserts that the fork is canonical on the node and the reverted blocks are not
+    pub fn assert_reorged<Node: FullNodeComponents>(
+        &self,
+        node: &NodeTestContext<Node>,
+    ) -> eyre::Result<()> {
+        let provider = &node.inner.provider;
+        for (number, hash) in &self.new_chain {
+            let block = provider.block_by_number(*number)?;
+            if block.map(|block| block.hash_slow()) != Some(*hash) {
+                eyre::bail!("fork block {number} is not canonical")
+            }
+        }
+        for (number, hash) in &self.reverted {
+            let block = provider.block_by_number(*number)?;
+            if block.map(|block| block.hash_slow()) == Some(*hash) {
+                eyre::bail!("reverted block {number} is still canonical")
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Helper to build competing chains and reorg a node via the engine API
+pub struct ReorgTestContext;
+
+impl ReorgTestContext {
+    /// Reorgs `node` by `depth` blocks.
+    ///
+    /// Both nodes must have the same tip. First `node` advances by `depth` blocks, then
+    /// `fork_node` builds a competing chain of `depth + 1` blocks, which is imported into `node`
+    /// with `newPayload` and made canonical with `forkchoiceUpdated`. Both chains are built
+    /// without marking their blocks safe or finalized, so the blocks above the fork point can be
+    /// reverted.
+    ///
+    /// The transactions of the two generators must not conflict, e.g. they should be sent from
+    /// different wallets if the nodes are peered.
+    pub async fn reorg<Node: FullNodeComponents>(
+        node: &mut NodeTestContext<Node>, // Node that is reorged
+        fork_node: &mut NodeTestContext<Node>, // Node that builds the competing chain
+        depth: u64, // Number of blocks that are reverted
+        tx_generator: impl Fn(u64) -> Pin<Box<dyn Future<Output = Bytes>>>, // Txs of `node`
+        fork_tx_generator: impl Fn(u64) -> Pin<Box<dyn Future<Output = Bytes>>>, // Txs of the fork
+        attributes_generator: impl Fn(u64) -> <Node::Engine as PayloadTypes>::PayloadBuilderAttributes
+            + Copy, // Payload attributes generator
+    ) -> eyre::Result<ReorgOutcome>
+    where
+        <Node::Engine as EngineTypes>::ExecutionPayloadV3:
+            From<<Node::Engine as PayloadTypes>::BuiltPayload> + PayloadEnvelopeExt,
+    {
+        // Build the canonical chain and the competing chain
+        let chain = advance_unfinalized(node, depth, tx_generator, attributes_generator).await?;
+        let fork =
+            advance_unfinalized(fork_node, depth + 1, fork_tx_generator, attributes_generator)
+                .await?;
+
+        // Import the competing chain into the node
+        for (payload, attributes) in &fork {
+            node.engine_api
+                .submit_payload(
+                    payload.clone(), // Clone payload
+                    attributes.clone(), // Clone attributes
+                    PayloadStatusEnum::Valid, // Side chain blocks are executed
+                    vec![], // No blob transactions
+                )
+                .await?;
+        }
+
+        // Make the tip of the competing chain canonical, without finalizing the reverted blocks
+        let fork_tip = fork.last().expect("fork chain is not empty").0.block();
+        node.engine_api.update_optimistic_forkchoice(fork_tip.hash()).await?;
+
+        // Expect the reorg notification
+        match node.engine_api.canonical_stream.next().await {
+            Some(CanonStateNotification::Reorg { old, new }) => {
+                if old.len() as u64 != depth {
+                    eyre::bail!("expected {depth} reverted blocks, got {}", old.len())
+                }
+                if new.tip().hash() != fork_tip.hash() {
+                    eyre::bail!("expected new tip {}, got {}", fork_tip.hash(), new.tip().hash())
+                }
+            }
+            notification => eyre::bail!("expected a reorg notification, got: {notification:?}"),
+        }
+
+        let outcome = ReorgOutcome {
+            reverted: chain.iter().map(|(p, _)| (p.block().number, p.block().hash())).collect(),
+            new_chain: fork.iter().map(|(p, _)| (p.block().number, p.block().hash())).collect(),
+        };
+        outcome.assert_reorged(node)?;
+        Ok(outcome)
+    }
+}
+
+/// Advances `node` by `length` blocks like [`NodeTestContext::advance`], but keeps the safe and
+/// finalized blocks where they are, so the new blocks can be reorged.
+async fn advance_unfinalized<Node: FullNodeComponents>(
+    node: &mut NodeTestContext<Node>,
+    length: u64,
+    tx_generator: impl Fn(u64) -> Pin<Box<dyn Future<Output = Bytes>>>,
+    attributes_generator: impl Fn(u64) -> <Node::Engine as PayloadTypes>::PayloadBuilderAttributes
+        + Copy,
+) -> eyre::Result<
+    Vec<(
+        <Node::Engine as PayloadTypes>::BuiltPayload,
+        <Node::Engine as PayloadTypes>::PayloadBuilderAttributes,
+    )>,
+>
+where
+    <Node::Engine as EngineTypes>::ExecutionPayloadV3:
+        From<<Node::Engine as PayloadTypes>::BuiltPayload> + PayloadEnvelopeExt,
+{
+    let mut chain = Vec::with_capacity(length as usize);
+    for i in 0..length {
+        let raw_tx = tx_generator(i).await;
+        let tx_hash = node.rpc.inject_tx(raw_tx).await?;
+        let (payload, attributes) = node.new_payload(attributes_generator).await?;
+        let block_hash = node
+            .engine_api
+            .submit_payload(payload.clone(), attributes.clone(), PayloadStatusEnum::Valid, vec![])
+            .await?;
+        // Zero safe and finalized hashes leave the safe and finalized blocks unchanged
+        node.engine_api.update_optimistic_forkchoice(block_hash).await?;
+        node.assert_new_block(tx_hash, block_hash, payload.block().number).await?;
+        chain.push((payload, attributes));
+    }
+    Ok(chain)
+}
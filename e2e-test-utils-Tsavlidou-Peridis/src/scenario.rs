@@ -0,0 +1,123 @@
+// Import necessary modules and components
+use crate::{node::NodeTestContext, traits::PayloadEnvelopeExt};
+use alloy_consensus::{TxEip4844Variant, TxEnvelope};
+use alloy_network::eip2718::Encodable2718;
+use reth::{
+    api::{EngineTypes, FullNodeComponents},
+    payload::PayloadTypes,
+    providers::{StateProviderFactory, TransactionsProvider},
+};
+use reth_primitives::{Address, B256, U256};
+
+/// A step of a [`Scenario`]
+#[derive(Debug, Clone)]
+enum Step {
+    SendTx(TxEnvelope),           // Submit a transaction via RPC
+    AdvanceBlock,                 // Build and commit the next block
+    AssertBalance(Address, U256), // Expect the balance of an account
+    AssertNonce(Address, u64),    // Expect the nonce of an account
+    AssertIncluded,               // Expect all sent transactions to be included
+}
+
+/// A sequence of steps that drives a node and asserts its state, e.g.
+///
+/// ```ignore
+/// Scenario::new()
+///     .send_tx(wallet.eip1559_tx(to, value).await)
+///     .advance_block()
+///     .assert_included()
+///     .assert_balance(to, value)
+///     .run(&mut node, eth_payload_attributes)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scenario {
+    steps: Vec<Step>, // Steps in the order they are run
+}
+
+impl Scenario {
+    /// Creates a new scenario without any steps
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submits the transaction to the node via RPC
+    pub fn send_tx(mut self, tx: TxEnvelope) -> Self {
+        self.steps.push(Step::SendTx(tx));
+        self
+    }
+
+    /// Builds the next block with the transactions in the pool and makes it canonical
+    pub fn advance_block(mut self) -> Self {
+        self.steps.push(Step::AdvanceBlock);
+        self
+    }
+
+    /// Asserts the balance of the account in the latest state
+    pub fn assert_balance(mut self, address: Address, balance: U256) -> Self {
+        self.steps.push(Step::AssertBalance(address, balance));
+        self
+    }
+
+    /// Asserts the nonce of the account in the latest state
+    pub fn assert_nonce(mut self, address: Address, nonce: u64) -> Self {
+        self.steps.push(Step::AssertNonce(address, nonce));
+        self
+    }
+
+    /// Asserts that all transactions sent so far are included in the chain
+    pub fn assert_included(mut self) -> Self {
+        self.steps.push(Step::AssertIncluded);
+        self
+    }
+
+    /// Runs all steps against the node
+    pub async fn run<Node: FullNodeComponents>(
+        self,
+        node: &mut NodeTestContext<Node>, // Node the scenario is run against
+        attributes_generator: impl Fn(u64) -> <Node::Engine as PayloadTypes>::PayloadBuilderAttributes
+            + Copy, // Payload attributes generator
+    ) -> eyre::Result<()>
+    where
+        <Node::Engine as EngineTypes>::ExecutionPayloadV3:
+            From<<Node::Engine as PayloadTypes>::BuiltPayload> + PayloadEnvelopeExt,
+    {
+        let mut sent: Vec<B256> = Vec::new(); // Hashes of all sent transactions
+        let mut versioned_hashes: Vec<B256> = Vec::new(); // Blobs of the next block
+
+        for (idx, step) in self.steps.into_iter().enumerate() {
+            match step {
+                Step::SendTx(tx) => {
+                    // Remember the blobs, they're required to submit the next block
+                    if let TxEnvelope::Eip4844(signed) = &tx {
+                        if let TxEip4844Variant::TxEip4844WithSidecar(tx) = signed.tx() {
+                            versioned_hashes.extend(tx.sidecar.versioned_hashes());
+                        }
+                    }
+                    sent.push(node.rpc.inject_tx(tx.encoded_2718().into()).await?);
+                }
+                Step::AdvanceBlock => {
+                    let versioned_hashes = std::mem::take(&mut versioned_hashes);
+                    node.advance_block(versioned_hashes, attributes_generator).await?;
+                }
+                Step::AssertBalance(address, expected) => {
+                    let state = node.inner.provider.latest()?;
+                    let balance = state.account_balance(address)?.unwrap_or_default();
+                    assert_eq!(balance, expected, "step {idx}: unexpected balance of {address}");
+                }
+                Step::AssertNonce(address, expected) => {
+                    let state = node.inner.provider.latest()?;
+                    let nonce = state.account_nonce(address)?.unwrap_or_default();
+                    assert_eq!(nonce, expected, "step {idx}: unexpected nonce of {address}");
+                }
+                Step::AssertIncluded => {
+                    for hash in &sent {
+                        let tx = node.inner.provider.transaction_by_hash(*hash)?;
+                        assert!(tx.is_some(), "step {idx}: transaction {hash} is not included");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
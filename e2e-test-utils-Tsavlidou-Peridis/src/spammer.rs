@@ -0,0 +1,167 @@
+// Import necessary modules and components
+use crate::{node::NodeTestContext, wallet::Wallet};
+use alloy_network::{eip2718::Encodable2718, TransactionBuilder};
+use alloy_rpc_types::TransactionRequest;
+use alloy_signer::Signer;
+use rand::Rng;
+use reth::{api::FullNodeComponents, providers::TransactionsProvider};
+use reth_primitives::{Address, B256, U256};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Gas limit of the spammed transfers
+const TRANSFER_GAS: u128 = 21_000;
+
+/// Base fee per gas of the spammed transactions (20 gwei)
+const BASE_FEE_PER_GAS: u128 = 20_000_000_000;
+
+/// Signs and submits transactions to a node at a fixed rate, e.g. for soak tests of the pool.
+///
+/// The spammer cycles through legacy, EIP-2930 and EIP-1559 transfers from the first account of
+/// the wallet and tracks how long it takes until they are included in a block.
+pub struct TxSpammer {
+    wallet: Wallet,                // Wallet that signs the transactions
+    interval: Duration,            // Time between two transactions
+    gas_jitter: f64,               // Max relative increase of the fees of a transaction
+    inclusion_timeout: Duration,   // Time to wait for inclusion after spamming stopped
+}
+
+impl TxSpammer {
+    /// Creates a new spammer that submits `txs_per_second` transactions per second
+    pub fn new(wallet: Wallet, txs_per_second: u64) -> Self {
+        Self {
+            wallet,
+            interval: Duration::from_secs_f64(1.0 / txs_per_second.max(1) as f64),
+            gas_jitter: 0.0,
+            inclusion_timeout: Duration::from_secs(12),
+        }
+    }
+
+    /// Sets the max relative increase of the fees, e.g. `0.5` raises the fees of each transaction
+    /// by a random factor between 1 and 1.5
+    ///
+    /// Negative and non-finite values disable the jitter
+    pub fn with_gas_jitter(mut self, gas_jitter: f64) -> Self {
+        self.gas_jitter = if gas_jitter.is_finite() { gas_jitter.max(0.0) } else { 0.0 };
+        self
+    }
+
+    /// Sets the time to wait for the inclusion of submitted transactions after spamming stopped
+    pub fn with_inclusion_timeout(mut self, inclusion_timeout: Duration) -> Self {
+        self.inclusion_timeout = inclusion_timeout;
+        self
+    }
+
+    /// Returns the wallet of the spammer
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    /// Builds the next transaction, the type is determined by the number of the transaction
+    fn next_tx(&self, idx: usize) -> TransactionRequest {
+        let fee = BASE_FEE_PER_GAS +
+            (BASE_FEE_PER_GAS as f64 * rand::thread_rng().gen_range(0.0..=self.gas_jitter)) as u128;
+        let tx = TransactionRequest::default()
+            .with_from(self.wallet.inner.address())
+            .with_to(Address::random())
+            .with_value(U256::from(1))
+            .with_gas_limit(TRANSFER_GAS);
+
+        match idx % 3 {
+            0 => tx.with_gas_price(fee),
+            1 => tx.with_gas_price(fee).with_access_list(Default::default()),
+            _ => tx.with_max_fee_per_gas(fee).with_max_priority_fee_per_gas(fee),
+        }
+    }
+
+    /// Spams the node for the given duration and waits for the inclusion of the submitted
+    /// transactions.
+    ///
+    /// The node must produce blocks on its own, e.g. in dev mode.
+    pub async fn run<Node: FullNodeComponents>(
+        &mut self,
+        node: &mut NodeTestContext<Node>,
+        duration: Duration,
+    ) -> eyre::Result<SpamReport> {
+        let mut report = SpamReport::default();
+        let mut pending: Vec<(B256, Instant)> = Vec::new(); // Submitted but not yet included
+        let mut interval = tokio::time::interval(self.interval);
+
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            interval.tick().await;
+
+            let nonce = self.wallet.next_nonce();
+            let tx = self.next_tx(report.submitted + report.rejected).with_nonce(nonce);
+            let raw_tx = self.wallet.sign(tx).await.encoded_2718().into();
+            match node.rpc.inject_tx(raw_tx).await {
+                Ok(hash) => {
+                    report.submitted += 1;
+                    pending.push((hash, Instant::now()));
+                }
+                Err(err) => {
+                    // the nonce was not used, so it can be used by the next transaction
+                    debug!(%err, "spammed transaction rejected");
+                    self.wallet.release_nonce(nonce);
+                    report.rejected += 1;
+                }
+            }
+
+            report.record_included(node, &mut pending)?;
+        }
+
+        // Wait for the remaining transactions to be included
+        let deadline = Instant::now() + self.inclusion_timeout;
+        while !pending.is_empty() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            report.record_included(node, &mut pending)?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// The outcome of a [`TxSpammer`] run
+#[derive(Debug, Clone, Default)]
+pub struct SpamReport {
+    pub submitted: usize, // Number of transactions accepted by the node
+    pub rejected: usize, // Number of transactions rejected by the node
+    pub latencies: Vec<Duration>, // Inclusion latency of each included transaction
+}
+
+impl SpamReport {
+    /// Records the latency of all pending transactions that are included by now
+    fn record_included<Node: FullNodeComponents>(
+        &mut self,
+        node: &NodeTestContext<Node>,
+        pending: &mut Vec<(B256, Instant)>,
+    ) -> eyre::Result<()> {
+        let mut idx = 0;
+        while idx < pending.len() {
+            let (hash, submitted_at) = pending[idx];
+            if node.inner.provider.transaction_by_hash(hash)?.is_some() {
+                self.latencies.push(submitted_at.elapsed());
+                pending.swap_remove(idx);
+            } else {
+                idx += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of included transactions
+    pub fn included(&self) -> usize {
+        self.latencies.len()
+    }
+
+    /// Returns the mean inclusion latency
+    pub fn mean_latency(&self) -> Option<Duration> {
+        let total = self.latencies.iter().sum::<Duration>();
+        (!self.latencies.is_empty()).then(|| total / self.latencies.len() as u32)
+    }
+
+    /// Returns the max inclusion latency
+    pub fn max_latency(&self) -> Option<Duration> {
+        self.latencies.iter().max().copied()
+    }
+}
@@ -1,8 +1,6 @@
 // Import necessary modules and components
-use alloy_consensus::{
-    BlobTransactionSidecar, EnvKzgSettings, SidecarBuilder, SimpleCoder, TxEip4844Variant,
-    TxEnvelope,
-};
+use crate::blobs::blob_sidecar;
+use alloy_consensus::{EnvKzgSettings, TxEip4844Variant, TxEnvelope};
 use alloy_network::{eip2718::Encodable2718, EthereumWallet, TransactionBuilder};
 use alloy_rpc_types::{TransactionInput, TransactionRequest};
 use alloy_signer_local::PrivateKeySigner;
@@ -33,13 +31,8 @@ impl TransactionTestContext {
     ) -> eyre::Result<TxEnvelope> {
         let mut tx = tx(chain_id, None, 0); // Create a transaction
 
-        // Build the sidecar with a dummy blob
-        let mut builder = SidecarBuilder::<SimpleCoder>::new();
-        builder.ingest(b"dummy blob");
-        let sidecar: BlobTransactionSidecar = builder.build()?;
-
-        // Set the sidecar and max fee per blob gas in the transaction
-        tx.set_blob_sidecar(sidecar);
+        // Set the dummy sidecar and max fee per blob gas in the transaction
+        tx.set_blob_sidecar(blob_sidecar(b"dummy blob")?);
         tx.set_max_fee_per_blob_gas(15e9 as u128);
 
         // Sign the transaction and return it
@@ -1,20 +1,104 @@
-use alloy_signer::Signer;
+use crate::{blobs::blob_sidecar, transaction::TransactionTestContext};
+use alloy_consensus::{BlobTransactionSidecar, TxEnvelope};
+use alloy_dyn_abi::TypedData;
+use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
+use alloy_network::TransactionBuilder;
+use alloy_rpc_types::TransactionRequest;
+use alloy_signer::{Signature, Signer};
 use alloy_signer_local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
+use alloy_sol_types::{Eip712Domain, SolStruct};
+use reth_primitives::{keccak256, Address, B256, U256};
+use reth_provider::StateProviderFactory;
+use std::{
+    ops::Range,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Gas limit of the transactions built by the wallet.
+const GAS_LIMIT: u128 = 210_000;
+
+/// Fee per gas of the transactions built by the wallet (20 gwei).
+const FEE_PER_GAS: u128 = 20_000_000_000;
+
+/// Max fee per blob gas of the blob transactions built by the wallet (15 gwei).
+const FEE_PER_BLOB_GAS: u128 = 15_000_000_000;
 
 /// Represents a wallet with a private key and related information.
+///
+/// Clones of the wallet share the nonce, so transactions can be signed from multiple tasks.
+#[derive(Clone)]
 pub struct Wallet {
     pub inner: PrivateKeySigner,
-    pub inner_nonce: u64,
+    inner_nonce: Arc<AtomicU64>,
     pub chain_id: u64,
     amount: usize,
     derivation_path: Option<String>,
+    /// The mnemonic the accounts are derived from, `None` for a wallet with an imported key.
+    mnemonic: Option<String>,
 }
 
 impl Wallet {
     /// Creates a new wallet with a specified amount using a predefined mnemonic.
     pub fn new(amount: usize) -> Self {
-        let inner = MnemonicBuilder::<English>::default().phrase(TEST_MNEMONIC).build().unwrap();
-        Self { inner, chain_id: 1, amount, derivation_path: None, inner_nonce: 0 }
+        Self::new_with_mnemonic(TEST_MNEMONIC).unwrap().with_amount(amount)
+    }
+
+    /// Creates a new wallet with one account derived from the given mnemonic.
+    pub fn new_with_mnemonic(phrase: impl Into<String>) -> eyre::Result<Self> {
+        let phrase = phrase.into();
+        let inner = MnemonicBuilder::<English>::default().phrase(phrase.as_str()).build()?;
+        Ok(Self {
+            inner,
+            chain_id: 1,
+            amount: 1,
+            derivation_path: None,
+            mnemonic: Some(phrase),
+            inner_nonce: Default::default(),
+        })
+    }
+
+    /// Creates a new wallet with the key of the given encrypted keystore file.
+    ///
+    /// The wallet holds a single account, so [`Self::gen`] only returns this key.
+    pub fn from_keystore(path: impl AsRef<Path>, password: impl AsRef<[u8]>) -> eyre::Result<Self> {
+        let inner = PrivateKeySigner::decrypt_keystore(path, password)?;
+        Ok(Self {
+            inner,
+            chain_id: 1,
+            amount: 1,
+            derivation_path: None,
+            mnemonic: None,
+            inner_nonce: Default::default(),
+        })
+    }
+
+    /// Sets the number of accounts that are derived from the mnemonic.
+    pub fn with_amount(mut self, amount: usize) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Sets the derivation path the accounts are derived with, e.g. `m/44'/60'/0'/0/`.
+    ///
+    /// The index of the account is appended to the path.
+    pub fn with_derivation_path(
+        mut self,
+        derivation_path: impl Into<String>,
+    ) -> eyre::Result<Self> {
+        let derivation_path = derivation_path.into();
+        if let Some(phrase) = &self.mnemonic {
+            // the first account is the inner signer
+            self.inner = MnemonicBuilder::<English>::default()
+                .phrase(phrase.as_str())
+                .derivation_path(format!("{derivation_path}0"))?
+                .build()?;
+        }
+        self.derivation_path = Some(derivation_path);
+        Ok(self)
     }
 
     /// Sets the chain ID for the wallet.
@@ -30,7 +114,11 @@ impl Wallet {
 
     /// Generates a vector of wallets based on the amount.
     pub fn gen(&self) -> Vec<PrivateKeySigner> {
-        let builder = MnemonicBuilder::<English>::default().phrase(TEST_MNEMONIC);
+        let Some(phrase) = &self.mnemonic else {
+            // an imported key can't derive other accounts
+            return vec![self.inner.clone().with_chain_id(Some(self.chain_id))]
+        };
+        let builder = MnemonicBuilder::<English>::default().phrase(phrase.as_str());
         let derivation_path = self.get_derivation_path();
 
         let mut wallets = Vec::with_capacity(self.amount);
@@ -42,6 +130,154 @@ impl Wallet {
         }
         wallets
     }
+
+    /// Returns the nonce the next transaction is signed with.
+    pub fn nonce(&self) -> u64 {
+        self.inner_nonce.load(Ordering::SeqCst)
+    }
+
+    /// Sets the nonce the next transaction is signed with.
+    pub fn set_nonce(&self, nonce: u64) {
+        self.inner_nonce.store(nonce, Ordering::SeqCst)
+    }
+
+    /// Returns the nonce for the next transaction and increments the nonce of the wallet.
+    pub fn next_nonce(&self) -> u64 {
+        self.inner_nonce.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Reserves `n` consecutive nonces, which are not handed out again.
+    pub fn reserve_nonces(&self, n: u64) -> Range<u64> {
+        let start = self.inner_nonce.fetch_add(n, Ordering::SeqCst);
+        start..start + n
+    }
+
+    /// Hands out the given nonce again, e.g. if the transaction was rejected.
+    ///
+    /// This only succeeds if the nonce is the last one that was handed out, otherwise it would
+    /// leave a nonce gap.
+    pub fn release_nonce(&self, nonce: u64) -> bool {
+        self.inner_nonce
+            .compare_exchange(nonce + 1, nonce, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Sets the nonce to the nonce of the first account in the latest state of the chain.
+    ///
+    /// Note: this ignores transactions that are still pending.
+    pub fn resync_nonce<P: StateProviderFactory>(&self, provider: &P) -> eyre::Result<u64> {
+        let nonce = provider.latest()?.account_nonce(self.inner.address())?.unwrap_or_default();
+        self.set_nonce(nonce);
+        Ok(nonce)
+    }
+
+    /// Returns a value transfer from the wallet, without any fees set.
+    fn transfer(&self, to: Address, value: U256) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_from(self.inner.address())
+            .with_to(to)
+            .with_value(value)
+            .with_gas_limit(GAS_LIMIT)
+    }
+
+    /// Sets the chain ID of the transaction and signs it with the wallet.
+    ///
+    /// If the transaction has no nonce, the next nonce of the wallet is used. The transaction type
+    /// is determined by the fields that are set on the request.
+    pub async fn sign(&self, tx: TransactionRequest) -> TxEnvelope {
+        let nonce = tx.nonce.unwrap_or_else(|| self.next_nonce());
+        let tx = tx.with_nonce(nonce).with_chain_id(self.chain_id);
+        TransactionTestContext::sign_tx(self.inner.clone(), tx).await
+    }
+
+    /// Builds and signs a legacy transfer.
+    pub async fn legacy_tx(&self, to: Address, value: U256) -> TxEnvelope {
+        let tx = self.transfer(to, value).with_gas_price(FEE_PER_GAS);
+        self.sign(tx).await
+    }
+
+    /// Builds and signs an EIP-2930 transfer with the given access list.
+    pub async fn eip2930_tx(
+        &self,
+        to: Address,
+        value: U256,
+        access_list: AccessList,
+    ) -> TxEnvelope {
+        let tx = self.transfer(to, value).with_gas_price(FEE_PER_GAS).with_access_list(access_list);
+        self.sign(tx).await
+    }
+
+    /// Builds and signs an EIP-1559 transfer.
+    pub async fn eip1559_tx(&self, to: Address, value: U256) -> TxEnvelope {
+        let tx = self
+            .transfer(to, value)
+            .with_max_fee_per_gas(FEE_PER_GAS)
+            .with_max_priority_fee_per_gas(FEE_PER_GAS);
+        self.sign(tx).await
+    }
+
+    /// Builds and signs an EIP-4844 transaction with a dummy blob sidecar.
+    pub async fn eip4844_tx(&self, to: Address) -> eyre::Result<TxEnvelope> {
+        Ok(self.blob_tx(to, blob_sidecar(b"dummy blob")?).await)
+    }
+
+    /// Builds and signs an EIP-4844 transaction with the given blob sidecar, see
+    /// [`random_blob_sidecar`](crate::blobs::random_blob_sidecar).
+    pub async fn blob_tx(&self, to: Address, sidecar: BlobTransactionSidecar) -> TxEnvelope {
+        let mut tx = self
+            .transfer(to, U256::ZERO)
+            .with_max_fee_per_gas(FEE_PER_GAS)
+            .with_max_priority_fee_per_gas(FEE_PER_GAS)
+            .with_max_fee_per_blob_gas(FEE_PER_BLOB_GAS);
+        tx.set_blob_sidecar(sidecar);
+        self.sign(tx).await
+    }
+
+    /// Builds and signs an EIP-7702 transaction with the given authorizations.
+    pub async fn eip7702_tx(
+        &self,
+        to: Address,
+        authorization_list: Vec<SignedAuthorization>,
+    ) -> TxEnvelope {
+        let mut tx = self
+            .transfer(to, U256::ZERO)
+            .with_max_fee_per_gas(FEE_PER_GAS)
+            .with_max_priority_fee_per_gas(FEE_PER_GAS);
+        tx.authorization_list = Some(authorization_list);
+        self.sign(tx).await
+    }
+
+    /// Signs the message with the EIP-191 prefix, like the `personal_sign` RPC method.
+    ///
+    /// The signature can be checked against [`Self::personal_sign_hash`] with `ecrecover`.
+    pub async fn personal_sign(&self, message: impl AsRef<[u8]>) -> eyre::Result<Signature> {
+        Ok(self.inner.sign_message(message.as_ref()).await?)
+    }
+
+    /// Returns the hash that is signed by [`Self::personal_sign`].
+    pub fn personal_sign_hash(message: impl AsRef<[u8]>) -> B256 {
+        let message = message.as_ref();
+        let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+        keccak256([prefix.as_bytes(), message].concat())
+    }
+
+    /// Signs the EIP-712 hash of the typed struct in the given domain.
+    pub async fn sign_typed_data<T>(
+        &self,
+        payload: &T,
+        domain: &Eip712Domain,
+    ) -> eyre::Result<Signature>
+    where
+        T: SolStruct + Send + Sync,
+    {
+        Ok(self.inner.sign_typed_data(payload, domain).await?)
+    }
+
+    /// Signs the EIP-712 hash of the given JSON typed data, like the `eth_signTypedData_v4` RPC
+    /// method.
+    pub async fn sign_typed_data_json(&self, typed_data: &TypedData) -> eyre::Result<Signature> {
+        Ok(self.inner.sign_dynamic_typed_data(typed_data).await?)
+    }
 }
 
 /// A predefined mnemonic for testing.
@@ -1,12 +1,12 @@
 #[cfg(not(feature = "std"))]
 use alloc::{
-    collections::BTreeMap, /// Import BTreeMap from alloc crate for collections
-    format,                /// Import format from alloc crate for string formatting
-    string::{String, ToString}, /// Import String and ToString trait from alloc crate for string operations
-    vec::Vec,              /// Import Vec from alloc crate for dynamic arrays
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
 };
 
-use crate::{hardforks::Hardforks, ForkCondition}; /// Import Hardforks trait and ForkCondition enum from local crate
+use crate::{hardforks::Hardforks, ForkCondition};
 
 /// A container to pretty-print a hardfork.
 ///
@@ -14,6 +14,8 @@ use crate::{hardforks::Hardforks, ForkCondition}; /// Import Hardforks trait and
 ///
 /// - Block and timestamp based forks are formatted in the same manner (`{name} <({eip})>
 ///   @{condition}`)
+/// - Forks based on both a block and a timestamp are formatted as `{name} <({eip})> @{block} and
+///   @{timestamp}`
 /// - TTD based forks are formatted separately as `{name} <({eip})> @{ttd} (network is <not> known
 ///   to be merged)`
 ///
@@ -32,14 +34,17 @@ struct DisplayFork {
 impl core::fmt::Display for DisplayFork {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let name_with_eip = if let Some(eip) = &self.eip {
-            format!("{} ({})", self.name, eip) /// Format name with EIP if present
+            format!("{} ({})", self.name, eip)
         } else {
-            self.name.clone() /// Otherwise, clone the name
+            self.name.clone()
         };
 
         match self.activated_at {
             ForkCondition::Block(at) | ForkCondition::Timestamp(at) => {
-                write!(f, "{name_with_eip:32} @{at}")?; /// Format block or timestamp based fork
+                write!(f, "{name_with_eip:32} @{at}")?;
+            }
+            ForkCondition::BlockAndTimestamp { block, timestamp } => {
+                write!(f, "{name_with_eip:32} @{block} and @{timestamp}")?;
             }
             ForkCondition::TTD { fork_block, total_difficulty } => {
                 write!(
@@ -112,12 +117,12 @@ impl core::fmt::Display for DisplayHardforks {
             next_is_empty: bool,
             f: &mut core::fmt::Formatter<'_>,
         ) -> core::fmt::Result {
-            writeln!(f, "{header}:")?; /// Write header to output
-            let mut iter = forks.iter().peekable(); /// Create iterator over forks with peeking capability
+            writeln!(f, "{header}:")?;
+            let mut iter = forks.iter().peekable();
             while let Some(fork) = iter.next() {
-                write!(f, "- {fork}")?; /// Write each fork with bullet point
+                write!(f, "- {fork}")?;
                 if !next_is_empty || iter.peek().is_some() {
-                    writeln!(f)?; /// Write newline if not last item or next section is not empty
+                    writeln!(f)?;
                 }
             }
             Ok(())
@@ -148,30 +153,30 @@ impl core::fmt::Display for DisplayHardforks {
 impl DisplayHardforks {
     /// Creates a new [`DisplayHardforks`] from an iterator of hardforks.
     pub fn new<H: Hardforks>(hardforks: &H, known_paris_block: Option<u64>) -> Self {
-        let mut pre_merge = Vec::new(); /// Initialize vector for pre-merge hardforks
-        let mut with_merge = Vec::new(); /// Initialize vector for merge hardforks
-        let mut post_merge = Vec::new(); ////// Initialize vector for post-merge hardforks
+        let mut pre_merge = Vec::new();
+        let mut with_merge = Vec::new();
+        let mut post_merge = Vec::new();
 
         for (fork, condition) in hardforks.forks_iter() {
             let mut display_fork =
-                DisplayFork { name: fork.name().to_string(), activated_at: condition, eip: None }; /// Create DisplayFork instance
+                DisplayFork { name: fork.name().to_string(), activated_at: condition, eip: None };
 
             match condition {
                 ForkCondition::Block(_) => {
-                    pre_merge.push(display_fork); /// Push block-based fork to pre-merge vector
+                    pre_merge.push(display_fork);
                 }
                 ForkCondition::TTD { total_difficulty, .. } => {
                     display_fork.activated_at =
                         ForkCondition::TTD { fork_block: known_paris_block, total_difficulty };
-                    with_merge.push(display_fork); /// Push TTD-based fork to merge vector
+                    with_merge.push(display_fork);
                 }
-                ForkCondition::Timestamp(_) => {
-                    post_merge.push(display_fork); /// Push timestamp-based fork to post-merge vector
+                ForkCondition::Timestamp(_) | ForkCondition::BlockAndTimestamp { .. } => {
+                    post_merge.push(display_fork);
                 }
-                ForkCondition::Never => continue, /// Skip Never variant
+                ForkCondition::Never => continue,
             }
         }
 
-        Self { pre_merge, with_merge, post_merge } /// Return initialized DisplayHardForks instance
+        Self { pre_merge, with_merge, post_merge }
     }
 }
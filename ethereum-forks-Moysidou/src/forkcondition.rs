@@ -1,9 +1,15 @@
 use crate::Head;
 use alloy_primitives::{BlockNumber, U256};
+#[cfg(any(test, feature = "arbitrary"))]
+use arbitrary::Arbitrary;
+use core::{fmt, str::FromStr};
+#[cfg(any(test, feature = "arbitrary"))]
+use proptest_derive::Arbitrary as PropTestArbitrary;
 
 /// The condition at which a fork is activated.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(PropTestArbitrary, Arbitrary))]
 pub enum ForkCondition {
     /// The fork is activated after a certain block.
     Block(BlockNumber),
@@ -21,15 +27,23 @@ pub enum ForkCondition {
     },
     /// The fork is activated after a specific timestamp.
     Timestamp(u64),
+    /// The fork is activated once both a certain block and a specific timestamp are reached.
+    BlockAndTimestamp {
+        /// The block number from which the fork can be activated.
+        block: BlockNumber,
+        /// The timestamp from which the fork can be activated.
+        timestamp: u64,
+    },
     /// The fork is never activated
     #[default]
     Never,
 }
 
 impl ForkCondition {
-    /// Returns true if the fork condition is timestamp based.
+    /// Returns true if the fork condition is timestamp based, which includes
+    /// [`Self::BlockAndTimestamp`] conditions.
     pub const fn is_timestamp(&self) -> bool {
-        matches!(self, Self::Timestamp(_))
+        matches!(self, Self::Timestamp(_) | Self::BlockAndTimestamp { .. })
     }
 
     /// Checks whether the fork condition is satisfied at the given block.
@@ -63,6 +77,18 @@ impl ForkCondition {
             if ttd.saturating_sub(difficulty) >= *total_difficulty)
     }
 
+    /// Checks whether the total difficulty of a block reached the terminal total difficulty of the
+    /// fork condition, i.e. whether the block is the terminal proof-of-work block or a descendant
+    /// of it.
+    ///
+    /// Unlike [`Self::active_at_ttd`], this does not tell whether the fork is active _at_ the
+    /// block, since the terminal block itself is still a proof-of-work block.
+    ///
+    /// This will return false for any condition that is not TTD-based.
+    pub fn ttd_reached(&self, total_difficulty: U256) -> bool {
+        matches!(self, Self::TTD { total_difficulty: ttd, .. } if total_difficulty >= *ttd)
+    }
+
     /// Checks whether the fork condition is satisfied at the given timestamp.
     ///
     /// This will return false for any condition that is not timestamp-based.
@@ -70,6 +96,22 @@ impl ForkCondition {
         matches!(self, Self::Timestamp(time) if timestamp >= *time)
     }
 
+    /// Checks whether the fork condition is satisfied at the given block and timestamp.
+    ///
+    /// Unlike [`Self::active_at_block`] and [`Self::active_at_timestamp`], this also handles
+    /// [`Self::BlockAndTimestamp`] conditions, which are only satisfied once both the block and the
+    /// timestamp are reached.
+    ///
+    /// This will return false for TTD conditions whose activation block is not known.
+    pub const fn active_at(&self, block: BlockNumber, timestamp: u64) -> bool {
+        match self {
+            Self::BlockAndTimestamp { block: fork_block, timestamp: fork_timestamp } => {
+                block >= *fork_block && timestamp >= *fork_timestamp
+            }
+            _ => self.active_at_block(block) || self.active_at_timestamp(timestamp),
+        }
+    }
+
     /// Checks if the given block is the first block that satisfies the fork condition.
     ///
     /// This will return false for any condition that is not timestamp based.
@@ -83,10 +125,10 @@ impl ForkCondition {
     ///
     /// - The condition is satisfied by the block number;
     /// - The condition is satisfied by the timestamp;
+    /// - The condition is satisfied by both the block number and the timestamp;
     /// - or the condition is satisfied by the total difficulty
     pub fn active_at_head(&self, head: &Head) -> bool {
-        self.active_at_block(head.number) ||
-            self.active_at_timestamp(head.timestamp) ||
+        self.active_at(head.number, head.timestamp) ||
             self.active_at_ttd(head.total_difficulty, head.difficulty)
     }
 
@@ -100,6 +142,32 @@ impl ForkCondition {
         }
     }
 
+    /// Returns the number of blocks from `current_block` until the fork condition's activation
+    /// block, or `0` if it was already reached.
+    ///
+    /// Returns `None` for fork conditions without a known activation block.
+    pub const fn blocks_until(&self, current_block: BlockNumber) -> Option<u64> {
+        match self {
+            Self::Block(block) |
+            Self::TTD { fork_block: Some(block), .. } |
+            Self::BlockAndTimestamp { block, .. } => Some(block.saturating_sub(current_block)),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of seconds from `timestamp` until the fork condition's activation
+    /// timestamp, or `0` if it was already reached.
+    ///
+    /// Returns `None` for fork conditions that are not timestamp based.
+    pub const fn seconds_until(&self, timestamp: u64) -> Option<u64> {
+        match self {
+            Self::Timestamp(time) | Self::BlockAndTimestamp { timestamp: time, .. } => {
+                Some(time.saturating_sub(timestamp))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the timestamp of the fork condition, if it is timestamp based.
     pub const fn as_timestamp(&self) -> Option<u64> {
         match self {
@@ -108,3 +176,200 @@ impl ForkCondition {
         }
     }
 }
+
+/// Formats the condition as `block:<number>`, `timestamp:<timestamp>`,
+/// `block:<number>,timestamp:<timestamp>`, `ttd:<total difficulty>[,block:<number>]` or `never`.
+///
+/// The output can be parsed back with [`ForkCondition::from_str`].
+impl fmt::Display for ForkCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block(block) => write!(f, "block:{block}"),
+            Self::Timestamp(timestamp) => write!(f, "timestamp:{timestamp}"),
+            Self::BlockAndTimestamp { block, timestamp } => {
+                write!(f, "block:{block},timestamp:{timestamp}")
+            }
+            Self::TTD { fork_block: None, total_difficulty } => write!(f, "ttd:{total_difficulty}"),
+            Self::TTD { fork_block: Some(block), total_difficulty } => {
+                write!(f, "ttd:{total_difficulty},block:{block}")
+            }
+            Self::Never => f.write_str("never"),
+        }
+    }
+}
+
+/// Parses a condition in the format of its [`Display`](fmt::Display) implementation, e.g.
+/// `block:12965000`, `timestamp:1710338135` or `ttd:5.875e22`.
+///
+/// Block numbers and timestamps are decimal. Total difficulties can also be hexadecimal
+/// (`0x`-prefixed) or in scientific notation, as long as they are integers.
+impl FromStr for ForkCondition {
+    type Err = ParseForkConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("never") {
+            return Ok(Self::Never)
+        }
+
+        let (mut block, mut timestamp, mut ttd) = (None, None, None);
+        for part in s.split(',') {
+            let (key, value) =
+                part.split_once(':').ok_or(ParseForkConditionError::InvalidFormat)?;
+            let (key, value) = (key.trim(), value.trim());
+            let duplicate = if key.eq_ignore_ascii_case("block") {
+                block.replace(parse_u64(value)?).is_some()
+            } else if key.eq_ignore_ascii_case("timestamp") {
+                timestamp.replace(parse_u64(value)?).is_some()
+            } else if key.eq_ignore_ascii_case("ttd") {
+                ttd.replace(parse_ttd(value)?).is_some()
+            } else {
+                return Err(ParseForkConditionError::InvalidFormat)
+            };
+            if duplicate {
+                return Err(ParseForkConditionError::InvalidFormat)
+            }
+        }
+
+        match (block, timestamp, ttd) {
+            (Some(block), None, None) => Ok(Self::Block(block)),
+            (None, Some(timestamp), None) => Ok(Self::Timestamp(timestamp)),
+            (Some(block), Some(timestamp), None) => {
+                Ok(Self::BlockAndTimestamp { block, timestamp })
+            }
+            (fork_block, None, Some(total_difficulty)) => {
+                Ok(Self::TTD { fork_block, total_difficulty })
+            }
+            _ => Err(ParseForkConditionError::InvalidFormat),
+        }
+    }
+}
+
+/// Parses a decimal block number or timestamp.
+fn parse_u64(value: &str) -> Result<u64, ParseForkConditionError> {
+    value.parse().map_err(|_| ParseForkConditionError::InvalidNumber)
+}
+
+/// Parses a total difficulty, which can be decimal, hexadecimal or in scientific notation.
+fn parse_ttd(value: &str) -> Result<U256, ParseForkConditionError> {
+    let scientific = if value.starts_with("0x") || value.starts_with("0X") {
+        None
+    } else {
+        value.split_once(['e', 'E'])
+    };
+    let Some((mantissa, exponent)) = scientific else {
+        return U256::from_str(value).map_err(|_| ParseForkConditionError::InvalidNumber)
+    };
+
+    let exponent: usize = exponent.parse().map_err(|_| ParseForkConditionError::InvalidNumber)?;
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ParseForkConditionError::InvalidNumber)
+    }
+    // the fractional digits are shifted into the integer part by the exponent
+    let exponent =
+        exponent.checked_sub(fraction.len()).ok_or(ParseForkConditionError::InvalidNumber)?;
+
+    let mut digits = U256::ZERO;
+    for digit in integer.chars().chain(fraction.chars()) {
+        let digit = digit.to_digit(10).ok_or(ParseForkConditionError::InvalidNumber)?;
+        digits = digits
+            .checked_mul(U256::from(10))
+            .and_then(|digits| digits.checked_add(U256::from(digit)))
+            .ok_or(ParseForkConditionError::InvalidNumber)?;
+    }
+
+    U256::from(10)
+        .checked_pow(U256::from(exponent))
+        .and_then(|scale| digits.checked_mul(scale))
+        .ok_or(ParseForkConditionError::InvalidNumber)
+}
+
+/// Error returned when parsing a [`ForkCondition`] from a string fails.
+#[derive(Clone, Copy, Debug, thiserror_no_std::Error, PartialEq, Eq)]
+pub enum ParseForkConditionError {
+    /// The string is not one of the supported condition formats.
+    #[error("invalid fork condition, expected e.g. `block:<number>` or `timestamp:<timestamp>`")]
+    InvalidFormat,
+    /// A value of the condition is not a valid number.
+    #[error("invalid number in fork condition")]
+    InvalidNumber,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::uint;
+
+    #[test]
+    fn fork_condition_display_from_str_roundtrip() {
+        let conditions = [
+            ForkCondition::Block(12_965_000),
+            ForkCondition::Timestamp(1_710_338_135),
+            ForkCondition::BlockAndTimestamp { block: 1, timestamp: 2 },
+            ForkCondition::TTD {
+                fork_block: None,
+                total_difficulty: uint!(58_750_000_000_000_000_000_000_U256),
+            },
+            ForkCondition::TTD { fork_block: Some(1_735_371), total_difficulty: U256::from(17) },
+            ForkCondition::Never,
+        ];
+
+        for condition in conditions {
+            assert_eq!(condition.to_string().parse::<ForkCondition>(), Ok(condition));
+        }
+    }
+
+    #[test]
+    fn fork_condition_is_timestamp() {
+        assert!(ForkCondition::Timestamp(1).is_timestamp());
+        assert!(ForkCondition::BlockAndTimestamp { block: 1, timestamp: 2 }.is_timestamp());
+        assert!(!ForkCondition::Block(1).is_timestamp());
+        let ttd = ForkCondition::TTD { fork_block: None, total_difficulty: U256::ZERO };
+        assert!(!ttd.is_timestamp());
+        assert!(!ForkCondition::Never.is_timestamp());
+    }
+
+    #[test]
+    fn fork_condition_ttd_reached() {
+        let condition = ForkCondition::TTD { fork_block: None, total_difficulty: U256::from(10) };
+        assert!(!condition.ttd_reached(U256::from(9)));
+        assert!(condition.ttd_reached(U256::from(10)));
+        assert!(condition.ttd_reached(U256::from(11)));
+        // the terminal block is not a post-merge block yet
+        assert!(!condition.active_at_ttd(U256::from(10), U256::from(1)));
+        assert!(condition.active_at_ttd(U256::from(11), U256::from(1)));
+
+        assert!(!ForkCondition::Block(0).ttd_reached(U256::MAX));
+    }
+
+    #[test]
+    fn fork_condition_from_str() {
+        assert_eq!(
+            "ttd:5.875e22".parse::<ForkCondition>(),
+            Ok(ForkCondition::TTD {
+                fork_block: None,
+                total_difficulty: uint!(58_750_000_000_000_000_000_000_U256),
+            })
+        );
+        assert_eq!(
+            "ttd:0xe0".parse::<ForkCondition>(),
+            Ok(ForkCondition::TTD { fork_block: None, total_difficulty: U256::from(0xe0) })
+        );
+        assert_eq!(
+            "ttd:1.5e0".parse::<ForkCondition>(),
+            Err(ParseForkConditionError::InvalidNumber)
+        );
+        for empty_mantissa in ["ttd:e18", "ttd:.e18"] {
+            assert_eq!(
+                empty_mantissa.parse::<ForkCondition>(),
+                Err(ParseForkConditionError::InvalidNumber)
+            );
+        }
+        assert_eq!(
+            "block:1,block:2".parse::<ForkCondition>(),
+            Err(ParseForkConditionError::InvalidFormat)
+        );
+        assert_eq!("slot:1".parse::<ForkCondition>(), Err(ParseForkConditionError::InvalidFormat));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{hardfork, ChainHardforks, EthereumHardfork, ForkCondition, Hardfork};
+use alloy_chains::Chain;
+use alloy_primitives::U256;
+use core::any::Any;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+hardfork!(
+    /// The name of an Arbitrum hardfork, i.e. an `ArbOS` version.
+    ///
+    /// When building a list of hardforks for a chain, it's still expected to mix with
+    /// [`EthereumHardfork`].
+    ArbitrumHardfork {
+        /// `ArbOS` 11: <https://docs.arbitrum.io/run-arbitrum-node/arbos-releases/arbos11>.
+        ArbOS11,
+        /// `ArbOS` 20 Atlas: <https://docs.arbitrum.io/run-arbitrum-node/arbos-releases/arbos20>.
+        ArbOS20Atlas,
+    }
+);
+
+impl ArbitrumHardfork {
+    /// Retrieves the activation block for the specified hardfork on the given chain.
+    pub fn activation_block<H: Hardfork>(self, fork: H, chain: Chain) -> Option<u64> {
+        if chain == Chain::arbitrum_mainnet() {
+            return Self::arbitrum_one_activation_block(fork)
+        }
+        if chain == Chain::arbitrum_sepolia() {
+            return Self::arbitrum_sepolia_activation_block(fork)
+        }
+
+        None
+    }
+
+    /// Retrieves the activation timestamp for the specified hardfork on the given chain.
+    pub fn activation_timestamp<H: Hardfork>(self, fork: H, chain: Chain) -> Option<u64> {
+        if chain == Chain::arbitrum_mainnet() {
+            return Self::arbitrum_one_activation_timestamp(fork)
+        }
+        if chain == Chain::arbitrum_sepolia() {
+            return Self::arbitrum_sepolia_activation_timestamp(fork)
+        }
+
+        None
+    }
+
+    /// Retrieves the activation block for the specified hardfork on the Arbitrum One mainnet.
+    pub fn arbitrum_one_activation_block<H: Hardfork>(fork: H) -> Option<u64> {
+        match_hardfork(fork, EthereumHardfork::arbitrum_activation_block, |fork| match fork {
+            Self::ArbOS11 => Some(184097479),
+            Self::ArbOS20Atlas => Some(190301729),
+        })
+    }
+
+    /// Retrieves the activation block for the specified hardfork on the Arbitrum Sepolia testnet.
+    pub fn arbitrum_sepolia_activation_block<H: Hardfork>(fork: H) -> Option<u64> {
+        match_hardfork(fork, EthereumHardfork::arbitrum_sepolia_activation_block, |fork| {
+            match fork {
+                Self::ArbOS11 => Some(10653737),
+                Self::ArbOS20Atlas => Some(18683405),
+            }
+        })
+    }
+
+    /// Retrieves the activation timestamp for the specified hardfork on the Arbitrum One mainnet.
+    pub fn arbitrum_one_activation_timestamp<H: Hardfork>(fork: H) -> Option<u64> {
+        match_hardfork(fork, EthereumHardfork::arbitrum_activation_timestamp, |fork| match fork {
+            Self::ArbOS11 => Some(1708804873),
+            Self::ArbOS20Atlas => Some(1710424089),
+        })
+    }
+
+    /// Retrieves the activation timestamp for the specified hardfork on the Arbitrum Sepolia
+    /// testnet.
+    pub fn arbitrum_sepolia_activation_timestamp<H: Hardfork>(fork: H) -> Option<u64> {
+        match_hardfork(fork, EthereumHardfork::arbitrum_sepolia_activation_timestamp, |fork| {
+            match fork {
+                Self::ArbOS11 => Some(1706634000),
+                Self::ArbOS20Atlas => Some(1709229600),
+            }
+        })
+    }
+
+    /// Arbitrum One list of hardforks.
+    pub fn arbitrum_one() -> ChainHardforks {
+        ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Dao.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::MuirGlacier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::ArrowGlacier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::GrayGlacier.boxed(), ForkCondition::Block(0)),
+            (
+                EthereumHardfork::Paris.boxed(),
+                ForkCondition::TTD { fork_block: Some(0), total_difficulty: U256::ZERO },
+            ),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(1708804873)),
+            (Self::ArbOS11.boxed(), ForkCondition::Timestamp(1708804873)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(1710424089)),
+            (Self::ArbOS20Atlas.boxed(), ForkCondition::Timestamp(1710424089)),
+        ])
+    }
+
+    /// Arbitrum Sepolia list of hardforks.
+    pub fn arbitrum_sepolia() -> ChainHardforks {
+        ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Dao.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::MuirGlacier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::ArrowGlacier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::GrayGlacier.boxed(), ForkCondition::Block(0)),
+            (
+                EthereumHardfork::Paris.boxed(),
+                ForkCondition::TTD { fork_block: Some(0), total_difficulty: U256::ZERO },
+            ),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(1706634000)),
+            (Self::ArbOS11.boxed(), ForkCondition::Timestamp(1706634000)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(1709229600)),
+            (Self::ArbOS20Atlas.boxed(), ForkCondition::Timestamp(1709229600)),
+        ])
+    }
+}
+
+/// Match helper method since it's not possible to match on `dyn Hardfork`
+fn match_hardfork<H, HF, AHF>(fork: H, hardfork_fn: HF, arbitrum_hardfork_fn: AHF) -> Option<u64>
+where
+    H: Hardfork,
+    HF: Fn(&EthereumHardfork) -> Option<u64>,
+    AHF: Fn(&ArbitrumHardfork) -> Option<u64>,
+{
+    let fork: &dyn Any = &fork;
+    if let Some(fork) = fork.downcast_ref::<EthereumHardfork>() {
+        return hardfork_fn(fork)
+    }
+    fork.downcast_ref::<ArbitrumHardfork>().and_then(arbitrum_hardfork_fn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn check_arbitrum_hardfork_from_str() {
+        let hardfork_str = ["ArbOs11", "arbos20atlas"];
+        let expected_hardforks = [ArbitrumHardfork::ArbOS11, ArbitrumHardfork::ArbOS20Atlas];
+
+        let hardforks: Vec<ArbitrumHardfork> =
+            hardfork_str.iter().map(|h| ArbitrumHardfork::from_str(h).unwrap()).collect();
+
+        assert_eq!(hardforks, expected_hardforks);
+    }
+
+    #[test]
+    fn test_match_hardfork() {
+        assert_eq!(
+            ArbitrumHardfork::arbitrum_one_activation_block(EthereumHardfork::Cancun),
+            Some(190301729)
+        );
+        assert_eq!(
+            ArbitrumHardfork::arbitrum_one_activation_block(ArbitrumHardfork::ArbOS20Atlas),
+            Some(190301729)
+        );
+    }
+}
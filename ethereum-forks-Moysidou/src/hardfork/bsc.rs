@@ -0,0 +1,103 @@
+use crate::{hardfork, ChainHardforks, EthereumHardfork, ForkCondition};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+hardfork!(
+    /// The name of a BNB Smart Chain hardfork.
+    ///
+    /// When building a list of hardforks for a chain, it's still expected to mix with
+    /// [`EthereumHardfork`].
+    BscHardfork {
+        /// Ramanujan.
+        Ramanujan,
+        /// Niels.
+        Niels,
+        /// `MirrorSync`.
+        MirrorSync,
+        /// Bruno.
+        Bruno,
+        /// Euler.
+        Euler,
+        /// Nano.
+        Nano,
+        /// Moran.
+        Moran,
+        /// Gibbs.
+        Gibbs,
+        /// Planck.
+        Planck,
+        /// Luban.
+        Luban,
+        /// Plato.
+        Plato,
+        /// Hertz.
+        Hertz,
+        /// `HertzFix`.
+        HertzFix,
+        /// Kepler.
+        Kepler,
+        /// Feynman.
+        Feynman,
+        /// `FeynmanFix`.
+        FeynmanFix,
+        /// Haber.
+        Haber,
+        /// `HaberFix`.
+        HaberFix,
+        /// Bohr.
+        Bohr,
+    }
+);
+
+impl BscHardfork {
+    /// BNB Smart Chain mainnet list of hardforks.
+    pub fn bsc_mainnet() -> ChainHardforks {
+        ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::MuirGlacier.boxed(), ForkCondition::Block(0)),
+            (Self::Ramanujan.boxed(), ForkCondition::Block(0)),
+            (Self::Niels.boxed(), ForkCondition::Block(0)),
+            (Self::MirrorSync.boxed(), ForkCondition::Block(5184000)),
+            (Self::Bruno.boxed(), ForkCondition::Block(13082000)),
+            (Self::Euler.boxed(), ForkCondition::Block(18907621)),
+            (Self::Nano.boxed(), ForkCondition::Block(21962149)),
+            (Self::Moran.boxed(), ForkCondition::Block(22107423)),
+            (Self::Gibbs.boxed(), ForkCondition::Block(23846001)),
+            (Self::Planck.boxed(), ForkCondition::Block(27281024)),
+            (Self::Luban.boxed(), ForkCondition::Block(29020050)),
+            (Self::Plato.boxed(), ForkCondition::Block(30720096)),
+            (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(31302048)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(31302048)),
+            (Self::Hertz.boxed(), ForkCondition::Block(31302048)),
+            (Self::HertzFix.boxed(), ForkCondition::Block(34140700)),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(1705996800)),
+            (Self::Kepler.boxed(), ForkCondition::Timestamp(1705996800)),
+            (Self::Feynman.boxed(), ForkCondition::Timestamp(1713419340)),
+            (Self::FeynmanFix.boxed(), ForkCondition::Timestamp(1713419340)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(1718863500)),
+            (Self::Haber.boxed(), ForkCondition::Timestamp(1718863500)),
+            (Self::HaberFix.boxed(), ForkCondition::Timestamp(1720591588)),
+            (Self::Bohr.boxed(), ForkCondition::Timestamp(1727317200)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn check_bsc_hardfork_from_str() {
+        assert_eq!(BscHardfork::from_str("hertzfix"), Ok(BscHardfork::HertzFix));
+        assert!(BscHardfork::bsc_mainnet().validate().is_ok());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Hardfork;
+#[cfg(feature = "serde")]
+use crate::{ArbitrumHardfork, EthereumHardfork, OptimismHardfork};
+#[cfg(feature = "serde")]
+use core::str::FromStr;
+
+#[cfg(all(not(feature = "std"), feature = "serde"))]
+use alloc::{boxed::Box, string::String};
+
+/// A hardfork that is not known to reth, defined by its name only.
+///
+/// Custom hardforks are created when a schedule loaded from a file contains a fork name that is
+/// not an [`EthereumHardfork`](crate::EthereumHardfork), an
+/// [`OptimismHardfork`](crate::OptimismHardfork) or an
+/// [`ArbitrumHardfork`](crate::ArbitrumHardfork), and can be used to query the resulting
+/// [`ChainHardforks`](crate::ChainHardforks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomHardfork(&'static str);
+
+impl CustomHardfork {
+    /// Creates a new custom hardfork with the given name.
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+}
+
+impl Hardfork for CustomHardfork {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// Returns the hardfork with the given name.
+///
+/// Names of known forks are matched case-insensitively. Any other name is turned into a
+/// [`CustomHardfork`], which leaks the name to obtain the `&'static str` required by
+/// [`Hardfork::name`]. Schedules are expected to be loaded once at startup.
+#[cfg(feature = "serde")]
+pub(crate) fn hardfork_by_name(name: String) -> Box<dyn Hardfork> {
+    if let Ok(fork) = EthereumHardfork::from_str(&name) {
+        return fork.boxed()
+    }
+    if let Ok(fork) = OptimismHardfork::from_str(&name) {
+        return fork.boxed()
+    }
+    if let Ok(fork) = ArbitrumHardfork::from_str(&name) {
+        return fork.boxed()
+    }
+    #[cfg(feature = "bsc")]
+    if let Ok(fork) = crate::BscHardfork::from_str(&name) {
+        return fork.boxed()
+    }
+    #[cfg(feature = "polygon")]
+    if let Ok(fork) = crate::PolygonHardfork::from_str(&name) {
+        return fork.boxed()
+    }
+    Box::new(CustomHardfork::new(Box::leak(name.into_boxed_str())))
+}
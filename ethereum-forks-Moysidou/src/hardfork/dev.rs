@@ -1,37 +1,89 @@
 use crate::{ChainHardforks, EthereumHardfork, ForkCondition};
 use alloy_primitives::U256;
+#[cfg(feature = "std")]
 use once_cell::sync::Lazy;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// Dev hardforks
-pub static DEV_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(|| {
-    /// Initialize ChainHardforks with a vector of tuples containing hardforks and their conditions
-    ChainHardforks::new(vec![
-        /// Ethereum hardforks activated at block 0
-        (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Dao.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(0)),
-        (EthereumHardfork::London.boxed(), ForkCondition::Block(0)),
-        (
-            EthereumHardfork::Paris.boxed(),
-            ForkCondition::TTD { fork_block: None, total_difficulty: U256::ZERO },
-        ),
-        /// Shanghai and Cancun hardforks activated at timestamp 0
-        (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(0)),
-        (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(0)),
-
-        /// Optimism hardforks activated conditionally based on feature flag "optimism"
-        #[cfg(feature = "optimism")]
-        (crate::OptimismHardfork::Regolith.boxed(), ForkCondition::Timestamp(0)),
-        #[cfg(feature = "optimism")]
-        (crate::OptimismHardfork::Bedrock.boxed(), ForkCondition::Block(0)),
+///
+/// Only available with the `std` feature, use [`DevHardforksBuilder`] otherwise.
+#[cfg(feature = "std")]
+pub static DEV_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(|| DevHardforksBuilder::new().build());
+
+/// Builder for the hardforks of a dev chain, where every fork is activated at genesis.
+///
+/// By default, all forks up to [`EthereumHardfork::Cancun`] are activated, which is what
+/// [`DEV_HARDFORKS`] contains. [`DevHardforksBuilder::up_to`] selects a different last fork, e.g.
+/// to simulate a pre-Cancun dev chain:
+///
+/// ```
+/// use reth_ethereum_forks::{DevHardforksBuilder, EthereumHardfork, ForkCondition};
+///
+/// let hardforks = DevHardforksBuilder::new().up_to(EthereumHardfork::Shanghai).build();
+/// assert_eq!(hardforks.fork(EthereumHardfork::Shanghai), ForkCondition::Timestamp(0));
+/// assert_eq!(hardforks.fork(EthereumHardfork::Cancun), ForkCondition::Never);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DevHardforksBuilder {
+    /// The last activated fork.
+    up_to: EthereumHardfork,
+}
+
+impl DevHardforksBuilder {
+    /// Creates a new builder that activates all forks up to [`EthereumHardfork::Cancun`].
+    pub const fn new() -> Self {
+        Self { up_to: EthereumHardfork::Cancun }
+    }
+
+    /// Sets the last activated fork. Later forks are not part of the built hardforks.
+    pub const fn up_to(mut self, fork: EthereumHardfork) -> Self {
+        self.up_to = fork;
+        self
+    }
+
+    /// Builds the hardforks.
+    pub fn build(self) -> ChainHardforks {
+        let forks = EthereumHardfork::VARIANTS
+            .iter()
+            .copied()
+            .filter(|fork| *fork <= self.up_to)
+            .filter_map(|fork| {
+                let condition = match fork {
+                    // difficulty bomb delays are irrelevant for dev chains
+                    EthereumHardfork::MuirGlacier |
+                    EthereumHardfork::ArrowGlacier |
+                    EthereumHardfork::GrayGlacier => return None,
+                    EthereumHardfork::Paris => {
+                        ForkCondition::TTD { fork_block: None, total_difficulty: U256::ZERO }
+                    }
+                    fork if fork > EthereumHardfork::Paris => ForkCondition::Timestamp(0),
+                    _ => ForkCondition::Block(0),
+                };
+                Some((fork.boxed(), condition))
+            })
+            .collect::<Vec<_>>();
+
         #[cfg(feature = "optimism")]
-        (crate::OptimismHardfork::Ecotone.boxed(), ForkCondition::Timestamp(0)),
-    ])
-});
+        let forks = {
+            use crate::OptimismHardfork;
+
+            let mut forks = forks;
+            forks.push((OptimismHardfork::Regolith.boxed(), ForkCondition::Timestamp(0)));
+            forks.push((OptimismHardfork::Bedrock.boxed(), ForkCondition::Block(0)));
+            if self.up_to >= EthereumHardfork::Cancun {
+                forks.push((OptimismHardfork::Ecotone.boxed(), ForkCondition::Timestamp(0)));
+            }
+            forks
+        };
+
+        ChainHardforks::new(forks)
+    }
+}
+
+impl Default for DevHardforksBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,13 +1,9 @@
 use crate::{hardfork, ChainHardforks, ForkCondition, Hardfork};
 use alloy_chains::Chain;
 use alloy_primitives::{uint, U256};
-use core::{
-    fmt,
-    fmt::{Display, Formatter},
-    str::FromStr,
-};
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 
 /// Define the Ethereum hardforks using the hardfork! macro.
@@ -443,6 +439,58 @@ impl EthereumHardfork {
             (Self::Cancun, ForkCondition::Timestamp(1707305664)),
         ]
     }
+
+    /// Ethereum hoodi list of hardforks.
+    pub const fn hoodi() -> [(Self, ForkCondition); 16] {
+        [
+            (Self::Frontier, ForkCondition::Block(0)),
+            (Self::Homestead, ForkCondition::Block(0)),
+            (Self::Dao, ForkCondition::Block(0)),
+            (Self::Tangerine, ForkCondition::Block(0)),
+            (Self::SpuriousDragon, ForkCondition::Block(0)),
+            (Self::Byzantium, ForkCondition::Block(0)),
+            (Self::Constantinople, ForkCondition::Block(0)),
+            (Self::Petersburg, ForkCondition::Block(0)),
+            (Self::Istanbul, ForkCondition::Block(0)),
+            (Self::MuirGlacier, ForkCondition::Block(0)),
+            (Self::Berlin, ForkCondition::Block(0)),
+            (Self::London, ForkCondition::Block(0)),
+            (Self::Paris, ForkCondition::TTD { fork_block: Some(0), total_difficulty: U256::ZERO }),
+            (Self::Shanghai, ForkCondition::Timestamp(0)),
+            (Self::Cancun, ForkCondition::Timestamp(0)),
+            (Self::Prague, ForkCondition::Timestamp(1742999832)),
+        ]
+    }
+
+    /// Returns the numbers of the EIPs activated at the hardfork.
+    pub const fn eips(&self) -> &'static [u64] {
+        match self {
+            Self::Frontier => &[],
+            Self::Homestead => &[2, 7, 8],
+            Self::Dao => &[779],
+            Self::Tangerine => &[150],
+            Self::SpuriousDragon => &[155, 160, 161, 170],
+            Self::Byzantium => &[100, 140, 196, 197, 198, 211, 214, 649, 658],
+            Self::Constantinople => &[145, 1014, 1052, 1234, 1283],
+            // Petersburg is Constantinople without EIP-1283
+            Self::Petersburg => &[145, 1014, 1052, 1234],
+            Self::Istanbul => &[152, 1108, 1344, 1884, 2028, 2200],
+            Self::MuirGlacier => &[2384],
+            Self::Berlin => &[2565, 2718, 2929, 2930],
+            Self::London => &[1559, 3198, 3529, 3541, 3554],
+            Self::ArrowGlacier => &[4345],
+            Self::GrayGlacier => &[5133],
+            Self::Paris => &[3675, 4399],
+            Self::Shanghai => &[3651, 3855, 3860, 4895, 6049],
+            Self::Cancun => &[1153, 4788, 4844, 5656, 6780, 7044, 7045, 7514, 7516],
+            Self::Prague => &[2537, 2935, 6110, 7002, 7251, 7549, 7623, 7685, 7691, 7702, 7840],
+        }
+    }
+
+    /// Returns the first hardfork that activates the given EIP, if any.
+    pub fn fork_activating(eip: u64) -> Option<Self> {
+        Self::VARIANTS.iter().copied().find(|fork| fork.eips().contains(&eip))
+    }
 }
 
 impl<const N: usize> From<[(EthereumHardfork, ForkCondition); N]> for ChainHardforks {
@@ -1,17 +1,52 @@
 /// Macro that defines different variants of a chain specific enum. See [`crate::Hardfork`] as an
 /// example.
+///
+/// The enum implements [`Hardfork`](crate::Hardfork), a case-insensitive
+/// [`FromStr`](core::str::FromStr), [`Display`](core::fmt::Display) and is ordered by declaration,
+/// so forks can be compared by activation order. All paths are resolved through this crate, so
+/// the macro can be used to declare the hardforks of a custom chain:
+///
+/// ```
+/// use reth_ethereum_forks::{hardfork, ChainHardforks, ForkCondition};
+///
+/// hardfork!(
+///     /// The hardforks of my chain.
+///     MyHardfork {
+///         /// The first fork.
+///         Genesis,
+///         /// The second fork.
+///         Lightning,
+///     }
+/// );
+///
+/// assert_eq!("lIgHtNiNg".parse::<MyHardfork>(), Ok(MyHardfork::Lightning));
+/// assert_eq!(MyHardfork::Lightning.to_string(), "Lightning");
+/// assert!(MyHardfork::Genesis < MyHardfork::Lightning);
+///
+/// let hardforks = ChainHardforks::new(vec![
+///     (MyHardfork::Genesis.boxed(), ForkCondition::Block(0)),
+///     (MyHardfork::Lightning.boxed(), ForkCondition::Timestamp(1_700_000_000)),
+/// ]);
+/// assert!(hardforks.is_fork_active_at_block(MyHardfork::Genesis, 0));
+/// ```
+///
+/// If the crate invoking the macro has a `serde` feature, the enum also derives `Serialize` and
+/// `Deserialize`, which requires a dependency on `serde`.
 #[macro_export]
 macro_rules! hardfork {
     ($(#[$enum_meta:meta])* $enum:ident { $( $(#[$meta:meta])* $variant:ident ),* $(,)? }) => {
         /// Metadata for the enum
         $(#[$enum_meta])*
-        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
         #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
         pub enum $enum {
-            $( $(#[$meta])* $variant ),* /// Variants of the enum
+            $( $(#[$meta])* $variant ),*
         }
 
         impl $enum {
+            /// All variants, in declaration order.
+            pub const VARIANTS: &'static [Self] = &[$( $enum::$variant, )*];
+
             /// Returns variant as `str`.
             pub const fn name(&self) -> &'static str {
                 match self {
@@ -20,28 +55,29 @@ macro_rules! hardfork {
             }
 
             /// Boxes `self` and returns it as `Box<dyn Hardfork>`.
-            pub fn boxed(self) -> Box<dyn Hardfork> {
-                Box::new(self)
+            pub fn boxed(self) -> $crate::__private::Box<dyn $crate::Hardfork> {
+                $crate::__private::Box::new(self)
             }
         }
 
-        impl FromStr for $enum {
-            type Err = String;
+        impl ::core::str::FromStr for $enum {
+            type Err = $crate::__private::String;
 
-            /// Attempt to parse a string into a variant of the enum.
-            /// /// Returns `Ok(Self)` with the parsed enum variant if successful, otherwise returns
+            /// Attempt to parse a string into a variant of the enum, ignoring the case.
+            ///
+            /// Returns `Ok(Self)` with the parsed enum variant if successful, otherwise returns
             /// `Err(String)` with an error message.
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                match s.to_lowercase().as_str() {
-                    $(
-                        s if s == stringify!($variant).to_lowercase() => Ok($enum::$variant),
-                    )*
-                    _ => return Err(format!("Unknown hardfork: {s}")),
-                }
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case(stringify!($variant)) {
+                        return Ok($enum::$variant)
+                    }
+                )*
+                Err($crate::__private::format!("Unknown hardfork: {s}"))
             }
         }
 
-        impl Hardfork for $enum {
+        impl $crate::Hardfork for $enum {
             /// Implements the `name` method of the `Hardfork` trait for the enum.
             ///
             /// Returns the name of the variant as a static string.
@@ -50,13 +86,40 @@ macro_rules! hardfork {
             }
         }
 
-        impl Display for $enum {
+        impl ::core::fmt::Display for $enum {
             /// Implements the `fmt` method of the `Display` trait for the enum.
             ///
             /// Writes the enum variant as a formatted string to the provided formatter.
-            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 write!(f, "{self:?}")
             }
         }
     }
 }
+
+/// Implements `arbitrary` and `proptest` support for enums declared with [`hardfork!`], picking
+/// one of the variants uniformly.
+#[cfg(any(test, feature = "arbitrary"))]
+macro_rules! impl_arbitrary_hardfork {
+    ($($enum:ident),+ $(,)?) => {
+        $(
+            impl<'a> arbitrary::Arbitrary<'a> for $enum {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    u.choose(Self::VARIANTS).copied()
+                }
+            }
+
+            impl proptest::arbitrary::Arbitrary for $enum {
+                type Parameters = ();
+                type Strategy = proptest::sample::Select<Self>;
+
+                fn arbitrary_with((): ()) -> Self::Strategy {
+                    proptest::sample::select(Self::VARIANTS)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+pub(crate) use impl_arbitrary_hardfork;
@@ -6,9 +6,43 @@ pub use ethereum::EthereumHardfork;
 mod optimism;
 pub use optimism::OptimismHardfork;
 
+mod arbitrum;
+pub use arbitrum::ArbitrumHardfork;
+
+#[cfg(feature = "bsc")]
+mod bsc;
+#[cfg(feature = "bsc")]
+pub use bsc::BscHardfork;
+
+#[cfg(feature = "polygon")]
+mod polygon;
+#[cfg(feature = "polygon")]
+pub use polygon::PolygonHardfork;
+
 mod dev;
+pub use dev::DevHardforksBuilder;
+#[cfg(feature = "std")]
 pub use dev::DEV_HARDFORKS;
 
+mod networks;
+pub use networks::{
+    GOERLI_CHAIN_ID, HOLESKY_CHAIN_ID, HOODI_CHAIN_ID, MAINNET_CHAIN_ID, SEPOLIA_CHAIN_ID,
+};
+#[cfg(feature = "std")]
+pub use networks::{HOLESKY_HARDFORKS, HOODI_HARDFORKS, MAINNET_HARDFORKS, SEPOLIA_HARDFORKS};
+
+mod custom;
+#[cfg(feature = "serde")]
+pub(crate) use custom::hardfork_by_name;
+pub use custom::CustomHardfork;
+
+#[cfg(any(test, feature = "arbitrary"))]
+macros::impl_arbitrary_hardfork!(EthereumHardfork, OptimismHardfork, ArbitrumHardfork);
+#[cfg(all(any(test, feature = "arbitrary"), feature = "bsc"))]
+macros::impl_arbitrary_hardfork!(BscHardfork);
+#[cfg(all(any(test, feature = "arbitrary"), feature = "polygon"))]
+macros::impl_arbitrary_hardfork!(PolygonHardfork);
+
 use core::{
     any::Any,
     hash::{Hash, Hasher},
@@ -19,7 +53,7 @@ use dyn_clone::DynClone;
 use alloc::{format, string::String};
 
 /// Generic hardfork trait.
-/// ///
+///
 /// This trait defines common behavior for hardforks in various blockchains or similar systems.
 /// It provides methods to retrieve the name of the hardfork and supports cloning and hashing.
 #[auto_impl::auto_impl(&, Box)]
@@ -138,6 +172,17 @@ mod tests {
         assert_eq!(hardforks, expected_hardforks);
     }
 
+    #[test]
+    fn check_fork_activating_eip() {
+        assert_eq!(EthereumHardfork::fork_activating(1559), Some(EthereumHardfork::London));
+        assert_eq!(EthereumHardfork::fork_activating(145), Some(EthereumHardfork::Constantinople));
+        assert_eq!(EthereumHardfork::fork_activating(4844), Some(EthereumHardfork::Cancun));
+        assert_eq!(EthereumHardfork::fork_activating(1), None);
+
+        assert_eq!(OptimismHardfork::fork_activating(4788), Some(OptimismHardfork::Ecotone));
+        assert_eq!(OptimismHardfork::fork_activating(4844), None);
+    }
+
     #[test]
     fn check_nonexistent_hardfork_from_str() {
         /// Test for a non-existent hardfork name
@@ -0,0 +1,106 @@
+use crate::{ChainHardforks, EthereumHardfork};
+#[cfg(feature = "std")]
+use once_cell::sync::Lazy;
+
+/// The chain ID of the Ethereum mainnet.
+pub const MAINNET_CHAIN_ID: u64 = 1;
+/// The chain ID of the Goerli testnet.
+pub const GOERLI_CHAIN_ID: u64 = 5;
+/// The chain ID of the Sepolia testnet.
+pub const SEPOLIA_CHAIN_ID: u64 = 11155111;
+/// The chain ID of the Holesky testnet.
+pub const HOLESKY_CHAIN_ID: u64 = 17000;
+/// The chain ID of the Hoodi testnet.
+pub const HOODI_CHAIN_ID: u64 = 560048;
+
+/// Ethereum mainnet hardforks
+///
+/// Only available with the `std` feature, use [`ChainHardforks::mainnet`] otherwise.
+#[cfg(feature = "std")]
+pub static MAINNET_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(ChainHardforks::mainnet);
+
+/// Sepolia hardforks
+///
+/// Only available with the `std` feature, use [`ChainHardforks::sepolia`] otherwise.
+#[cfg(feature = "std")]
+pub static SEPOLIA_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(ChainHardforks::sepolia);
+
+/// Holesky hardforks
+///
+/// Only available with the `std` feature, use [`ChainHardforks::holesky`] otherwise.
+#[cfg(feature = "std")]
+pub static HOLESKY_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(ChainHardforks::holesky);
+
+/// Hoodi hardforks
+///
+/// Only available with the `std` feature, use [`ChainHardforks::hoodi`] otherwise.
+#[cfg(feature = "std")]
+pub static HOODI_HARDFORKS: Lazy<ChainHardforks> = Lazy::new(ChainHardforks::hoodi);
+
+impl ChainHardforks {
+    /// Returns the hardforks of the Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        EthereumHardfork::mainnet().into()
+    }
+
+    /// Returns the hardforks of the Goerli testnet.
+    pub fn goerli() -> Self {
+        EthereumHardfork::goerli().into()
+    }
+
+    /// Returns the hardforks of the Sepolia testnet.
+    pub fn sepolia() -> Self {
+        EthereumHardfork::sepolia().into()
+    }
+
+    /// Returns the hardforks of the Holesky testnet.
+    pub fn holesky() -> Self {
+        EthereumHardfork::holesky().into()
+    }
+
+    /// Returns the hardforks of the Hoodi testnet.
+    pub fn hoodi() -> Self {
+        EthereumHardfork::hoodi().into()
+    }
+
+    /// Returns the hardforks of the public Ethereum network with the given chain ID, or `None` if
+    /// the chain is not known.
+    ///
+    /// ```
+    /// use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, ForkCondition};
+    ///
+    /// let hardforks = ChainHardforks::for_chain_id(11155111).unwrap();
+    /// let shanghai = hardforks.fork(EthereumHardfork::Shanghai);
+    /// assert_eq!(shanghai, ForkCondition::Timestamp(1677557088));
+    /// assert!(ChainHardforks::for_chain_id(1337).is_none());
+    /// ```
+    pub fn for_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            MAINNET_CHAIN_ID => Some(Self::mainnet()),
+            GOERLI_CHAIN_ID => Some(Self::goerli()),
+            SEPOLIA_CHAIN_ID => Some(Self::sepolia()),
+            HOLESKY_CHAIN_ID => Some(Self::holesky()),
+            HOODI_CHAIN_ID => Some(Self::hoodi()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthereumHardforks, ForkCondition};
+
+    #[test]
+    fn for_chain_id() {
+        let hoodi = ChainHardforks::for_chain_id(HOODI_CHAIN_ID).unwrap();
+        assert_eq!(hoodi, ChainHardforks::hoodi());
+        assert_eq!(hoodi.paris_activation().block, Some(0));
+        assert!(hoodi.is_cancun_active_at_timestamp(0));
+        assert_eq!(hoodi.fork(EthereumHardfork::Prague), ForkCondition::Timestamp(1742999832));
+
+        assert_eq!(ChainHardforks::for_chain_id(MAINNET_CHAIN_ID).unwrap().len(), 17);
+        assert_eq!(ChainHardforks::for_chain_id(HOLESKY_CHAIN_ID), Some(ChainHardforks::holesky()));
+        assert_eq!(ChainHardforks::for_chain_id(0), None);
+    }
+}
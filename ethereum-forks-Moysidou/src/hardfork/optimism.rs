@@ -1,13 +1,10 @@
 use crate::{hardfork, ChainHardforks, EthereumHardfork, ForkCondition, Hardfork};
 use alloy_chains::Chain;
 use alloy_primitives::U256;
-use core::{
-    any::Any,
-    fmt::{self, Display, Formatter},
-    str::FromStr,
-};
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use core::any::Any;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
 
 /// Import the `hardfork!` macro for defining hardforks.
 hardfork!(
@@ -329,6 +326,26 @@ impl OptimismHardfork {
             (Self::Fjord.boxed(), ForkCondition::Timestamp(1720627201)),
         ])
     }
+
+    /// Returns the numbers of the EIPs activated at the hardfork.
+    ///
+    /// Canyon and Ecotone activate the EIPs of [`EthereumHardfork::Shanghai`] and
+    /// [`EthereumHardfork::Cancun`] respectively, except for the ones that do not apply to
+    /// Optimism, e.g. the blob EIPs.
+    pub const fn eips(&self) -> &'static [u64] {
+        match self {
+            Self::Bedrock | Self::Regolith => &[],
+            Self::Canyon => &[3651, 3855, 3860, 4895, 6049],
+            Self::Ecotone => &[1153, 4788, 5656, 6780, 7516],
+            // Fjord activates RIP-7212 and no EIPs
+            Self::Fjord => &[],
+        }
+    }
+
+    /// Returns the first hardfork that activates the given EIP, if any.
+    pub fn fork_activating(eip: u64) -> Option<Self> {
+        Self::VARIANTS.iter().copied().find(|fork| fork.eips().contains(&eip))
+    }
 }
 
 /// Match helper method since it's not possible to match on `dyn Hardfork`
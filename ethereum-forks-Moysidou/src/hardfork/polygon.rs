@@ -0,0 +1,69 @@
+use crate::{hardfork, ChainHardforks, EthereumHardfork, ForkCondition};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+hardfork!(
+    /// The name of a Polygon PoS hardfork.
+    ///
+    /// Polygon activates all hardforks by block number, including the Ethereum hardforks after the
+    /// merge. When building a list of hardforks for a chain, it's still expected to mix with
+    /// [`EthereumHardfork`].
+    PolygonHardfork {
+        /// Jaipur.
+        Jaipur,
+        /// Delhi.
+        Delhi,
+        /// Indore.
+        Indore,
+        /// Agra, which activates Shanghai.
+        Agra,
+        /// Napoli, which activates Cancun.
+        Napoli,
+        /// Ahmedabad.
+        Ahmedabad,
+        /// Bhilai, which activates Prague.
+        Bhilai,
+    }
+);
+
+impl PolygonHardfork {
+    /// Polygon PoS mainnet list of hardforks.
+    pub fn polygon_mainnet() -> ChainHardforks {
+        ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(3395000)),
+            (EthereumHardfork::MuirGlacier.boxed(), ForkCondition::Block(3395000)),
+            (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(14750000)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(23850000)),
+            (Self::Jaipur.boxed(), ForkCondition::Block(23850000)),
+            (Self::Delhi.boxed(), ForkCondition::Block(38189056)),
+            (Self::Indore.boxed(), ForkCondition::Block(44934656)),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Block(50523000)),
+            (Self::Agra.boxed(), ForkCondition::Block(50523000)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Block(54876000)),
+            (Self::Napoli.boxed(), ForkCondition::Block(54876000)),
+            (Self::Ahmedabad.boxed(), ForkCondition::Block(62278656)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Block(73440256)),
+            (Self::Bhilai.boxed(), ForkCondition::Block(73440256)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn check_polygon_hardfork_from_str() {
+        assert_eq!(PolygonHardfork::from_str("NAPOLI"), Ok(PolygonHardfork::Napoli));
+        assert!(PolygonHardfork::polygon_mainnet().validate().is_ok());
+    }
+}
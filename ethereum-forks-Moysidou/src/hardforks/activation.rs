@@ -0,0 +1,152 @@
+use crate::{ChainHardforks, ForkCondition, Hardfork, Head};
+
+#[cfg(all(not(feature = "std"), feature = "stream"))]
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(all(feature = "std", feature = "stream"))]
+use std::collections::VecDeque;
+
+/// A hardfork that became active at a canonical head.
+#[derive(Debug, Clone)]
+pub struct ForkActivation {
+    /// The activated fork.
+    pub fork: Box<dyn Hardfork>,
+    /// The activation condition of the fork.
+    pub condition: ForkCondition,
+    /// The first canonical head at which the fork is active.
+    pub head: Head,
+}
+
+impl PartialEq for ForkActivation {
+    fn eq(&self, other: &Self) -> bool {
+        self.fork.name() == other.fork.name() &&
+            self.condition == other.condition &&
+            self.head == other.head
+    }
+}
+
+impl Eq for ForkActivation {}
+
+/// Tracks which hardforks are active as the canonical head advances, and reports the forks that
+/// activate.
+///
+/// A fork is reported at the first head that satisfies its condition, so timestamp based forks are
+/// reported at the first block at or after their activation timestamp. Forks that are already
+/// active at the initial head are not reported. If the head moves back below an activation, e.g.
+/// during a reorg, the fork is considered inactive again and reported once more when it
+/// re-activates.
+#[derive(Debug, Clone)]
+pub struct ForkActivationTracker {
+    /// The tracked hardforks.
+    hardforks: ChainHardforks,
+    /// Whether each fork of `hardforks` is active, in the order of [`ChainHardforks::forks_iter`].
+    active: Vec<bool>,
+}
+
+impl ForkActivationTracker {
+    /// Creates a tracker for the given hardforks, starting at the given head.
+    pub fn new(hardforks: ChainHardforks, head: &Head) -> Self {
+        let active =
+            hardforks.forks_iter().map(|(_, condition)| condition.active_at_head(head)).collect();
+        Self { hardforks, active }
+    }
+
+    /// Returns the tracked hardforks.
+    pub const fn hardforks(&self) -> &ChainHardforks {
+        &self.hardforks
+    }
+
+    /// Advances to a new canonical head, returning the forks that became active at it in
+    /// activation order.
+    pub fn on_new_head(&mut self, head: &Head) -> Vec<ForkActivation> {
+        let mut activations = Vec::new();
+        for ((fork, condition), active) in self.hardforks.forks_iter().zip(&mut self.active) {
+            let now_active = condition.active_at_head(head);
+            if now_active && !*active {
+                activations.push(ForkActivation {
+                    fork: dyn_clone::clone_box(fork),
+                    condition,
+                    head: *head,
+                });
+            }
+            *active = now_active;
+        }
+        activations
+    }
+
+    /// Wraps a stream of canonical heads into a stream of the forks that activate at them.
+    #[cfg(feature = "stream")]
+    pub const fn into_stream<S>(self, heads: S) -> ForkActivationStream<S> {
+        ForkActivationStream { tracker: self, heads, pending: VecDeque::new() }
+    }
+}
+
+/// A stream of the hardforks that activate as the canonical head advances, see
+/// [`ForkActivationTracker`].
+///
+/// The stream ends when the stream of heads ends.
+#[cfg(feature = "stream")]
+#[derive(Debug)]
+pub struct ForkActivationStream<S> {
+    /// Tracks the active forks.
+    tracker: ForkActivationTracker,
+    /// The stream of canonical heads.
+    heads: S,
+    /// Activations that were not yielded yet.
+    pending: VecDeque<ForkActivation>,
+}
+
+#[cfg(feature = "stream")]
+impl<S> futures_core::Stream for ForkActivationStream<S>
+where
+    S: futures_core::Stream<Item = Head> + Unpin,
+{
+    type Item = ForkActivation;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(activation) = this.pending.pop_front() {
+                return core::task::Poll::Ready(Some(activation))
+            }
+            let Some(head) = core::task::ready!(core::pin::Pin::new(&mut this.heads).poll_next(cx))
+            else {
+                return core::task::Poll::Ready(None)
+            };
+            this.pending.extend(this.tracker.on_new_head(&head));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthereumHardfork;
+
+    #[test]
+    fn reports_each_activation_once() {
+        let hardforks = ChainHardforks::mainnet();
+        let head = |number, timestamp| Head { number, timestamp, ..Default::default() };
+        let mut tracker = ForkActivationTracker::new(hardforks, &head(1_149_999, 0));
+
+        let activations = tracker.on_new_head(&head(1_150_000, 0));
+        assert_eq!(activations.len(), 1);
+        assert_eq!(activations[0].fork.name(), EthereumHardfork::Homestead.name());
+        assert!(tracker.on_new_head(&head(1_150_001, 0)).is_empty());
+
+        // Shanghai is reported at the first block past its timestamp
+        let shanghai = tracker.on_new_head(&head(17_034_870, 1_681_338_459));
+        assert!(shanghai.iter().any(|a| a.fork.name() == EthereumHardfork::Shanghai.name()));
+        assert!(tracker.on_new_head(&head(17_034_871, 1_681_338_471)).is_empty());
+
+        // a reorg below the activation reports the fork again once it re-activates
+        assert!(tracker.on_new_head(&head(17_034_869, 1_681_338_443)).is_empty());
+        let activations = tracker.on_new_head(&head(17_034_870, 1_681_338_459));
+        assert_eq!(activations.len(), 1);
+        assert_eq!(activations[0].head.number, 17_034_870);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{ArbitrumHardfork, ChainHardforks, EthereumHardforks};
+
+/// Extends [`crate::EthereumHardforks`] with arbitrum helper methods.
+pub trait ArbitrumHardforks: EthereumHardforks {
+    /// Convenience method to check if [`ArbitrumHardfork::ArbOS11`] is active at a given
+    /// timestamp.
+    fn is_arbos11_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(ArbitrumHardfork::ArbOS11, timestamp)
+    }
+
+    /// Convenience method to check if [`ArbitrumHardfork::ArbOS20Atlas`] is active at a given
+    /// timestamp.
+    fn is_arbos20_atlas_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(ArbitrumHardfork::ArbOS20Atlas, timestamp)
+    }
+}
+
+impl ArbitrumHardforks for ChainHardforks {}
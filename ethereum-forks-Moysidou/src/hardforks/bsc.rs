@@ -0,0 +1,41 @@
+use crate::{BscHardfork, ChainHardforks, EthereumHardforks};
+
+/// Extends [`crate::EthereumHardforks`] with BNB Smart Chain helper methods.
+pub trait BscHardforks: EthereumHardforks {
+    /// Convenience method to check if [`BscHardfork::Luban`] is active at a given block number.
+    fn is_luban_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(BscHardfork::Luban, block_number)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Plato`] is active at a given block number.
+    fn is_plato_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(BscHardfork::Plato, block_number)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Hertz`] is active at a given block number.
+    fn is_hertz_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(BscHardfork::Hertz, block_number)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Kepler`] is active at a given timestamp.
+    fn is_kepler_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(BscHardfork::Kepler, timestamp)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Feynman`] is active at a given timestamp.
+    fn is_feynman_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(BscHardfork::Feynman, timestamp)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Haber`] is active at a given timestamp.
+    fn is_haber_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(BscHardfork::Haber, timestamp)
+    }
+
+    /// Convenience method to check if [`BscHardfork::Bohr`] is active at a given timestamp.
+    fn is_bohr_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(BscHardfork::Bohr, timestamp)
+    }
+}
+
+impl BscHardforks for ChainHardforks {}
@@ -1,8 +1,85 @@
 use crate::{
-    hardforks::{ChainHardforks, Hardforks}, /// Import ChainHardforks and Hardforks traits from local crate
-    EthereumHardfork,   /// Import EthereumHardfork enum from local crate
-    ForkCondition,      /// Import ForkCondition enum from local crate
+    hardforks::{ChainHardforks, Hardforks, ProtocolParams},
+    EthereumHardfork, ForkCondition,
 };
+use alloy_primitives::{BlockNumber, U256};
+
+/// The number of historical block hashes served from the history storage contract introduced in
+/// [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935).
+pub const HISTORY_SERVE_WINDOW: u64 = 8192;
+
+/// The number of historical block hashes served by the `BLOCKHASH` opcode.
+pub const BLOCKHASH_SERVE_WINDOW: u64 = 256;
+
+/// How the [`EthereumHardfork::Paris`] hardfork (merge) is activated on a chain.
+///
+/// The merge is triggered by the terminal total difficulty, but most chains also know the block at
+/// which it happened. This resolves the activation of the merge from whichever of the two is known,
+/// preferring the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParisActivation {
+    /// The first post-merge block, if it is known.
+    pub block: Option<BlockNumber>,
+    /// The terminal total difficulty, if the merge is triggered by it.
+    pub total_difficulty: Option<U256>,
+}
+
+impl ParisActivation {
+    /// Creates the activation of the merge from the [`ForkCondition`] of
+    /// [`EthereumHardfork::Paris`].
+    ///
+    /// Timestamp based conditions are not supported for the merge and yield an activation that is
+    /// never reached.
+    pub const fn from_condition(condition: ForkCondition) -> Self {
+        match condition {
+            ForkCondition::Block(block) => Self { block: Some(block), total_difficulty: None },
+            ForkCondition::TTD { fork_block, total_difficulty } => {
+                Self { block: fork_block, total_difficulty: Some(total_difficulty) }
+            }
+            _ => Self { block: None, total_difficulty: None },
+        }
+    }
+
+    /// Returns true if the chain never activates the merge.
+    pub const fn is_never(&self) -> bool {
+        self.block.is_none() && self.total_difficulty.is_none()
+    }
+
+    /// Checks whether the merge is active at the given block, or `None` if the merge block is not
+    /// known.
+    pub fn active_at_block(&self, block_number: BlockNumber) -> Option<bool> {
+        self.block.map(|block| block_number >= block)
+    }
+
+    /// Checks whether the merge is active at a block with the given total difficulty and
+    /// difficulty, or `None` if the merge is not triggered by a total difficulty.
+    ///
+    /// See [`ForkCondition::active_at_ttd`].
+    pub fn active_at_ttd(&self, total_difficulty: U256, difficulty: U256) -> Option<bool> {
+        self.total_difficulty.map(|ttd| total_difficulty.saturating_sub(difficulty) >= ttd)
+    }
+
+    /// Checks whether the merge is active at the given block with the given total difficulty and
+    /// difficulty.
+    ///
+    /// The block number decides if the merge block is known, otherwise the total difficulty.
+    pub fn is_active(
+        &self,
+        block_number: BlockNumber,
+        total_difficulty: U256,
+        difficulty: U256,
+    ) -> bool {
+        self.active_at_block(block_number)
+            .or_else(|| self.active_at_ttd(total_difficulty, difficulty))
+            .unwrap_or_default()
+    }
+}
+
+impl From<ForkCondition> for ParisActivation {
+    fn from(condition: ForkCondition) -> Self {
+        Self::from_condition(condition)
+    }
+}
 
 /// Helper methods for Ethereum forks.
 pub trait EthereumHardforks: Hardforks {
@@ -22,6 +99,27 @@ pub trait EthereumHardforks: Hardforks {
         self.is_fork_active_at_timestamp(EthereumHardfork::Prague, timestamp)
     }
 
+    /// Convenience method to check if [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) set code
+    /// transactions are enabled at a given timestamp. EIP-7702 is activated with
+    /// [`EthereumHardfork::Prague`].
+    fn is_eip7702_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_prague_active_at_timestamp(timestamp)
+    }
+
+    /// Convenience method to check if [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935)
+    /// historical block hashes are served from state at a given timestamp. EIP-2935 is activated
+    /// with [`EthereumHardfork::Prague`].
+    fn is_eip2935_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_prague_active_at_timestamp(timestamp)
+    }
+
+    /// Returns the number of historical block hashes served from the history storage contract at a
+    /// given timestamp, or `None` if [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935) is not
+    /// active yet.
+    fn history_serve_window_at_timestamp(&self, timestamp: u64) -> Option<u64> {
+        self.is_eip2935_active_at_timestamp(timestamp).then_some(HISTORY_SERVE_WINDOW)
+    }
+
     /// Convenience method to check if [`EthereumHardfork::Byzantium`] is active at a given block
     /// number.
     fn is_byzantium_active_at_block(&self, block_number: u64) -> bool {
@@ -40,17 +138,24 @@ pub trait EthereumHardforks: Hardforks {
         self.fork(EthereumHardfork::Homestead).active_at_block(block_number)
     }
 
+    /// Returns the protocol constants at a given block number and timestamp, see
+    /// [`ProtocolParams`].
+    fn protocol_params_at(&self, block_number: u64, timestamp: u64) -> ProtocolParams {
+        ProtocolParams::from_active_forks(|fork| self.fork(fork).active_at(block_number, timestamp))
+    }
+
+    /// Returns how the Paris hardfork (merge) is activated, see [`ParisActivation`].
+    fn paris_activation(&self) -> ParisActivation {
+        ParisActivation::from_condition(self.fork(EthereumHardfork::Paris))
+    }
+
     /// The Paris hardfork (merge) is activated via block number. If we have knowledge of the block,
     /// this function will return true if the block number is greater than or equal to the Paris
     /// (merge) block.
+    ///
+    /// Returns `None` if the merge block is not known, see [`ParisActivation::active_at_block`].
     fn is_paris_active_at_block(&self, block_number: u64) -> Option<bool> {
-        match self.fork(EthereumHardfork::Paris) {
-            ForkCondition::Block(paris_block) => Some(block_number >= paris_block),
-            ForkCondition::TTD { fork_block, .. } => {
-                fork_block.map(|paris_block| block_number >= paris_block)
-            }
-            _ => None,
-        }
+        self.paris_activation().active_at_block(block_number)
     }
 }
 
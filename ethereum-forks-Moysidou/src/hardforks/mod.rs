@@ -1,13 +1,72 @@
 /// Ethereum helper methods
 mod ethereum;
-pub use ethereum::EthereumHardforks;
+pub use ethereum::{
+    EthereumHardforks, ParisActivation, BLOCKHASH_SERVE_WINDOW, HISTORY_SERVE_WINDOW,
+};
 
 /// Optimism helper methods
 mod optimism;
 pub use optimism::OptimismHardforks;
 
-use crate::{ForkCondition, Hardfork};
-use rustc_hash::FxHashMap;
+/// Arbitrum helper methods
+mod arbitrum;
+pub use arbitrum::ArbitrumHardforks;
+
+/// BNB Smart Chain helper methods
+#[cfg(feature = "bsc")]
+mod bsc;
+#[cfg(feature = "bsc")]
+pub use bsc::BscHardforks;
+
+/// Polygon helper methods
+#[cfg(feature = "polygon")]
+mod polygon;
+#[cfg(feature = "polygon")]
+pub use polygon::PolygonHardforks;
+
+/// Hardfork schedules loaded from JSON or TOML files
+#[cfg(feature = "spec")]
+mod spec;
+#[cfg(feature = "spec")]
+pub use spec::{HardforkSpec, HardforksSpec, HardforksSpecError};
+
+/// Hardfork schedules of OP-stack chains loaded from the superchain registry
+#[cfg(feature = "spec")]
+mod superchain;
+#[cfg(feature = "spec")]
+pub use superchain::{
+    SuperchainConfig, SuperchainGenesis, SuperchainGenesisBlock, SuperchainHardforkTimes,
+};
+
+/// Tracking of hardfork activations as the canonical head advances
+mod activation;
+pub use activation::{ForkActivation, ForkActivationTracker};
+#[cfg(feature = "stream")]
+pub use activation::ForkActivationStream;
+
+/// Protocol constants that depend on the active hardforks
+mod params;
+pub use params::{BlobParams, ProtocolParams};
+
+/// Hardfork schedule validation and diffing
+mod validation;
+pub use validation::{HardforkDiff, HardforksValidationError, MisorderedHardfork};
+
+use crate::{
+    EthereumHardfork, ForkCondition, ForkFilter, ForkFilterKey, ForkHash, ForkId, Hardfork, Head,
+};
+use alloy_primitives::{BlockNumber, B256, U256};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::{btree_map::Entry, BTreeMap},
+    vec::Vec,
+};
+#[cfg(all(not(feature = "std"), feature = "serde"))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::collections::{btree_map::Entry, BTreeMap};
 
 /// Generic trait over a set of ordered hardforks
 pub trait Hardforks: Default + Clone {
@@ -32,8 +91,10 @@ pub trait Hardforks: Default + Clone {
 /// Ordered list of a chain hardforks that implement [`Hardfork`].
 #[derive(Default, Clone, PartialEq, Eq)]
 pub struct ChainHardforks {
-    forks: Vec<(Box<dyn Hardfork>, ForkCondition)>, /// Vector of hardforks with their conditions
-    map: FxHashMap<&'static str, ForkCondition>,    /// HashMap for quick lookup by fork name
+    /// Vector of hardforks with their conditions
+    forks: Vec<(Box<dyn Hardfork>, ForkCondition)>,
+    /// Map for quick lookup by fork name, backed by a `BTreeMap` so it is available without `std`
+    map: BTreeMap<&'static str, ForkCondition>,
 }
 
 impl ChainHardforks {
@@ -72,6 +133,45 @@ impl ChainHardforks {
         self.forks.iter().map(|(f, b)| (&**f, *b))
     }
 
+    /// Get an iterator of all hardforks sorted by activation: block based forks first, then TTD
+    /// based forks, then timestamp based forks, each in ascending order. Forks that are never
+    /// activated come last.
+    ///
+    /// Forks with the same activation keep the order of the list.
+    pub fn forks_by_activation(&self) -> impl Iterator<Item = (&dyn Hardfork, ForkCondition)> {
+        let mut forks = self.forks_iter().collect::<Vec<_>>();
+        forks.sort_by_key(|(_, condition)| activation_order(condition));
+        forks.into_iter()
+    }
+
+    /// Get an iterator of all hardforks that are active at the given block and timestamp.
+    ///
+    /// TTD based forks are only included if their activation block is known.
+    pub fn forks_active_at(
+        &self,
+        block: BlockNumber,
+        timestamp: u64,
+    ) -> impl Iterator<Item = &dyn Hardfork> {
+        self.forks_iter()
+            .filter(move |(_, condition)| condition.active_at(block, timestamp))
+            .map(|(fork, _)| fork)
+    }
+
+    /// Returns the most recent fork that is active at the given block and timestamp, according to
+    /// [`Self::forks_by_activation`].
+    ///
+    /// TTD based forks are only considered if their activation block is known. If no fork is
+    /// active, [`EthereumHardfork::Frontier`] is returned.
+    pub fn latest_fork_at(&self, block: BlockNumber, timestamp: u64) -> Box<dyn Hardfork> {
+        self.forks_by_activation()
+            .filter(|(_, condition)| condition.active_at(block, timestamp))
+            .last()
+            .map_or_else(
+                || EthereumHardfork::Frontier.boxed(),
+                |(fork, _)| dyn_clone::clone_box(fork),
+            )
+    }
+
     /// Get last hardfork from the list.
     pub fn last(&self) -> Option<(Box<dyn Hardfork>, ForkCondition)> {
         self.forks.last().map(|(f, b)| (f.clone(), *b))
@@ -90,7 +190,7 @@ impl ChainHardforks {
     /// Inserts `fork` into list, updating with a new [`ForkCondition`] if it already exists.
     pub fn insert<H: Hardfork>(&mut self, fork: H, condition: ForkCondition) {
         match self.map.entry(fork.name()) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
+            Entry::Occupied(mut entry) => {
                 *entry.get_mut() = condition;
                 if let Some((_, inner)) =
                     self.forks.iter_mut().find(|(inner, _)| inner.name() == fork.name())
@@ -98,7 +198,7 @@ impl ChainHardforks {
                     *inner = condition;
                 }
             }
-            std::collections::hash_map::Entry::Vacant(entry) => {
+            Entry::Vacant(entry) => {
                 entry.insert(condition);
                 self.forks.push((Box::new(fork), condition));
             }
@@ -110,6 +210,135 @@ impl ChainHardforks {
         self.forks.retain(|(inner_fork, _)| inner_fork.name() != fork.name());
         self.map.remove(fork.name());
     }
+
+    /// Returns a copy of the hardforks with the given overrides applied, e.g. to define a shadow
+    /// fork of mainnet with Prague activated at a custom timestamp.
+    ///
+    /// Forks that are already part of the list keep their position and only change their
+    /// [`ForkCondition`]. Other forks are inserted after the last fork that is activated no later
+    /// than them, see [`Self::forks_by_activation`].
+    pub fn with_overrides<I, H>(&self, overrides: I) -> Self
+    where
+        I: IntoIterator<Item = (H, ForkCondition)>,
+        H: Hardfork,
+    {
+        let mut forks = self.clone();
+        for (fork, condition) in overrides {
+            if forks.map.contains_key(fork.name()) {
+                forks.insert(fork, condition);
+                continue
+            }
+
+            let order = activation_order(&condition);
+            let index = forks
+                .forks
+                .iter()
+                .rposition(|(_, existing)| activation_order(existing) <= order)
+                .map_or(0, |index| index + 1);
+            forks.map.insert(fork.name(), condition);
+            forks.forks.insert(index, (Box::new(fork), condition));
+        }
+        forks
+    }
+
+    /// Returns the next fork that is not active yet at the given block and timestamp, along with
+    /// its activation condition.
+    ///
+    /// Forks that are never activated and TTD based forks without a known activation block are
+    /// skipped, since their activation can not be scheduled. Use [`ForkCondition::blocks_until`]
+    /// and [`ForkCondition::seconds_until`] on the returned condition for a countdown.
+    pub fn next_fork_after(
+        &self,
+        block: BlockNumber,
+        timestamp: u64,
+    ) -> Option<(&dyn Hardfork, ForkCondition)> {
+        self.forks_iter().find(|(_, condition)| match condition {
+            ForkCondition::Never | ForkCondition::TTD { fork_block: None, .. } => false,
+            _ => !condition.active_at(block, timestamp),
+        })
+    }
+
+    /// Creates a [`ForkFilter`] for the chain with the given genesis at the given head.
+    ///
+    /// TTD based forks without a known activation block are not part of the filter. Forks based on
+    /// both a block and a timestamp are keyed by their timestamp.
+    pub fn fork_filter(
+        &self,
+        head: Head,
+        genesis_hash: B256,
+        genesis_timestamp: u64,
+    ) -> ForkFilter {
+        let forks = self.forks_iter().filter_map(|(_, condition)| match condition {
+            ForkCondition::Block(block) | ForkCondition::TTD { fork_block: Some(block), .. } => {
+                Some(ForkFilterKey::Block(block))
+            }
+            ForkCondition::Timestamp(timestamp) |
+            ForkCondition::BlockAndTimestamp { timestamp, .. } => {
+                Some(ForkFilterKey::Time(timestamp))
+            }
+            _ => None,
+        });
+
+        ForkFilter::new(head, genesis_hash, genesis_timestamp, forks)
+    }
+
+    /// Computes the [EIP-2124](https://eips.ethereum.org/EIPS/eip-2124) [`ForkId`] of the chain
+    /// with the given genesis at the given head.
+    ///
+    /// Block based forks are applied before timestamp based forks, as required by
+    /// [EIP-6122](https://eips.ethereum.org/EIPS/eip-6122).
+    pub fn fork_id(&self, head: &Head, genesis_hash: B256, genesis_timestamp: u64) -> ForkId {
+        let mut fork_hash = ForkHash::from(genesis_hash);
+        let mut current_applied = 0;
+
+        for (_, condition) in self.forks_iter() {
+            // TTD based forks are only part of the fork id if their activation block is known
+            if let ForkCondition::Block(block) |
+            ForkCondition::TTD { fork_block: Some(block), .. } = condition
+            {
+                if condition.active_at_head(head) {
+                    if block != current_applied {
+                        fork_hash += block;
+                        current_applied = block;
+                    }
+                } else {
+                    // block based forks are ordered, so this is the next fork
+                    return ForkId { hash: fork_hash, next: block }
+                }
+            }
+        }
+
+        let timestamps = self.forks_iter().filter_map(|(_, condition)| match condition {
+            ForkCondition::Timestamp(timestamp) |
+            ForkCondition::BlockAndTimestamp { timestamp, .. } => {
+                Some(timestamp).filter(|timestamp| *timestamp > genesis_timestamp)
+            }
+            _ => None,
+        });
+        for timestamp in timestamps {
+            if head.timestamp >= timestamp {
+                if timestamp != current_applied {
+                    fork_hash += timestamp;
+                    current_applied = timestamp;
+                }
+            } else {
+                return ForkId { hash: fork_hash, next: timestamp }
+            }
+        }
+
+        ForkId { hash: fork_hash, next: 0 }
+    }
+}
+
+/// Returns the key [`ChainHardforks::forks_by_activation`] sorts by.
+fn activation_order(condition: &ForkCondition) -> (u8, U256) {
+    match *condition {
+        ForkCondition::Block(block) => (0, U256::from(block)),
+        ForkCondition::TTD { total_difficulty, .. } => (1, total_difficulty),
+        ForkCondition::Timestamp(timestamp) |
+        ForkCondition::BlockAndTimestamp { timestamp, .. } => (2, U256::from(timestamp)),
+        ForkCondition::Never => (3, U256::ZERO),
+    }
 }
 
 impl Hardforks for ChainHardforks {
@@ -122,6 +351,42 @@ impl Hardforks for ChainHardforks {
     }
 }
 
+/// A single entry of a serialized [`ChainHardforks`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerdeHardfork<N> {
+    name: N,
+    condition: ForkCondition,
+}
+
+/// Serializes the hardforks as an ordered list of `{ "name": .., "condition": .. }` entries.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ChainHardforks {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.forks_iter()
+                .map(|(fork, condition)| SerdeHardfork { name: fork.name(), condition }),
+        )
+    }
+}
+
+/// Deserializes the hardforks from an ordered list of `{ "name": .., "condition": .. }` entries.
+///
+/// Forks that are not an [`EthereumHardfork`](crate::EthereumHardfork), an
+/// [`OptimismHardfork`](crate::OptimismHardfork) or an
+/// [`ArbitrumHardfork`](crate::ArbitrumHardfork) are deserialized as a
+/// [`CustomHardfork`](crate::CustomHardfork).
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ChainHardforks {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let forks = Vec::<SerdeHardfork<String>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|fork| (crate::hardfork::hardfork_by_name(fork.name), fork.condition))
+            .collect();
+        Ok(Self::new(forks))
+    }
+}
+
 impl core::fmt::Debug for ChainHardforks {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         /// Debug formatting for ChainHardforks
@@ -130,3 +395,184 @@ impl core::fmt::Debug for ChainHardforks {
             .finish()
     }
 }
+
+/// Builds an Ethereum schedule activating one fork per gap, in declaration order.
+///
+/// Every fork is activated `gap` blocks, or seconds for timestamp based forks, after the previous
+/// one, so gaps of `0` activate several forks at once. Paris is TTD based with a known block.
+#[cfg(any(test, feature = "arbitrary"))]
+fn arbitrary_schedule(gaps: impl IntoIterator<Item = u16>) -> ChainHardforks {
+    let (mut block, mut timestamp) = (0, 0);
+    let forks = EthereumHardfork::VARIANTS
+        .iter()
+        .zip(gaps)
+        .map(|(fork, gap)| {
+            let condition = if *fork < EthereumHardfork::Paris {
+                block += u64::from(gap);
+                ForkCondition::Block(block)
+            } else if *fork == EthereumHardfork::Paris {
+                block += u64::from(gap);
+                ForkCondition::TTD { fork_block: Some(block), total_difficulty: U256::from(block) }
+            } else {
+                timestamp += u64::from(gap);
+                ForkCondition::Timestamp(timestamp)
+            };
+            (fork.boxed(), condition)
+        })
+        .collect();
+    ChainHardforks::new(forks)
+}
+
+/// Generates valid Ethereum schedules activating a prefix of the [`EthereumHardfork`]s.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for ChainHardforks {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let forks = u.int_in_range(0..=EthereumHardfork::VARIANTS.len())?;
+        let gaps = (0..forks).map(|_| u.arbitrary()).collect::<arbitrary::Result<Vec<u16>>>()?;
+        Ok(arbitrary_schedule(gaps))
+    }
+}
+
+/// Generates valid Ethereum schedules activating a prefix of the [`EthereumHardfork`]s.
+#[cfg(any(test, feature = "arbitrary"))]
+impl proptest::arbitrary::Arbitrary for ChainHardforks {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        proptest::collection::vec(any::<u16>(), 0..=EthereumHardfork::VARIANTS.len())
+            .prop_map(arbitrary_schedule)
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+    use proptest::proptest;
+
+    proptest! {
+        #[test]
+        fn arbitrary_schedules_are_valid(hardforks: ChainHardforks) {
+            assert!(hardforks.validate().is_ok());
+        }
+    }
+
+    const MAINNET_GENESIS_HASH: B256 =
+        b256!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+
+    fn mainnet() -> ChainHardforks {
+        ChainHardforks::new(
+            EthereumHardfork::mainnet()
+                .into_iter()
+                .map(|(fork, condition)| (fork.boxed(), condition))
+                .collect(),
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        let mut hardforks = mainnet();
+        hardforks.insert(crate::CustomHardfork::new("Custom"), ForkCondition::Timestamp(1));
+
+        let json = serde_json::to_string(&hardforks).unwrap();
+        let decoded: ChainHardforks = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, hardforks);
+        assert_eq!(
+            decoded.fork(EthereumHardfork::London),
+            hardforks.fork(EthereumHardfork::London)
+        );
+    }
+
+    #[test]
+    fn forks_by_activation() {
+        let hardforks = ChainHardforks::new(vec![
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(10)),
+            (
+                EthereumHardfork::Paris.boxed(),
+                ForkCondition::TTD { fork_block: None, total_difficulty: U256::ZERO },
+            ),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Never),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(5)),
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+        ]);
+
+        let names =
+            hardforks.forks_by_activation().map(|(fork, _)| fork.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["Frontier", "London", "Paris", "Shanghai", "Prague"]);
+
+        let active = hardforks.forks_active_at(5, 9).map(|fork| fork.name()).collect::<Vec<_>>();
+        assert_eq!(active, ["London", "Frontier"]);
+
+        assert_eq!(hardforks.latest_fork_at(4, 0).name(), "Frontier");
+        assert_eq!(hardforks.latest_fork_at(5, 9).name(), "London");
+        assert_eq!(hardforks.latest_fork_at(5, 10).name(), "Shanghai");
+        assert_eq!(ChainHardforks::default().latest_fork_at(5, 10).name(), "Frontier");
+    }
+
+    #[test]
+    fn shadow_fork_overrides() {
+        let hardforks = mainnet();
+        let shadow = hardforks.with_overrides([
+            (EthereumHardfork::Prague, ForkCondition::Timestamp(1_800_000_000)),
+            (EthereumHardfork::Cancun, ForkCondition::Timestamp(1_720_000_000)),
+        ]);
+
+        assert_eq!(shadow.len(), hardforks.len() + 1);
+        assert_eq!(shadow.fork(EthereumHardfork::Cancun), ForkCondition::Timestamp(1_720_000_000));
+        assert_eq!(shadow.last().unwrap().0.name(), EthereumHardfork::Prague.name());
+        assert_eq!(
+            hardforks.fork(EthereumHardfork::Cancun),
+            ForkCondition::Timestamp(1_710_338_135)
+        );
+
+        let shadow =
+            hardforks.with_overrides([(EthereumHardfork::Prague, ForkCondition::Block(0))]);
+        assert_eq!(shadow.forks_iter().nth(1).unwrap().0.name(), EthereumHardfork::Prague.name());
+    }
+
+    #[test]
+    fn next_fork() {
+        let hardforks = mainnet();
+
+        let (fork, condition) = hardforks.next_fork_after(1_000_000, 0).unwrap();
+        assert_eq!(fork.name(), EthereumHardfork::Homestead.name());
+        assert_eq!(condition.blocks_until(1_000_000), Some(150_000));
+        assert_eq!(condition.seconds_until(0), None);
+
+        let (fork, condition) = hardforks.next_fork_after(17_034_870, 1_681_338_455).unwrap();
+        assert_eq!(fork.name(), EthereumHardfork::Cancun.name());
+        assert_eq!(condition.seconds_until(1_681_338_455), Some(1_710_338_135 - 1_681_338_455));
+
+        assert!(hardforks.next_fork_after(19_426_587, 1_710_338_135).is_none());
+    }
+
+    #[test]
+    fn mainnet_fork_ids() {
+        let hardforks = mainnet();
+        let cases = [
+            (Head { number: 0, ..Default::default() }, hex!("fc64ec04"), 1_150_000),
+            (Head { number: 1_150_000, ..Default::default() }, hex!("97c2c34c"), 1_920_000),
+            (
+                Head { number: 17_034_870, timestamp: 1_681_338_455, ..Default::default() },
+                hex!("dce96c2d"),
+                1_710_338_135,
+            ),
+            (
+                Head { number: 19_426_587, timestamp: 1_710_338_135, ..Default::default() },
+                hex!("9f3d2254"),
+                0,
+            ),
+        ];
+
+        for (head, hash, next) in cases {
+            let expected = ForkId { hash: ForkHash(hash), next };
+            assert_eq!(hardforks.fork_id(&head, MAINNET_GENESIS_HASH, 0), expected);
+            assert_eq!(hardforks.fork_filter(head, MAINNET_GENESIS_HASH, 0).current(), expected);
+        }
+    }
+}
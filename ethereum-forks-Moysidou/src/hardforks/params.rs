@@ -0,0 +1,156 @@
+use crate::EthereumHardfork;
+
+/// The blob gas parameters introduced in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    pub target_blobs_per_block: u64,
+    /// The maximum number of blobs per block.
+    pub max_blobs_per_block: u64,
+    /// The gas used by a single blob.
+    pub blob_gas_per_blob: u64,
+    /// The denominator of the blob base fee update, which controls how fast it adjusts.
+    pub update_fraction: u128,
+}
+
+impl BlobParams {
+    /// The blob parameters of [`EthereumHardfork::Cancun`].
+    pub const CANCUN: Self = Self {
+        target_blobs_per_block: 3,
+        max_blobs_per_block: 6,
+        blob_gas_per_blob: 131_072,
+        update_fraction: 3_338_477,
+    };
+
+    /// The blob parameters of [`EthereumHardfork::Prague`], see
+    /// [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691).
+    pub const PRAGUE: Self = Self {
+        target_blobs_per_block: 6,
+        max_blobs_per_block: 9,
+        blob_gas_per_blob: 131_072,
+        update_fraction: 5_007_716,
+    };
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blobs_per_block * self.blob_gas_per_blob
+    }
+
+    /// Returns the maximum blob gas per block.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blobs_per_block * self.blob_gas_per_blob
+    }
+}
+
+/// Protocol constants that changed with Ethereum hardforks.
+///
+/// This collects the values validation and execution have to agree on, so they are looked up in
+/// one place instead of being derived from fork checks wherever they are needed. Use
+/// [`EthereumHardforks::protocol_params_at`](crate::EthereumHardforks::protocol_params_at) to get
+/// the parameters of a chain at a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolParams {
+    /// The intrinsic gas of a transaction.
+    pub tx_gas: u64,
+    /// The intrinsic gas of a contract creation transaction, which was raised by
+    /// [EIP-2](https://eips.ethereum.org/EIPS/eip-2).
+    pub tx_create_gas: u64,
+    /// The gas per zero byte of transaction data.
+    pub tx_data_zero_gas: u64,
+    /// The gas per non-zero byte of transaction data, which was lowered by
+    /// [EIP-2028](https://eips.ethereum.org/EIPS/eip-2028).
+    pub tx_data_non_zero_gas: u64,
+    /// The gas per address of an access list, if access lists are supported
+    /// ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)).
+    pub access_list_address_gas: Option<u64>,
+    /// The gas per storage key of an access list, if access lists are supported
+    /// ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)).
+    pub access_list_storage_key_gas: Option<u64>,
+    /// The gas per 32 byte word of init code, if it is charged
+    /// ([EIP-3860](https://eips.ethereum.org/EIPS/eip-3860)).
+    pub init_code_word_gas: Option<u64>,
+    /// The gas per authorization of a set code transaction, if they are supported
+    /// ([EIP-7702](https://eips.ethereum.org/EIPS/eip-7702)).
+    pub authorization_gas: Option<u64>,
+    /// The gas per token of the calldata floor cost, if transactions are charged at least the
+    /// floor ([EIP-7623](https://eips.ethereum.org/EIPS/eip-7623)).
+    pub total_cost_floor_per_token: Option<u64>,
+    /// The maximum size of deployed code, if it is limited
+    /// ([EIP-170](https://eips.ethereum.org/EIPS/eip-170)).
+    pub max_code_size: Option<usize>,
+    /// The maximum size of init code, if it is limited
+    /// ([EIP-3860](https://eips.ethereum.org/EIPS/eip-3860)).
+    pub max_init_code_size: Option<usize>,
+    /// Whether blocks have a base fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
+    pub base_fee: bool,
+    /// The blob gas parameters, if blob transactions are supported.
+    pub blob: Option<BlobParams>,
+}
+
+impl ProtocolParams {
+    /// Returns the parameters once all forks up to and including `fork` are active.
+    pub fn for_fork(fork: EthereumHardfork) -> Self {
+        Self::from_active_forks(|active| active <= fork)
+    }
+
+    /// Returns the parameters given which forks are active.
+    ///
+    /// Forks are checked individually, so chains that skip a fork get the parameters of the forks
+    /// they do activate.
+    pub fn from_active_forks(is_active: impl Fn(EthereumHardfork) -> bool) -> Self {
+        let berlin = is_active(EthereumHardfork::Berlin);
+        let shanghai = is_active(EthereumHardfork::Shanghai);
+        let prague = is_active(EthereumHardfork::Prague);
+
+        let blob = if prague {
+            Some(BlobParams::PRAGUE)
+        } else if is_active(EthereumHardfork::Cancun) {
+            Some(BlobParams::CANCUN)
+        } else {
+            None
+        };
+
+        Self {
+            tx_gas: 21_000,
+            tx_create_gas: if is_active(EthereumHardfork::Homestead) { 53_000 } else { 21_000 },
+            tx_data_zero_gas: 4,
+            tx_data_non_zero_gas: if is_active(EthereumHardfork::Istanbul) { 16 } else { 68 },
+            access_list_address_gas: berlin.then_some(2_400),
+            access_list_storage_key_gas: berlin.then_some(1_900),
+            init_code_word_gas: shanghai.then_some(2),
+            authorization_gas: prague.then_some(25_000),
+            total_cost_floor_per_token: prague.then_some(10),
+            max_code_size: is_active(EthereumHardfork::SpuriousDragon).then_some(0x6000),
+            max_init_code_size: shanghai.then_some(2 * 0x6000),
+            base_fee: is_active(EthereumHardfork::London),
+            blob,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChainHardforks, EthereumHardforks};
+
+    #[test]
+    fn mainnet_protocol_params() {
+        let hardforks = ChainHardforks::mainnet();
+
+        let frontier = hardforks.protocol_params_at(0, 0);
+        assert_eq!(frontier, ProtocolParams::for_fork(EthereumHardfork::Frontier));
+        assert_eq!(frontier.tx_create_gas, 21_000);
+        assert_eq!(frontier.max_code_size, None);
+
+        let london = hardforks.protocol_params_at(12_965_000, 1_628_166_822);
+        assert!(london.base_fee);
+        assert_eq!(london.tx_data_non_zero_gas, 16);
+        assert_eq!(london.max_init_code_size, None);
+
+        let cancun = hardforks.protocol_params_at(19_426_587, 1_710_338_135);
+        assert_eq!(cancun, ProtocolParams::for_fork(EthereumHardfork::Cancun));
+        assert_eq!(cancun.max_init_code_size, Some(49_152));
+        assert_eq!(cancun.blob.map(|blob| blob.max_blob_gas_per_block()), Some(786_432));
+        assert_eq!(cancun.authorization_gas, None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{ChainHardforks, EthereumHardforks, PolygonHardfork};
+
+/// Extends [`crate::EthereumHardforks`] with Polygon helper methods.
+pub trait PolygonHardforks: EthereumHardforks {
+    /// Convenience method to check if [`PolygonHardfork::Jaipur`] is active at a given block
+    /// number.
+    fn is_jaipur_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Jaipur, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Delhi`] is active at a given block
+    /// number.
+    fn is_delhi_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Delhi, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Indore`] is active at a given block
+    /// number.
+    fn is_indore_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Indore, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Agra`] is active at a given block number.
+    fn is_agra_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Agra, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Napoli`] is active at a given block
+    /// number.
+    fn is_napoli_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Napoli, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Ahmedabad`] is active at a given block
+    /// number.
+    fn is_ahmedabad_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Ahmedabad, block_number)
+    }
+
+    /// Convenience method to check if [`PolygonHardfork::Bhilai`] is active at a given block
+    /// number.
+    fn is_bhilai_active_at_block(&self, block_number: u64) -> bool {
+        self.is_fork_active_at_block(PolygonHardfork::Bhilai, block_number)
+    }
+}
+
+impl PolygonHardforks for ChainHardforks {}
@@ -0,0 +1,241 @@
+use crate::{
+    hardfork::hardfork_by_name, ChainHardforks, ForkCondition, Hardfork, HardforksValidationError,
+};
+use alloy_primitives::{BlockNumber, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs, io, path::Path};
+
+/// The activation of a single hardfork in a [`HardforksSpec`].
+///
+/// Either `block`, `timestamp`, both of them (see [`ForkCondition::BlockAndTimestamp`]) or `ttd`
+/// must be set. `fork_block` is only allowed together with `ttd`, see [`ForkCondition::TTD`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HardforkSpec {
+    /// The name of the fork, e.g. `London` or the name of a custom fork.
+    pub name: String,
+    /// The block the fork is activated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockNumber>,
+    /// The timestamp the fork is activated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// The total difficulty the fork is activated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttd: Option<U256>,
+    /// The block the total difficulty is reached at, if it is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_block: Option<BlockNumber>,
+}
+
+impl HardforkSpec {
+    /// Returns the [`ForkCondition`] of the fork.
+    pub fn condition(&self) -> Result<ForkCondition, HardforksSpecError> {
+        match (self.block, self.timestamp, self.ttd, self.fork_block) {
+            (Some(block), None, None, None) => Ok(ForkCondition::Block(block)),
+            (None, Some(timestamp), None, None) => Ok(ForkCondition::Timestamp(timestamp)),
+            (Some(block), Some(timestamp), None, None) => {
+                Ok(ForkCondition::BlockAndTimestamp { block, timestamp })
+            }
+            (None, None, Some(total_difficulty), fork_block) => {
+                Ok(ForkCondition::TTD { fork_block, total_difficulty })
+            }
+            _ => Err(HardforksSpecError::InvalidCondition(self.name.clone())),
+        }
+    }
+
+    /// Returns the hardfork with the name of the spec.
+    ///
+    /// Names of known forks are matched case-insensitively. Any other name is turned into a
+    /// [`CustomHardfork`](crate::CustomHardfork), which leaks the name. Specs are expected to be
+    /// loaded once at startup.
+    pub fn hardfork(&self) -> Box<dyn Hardfork> {
+        hardfork_by_name(self.name.clone())
+    }
+}
+
+/// A user-supplied hardfork schedule, e.g. of a private network.
+///
+/// The schedule can be written in JSON or TOML:
+///
+/// ```toml
+/// [[hardforks]]
+/// name = "London"
+/// block = 0
+///
+/// [[hardforks]]
+/// name = "Paris"
+/// ttd = "0x0"
+///
+/// [[hardforks]]
+/// name = "Shanghai"
+/// timestamp = 0
+///
+/// [[hardforks]]
+/// name = "MyCustomFork"
+/// timestamp = 1700000000
+/// ```
+///
+/// Forks **must be ordered** by activation, see [`ChainHardforks::new`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HardforksSpec {
+    /// The hardforks of the chain, ordered by activation.
+    pub hardforks: Vec<HardforkSpec>,
+}
+
+impl HardforksSpec {
+    /// Parses a spec from a JSON string.
+    pub fn from_json(s: &str) -> Result<Self, HardforksSpecError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Parses a spec from a TOML string.
+    pub fn from_toml(s: &str) -> Result<Self, HardforksSpecError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Loads a spec from a `.json` or `.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, HardforksSpecError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            _ => Err(HardforksSpecError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    /// Builds the [`ChainHardforks`] defined by the spec.
+    ///
+    /// Returns an error if the forks are not ordered by activation, see
+    /// [`ChainHardforks::validate`].
+    pub fn into_hardforks(self) -> Result<ChainHardforks, HardforksSpecError> {
+        let mut names = HashSet::with_capacity(self.hardforks.len());
+        let mut forks = Vec::with_capacity(self.hardforks.len());
+        for spec in self.hardforks {
+            let condition = spec.condition()?;
+            let fork = spec.hardfork();
+            if !names.insert(fork.name()) {
+                return Err(HardforksSpecError::DuplicateFork(spec.name))
+            }
+            forks.push((fork, condition));
+        }
+
+        let hardforks = ChainHardforks::new(forks);
+        hardforks.validate()?;
+        Ok(hardforks)
+    }
+}
+
+impl ChainHardforks {
+    /// Loads the hardforks of a chain from a `.json` or `.toml` spec file.
+    ///
+    /// See [`HardforksSpec`] for the format of the file.
+    pub fn from_spec_file(path: impl AsRef<Path>) -> Result<Self, HardforksSpecError> {
+        HardforksSpec::load(path)?.into_hardforks()
+    }
+}
+
+/// Errors that can occur when loading a [`HardforksSpec`].
+#[derive(Debug, thiserror_no_std::Error)]
+pub enum HardforksSpecError {
+    /// Failed to read the spec file.
+    #[error("failed to read hardforks spec: {0}")]
+    Io(#[from] io::Error),
+    /// Failed to parse a JSON spec.
+    #[error("failed to parse hardforks spec: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Failed to parse a TOML spec.
+    #[error("failed to parse hardforks spec: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The spec file is neither JSON nor TOML.
+    #[error("unsupported hardforks spec format: {0}")]
+    UnsupportedFormat(String),
+    /// A fork does not have a valid activation condition.
+    #[error("hardfork {0} must be activated by `block` and/or `timestamp`, or by `ttd`")]
+    InvalidCondition(String),
+    /// A fork is defined more than once.
+    #[error("hardfork {0} is defined more than once")]
+    DuplicateFork(String),
+    /// The forks are not ordered by activation.
+    #[error("invalid hardforks spec: {0}")]
+    Invalid(#[from] HardforksValidationError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CustomHardfork, EthereumHardfork};
+
+    #[test]
+    fn parse_toml_spec() {
+        let spec = HardforksSpec::from_toml(
+            r#"
+            [[hardforks]]
+            name = "london"
+            block = 0
+
+            [[hardforks]]
+            name = "Paris"
+            ttd = "0x0"
+
+            [[hardforks]]
+            name = "Shanghai"
+            timestamp = 0
+
+            [[hardforks]]
+            name = "MyCustomFork"
+            timestamp = 100
+            "#,
+        )
+        .unwrap();
+        let forks = spec.into_hardforks().unwrap();
+
+        assert_eq!(forks.len(), 4);
+        assert_eq!(forks.fork(EthereumHardfork::London), ForkCondition::Block(0));
+        assert_eq!(
+            forks.fork(EthereumHardfork::Paris),
+            ForkCondition::TTD { fork_block: None, total_difficulty: U256::ZERO }
+        );
+        assert_eq!(forks.fork(EthereumHardfork::Shanghai), ForkCondition::Timestamp(0));
+        assert_eq!(forks.fork(CustomHardfork::new("MyCustomFork")), ForkCondition::Timestamp(100));
+        assert!(!forks.is_fork_active_at_timestamp(CustomHardfork::new("MyCustomFork"), 99));
+    }
+
+    #[test]
+    fn parse_json_spec() {
+        let spec = HardforksSpec::from_json(
+            r#"{"hardforks":[{"name":"Frontier","block":0},{"name":"Cancun","timestamp":10}]}"#,
+        )
+        .unwrap();
+        let forks = spec.into_hardforks().unwrap();
+
+        assert_eq!(forks.fork(EthereumHardfork::Frontier), ForkCondition::Block(0));
+        assert_eq!(forks.fork(EthereumHardfork::Cancun), ForkCondition::Timestamp(10));
+    }
+
+    #[test]
+    fn reject_invalid_specs() {
+        let spec = HardforksSpec::from_json(
+            r#"{"hardforks":[{"name":"London","block":0,"ttd":"0x0"}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(spec.into_hardforks(), Err(HardforksSpecError::InvalidCondition(_))));
+
+        let spec = HardforksSpec::from_json(
+            r#"{"hardforks":[{"name":"London","block":0},{"name":"london","block":1}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(spec.into_hardforks(), Err(HardforksSpecError::DuplicateFork(_))));
+
+        let spec = HardforksSpec::from_json(
+            r#"{"hardforks":[{"name":"London","block":10},{"name":"Berlin","block":0}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            spec.into_hardforks(),
+            Err(HardforksSpecError::Invalid(HardforksValidationError::Misordered(_)))
+        ));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{ChainHardforks, EthereumHardfork, ForkCondition, HardforksSpecError, OptimismHardfork};
+use alloy_primitives::{BlockNumber, U256};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// The activation timestamps of the OP-stack hardforks of a chain.
+///
+/// Forks that are not an [`OptimismHardfork`] yet, e.g. Delta, are ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct SuperchainHardforkTimes {
+    /// The Regolith activation timestamp. Absent for chains that activated it at genesis.
+    pub regolith_time: Option<u64>,
+    /// The Canyon activation timestamp, which also activates
+    /// [`EthereumHardfork::Shanghai`].
+    pub canyon_time: Option<u64>,
+    /// The Ecotone activation timestamp, which also activates [`EthereumHardfork::Cancun`].
+    pub ecotone_time: Option<u64>,
+    /// The Fjord activation timestamp.
+    pub fjord_time: Option<u64>,
+}
+
+/// The L2 genesis block of a [`SuperchainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct SuperchainGenesisBlock {
+    /// The number of the block, which is the Bedrock activation block.
+    pub number: BlockNumber,
+}
+
+/// The genesis of a [`SuperchainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct SuperchainGenesis {
+    /// The L2 genesis block.
+    pub l2: SuperchainGenesisBlock,
+}
+
+/// The config of a chain in the OP-stack
+/// [superchain registry](https://github.com/ethereum-optimism/superchain-registry), e.g.
+/// `superchain/configs/mainnet/base.toml`.
+///
+/// Only the fields needed to derive the hardforks of the chain are parsed. Hardfork timestamps
+/// are read both from the top level of the config and from a `[hardforks]` table, which is used
+/// by newer registry versions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SuperchainConfig {
+    /// The name of the chain.
+    pub name: String,
+    /// The chain ID.
+    pub chain_id: u64,
+    /// The genesis of the chain.
+    pub genesis: SuperchainGenesis,
+    /// Hardfork timestamps defined at the top level of the config.
+    #[serde(flatten)]
+    pub hardfork_times: SuperchainHardforkTimes,
+    /// Hardfork timestamps defined in the `[hardforks]` table of the config.
+    #[serde(default)]
+    pub hardforks: Option<SuperchainHardforkTimes>,
+}
+
+impl SuperchainConfig {
+    /// Parses a registry chain config from a TOML string.
+    pub fn from_toml(s: &str) -> Result<Self, HardforksSpecError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Loads a registry chain config from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, HardforksSpecError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the hardfork timestamps of the chain, preferring the `[hardforks]` table.
+    pub fn times(&self) -> SuperchainHardforkTimes {
+        let top_level = self.hardfork_times;
+        let Some(table) = self.hardforks else { return top_level };
+        SuperchainHardforkTimes {
+            regolith_time: table.regolith_time.or(top_level.regolith_time),
+            canyon_time: table.canyon_time.or(top_level.canyon_time),
+            ecotone_time: table.ecotone_time.or(top_level.ecotone_time),
+            fjord_time: table.fjord_time.or(top_level.fjord_time),
+        }
+    }
+
+    /// Builds the hardforks of the chain, in the same layout as [`OptimismHardfork::op_mainnet`].
+    ///
+    /// Pre-Bedrock history is not part of the registry, so the Ethereum hardforks up to Berlin are
+    /// activated at genesis, and the later ones at the Bedrock block.
+    pub fn hardforks(&self) -> ChainHardforks {
+        let bedrock_block = self.genesis.l2.number;
+        let times = self.times();
+
+        let mut forks = vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Homestead.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Tangerine.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::SpuriousDragon.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Byzantium.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Constantinople.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Petersburg.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Istanbul.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::MuirGlacier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Berlin.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(bedrock_block)),
+            (EthereumHardfork::ArrowGlacier.boxed(), ForkCondition::Block(bedrock_block)),
+            (EthereumHardfork::GrayGlacier.boxed(), ForkCondition::Block(bedrock_block)),
+            (
+                EthereumHardfork::Paris.boxed(),
+                ForkCondition::TTD {
+                    fork_block: Some(bedrock_block),
+                    total_difficulty: U256::ZERO,
+                },
+            ),
+            (OptimismHardfork::Bedrock.boxed(), ForkCondition::Block(bedrock_block)),
+            (
+                OptimismHardfork::Regolith.boxed(),
+                ForkCondition::Timestamp(times.regolith_time.unwrap_or_default()),
+            ),
+        ];
+
+        if let Some(canyon) = times.canyon_time {
+            forks.push((EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(canyon)));
+            forks.push((OptimismHardfork::Canyon.boxed(), ForkCondition::Timestamp(canyon)));
+        }
+        if let Some(ecotone) = times.ecotone_time {
+            forks.push((EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(ecotone)));
+            forks.push((OptimismHardfork::Ecotone.boxed(), ForkCondition::Timestamp(ecotone)));
+        }
+        if let Some(fjord) = times.fjord_time {
+            forks.push((OptimismHardfork::Fjord.boxed(), ForkCondition::Timestamp(fjord)));
+        }
+
+        ChainHardforks::new(forks)
+    }
+}
+
+impl ChainHardforks {
+    /// Loads the hardforks of an OP-stack chain from its superchain registry config file.
+    ///
+    /// See [`SuperchainConfig`].
+    pub fn from_superchain_config_file(path: impl AsRef<Path>) -> Result<Self, HardforksSpecError> {
+        Ok(SuperchainConfig::load(path)?.hardforks())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_mainnet_from_registry() {
+        let config = SuperchainConfig::from_toml(
+            r#"
+            name = "Base"
+            chain_id = 8453
+            public_rpc = "https://mainnet.base.org"
+            superchain_time = 0
+            canyon_time = 1704992401
+            delta_time = 1708560000
+            ecotone_time = 1710374401
+            fjord_time = 1720627201
+
+            [genesis]
+              l2_time = 1686789347
+              [genesis.l1]
+                hash = "0x5c13d307623a926cd31415036c8b7fa14572f9dac64528e857a470511fc30771"
+                number = 17481768
+              [genesis.l2]
+                hash = "0xf712aa9241cc24369b143cf6dce85f0902a9731e70d66818a3a5845b296c73dd"
+                number = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.chain_id, 8453);
+        assert_eq!(config.hardforks(), OptimismHardfork::base_mainnet());
+    }
+
+    #[test]
+    fn hardforks_table() {
+        let config = SuperchainConfig::from_toml(
+            r#"
+            name = "Test"
+            chain_id = 1
+            canyon_time = 1
+
+            [hardforks]
+              canyon_time = 10
+              ecotone_time = 20
+
+            [genesis.l2]
+              number = 5
+            "#,
+        )
+        .unwrap();
+        let hardforks = config.hardforks();
+
+        assert_eq!(hardforks.fork(OptimismHardfork::Bedrock), ForkCondition::Block(5));
+        assert_eq!(hardforks.fork(OptimismHardfork::Canyon), ForkCondition::Timestamp(10));
+        assert_eq!(hardforks.fork(EthereumHardfork::Cancun), ForkCondition::Timestamp(20));
+        assert_eq!(hardforks.fork(OptimismHardfork::Fjord), ForkCondition::Never);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{ChainHardforks, ForkCondition};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+/// Error returned by [`ChainHardforks::validate`].
+#[derive(Clone, Debug, thiserror_no_std::Error, PartialEq, Eq)]
+pub enum HardforksValidationError {
+    /// A fork is activated before a fork that precedes it in the schedule.
+    #[error(
+        "hardfork {} ({:?}) is activated before the preceding hardfork {} ({:?})",
+        .0.fork,
+        .0.condition,
+        .0.previous,
+        .0.previous_condition
+    )]
+    Misordered(Box<MisorderedHardfork>),
+}
+
+/// A fork that is activated before a fork that precedes it in the schedule, see
+/// [`HardforksValidationError::Misordered`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MisorderedHardfork {
+    /// The fork that is activated too early.
+    pub fork: &'static str,
+    /// The activation condition of the fork.
+    pub condition: ForkCondition,
+    /// The preceding fork.
+    pub previous: &'static str,
+    /// The activation condition of the preceding fork.
+    pub previous_condition: ForkCondition,
+}
+
+/// A fork whose activation differs between two [`ChainHardforks`], see [`ChainHardforks::diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HardforkDiff {
+    /// The name of the fork.
+    pub name: &'static str,
+    /// The activation condition in the schedule `diff` was called on, `None` if the fork is not
+    /// part of it.
+    pub ours: Option<ForkCondition>,
+    /// The activation condition in the other schedule, `None` if the fork is not part of it.
+    pub theirs: Option<ForkCondition>,
+}
+
+impl ChainHardforks {
+    /// Checks that the forks are ordered by activation.
+    ///
+    /// Block based forks (including TTD based forks with a known activation block) and timestamp
+    /// based forks must each be activated in ascending order, and no block based fork may follow a
+    /// timestamp based one. Forks that are never activated and TTD based forks without a known
+    /// activation block are ignored.
+    pub fn validate(&self) -> Result<(), HardforksValidationError> {
+        let mut last_block = None;
+        let mut last_timestamp = None;
+
+        for (fork, condition) in self.forks_iter() {
+            let (previous, ordered) = match condition {
+                ForkCondition::Block(block) |
+                ForkCondition::TTD { fork_block: Some(block), .. } => {
+                    // a timestamp based fork must not precede a block based fork
+                    let previous = last_timestamp.or(last_block);
+                    let ordered = last_timestamp.is_none() &&
+                        last_block.map_or(true, |(_, previous, _)| block >= previous);
+                    last_block = Some((fork.name(), block, condition));
+                    (previous, ordered)
+                }
+                ForkCondition::Timestamp(timestamp) |
+                ForkCondition::BlockAndTimestamp { timestamp, .. } => {
+                    let previous = last_timestamp;
+                    let ordered = previous.map_or(true, |(_, previous, _)| timestamp >= previous);
+                    last_timestamp = Some((fork.name(), timestamp, condition));
+                    (previous, ordered)
+                }
+                ForkCondition::TTD { fork_block: None, .. } | ForkCondition::Never => continue,
+            };
+
+            if !ordered {
+                let (previous, _, previous_condition) =
+                    previous.expect("misordered forks have a predecessor");
+                return Err(HardforksValidationError::Misordered(Box::new(MisorderedHardfork {
+                    fork: fork.name(),
+                    condition,
+                    previous,
+                    previous_condition,
+                })))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the forks whose activation differs between `self` and `other`, including forks that
+    /// are only part of one of them.
+    ///
+    /// The differences are ordered like the forks of `self`, followed by the forks only part of
+    /// `other`.
+    pub fn diff(&self, other: &Self) -> Vec<HardforkDiff> {
+        let ours = self.forks_iter().filter_map(|(fork, condition)| {
+            let theirs = other.map.get(fork.name()).copied();
+            (theirs != Some(condition)).then_some(HardforkDiff {
+                name: fork.name(),
+                ours: Some(condition),
+                theirs,
+            })
+        });
+        let theirs = other
+            .forks_iter()
+            .filter(|(fork, _)| !self.map.contains_key(fork.name()))
+            .map(|(fork, condition)| HardforkDiff {
+                name: fork.name(),
+                ours: None,
+                theirs: Some(condition),
+            });
+
+        ours.chain(theirs).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthereumHardfork;
+
+    #[test]
+    fn validate_schedule() {
+        let mut hardforks = ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::London.boxed(), ForkCondition::Block(10)),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(100)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(200)),
+        ]);
+        assert_eq!(hardforks.validate(), Ok(()));
+
+        hardforks.insert(EthereumHardfork::Cancun, ForkCondition::Timestamp(50));
+        assert_eq!(
+            hardforks.validate(),
+            Err(HardforksValidationError::Misordered(Box::new(MisorderedHardfork {
+                fork: "Cancun",
+                condition: ForkCondition::Timestamp(50),
+                previous: "Shanghai",
+                previous_condition: ForkCondition::Timestamp(100),
+            })))
+        );
+
+        hardforks.insert(EthereumHardfork::Cancun, ForkCondition::Block(20));
+        assert!(hardforks.validate().is_err());
+    }
+
+    #[test]
+    fn diff_schedules() {
+        let ours = ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(100)),
+            (EthereumHardfork::Cancun.boxed(), ForkCondition::Timestamp(200)),
+        ]);
+        let theirs = ChainHardforks::new(vec![
+            (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+            (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(150)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Timestamp(300)),
+        ]);
+
+        assert_eq!(
+            ours.diff(&theirs),
+            vec![
+                HardforkDiff {
+                    name: "Shanghai",
+                    ours: Some(ForkCondition::Timestamp(100)),
+                    theirs: Some(ForkCondition::Timestamp(150)),
+                },
+                HardforkDiff {
+                    name: "Cancun",
+                    ours: Some(ForkCondition::Timestamp(200)),
+                    theirs: None,
+                },
+                HardforkDiff {
+                    name: "Prague",
+                    ours: None,
+                    theirs: Some(ForkCondition::Timestamp(300)),
+                },
+            ]
+        );
+        assert!(ours.diff(&ours).is_empty());
+    }
+}
@@ -4,7 +4,15 @@
 //!
 //! ## Feature Flags
 //!
-//! - `arbitrary`: Adds `proptest` and `arbitrary` support for primitive types.
+//! - `arbitrary`: Adds `proptest` and `arbitrary` support for primitive types, hardforks, fork
+//!   conditions and [`ChainHardforks`].
+//! - `bsc`: Adds the `BscHardfork`s of BNB Smart Chain.
+//! - `polygon`: Adds the `PolygonHardfork`s of Polygon PoS.
+//! - `spec`: Adds loading of hardfork schedules from JSON or TOML files, and from OP-stack
+//!   superchain registry chain configs.
+//! - `stream`: Adds a `Stream` of the hardforks that activate as the canonical head advances.
+//! - `std`: Uses the standard library. Without it, the crate only depends on `alloc`, so
+//!   [`ChainHardforks`] can be used in `no_std` environments such as zkVM guest programs.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -35,13 +43,37 @@ pub use forkid::{
     EnrForkIdEntry, ForkFilter, ForkFilterKey, ForkHash, ForkId, ForkTransition, ValidationError,
 };
 /// Exports related to hardforks
-pub use hardfork::{EthereumHardfork, Hardfork, OptimismHardfork, DEV_HARDFORKS};
+pub use hardfork::{
+    ArbitrumHardfork, CustomHardfork, DevHardforksBuilder, EthereumHardfork, Hardfork,
+    OptimismHardfork, GOERLI_CHAIN_ID, HOLESKY_CHAIN_ID, HOODI_CHAIN_ID, MAINNET_CHAIN_ID,
+    SEPOLIA_CHAIN_ID,
+};
+/// BNB Smart Chain hardforks
+#[cfg(feature = "bsc")]
+pub use hardfork::BscHardfork;
+/// Polygon hardforks
+#[cfg(feature = "polygon")]
+pub use hardfork::PolygonHardfork;
+/// The dev and public network hardforks, which require `std` for lazy initialization
+#[cfg(feature = "std")]
+pub use hardfork::{
+    DEV_HARDFORKS, HOLESKY_HARDFORKS, HOODI_HARDFORKS, MAINNET_HARDFORKS, SEPOLIA_HARDFORKS,
+};
 /// Export the Head structure representing Ethereum block headers
 pub use head::Head;
 
-pub use display::DisplayHardforks;      /// Export for displaying hardforks
-pub use forkcondition::ForkCondition;   /// Export for fork conditions
-pub use hardforks::*;                   /// Export all hardforks definitions
+pub use display::DisplayHardforks;
+pub use forkcondition::{ForkCondition, ParseForkConditionError};
+pub use hardforks::*;
+
+/// Re-exports used by the [`hardfork!`] macro, so it works in crates without `std`
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(not(feature = "std"))]
+    pub use alloc::{boxed::Box, format, string::String};
+    #[cfg(feature = "std")]
+    pub use std::{boxed::Box, format, string::String};
+}
 
 /// Public exports when the "arbitrary" feature is enabled (for testing)
 #[cfg(any(test, feature = "arbitrary"))]
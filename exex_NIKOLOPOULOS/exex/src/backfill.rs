@@ -0,0 +1,163 @@
+use crate::ExExNotification;
+use reth_evm::execute::{BatchExecutor, BlockExecutionError, BlockExecutorProvider};
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    BlockReader, Chain, HeaderProvider, ProviderError, StateProviderFactory, TransactionVariant,
+};
+use reth_prune_types::PruneModes;
+use reth_revm::database::StateProviderDatabase;
+use reth_tracing::tracing::{debug, trace};
+use std::{ops::RangeInclusive, sync::Arc};
+
+/// The default number of blocks executed in a single backfill batch.
+pub const DEFAULT_BACKFILL_BATCH_SIZE: u64 = 1000;
+
+/// Factory for creating new backfill jobs.
+#[derive(Debug, Clone)]
+pub struct BackfillJobFactory<E, P> {
+    /// the block executor used to re-execute historical blocks.
+    executor: E,
+    /// the provider used to read historical blocks and state.
+    provider: P,
+    /// the prune modes passed to the batch executor.
+    prune_modes: PruneModes,
+    /// the maximum number of blocks executed in a single batch.
+    batch_size: u64,
+}
+
+impl<E, P> BackfillJobFactory<E, P> {
+    /// Creates a new [`BackfillJobFactory`].
+    pub fn new(executor: E, provider: P) -> Self {
+        Self {
+            executor,
+            provider,
+            prune_modes: PruneModes::none(),
+            batch_size: DEFAULT_BACKFILL_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the prune modes
+    pub fn with_prune_modes(mut self, prune_modes: PruneModes) -> Self {
+        self.prune_modes = prune_modes;
+        self
+    }
+
+    /// Sets the maximum number of blocks executed in a single batch.
+    ///
+    /// A batch size of `0` is treated as `1`.
+    pub const fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl<E: Clone, P: Clone> BackfillJobFactory<E, P> {
+    /// Creates a new backfill job for the given range.
+    pub fn backfill(&self, range: RangeInclusive<BlockNumber>) -> BackfillJob<E, P> {
+        BackfillJob {
+            executor: self.executor.clone(),
+            provider: self.provider.clone(),
+            prune_modes: self.prune_modes.clone(),
+            batch_size: self.batch_size.max(1),
+            range,
+        }
+    }
+}
+
+/// Backfill job started for a specific range.
+///
+/// The job is an [`Iterator`] that re-executes the historical blocks in the range in batches of
+/// at most `batch_size` blocks, and yields one [`ExExNotification::ChainCommitted`] per batch.
+/// This lets an `ExEx` consume historical blocks exactly like live notifications before switching
+/// over to [`ExExContext::notifications`](crate::ExExContext::notifications).
+///
+/// Execution is blocking, so the iterator should be driven from a blocking task (e.g.
+/// [`tokio::task::spawn_blocking`]).
+#[derive(Debug)]
+pub struct BackfillJob<E, P> {
+    executor: E,
+    provider: P,
+    prune_modes: PruneModes,
+    batch_size: u64,
+    range: RangeInclusive<BlockNumber>,
+}
+
+impl<E, P> BackfillJob<E, P> {
+    /// Returns the range of blocks that has not been backfilled yet.
+    pub const fn remaining(&self) -> &RangeInclusive<BlockNumber> {
+        &self.range
+    }
+}
+
+impl<E, P> Iterator for BackfillJob<E, P>
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + HeaderProvider + StateProviderFactory,
+{
+    type Item = Result<ExExNotification, BlockExecutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None
+        }
+
+        let start = *self.range.start();
+        let end = start.saturating_add(self.batch_size - 1).min(*self.range.end());
+
+        // advance the range before executing, so an error does not make the job loop forever
+        self.range = end.saturating_add(1)..=*self.range.end();
+
+        Some(self.execute_range(start..=end).map(|chain| ExExNotification::ChainCommitted {
+            new: Arc::new(chain),
+        }))
+    }
+}
+
+impl<E, P> BackfillJob<E, P>
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + HeaderProvider + StateProviderFactory,
+{
+    /// Executes the given range of blocks on top of the state at `range.start() - 1` and returns
+    /// the resulting [`Chain`].
+    fn execute_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Chain, BlockExecutionError> {
+        debug!(target: "exex::backfill", ?range, "Executing block range");
+
+        let mut executor = self.executor.batch_executor(
+            StateProviderDatabase::new(
+                self.provider.history_by_block_number(range.start().saturating_sub(1))?,
+            ),
+            self.prune_modes.clone(),
+        );
+
+        let mut blocks = Vec::with_capacity((range.end() - range.start() + 1) as usize);
+        for block_number in range.clone() {
+            let td = self
+                .provider
+                .header_td_by_number(block_number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            let block = self
+                .provider
+                .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+            trace!(
+                target: "exex::backfill",
+                number = block_number,
+                txs = block.body.len(),
+                "Executing block"
+            );
+            executor.execute_and_verify_one((&block, td).into())?;
+
+            blocks.push(block.seal_slow());
+        }
+
+        let execution_outcome = executor.finalize();
+        debug!(target: "exex::backfill", ?range, "Finished executing block range");
+
+        Ok(Chain::new(blocks, execution_outcome, None))
+    }
+}
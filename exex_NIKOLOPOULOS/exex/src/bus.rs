@@ -0,0 +1,65 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast;
+
+/// The number of messages of a single type buffered for the slowest subscriber.
+///
+/// Subscribers that fall further behind miss the oldest messages and receive a
+/// [`broadcast::error::RecvError::Lagged`] error.
+pub const DEFAULT_MESSAGE_BUS_CAPACITY: usize = 1024;
+
+/// A broadcast bus for typed messages between `ExEx`'s.
+///
+/// Every message type gets its own broadcast channel, which is created the first time the type
+/// is published or subscribed to. All `ExEx`'s of a node share the same bus, so one `ExEx` can
+/// publish e.g. decoded events that another `ExEx` consumes.
+///
+/// Messages published while there are no subscribers are dropped.
+#[derive(Debug, Clone)]
+pub struct ExExMessageBus {
+    /// the broadcast senders, keyed by the type of the message.
+    channels: Arc<Mutex<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>,
+    /// the capacity of newly created channels.
+    capacity: usize,
+}
+
+impl Default for ExExMessageBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_MESSAGE_BUS_CAPACITY)
+    }
+}
+
+impl ExExMessageBus {
+    /// Creates a new bus, buffering up to `capacity` messages per type.
+    pub fn new(capacity: usize) -> Self {
+        Self { channels: Default::default(), capacity }
+    }
+
+    /// Returns the sender for messages of type `T`, creating the channel if needed.
+    pub fn publisher<T: Clone + Send + Sync + 'static>(&self) -> broadcast::Sender<T> {
+        let mut channels = self.channels.lock().expect("message bus lock poisoned");
+        channels
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(broadcast::channel::<T>(self.capacity).0))
+            .downcast_ref::<broadcast::Sender<T>>()
+            .expect("channel is keyed by its message type")
+            .clone()
+    }
+
+    /// Subscribes to messages of type `T`.
+    ///
+    /// The receiver only gets messages published after this call.
+    pub fn subscribe<T: Clone + Send + Sync + 'static>(&self) -> broadcast::Receiver<T> {
+        self.publisher::<T>().subscribe()
+    }
+
+    /// Publishes a message to all subscribers of its type.
+    ///
+    /// Returns the number of subscribers the message was sent to.
+    pub fn publish<T: Clone + Send + Sync + 'static>(&self, message: T) -> usize {
+        self.publisher::<T>().send(message).unwrap_or_default()
+    }
+}
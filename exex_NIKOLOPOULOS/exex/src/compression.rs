@@ -0,0 +1,237 @@
+use crate::ExExNotification;
+use reth_primitives::{revm_primitives::StorageSlot, Address, Receipt, B256, U256};
+use reth_provider::Chain;
+use std::{
+    future::Future,
+    mem::{size_of, size_of_val},
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::oneshot;
+
+/// The zstd compression level of queued notifications.
+///
+/// Notifications are compressed for every lagging `ExEx`, so this favors speed over ratio.
+const COMPRESSION_LEVEL: i32 = 1;
+
+/// A notification queued for a lagging `ExEx`, either as is or compressed.
+///
+/// Compressed notifications do not hold on to the chains of the notification, so the memory of a
+/// chain is freed once every `ExEx` received or compressed it. Notifications are compressed and
+/// decompressed on blocking tasks, so the manager's task never waits for zstd or bincode.
+#[derive(Debug)]
+pub(crate) enum QueuedNotification {
+    /// the notification as received by the manager.
+    Full(ExExNotification),
+    /// the notification is being compressed on a blocking task.
+    Compressing {
+        /// the estimated size of the notification before compression.
+        size: usize,
+        /// receives the compressed notification.
+        compressed: oneshot::Receiver<QueuedNotification>,
+    },
+    /// the reverted and committed chains of the notification, serialized and compressed.
+    Compressed(Vec<u8>),
+}
+
+impl QueuedNotification {
+    /// Compresses the notification on a blocking task.
+    pub(crate) fn compress_in_background(notification: ExExNotification) -> Self {
+        let size = notification_size(&notification);
+        let (tx, compressed) = oneshot::channel();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(Self::compress(notification));
+        });
+        Self::Compressing { size, compressed }
+    }
+
+    /// Compresses the notification, falling back to keeping it as is if it can not be
+    /// serialized.
+    pub(crate) fn compress(notification: ExExNotification) -> Self {
+        let reverted = notification.reverted_chain();
+        let committed = notification.committed_chain();
+
+        let compressed = zstd::Encoder::new(Vec::new(), COMPRESSION_LEVEL)
+            .map_err(eyre::Report::from)
+            .and_then(|mut encoder| {
+                let chains = (reverted.as_deref(), committed.as_deref());
+                bincode::serialize_into(&mut encoder, &chains)?;
+                Ok(encoder.finish()?)
+            });
+        match compressed {
+            Ok(data) => Self::Compressed(data),
+            Err(_) => Self::Full(notification),
+        }
+    }
+
+    /// Returns the (estimated) number of bytes held in memory for the notification.
+    ///
+    /// A notification that is being compressed is counted with its size before compression.
+    pub(crate) fn size(&self) -> usize {
+        match self {
+            Self::Full(notification) => notification_size(notification),
+            Self::Compressing { size, .. } => *size,
+            Self::Compressed(data) => data.capacity(),
+        }
+    }
+
+    /// Returns the notification, decompressing it on a blocking task if needed.
+    pub(crate) fn decompress(self) -> Decompressing {
+        match self {
+            Self::Full(notification) => Decompressing::Ready(Some(notification)),
+            queued => {
+                let (tx, decompressed) = oneshot::channel();
+                tokio::task::spawn_blocking(move || {
+                    let _ = tx.send(queued.decompress_blocking());
+                });
+                Decompressing::Blocking(decompressed)
+            }
+        }
+    }
+
+    /// Returns the notification, waiting for the compression to finish and decompressing it if
+    /// needed.
+    fn decompress_blocking(self) -> eyre::Result<ExExNotification> {
+        let data = match self {
+            Self::Full(notification) => return Ok(notification),
+            Self::Compressing { compressed, .. } => {
+                return compressed
+                    .blocking_recv()
+                    .map_err(|_| eyre::eyre!("notification compression was aborted"))?
+                    .decompress_blocking()
+            }
+            Self::Compressed(data) => data,
+        };
+
+        let (reverted, committed): (Option<Chain>, Option<Chain>) =
+            bincode::deserialize_from(zstd::Decoder::new(data.as_slice())?)?;
+        match (reverted.map(Arc::new), committed.map(Arc::new)) {
+            (None, Some(new)) => Ok(ExExNotification::ChainCommitted { new }),
+            (Some(old), Some(new)) => Ok(ExExNotification::ChainReorged { old, new }),
+            (Some(old), None) => Ok(ExExNotification::ChainReverted { old }),
+            (None, None) => Err(eyre::eyre!("compressed notification without chains")),
+        }
+    }
+}
+
+/// A queued notification that is being decompressed, see [`QueuedNotification::decompress`].
+#[derive(Debug)]
+pub(crate) enum Decompressing {
+    /// the notification was not compressed.
+    Ready(Option<ExExNotification>),
+    /// the notification is decompressed on a blocking task.
+    Blocking(oneshot::Receiver<eyre::Result<ExExNotification>>),
+}
+
+impl Future for Decompressing {
+    type Output = eyre::Result<ExExNotification>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            Self::Ready(notification) => {
+                Poll::Ready(Ok(notification.take().expect("polled after completion")))
+            }
+            Self::Blocking(decompressed) => {
+                Poll::Ready(ready!(Pin::new(decompressed).poll(cx)).unwrap_or_else(|_| {
+                    Err(eyre::eyre!("notification decompression was aborted"))
+                }))
+            }
+        }
+    }
+}
+
+/// Returns an estimate of the number of bytes held in memory for the notification.
+///
+/// Chains shared with other notifications are counted for each of them.
+pub(crate) fn notification_size(notification: &ExExNotification) -> usize {
+    let reverted = notification.reverted_chain().map_or(0, |chain| chain_size(&chain));
+    let committed = notification.committed_chain().map_or(0, |chain| chain_size(&chain));
+    reverted + committed
+}
+
+/// Returns an estimate of the number of bytes held in memory for the chain.
+fn chain_size(chain: &Chain) -> usize {
+    let blocks = chain
+        .blocks_iter()
+        .map(|block| block.size() + block.senders.len() * size_of::<Address>())
+        .sum::<usize>();
+
+    let outcome = chain.execution_outcome();
+    let receipts = outcome
+        .receipts()
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|receipt| {
+            size_of::<Receipt>() +
+                receipt
+                    .logs
+                    .iter()
+                    .map(|log| {
+                        size_of::<Address>() +
+                            log.data.topics().len() * size_of::<B256>() +
+                            log.data.data.len()
+                    })
+                    .sum::<usize>()
+        })
+        .sum::<usize>();
+    let state = outcome
+        .bundle
+        .state
+        .values()
+        .map(|account| {
+            size_of::<Address>() +
+                size_of_val(account) +
+                account.storage.len() * size_of::<(U256, StorageSlot)>()
+        })
+        .sum::<usize>();
+
+    blocks + receipts + state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders};
+    use reth_provider::ExecutionOutcome;
+
+    fn chain(number: u64) -> Arc<Chain> {
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header: Header { number, gas_used: 21_000, ..Default::default() }.seal_slow(),
+                ..Default::default()
+            },
+            senders: vec![Address::with_last_byte(1)],
+        };
+        let receipt = Receipt { cumulative_gas_used: 21_000, ..Default::default() };
+        let execution_outcome = ExecutionOutcome {
+            receipts: vec![vec![Some(receipt)]].into(),
+            first_block: number,
+            ..Default::default()
+        };
+        Arc::new(Chain::new(vec![block], execution_outcome, None))
+    }
+
+    #[tokio::test]
+    async fn compress_roundtrip() {
+        for notification in [
+            ExExNotification::ChainCommitted { new: chain(1) },
+            ExExNotification::ChainReorged { old: chain(1), new: chain(2) },
+            ExExNotification::ChainReverted { old: chain(1) },
+        ] {
+            let compressed = QueuedNotification::compress(notification.clone());
+            assert!(matches!(compressed, QueuedNotification::Compressed(_)));
+            assert_eq!(compressed.decompress().await.unwrap(), notification);
+
+            let compressed = QueuedNotification::compress_in_background(notification.clone());
+            assert_eq!(compressed.decompress().await.unwrap(), notification);
+        }
+    }
+
+    #[tokio::test]
+    async fn decompress_corrupted() {
+        let compressed = QueuedNotification::Compressed(vec![1, 2, 3]);
+        assert!(compressed.decompress().await.is_err());
+    }
+}
@@ -1,13 +1,30 @@
-use crate::{ExExEvent, ExExNotification};
-use reth_node_api::FullNodeComponents;
+use crate::{
+    BackfillJob, BackfillJobFactory, ExExEvent, ExExMessageBus, ExExNotification,
+    ExExNotifications, ExExSnapshotHooks, ExExSnapshots, FinishedHeightStore,
+};
+use futures::Stream;
+use reth_node_api::{FullNodeComponents, PayloadTypes};
 use reth_node_core::node_config::NodeConfig;
-use reth_primitives::Head;
+use reth_primitives::{
+    BlockHashOrNumber, BlockNumber, Head, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, TxHash,
+};
+use reth_provider::{ProviderResult, StateProviderBox, StateProviderFactory};
 use reth_tasks::TaskExecutor;
-use std::fmt::Debug;
-use tokio::sync::mpsc::{Receiver, UnboundedSender};
+use reth_transaction_pool::{
+    error::PoolResult, FromRecoveredPooledTransaction, NewSubpoolTransactionStream,
+    TransactionOrigin, TransactionPool,
+};
+use reth_tracing::tracing::warn;
+use serde::de::DeserializeOwned;
+use std::{fmt::Debug, io, ops::RangeInclusive};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
 
 /// Captures the context that an `ExEx` has access to
 pub struct ExExContext<Node: FullNodeComponents> {
+    /// The unique ID of the exex
+    pub id: String,
     /// The current head of the blockchain at launch
     pub head: Head,
     /// The config of the node
@@ -28,7 +45,27 @@ pub struct ExExContext<Node: FullNodeComponents> {
     ///
     /// Once a an [`ExExNotification`] is sent over the channel, it is considered delivered by the
     /// node
-    pub notifications: Receiver<ExExNotification>,
+    ///
+    /// If the `ExEx` was registered with a [`NotificationFilter`](crate::NotificationFilter),
+    /// committed chains that do not match the filter are never sent over this channel.
+    ///
+    /// Notifications with blocks that were already received are skipped, see
+    /// [`ExExNotifications`].
+    pub notifications: ExExNotifications,
+    /// Token that is cancelled when the node requests the `ExEx` to shut down.
+    ///
+    /// # Important
+    ///
+    /// Once the token is cancelled, the exex should persist its state, emit an
+    /// [`ExExEvent::ShutdownComplete`] event and resolve with `Ok(())`. The node aborts the exex
+    /// if it does not do so within the shutdown timeout.
+    pub shutdown: CancellationToken,
+    /// The store the `FinishedHeight` events of the exex are persisted in, if any.
+    pub finished_height_store: Option<FinishedHeightStore>,
+    /// Bus for exchanging typed messages with the other exexes of the node
+    pub messages: ExExMessageBus,
+    /// Snapshot support for the derived state of the exex, if enabled by the node
+    pub snapshots: Option<ExExSnapshots>,
 
     /// node components
     pub components: Node,
@@ -38,6 +75,8 @@ impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
     /// Implements the `Debug` trait for `ExExContext` to allow for formatted output.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExExContext")
+            // Display the exex ID.
+            .field("id", &self.id)
             // Display the current head.
             .field("head", &self.head)
             // Display the node configuration.
@@ -48,6 +87,14 @@ impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
             .field("events", &self.events)
             // Display the notifications receiver.
             .field("notifications", &self.notifications)
+            // Display the shutdown token.
+            .field("shutdown", &self.shutdown)
+            // Display the finished height store.
+            .field("finished_height_store", &self.finished_height_store)
+            // Display the message bus.
+            .field("messages", &self.messages)
+            // Display the snapshot support.
+            .field("snapshots", &self.snapshots)
             // Display a placeholder for components to avoid verbose output.
             .field("components", &"...")
             .finish()
@@ -60,6 +107,36 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
         self.components.pool()
     }
 
+    /// Returns a stream of the transactions added to the pending sub-pool of the node's
+    /// transaction pool, i.e. the transactions that are ready to be included in the next block.
+    pub fn pending_transactions(
+        &self,
+    ) -> NewSubpoolTransactionStream<<Node::Pool as TransactionPool>::Transaction> {
+        self.pool().new_pending_pool_transactions_listener()
+    }
+
+    /// Submits a signed transaction to the node's transaction pool.
+    ///
+    /// The transaction is added as a local transaction, so it is propagated to peers and not
+    /// evicted in favor of external transactions.
+    pub async fn submit_transaction(
+        &self,
+        transaction: PooledTransactionsElementEcRecovered,
+    ) -> PoolResult<TxHash> {
+        let transaction =
+            FromRecoveredPooledTransaction::from_recovered_pooled_transaction(transaction);
+        self.pool().add_transaction(TransactionOrigin::Local, transaction).await
+    }
+
+    /// Decodes an EIP-2718 encoded signed transaction and submits it to the node's transaction
+    /// pool, see [`Self::submit_transaction`].
+    pub async fn submit_raw_transaction(&self, mut data: &[u8]) -> eyre::Result<TxHash> {
+        let transaction = PooledTransactionsElement::decode_enveloped(&mut data)?
+            .try_into_ecrecovered()
+            .map_err(|_| eyre::eyre!("invalid transaction signature"))?;
+        Ok(self.submit_transaction(transaction).await?)
+    }
+
     /// Returns the node's evm config
     pub fn evm_config(&self) -> &Node::Evm {
         self.components.evm_config()
@@ -75,6 +152,25 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
         self.components.provider()
     }
 
+    /// Returns a read-only state provider pinned at the state after the given block.
+    ///
+    /// The provider reads from a single database transaction, so it sees consistent state as of
+    /// that block while the node keeps writing new blocks. Pass the hash of the tip of the
+    /// notification being processed, see [`ExExNotification::sequence`], to also be safe against
+    /// reorgs of that block.
+    ///
+    /// The provider should be dropped when it is not needed anymore, since an open read
+    /// transaction keeps the database from reusing freed pages.
+    pub fn provider_at(
+        &self,
+        block: impl Into<BlockHashOrNumber>,
+    ) -> ProviderResult<StateProviderBox> {
+        match block.into() {
+            BlockHashOrNumber::Hash(hash) => self.provider().history_by_block_hash(hash),
+            BlockHashOrNumber::Number(number) => self.provider().history_by_block_number(number),
+        }
+    }
+
     /// Returns the handle to the network
     pub fn network(&self) -> &reth_network::NetworkHandle {
         self.components.network()
@@ -89,4 +185,102 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
     pub fn task_executor(&self) -> &TaskExecutor {
         self.components.task_executor()
     }
+
+    /// Subscribes to the payloads built by the node's payload builder.
+    ///
+    /// Unlike [`Self::notifications`], the stream yields pending blocks before they are
+    /// canonicalized (if ever), which is what MEV and preconfirmation services need. Payloads built
+    /// while the stream is not polled fast enough are skipped.
+    pub async fn pending_blocks(
+        &self,
+    ) -> eyre::Result<impl Stream<Item = <Node::Engine as PayloadTypes>::BuiltPayload>> {
+        let events = self.payload_builder().subscribe().await?;
+        Ok(events.into_built_payload_stream())
+    }
+
+    /// Loads the configuration of the exex from the `[exex.<id>]` section of the node's config
+    /// file.
+    ///
+    /// Returns `None` if the config file or the section does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```toml
+    /// [exex.my-indexer]
+    /// rpc_url = "http://localhost:8545"
+    /// database = "/var/lib/indexer"
+    /// ```
+    pub fn config<T: DeserializeOwned>(&self) -> eyre::Result<Option<T>> {
+        let path = self.config.config.clone().unwrap_or_else(|| self.config.datadir().config());
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut table: toml::Table = toml::from_str(&contents)?;
+        let section = match table.remove("exex") {
+            Some(toml::Value::Table(mut exexs)) => exexs.remove(&self.id),
+            _ => None,
+        };
+
+        section
+            .map(|section| section.try_into())
+            .transpose()
+            .map_err(|err| eyre::eyre!("invalid config for exex {}: {err}", self.id))
+    }
+
+    /// Returns the last finished height persisted for the exex, if any.
+    ///
+    /// An exex can use this to resume where it left off after a restart, e.g. by backfilling
+    /// from the block after it with [`Self::backfill`].
+    pub fn last_finished_height(&self) -> Option<BlockNumber> {
+        let store = self.finished_height_store.as_ref()?;
+        store
+            .load()
+            .inspect_err(|err| {
+                warn!(path = %store.path().display(), %err, "Failed to load finished height")
+            })
+            .ok()
+            .flatten()
+    }
+
+    /// Registers the hooks used to snapshot and restore the derived state of the exex.
+    ///
+    /// The latest snapshot, if any, is restored right away and the height it was taken at is
+    /// returned, so the exex can resume from the block after it instead of replaying from
+    /// genesis. From then on, the node takes a snapshot at a fixed block interval of the exex's
+    /// finished height.
+    ///
+    /// Returns an error if the node did not enable snapshots for the exex.
+    pub fn register_snapshot_hooks(
+        &self,
+        hooks: impl ExExSnapshotHooks,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        let snapshots = self
+            .snapshots
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("snapshots are not enabled for exex {}", self.id))?;
+        snapshots.register(Box::new(hooks))
+    }
+
+    /// Returns a factory for backfill jobs that re-execute historical blocks using the node's
+    /// executor and provider.
+    ///
+    /// Use this to configure the batch size or prune modes before creating jobs.
+    pub fn backfill_job_factory(&self) -> BackfillJobFactory<Node::Executor, Node::Provider> {
+        BackfillJobFactory::new(self.block_executor().clone(), self.provider().clone())
+    }
+
+    /// Creates a [`BackfillJob`] that yields [`ExExNotification::ChainCommitted`]s for the given
+    /// historical block range, using the default batch size.
+    ///
+    /// This lets an `ExEx` catch up from an arbitrary height (e.g. genesis) before it starts
+    /// consuming [`Self::notifications`].
+    pub fn backfill(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> BackfillJob<Node::Executor, Node::Provider> {
+        self.backfill_job_factory().backfill(range)
+    }
 }
@@ -0,0 +1,267 @@
+use crate::ExExNotification;
+use futures::Stream;
+use reth_primitives::{BlockHash, BlockNumber};
+use reth_provider::{Chain, ChainSplit, ChainSplitTarget};
+use reth_tracing::tracing::{debug, debug_span, field, Span};
+use std::{
+    collections::BTreeMap,
+    future::poll_fn,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Instant,
+};
+use tokio::sync::mpsc::Receiver;
+
+/// The default number of delivered blocks remembered by [`ExExNotifications`].
+pub const DEFAULT_DEDUP_MAX_TRACKED_BLOCKS: usize = 10_000;
+
+/// The notifications channel of an `ExEx`, which skips notifications the `ExEx` has already seen.
+///
+/// When the manager replays notifications, e.g. catch-up notifications from a backfill after a
+/// restart, they can overlap with the live notifications. The channel keeps track of the
+/// [sequence numbers](ExExNotification::sequence) of the committed blocks it delivered:
+///
+/// - a committed chain whose blocks were all delivered already is skipped.
+/// - a committed chain that partially overlaps with the delivered blocks is trimmed to the blocks
+///   after the overlap.
+/// - reorgs and reverts are always delivered, and the reverted blocks are forgotten, so they are
+///   delivered again if they are committed again.
+///
+/// Every delivered notification gets a tracing span with the ID of the `ExEx` and the block ranges
+/// of the notification, see [`ExExNotifications::span`]. The span is closed once the `ExEx` asks
+/// for the next notification, and records how long the `ExEx` took to process the notification.
+#[derive(Debug)]
+pub struct ExExNotifications {
+    /// the ID of the `ExEx`.
+    exex_id: String,
+    /// channel to receive notifications from the manager.
+    receiver: Receiver<ExExNotification>,
+    /// the span of the notification that is being processed and the time it was delivered.
+    current: Option<(Span, Instant)>,
+    /// hashes of the delivered canonical blocks, keyed by number.
+    delivered: BTreeMap<BlockNumber, BlockHash>,
+    /// the highest block number that is not tracked anymore, all delivered blocks at or below it
+    /// were evicted from `delivered`.
+    evicted_up_to: Option<BlockNumber>,
+    /// the maximum number of blocks kept in `delivered`.
+    max_tracked_blocks: usize,
+}
+
+impl ExExNotifications {
+    /// Creates a new deduplicating channel out of the notifications receiver of the `ExEx` with
+    /// the given ID.
+    pub fn new(exex_id: impl Into<String>, receiver: Receiver<ExExNotification>) -> Self {
+        Self {
+            exex_id: exex_id.into(),
+            receiver,
+            current: None,
+            delivered: BTreeMap::new(),
+            evicted_up_to: None,
+            max_tracked_blocks: DEFAULT_DEDUP_MAX_TRACKED_BLOCKS,
+        }
+    }
+
+    /// Sets the maximum number of delivered blocks that are remembered.
+    ///
+    /// Blocks older than that are assumed to be final, and committed again only by replays.
+    pub fn with_max_tracked_blocks(mut self, max_tracked_blocks: usize) -> Self {
+        self.max_tracked_blocks = max_tracked_blocks.max(1);
+        self
+    }
+
+    /// Receives the next notification that was not delivered before.
+    ///
+    /// Returns `None` once the channel is closed.
+    pub async fn recv(&mut self) -> Option<ExExNotification> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Polls to receive the next notification that was not delivered before.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<ExExNotification>> {
+        // The ExEx asks for the next notification, so it is done with the current one
+        if let Some((span, delivered_at)) = self.current.take() {
+            let elapsed = delivered_at.elapsed();
+            span.record("processing_ms", elapsed.as_millis() as u64);
+            span.in_scope(|| debug!(?elapsed, "Processed notification"));
+        }
+
+        loop {
+            let Some(notification) = ready!(self.receiver.poll_recv(cx)) else {
+                return Poll::Ready(None)
+            };
+            if let Some(notification) = self.deduplicate(notification) {
+                let span = debug_span!(
+                    target: "exex",
+                    "notification",
+                    exex_id = %self.exex_id,
+                    committed = ?notification.committed_chain().map(|chain| chain.range()),
+                    reverted = ?notification.reverted_chain().map(|chain| chain.range()),
+                    processing_ms = field::Empty,
+                );
+                self.current = Some((span, Instant::now()));
+                return Poll::Ready(Some(notification))
+            }
+        }
+    }
+
+    /// Returns the tracing span of the notification that is being processed.
+    ///
+    /// Instrumenting the processing of the notification with the span, e.g. with
+    /// `tracing::Instrument`, correlates all logs and spans of the `ExEx` with the notification.
+    /// Returns a disabled span if no notification is being processed.
+    pub fn span(&self) -> Span {
+        self.current.as_ref().map_or_else(Span::none, |(span, _)| span.clone())
+    }
+
+    /// Returns `true` if the block was delivered before.
+    fn is_delivered(&self, number: BlockNumber, hash: BlockHash) -> bool {
+        self.delivered.get(&number) == Some(&hash) ||
+            self.evicted_up_to.is_some_and(|evicted| number <= evicted)
+    }
+
+    /// Removes the blocks that were delivered before from the notification and records the
+    /// blocks it delivers.
+    ///
+    /// Returns `None` if nothing is left to deliver.
+    fn deduplicate(&mut self, notification: ExExNotification) -> Option<ExExNotification> {
+        let sequence = notification.sequence();
+
+        if let Some(old) = notification.reverted_chain() {
+            let first = old.first().number;
+            self.delivered.split_off(&first);
+            self.evicted_up_to = self
+                .evicted_up_to
+                .zip(first.checked_sub(1))
+                .map(|(evicted, parent)| evicted.min(parent));
+        }
+
+        let notification = match notification {
+            ExExNotification::ChainCommitted { new } => {
+                let overlap =
+                    new.blocks_iter().take_while(|b| self.is_delivered(b.number, b.hash())).count();
+                if overlap == new.len() {
+                    debug!(?sequence, "Skipping notification that was already delivered");
+                    return None
+                }
+                if overlap > 0 {
+                    let last_delivered = new.first().number + overlap as u64 - 1;
+                    debug!(%last_delivered, ?sequence, "Trimming overlapping notification");
+                    ExExNotification::ChainCommitted { new: trim(new, last_delivered) }
+                } else {
+                    ExExNotification::ChainCommitted { new }
+                }
+            }
+            notification => notification,
+        };
+
+        if let Some(new) = notification.committed_chain() {
+            self.delivered.extend(new.blocks_iter().map(|block| (block.number, block.hash())));
+            while self.delivered.len() > self.max_tracked_blocks {
+                let (number, _) = self.delivered.pop_first().expect("not empty");
+                self.evicted_up_to = Some(number);
+            }
+        }
+
+        Some(notification)
+    }
+}
+
+impl Stream for ExExNotifications {
+    type Item = ExExNotification;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+/// Returns the blocks of the chain after `last_delivered`.
+///
+/// `last_delivered` must be below the tip of the chain.
+fn trim(chain: Arc<Chain>, last_delivered: BlockNumber) -> Arc<Chain> {
+    match Arc::unwrap_or_clone(chain).split(ChainSplitTarget::Number(last_delivered)) {
+        ChainSplit::Split { pending, .. } => Arc::new(pending),
+        ChainSplit::NoSplitCanonical(chain) | ChainSplit::NoSplitPending(chain) => Arc::new(chain),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders, B256};
+    use reth_provider::ExecutionOutcome;
+    use std::ops::RangeInclusive;
+
+    /// Creates the given range of a chain of empty blocks that starts at genesis, `fork` makes the
+    /// hashes differ from other forks.
+    fn chain(range: RangeInclusive<BlockNumber>, fork: u64) -> Arc<Chain> {
+        let first_block = *range.start();
+        let mut parent_hash = B256::ZERO;
+        let blocks = (0..=*range.end())
+            .map(|number| {
+                let header = Header { number, parent_hash, timestamp: fork, ..Default::default() };
+                let header = header.seal_slow();
+                parent_hash = header.hash();
+                SealedBlockWithSenders {
+                    block: SealedBlock { header, ..Default::default() },
+                    senders: Vec::new(),
+                }
+            })
+            .filter(|block| range.contains(&block.number))
+            .collect::<Vec<_>>();
+        Arc::new(Chain::new(blocks, ExecutionOutcome { first_block, ..Default::default() }, None))
+    }
+
+    fn committed_range(notification: &ExExNotification) -> RangeInclusive<BlockNumber> {
+        notification.committed_chain().expect("committed chain").range()
+    }
+
+    #[tokio::test]
+    async fn skips_and_trims_delivered_blocks() {
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        let mut notifications = ExExNotifications::new("test", rx);
+
+        for notification in [
+            ExExNotification::ChainCommitted { new: chain(1..=3, 0) },
+            // replayed catch-up notification
+            ExExNotification::ChainCommitted { new: chain(1..=3, 0) },
+            // overlaps with the delivered blocks
+            ExExNotification::ChainCommitted { new: chain(2..=5, 0) },
+        ] {
+            tx.send(notification).await.unwrap();
+        }
+        drop(tx);
+
+        let first = notifications.recv().await.unwrap();
+        assert_eq!(committed_range(&first), 1..=3);
+        let second = notifications.recv().await.unwrap();
+        assert_eq!(committed_range(&second), 4..=5);
+        assert!(notifications.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn redelivers_blocks_after_revert() {
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        let mut notifications = ExExNotifications::new("test", rx);
+
+        for notification in [
+            ExExNotification::ChainCommitted { new: chain(1..=5, 0) },
+            ExExNotification::ChainReverted { old: chain(4..=5, 0) },
+            // the reverted blocks are committed again
+            ExExNotification::ChainCommitted { new: chain(4..=5, 0) },
+            // a block of another fork at a delivered height
+            ExExNotification::ChainReorged { old: chain(5..=5, 0), new: chain(5..=5, 1) },
+        ] {
+            tx.send(notification).await.unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(committed_range(&notifications.recv().await.unwrap()), 1..=5);
+        let reverted = notifications.recv().await.unwrap();
+        assert_eq!(reverted.reverted_chain().unwrap().range(), 4..=5);
+        assert_eq!(committed_range(&notifications.recv().await.unwrap()), 4..=5);
+        let reorged = notifications.recv().await.unwrap();
+        assert_eq!(reorged.committed_chain().unwrap().tip().hash(), chain(5..=5, 1).tip().hash());
+        assert!(notifications.recv().await.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use reth_primitives::BlockNumber;
 
 /// Events emitted by an `ExEx`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug)]
 /// ExExEvent enum: Describes the purpose of the enum and provides details on its variant.
 pub enum ExExEvent {
     /// Highest block processed by the `ExEx`.
@@ -11,4 +11,27 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumber),
+    /// The `ExEx` is running in a degraded state, with a human readable reason.
+    ///
+    /// The `ExEx` is considered healthy again once it emits its next `FinishedHeight` event.
+    Unhealthy(String),
+    /// The `ExEx` encountered an error it cannot recover from.
+    ///
+    /// If the `ExEx` was registered as critical, the manager shuts down the node.
+    FatalError(Box<dyn std::error::Error + Send + Sync>),
+    /// The `ExEx` finished shutting down after its shutdown token was cancelled.
+    ///
+    /// This must be the last event the `ExEx` emits. The node waits for this event (up to a
+    /// timeout) before it aborts the `ExEx` task.
+    ShutdownComplete,
 }   // FinishedHeight variant: Explains what this variant represents, including the implications for block pruning and reorganization.
+
+impl ExExEvent {
+    /// Returns the finished height if this is a [`Self::FinishedHeight`] event.
+    pub const fn as_finished_height(&self) -> Option<BlockNumber> {
+        match self {
+            Self::FinishedHeight(height) => Some(*height),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ExExNotification;
+use reth_primitives::{Address, Log, B256};
+use reth_provider::Chain;
+use std::collections::HashSet;
+
+/// A filter that selects which committed chains are delivered to an `ExEx`.
+///
+/// A chain matches the filter if any receipt in it contains a log that matches both the address
+/// and the topic criteria. An empty set of addresses or topics matches any address or topic
+/// respectively, so [`NotificationFilter::default`] matches every chain.
+///
+/// The filter only applies to [`ExExNotification::ChainCommitted`]. Reorgs and reverts are always
+/// delivered, because the `ExEx` must be able to unwind state it derived earlier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationFilter {
+    /// addresses of the log emitters to match.
+    addresses: HashSet<Address>,
+    /// topics to match, in any position of the log.
+    topics: HashSet<B256>,
+}
+
+impl NotificationFilter {
+    /// Creates a new filter that matches every chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an address to match logs against.
+    pub fn address(mut self, address: Address) -> Self {
+        self.addresses.insert(address);
+        self
+    }
+
+    /// Adds multiple addresses to match logs against.
+    pub fn addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.addresses.extend(addresses);
+        self
+    }
+
+    /// Adds a topic to match logs against.
+    pub fn topic(mut self, topic: B256) -> Self {
+        self.topics.insert(topic);
+        self
+    }
+
+    /// Adds multiple topics to match logs against.
+    pub fn topics(mut self, topics: impl IntoIterator<Item = B256>) -> Self {
+        self.topics.extend(topics);
+        self
+    }
+
+    /// Returns `true` if the filter matches everything.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty()
+    }
+
+    /// Returns `true` if the log matches the filter.
+    pub fn matches_log(&self, log: &Log) -> bool {
+        (self.addresses.is_empty() || self.addresses.contains(&log.address)) &&
+            (self.topics.is_empty() || log.topics().iter().any(|topic| self.topics.contains(topic)))
+    }
+
+    /// Returns `true` if any receipt in the chain contains a log that matches the filter.
+    pub fn matches_chain(&self, chain: &Chain) -> bool {
+        if self.is_empty() {
+            return true
+        }
+
+        chain
+            .execution_outcome()
+            .receipts()
+            .iter()
+            .flatten()
+            .flatten()
+            .flat_map(|receipt| receipt.logs.iter())
+            .any(|log| self.matches_log(log))
+    }
+
+    /// Returns `true` if the notification should be delivered to an `ExEx` using this filter.
+    pub fn matches(&self, notification: &ExExNotification) -> bool {
+        match notification {
+            ExExNotification::ChainCommitted { new } => self.matches_chain(new),
+            ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, Receipt, SealedBlock, SealedBlockWithSenders};
+    use reth_provider::ExecutionOutcome;
+    use std::sync::Arc;
+
+    /// Creates a chain of a single block with a receipt containing the given log.
+    fn chain(address: Address, topics: Vec<B256>) -> Arc<Chain> {
+        let log = Log::new_unchecked(address, topics, Bytes::new());
+        let receipt = Receipt { logs: vec![log], ..Default::default() };
+        let execution_outcome =
+            ExecutionOutcome { receipts: vec![vec![Some(receipt)]].into(), ..Default::default() };
+        let block = SealedBlockWithSenders { block: SealedBlock::default(), senders: Vec::new() };
+        Arc::new(Chain::new(vec![block], execution_outcome, None))
+    }
+
+    #[test]
+    fn matches_address_and_topic() {
+        let (address, topic) = (Address::with_last_byte(1), B256::with_last_byte(1));
+        let other = chain(Address::with_last_byte(2), vec![B256::with_last_byte(2)]);
+
+        assert!(NotificationFilter::new().matches_chain(&other));
+
+        let filter = NotificationFilter::new().address(address);
+        assert!(filter.matches_chain(&chain(address, Vec::new())));
+        assert!(!filter.matches_chain(&other));
+
+        let filter = filter.topic(topic);
+        assert!(filter.matches_chain(&chain(address, vec![B256::ZERO, topic])));
+        assert!(!filter.matches_chain(&chain(address, vec![B256::ZERO])));
+        assert!(!filter.matches_chain(&chain(Address::with_last_byte(2), vec![topic])));
+    }
+
+    #[test]
+    fn always_delivers_reverts() {
+        let filter = NotificationFilter::new().address(Address::with_last_byte(1));
+        let other = chain(Address::with_last_byte(2), Vec::new());
+
+        assert!(!filter.matches(&ExExNotification::ChainCommitted { new: other.clone() }));
+        assert!(filter.matches(&ExExNotification::ChainReverted { old: other.clone() }));
+        assert!(filter.matches(&ExExNotification::ChainReorged { old: other.clone(), new: other }));
+    }
+}
@@ -0,0 +1,239 @@
+//! Bridge that streams [`ExExNotification`]s to out-of-process extensions.
+//!
+//! The bridge is a regular `ExEx` that listens on a Unix domain socket. Every client connected to
+//! the socket receives each notification as a single line of JSON, which makes it possible to
+//! write extensions in any language that can read from a socket.
+//!
+//! ```ignore
+//! builder.install_exex("ipc", |ctx| launch_ipc_exex(ctx, "/tmp/reth-exex.ipc"));
+//! ```
+
+use crate::{ExExContext, ExExEvent, ExExNotification};
+use reth_node_api::FullNodeComponents;
+use reth_primitives::{BlockHash, BlockNumber, Log, TxHash};
+use reth_provider::Chain;
+use reth_tracing::tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{future::Future, path::PathBuf};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{UnixListener, UnixStream},
+};
+
+/// A notification as sent over the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IpcNotification {
+    /// See [`ExExNotification::ChainCommitted`].
+    ChainCommitted {
+        /// the new chain after commit
+        new: IpcChain,
+    },
+    /// See [`ExExNotification::ChainReorged`].
+    ChainReorged {
+        /// The old chain before reorg
+        old: IpcChain,
+        /// The new chain after reorg
+        new: IpcChain,
+    },
+    /// See [`ExExNotification::ChainReverted`].
+    ChainReverted {
+        /// The old chain before reversion
+        old: IpcChain,
+    },
+}
+
+impl From<&ExExNotification> for IpcNotification {
+    fn from(notification: &ExExNotification) -> Self {
+        match notification {
+            ExExNotification::ChainCommitted { new } => {
+                Self::ChainCommitted { new: new.as_ref().into() }
+            }
+            ExExNotification::ChainReorged { old, new } => {
+                Self::ChainReorged { old: old.as_ref().into(), new: new.as_ref().into() }
+            }
+            ExExNotification::ChainReverted { old } => {
+                Self::ChainReverted { old: old.as_ref().into() }
+            }
+        }
+    }
+}
+
+/// A chain of blocks as sent over the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcChain {
+    /// The blocks of the chain, in ascending order.
+    pub blocks: Vec<IpcBlock>,
+}
+
+impl From<&Chain> for IpcChain {
+    fn from(chain: &Chain) -> Self {
+        let blocks = chain
+            .blocks_and_receipts()
+            .map(|(block, receipts)| IpcBlock {
+                number: block.number,
+                hash: block.hash(),
+                parent_hash: block.parent_hash,
+                timestamp: block.timestamp,
+                transactions: block
+                    .body
+                    .iter()
+                    .zip(receipts.iter())
+                    .map(|(tx, receipt)| IpcTransaction {
+                        hash: tx.hash(),
+                        success: receipt.as_ref().map(|receipt| receipt.success),
+                        logs: receipt
+                            .as_ref()
+                            .map(|receipt| receipt.logs.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self { blocks }
+    }
+}
+
+/// A block as sent over the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcBlock {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block hash.
+    pub hash: BlockHash,
+    /// The hash of the parent block.
+    pub parent_hash: BlockHash,
+    /// The block timestamp.
+    pub timestamp: u64,
+    /// The transactions of the block, in execution order.
+    pub transactions: Vec<IpcTransaction>,
+}
+
+/// A transaction and the outcome of its execution as sent over the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcTransaction {
+    /// The transaction hash.
+    pub hash: TxHash,
+    /// Whether the transaction succeeded, `None` if the receipt was pruned.
+    pub success: Option<bool>,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// Launches the IPC bridge `ExEx`, listening on the Unix domain socket at `path`.
+///
+/// Any stale socket file at `path` is removed first. Use this as the launch closure given to
+/// `install_exex`.
+pub async fn launch_ipc_exex<Node: FullNodeComponents>(
+    ctx: ExExContext<Node>,
+    path: impl Into<PathBuf>,
+) -> eyre::Result<impl Future<Output = eyre::Result<()>>> {
+    let path = path.into();
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    info!(target: "exex::ipc", path = %path.display(), "IPC bridge listening");
+
+    Ok(ipc_exex(ctx, listener))
+}
+
+/// Runs the IPC bridge `ExEx`.
+///
+/// Clients are written to one after another, so a client that does not read from the socket
+/// slows the bridge down. Clients that disconnect or fail to receive a notification are dropped.
+async fn ipc_exex<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    listener: UnixListener,
+) -> eyre::Result<()> {
+    let mut clients: Vec<UnixStream> = Vec::new();
+
+    loop {
+        tokio::select! {
+            _ = ctx.shutdown.cancelled() => {
+                let _ = ctx.events.send(ExExEvent::ShutdownComplete);
+                return Ok(())
+            }
+            conn = listener.accept() => {
+                let (stream, _) = conn?;
+                debug!(target: "exex::ipc", clients = clients.len() + 1, "Client connected");
+                clients.push(stream);
+            }
+            notification = ctx.notifications.recv() => {
+                let Some(notification) = notification else { return Ok(()) };
+
+                let mut line = serde_json::to_vec(&IpcNotification::from(&notification))?;
+                line.push(b'\n');
+
+                let mut connected = Vec::with_capacity(clients.len());
+                for mut client in clients.drain(..) {
+                    match client.write_all(&line).await {
+                        Ok(()) => connected.push(client),
+                        Err(err) => warn!(target: "exex::ipc", %err, "Dropping client"),
+                    }
+                }
+                clients = connected;
+
+                if let Some(committed_chain) = notification.committed_chain() {
+                    ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().number))?;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Address, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders, TransactionSigned,
+        B256,
+    };
+    use reth_provider::ExecutionOutcome;
+    use std::sync::Arc;
+
+    #[test]
+    fn notification_json() {
+        let tx = TransactionSigned::default();
+        let log = Log::new_unchecked(Address::with_last_byte(1), vec![B256::ZERO], Bytes::new());
+        let receipt = Receipt { success: true, logs: vec![log.clone()], ..Default::default() };
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header: Header { number: 1, timestamp: 12, ..Default::default() }.seal_slow(),
+                body: vec![tx.clone()],
+                ..Default::default()
+            },
+            senders: vec![Address::ZERO],
+        };
+        let execution_outcome = ExecutionOutcome {
+            receipts: vec![vec![Some(receipt)]].into(),
+            first_block: 1,
+            ..Default::default()
+        };
+        let chain = Arc::new(Chain::new(vec![block.clone()], execution_outcome, None));
+
+        let notification =
+            IpcNotification::from(&ExExNotification::ChainReverted { old: chain.clone() });
+        let IpcNotification::ChainReverted { old } = &notification else {
+            panic!("unexpected notification {notification:?}")
+        };
+        assert_eq!(
+            old.blocks,
+            vec![IpcBlock {
+                number: 1,
+                hash: block.hash(),
+                parent_hash: B256::ZERO,
+                timestamp: 12,
+                transactions: vec![IpcTransaction {
+                    hash: tx.hash(),
+                    success: Some(true),
+                    logs: vec![log],
+                }],
+            }]
+        );
+
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["type"], "chain_reverted");
+        assert_eq!(serde_json::from_value::<IpcNotification>(json).unwrap(), notification);
+    }
+}
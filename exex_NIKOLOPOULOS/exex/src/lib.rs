@@ -35,18 +35,65 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+/// the backfill module, which re-executes historical block ranges for `ExEx` tasks.
+mod backfill;
+pub use backfill::*;
+
+/// the bus module, which lets `ExEx` tasks exchange typed messages.
+mod bus;
+pub use bus::*;
+
+/// the compression module, which compresses the notifications queued for lagging `ExEx` tasks.
+mod compression;
+
 /// the context module, which contains the definition and implementation of the `ExExContext` struct.
 mod context;
 pub use context::*;
 
+/// the dedup module, which skips notifications an `ExEx` has already received.
+mod dedup;
+pub use dedup::*;
+
 /// the event module, which contains the definition of the `ExExEvent` enum.
 mod event;
 pub use event::*;
 
+/// the filter module, which selects the notifications an `ExEx` is interested in.
+mod filter;
+pub use filter::*;
+
+/// the ipc module, which bridges notifications to out-of-process extensions.
+#[cfg(feature = "ipc")]
+pub mod ipc;
+
 /// the manager module, which manages the lifecycle and execution of `ExEx` tasks.
 mod manager;
 pub use manager::*;
 
+/// the persistence module, which stores the finished height of `ExEx` tasks across restarts.
+mod persistence;
+pub use persistence::*;
+
+/// the pipeline module, which composes `ExEx` tasks out of checkpointed stages.
+mod pipeline;
+pub use pipeline::*;
+
+/// the replay module, which throttles notifications replayed to lagging `ExEx` tasks.
+mod replay;
+pub use replay::{ReplayThrottle, DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS};
+
+/// the resources module, which accounts the poll time and allocations of `ExEx` tasks.
+mod resources;
+pub use resources::*;
+
+/// the snapshot module, which snapshots and restores the derived state of `ExEx` tasks.
+mod snapshot;
+pub use snapshot::*;
+
+/// the supervisor module, which restarts failed `ExEx` tasks according to a restart policy.
+mod supervisor;
+pub use supervisor::*;
+
 /// the notification module, which handles the notifications that `ExEx` tasks can receive.
 mod notification;
 pub use notification::*;
@@ -1,23 +1,34 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{
+    compression::{notification_size, Decompressing, QueuedNotification},
+    replay::{notification_blocks, replay_notifications, CatchUp, ReplayRateLimiter},
+    BackfillJob, ExExEvent, ExExNotification, ExExResourceSnapshot, ExExResourceUsage,
+    ExExSnapshots, FinishedExExHeight, FinishedHeightStore, NotificationFilter, ReplayThrottle,
+};
 use metrics::Gauge;
+use reth_evm::execute::BlockExecutorProvider;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
-use reth_tracing::tracing::debug;
+use reth_provider::{BlockReader, HeaderProvider, StateProviderFactory};
+use reth_tracing::tracing::{debug, debug_span, error, warn};
 use std::{
     collections::VecDeque,
     future::{poll_fn, Future},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
-use tokio::sync::{
-    mpsc::{self, error::SendError, Receiver, UnboundedReceiver, UnboundedSender},
-    watch,
+use tokio::{
+    sync::{
+        mpsc::{self, error::SendError, Receiver, UnboundedReceiver, UnboundedSender},
+        oneshot, watch,
+    },
+    time::{sleep, Sleep},
 };
-use tokio_util::sync::{PollSendError, PollSender, ReusableBoxFuture};
+use tokio_util::sync::{CancellationToken, PollSendError, PollSender, ReusableBoxFuture};
 
 /// metrics for an `ExEx`.
 #[derive(Metrics)]
@@ -27,6 +38,51 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// the total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// whether the `ExEx` reported itself as unhealthy (`1`) or not (`0`).
+    unhealthy: Gauge,
+    /// the total number of fatal errors an `ExEx` has reported.
+    fatal_errors_total: Counter,
+    /// the total number of notifications dropped for an `ExEx` by its backpressure policy.
+    notifications_dropped_total: Counter,
+    /// the total number of times an `ExEx` did not accept a notification within its send
+    /// timeout.
+    send_timeouts_total: Counter,
+    /// the number of notifications waiting to be sent to an `ExEx`, including the ones queued
+    /// outside of the manager's buffer.
+    queue_depth: Gauge,
+    /// the estimated number of bytes of the notifications queued outside of the manager's
+    /// buffer.
+    queued_bytes: Gauge,
+    /// the total number of queued notifications that were compressed.
+    notifications_compressed_total: Counter,
+    /// the number of blocks the finished height of an `ExEx` is behind the tip known to the
+    /// manager.
+    lag_blocks: Gauge,
+    /// the number of blocks per second an `ExEx` processed, measured between its last two
+    /// `FinishedHeight` events.
+    blocks_processed_per_second: Gauge,
+}
+
+/// the backpressure policy applied to a single `ExEx` when it does not keep up with the
+/// notifications sent by the [`ExExManager`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExBackpressure {
+    /// the `ExEx` holds notifications in the manager's shared buffer until it has received them.
+    ///
+    /// once the shared buffer is full, the manager stops accepting new notifications, which
+    /// stalls the node (and all other `ExEx`'s) until this `ExEx` catches up.
+    #[default]
+    Block,
+    /// when the manager's shared buffer is full and this `ExEx` is holding back the oldest
+    /// notification, that notification is dropped for this `ExEx`.
+    ///
+    /// only use this for `ExEx`'s that can tolerate gaps, e.g. best-effort indexers.
+    DropOldest,
+    /// the `ExEx` copies notifications into its own unbounded queue, so it never holds back the
+    /// manager's shared buffer.
+    ///
+    /// memory usage grows without bound if the `ExEx` never catches up.
+    Unbounded,
 }
 
 /// a handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -40,6 +96,8 @@ pub struct ExExHandle {
     id: String,
     /// metrics for an `ExEx`.
     metrics: ExExMetrics,
+    /// the resource usage of the `ExEx`'s future.
+    resource_usage: ExExResourceUsage,
 
     /// channel to send [`ExExNotification`]s to the `ExEx`.
     sender: PollSender<ExExNotification>,
@@ -52,6 +110,60 @@ pub struct ExExHandle {
     ///
     /// if this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumber>,
+    /// the time the `ExEx` last emitted a `FinishedHeight` event, used to measure throughput.
+    finished_height_at: Option<Instant>,
+    /// the tip of the chain after the last notification sent to the `ExEx`.
+    delivered_tip: Option<BlockNumber>,
+    /// the tip of the chain after the last notification skipped because of the filter of the
+    /// `ExEx`, if it was skipped after the last notification sent to the `ExEx`.
+    skipped_tip: Option<BlockNumber>,
+    /// the store the finished height is persisted to, if any.
+    finished_height_store: Option<FinishedHeightStore>,
+    /// snapshot support of the `ExEx`, if any.
+    snapshots: Option<ExExSnapshots>,
+
+    /// whether a fatal error from this `ExEx` should shut down the node.
+    critical: bool,
+    /// the delivery priority of the `ExEx`, higher priorities receive notifications first.
+    priority: u8,
+    /// the reason the `ExEx` last reported itself as unhealthy, if it has not recovered since.
+    unhealthy_reason: Option<String>,
+
+    /// the backpressure policy of the `ExEx`.
+    backpressure: ExExBackpressure,
+    /// how long a notification may wait for the `ExEx` to accept it, if limited.
+    send_timeout: Option<Duration>,
+    /// the deadline of the notification currently waiting for the `ExEx` to accept it.
+    send_deadline: Option<Pin<Box<Sleep>>>,
+    /// notifications taken out of the manager's buffer but not yet sent to the `ExEx`, which are
+    /// sent before any notification from the buffer.
+    ///
+    /// only used with [`ExExBackpressure::Unbounded`].
+    queued: VecDeque<(usize, QueuedNotification)>,
+    /// the estimated number of bytes held in `queued`.
+    queued_bytes: usize,
+    /// the queued notification that is being decompressed, sent before the rest of `queued`.
+    decompressing: Option<(usize, Decompressing)>,
+    /// the number of queued notifications after which newly queued notifications are compressed,
+    /// if enabled.
+    compress_queued_after: Option<usize>,
+    /// the catch-up notifications given in [`ExExHandle::with_catch_up`], sent before any other
+    /// notification. `None` once all of them were sent.
+    catch_up: Option<CatchUp>,
+    /// throttling of the catch-up and queued notifications.
+    replay_throttle: ReplayThrottle,
+    /// rate limiter enforcing [`ReplayThrottle::blocks_per_second`], if set.
+    replay_limiter: Option<ReplayRateLimiter>,
+    /// filter selecting the committed chains the `ExEx` is interested in.
+    filter: NotificationFilter,
+    /// IDs of the `ExEx`'s that must acknowledge a notification before it is sent to this one.
+    dependencies: Vec<String>,
+
+    /// token cancelled when the manager requests the `ExEx` to shut down.
+    shutdown: CancellationToken,
+    /// whether the `ExEx` has completed its shutdown, either by emitting
+    /// [`ExExEvent::ShutdownComplete`] or by dropping its event sender.
+    shutdown_complete: bool,
 }
 
 impl ExExHandle {
@@ -67,21 +179,438 @@ impl ExExHandle {
         (
             Self {
                 id: id.clone(),
+                resource_usage: ExExResourceUsage::new(&id),
                 metrics: ExExMetrics::new_with_labels(&[("exex", id)]),
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                finished_height_at: None,
+                delivered_tip: None,
+                skipped_tip: None,
+                finished_height_store: None,
+                snapshots: None,
+                critical: false,
+                priority: 0,
+                unhealthy_reason: None,
+                backpressure: ExExBackpressure::default(),
+                send_timeout: None,
+                send_deadline: None,
+                queued: VecDeque::new(),
+                queued_bytes: 0,
+                decompressing: None,
+                compress_queued_after: None,
+                catch_up: None,
+                replay_throttle: ReplayThrottle::default(),
+                replay_limiter: None,
+                filter: NotificationFilter::default(),
+                dependencies: Vec::new(),
+                shutdown: CancellationToken::new(),
+                shutdown_complete: false,
             },
             event_tx,
             notification_rx,
         )
     }
 
+    /// returns the token the manager cancels to request the `ExEx` to shut down.
+    ///
+    /// this should be given to the `ExEx` in its [`ExExContext`](crate::ExExContext).
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// returns the resource usage of the `ExEx`.
+    ///
+    /// the future of the `ExEx` should be run through [`ExExResourceUsage::track`], so the
+    /// manager can report how much time is spent polling it.
+    pub fn resource_usage(&self) -> ExExResourceUsage {
+        self.resource_usage.clone()
+    }
+
+    /// persists the finished height of the `ExEx` in the given store.
+    ///
+    /// the finished height persisted by a previous run is restored, so the `ExEx` resumes where
+    /// it left off: committed chains at or below that height are not sent to it again.
+    pub fn with_finished_height_store(
+        mut self,
+        store: FinishedHeightStore,
+    ) -> std::io::Result<Self> {
+        if let Some(height) = store.load()? {
+            debug!(exex_id = %self.id, %height, "Restored finished height");
+            self.finished_height = Some(height);
+        }
+        self.finished_height_store = Some(store);
+        Ok(self)
+    }
+
+    /// enables snapshots of the derived state of the `ExEx`.
+    ///
+    /// the same [`ExExSnapshots`] should be given to the `ExEx` in its
+    /// [`ExExContext`](crate::ExExContext), so it can register its hooks.
+    pub fn with_snapshots(mut self, snapshots: ExExSnapshots) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// marks the `ExEx` as critical.
+    ///
+    /// if a critical `ExEx` emits an [`ExExEvent::FatalError`], the [`ExExManager`] resolves with
+    /// an error, which shuts down the node.
+    pub const fn critical(mut self) -> Self {
+        self.critical = true;
+        self
+    }
+
+    /// sets the delivery priority of the `ExEx`.
+    ///
+    /// the manager sends each notification to `ExEx`'s with a higher priority before `ExEx`'s
+    /// with a lower priority, so latency sensitive `ExEx`'s (e.g. a sequencer feed) are not
+    /// delayed by indexers. `ExEx`'s with the same priority are served in registration order.
+    ///
+    /// defaults to `0`.
+    pub const fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// returns the delivery priority of the `ExEx`.
+    pub const fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    /// sets the backpressure policy of the `ExEx`.
+    ///
+    /// defaults to [`ExExBackpressure::Block`].
+    pub const fn with_backpressure(mut self, backpressure: ExExBackpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// sets the filter selecting which committed chains are sent to the `ExEx`.
+    ///
+    /// committed chains that do not match the filter are skipped, as if they were delivered.
+    /// since the `ExEx` never sees those blocks, it should periodically emit a `FinishedHeight`
+    /// event for the node's best block to not hold back pruning.
+    pub fn with_notification_filter(mut self, filter: NotificationFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// makes the `ExEx` depend on the `ExEx` with the given ID.
+    ///
+    /// a notification is only sent to this `ExEx` after it was sent to the dependency, and the
+    /// dependency acknowledged it by emitting a `FinishedHeight` event at or above the tip of the
+    /// chain after the notification. this gives pipelines of `ExEx`'s (e.g. derivation followed by
+    /// settlement) an ordered handoff. catch-up notifications are not held back.
+    ///
+    /// notifications the dependency skips because of its
+    /// [filter](Self::with_notification_filter) are acknowledged once it acknowledged everything
+    /// sent to it before.
+    ///
+    /// [`ExExManager::new`] rejects dependencies on unknown `ExEx`'s and dependency cycles.
+    pub fn with_dependency(mut self, exex_id: impl Into<String>) -> Self {
+        self.dependencies.push(exex_id.into());
+        self
+    }
+
+    /// sets the notifications that are sent to the `ExEx` before any live notification.
+    ///
+    /// this is used to catch up an `ExEx` registered with [`ExExManagerHandle::register_exex`]
+    /// while the node is running, e.g. with the output of a
+    /// [`BackfillJob`](crate::BackfillJob).
+    ///
+    /// the iterator is drained on a blocking task, which stays at most
+    /// [`ReplayThrottle::max_queued_notifications`] notifications ahead of delivery.
+    pub fn with_catch_up<I>(mut self, notifications: I) -> Self
+    where
+        I: IntoIterator<Item = ExExNotification>,
+        I::IntoIter: Send + 'static,
+    {
+        self.catch_up = Some(CatchUp::new(notifications.into_iter()));
+        self
+    }
+
+    /// sets the throttling of the notifications replayed to the `ExEx` while it catches up.
+    pub fn with_replay_throttle(mut self, throttle: ReplayThrottle) -> Self {
+        self.replay_limiter = throttle.blocks_per_second.map(ReplayRateLimiter::new);
+        self.replay_throttle = throttle;
+        self
+    }
+
+    /// sets how long a notification may wait for the `ExEx` to accept it.
+    ///
+    /// when the timeout elapses, the `ExEx` is reported as unhealthy. with
+    /// [`ExExBackpressure::DropOldest`] the notification is dropped for the `ExEx` as well.
+    pub const fn with_send_timeout(mut self, timeout: Duration) -> Self {
+        self.send_timeout = Some(timeout);
+        self
+    }
+
+    /// compresses the notifications queued for the `ExEx` once more than `after` notifications
+    /// are queued.
+    ///
+    /// compressed notifications do not keep their chains alive, which bounds the memory used by
+    /// an `ExEx` that stalls for a long time, at the cost of compressing and decompressing the
+    /// notifications on blocking tasks. only applies to [`ExExBackpressure::Unbounded`].
+    ///
+    /// a notification that fails to decompress can not be skipped without leaving a gap in the
+    /// chain the `ExEx` sees, so the manager resolves with an error.
+    pub const fn with_queue_compression(mut self, after: usize) -> Self {
+        self.compress_queued_after = Some(after);
+        self
+    }
+
+    /// returns the backpressure policy of the `ExEx`.
+    pub const fn backpressure(&self) -> ExExBackpressure {
+        self.backpressure
+    }
+
+    /// updates the finished height of the `ExEx` and its throughput metric.
+    fn update_finished_height(&mut self, height: BlockNumber) {
+        let now = Instant::now();
+        if let (Some(prev_height), Some(prev_at)) = (self.finished_height, self.finished_height_at)
+        {
+            let elapsed = now.duration_since(prev_at).as_secs_f64();
+            if elapsed > 0.0 {
+                let blocks = height.saturating_sub(prev_height) as f64;
+                self.metrics.blocks_processed_per_second.set(blocks / elapsed);
+            }
+        }
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.on_finished_height(self.finished_height, height);
+        }
+        self.finished_height = Some(height);
+        self.finished_height_at = Some(now);
+
+        if let Some(store) = &self.finished_height_store {
+            store.save_in_background(height);
+        }
+    }
+
+    /// queues a notification outside of the manager's buffer, compressing it if the queue is
+    /// long enough.
+    fn enqueue(&mut self, (notification_id, notification): (usize, ExExNotification)) {
+        let compress = self.compress_queued_after.is_some_and(|after| self.queued.len() >= after);
+        let notification = if compress {
+            self.metrics.notifications_compressed_total.increment(1);
+            QueuedNotification::compress_in_background(notification)
+        } else {
+            QueuedNotification::Full(notification)
+        };
+        self.queued_bytes += notification.size();
+        self.queued.push_back((notification_id, notification));
+    }
+
+    /// takes the next notification out of the queue, decompressing it if needed.
+    ///
+    /// returns an error if the notification fails to decompress.
+    fn poll_dequeue(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<eyre::Result<Option<(usize, ExExNotification)>>> {
+        loop {
+            if let Some((notification_id, decompressing)) = self.decompressing.as_mut() {
+                let notification_id = *notification_id;
+                let result = ready!(Pin::new(decompressing).poll(cx));
+                self.decompressing = None;
+                return Poll::Ready(match result {
+                    Ok(notification) => Ok(Some((notification_id, notification))),
+                    Err(err) => {
+                        error!(
+                            exex_id = %self.id,
+                            %notification_id,
+                            %err,
+                            "Failed to decompress queued notification"
+                        );
+                        Err(err.wrap_err(format!(
+                            "failed to decompress notification {notification_id} for ExEx {}",
+                            self.id
+                        )))
+                    }
+                })
+            }
+
+            let Some((notification_id, notification)) = self.queued.pop_front() else {
+                return Poll::Ready(Ok(None))
+            };
+            self.queued_bytes = self.queued_bytes.saturating_sub(notification.size());
+            self.decompressing = Some((notification_id, notification.decompress()));
+        }
+    }
+
+    /// sends the notifications held outside of the manager's buffer: first the catch-up
+    /// notifications, then the queued ones, subject to the replay throttle.
+    ///
+    /// returns [`Poll::Ready`] with `Ok(())` once there is nothing left to send, or with an error
+    /// if the channel was closed or a queued notification failed to decompress.
+    fn poll_send_replay(
+        &mut self,
+        cx: &mut Context<'_>,
+        barrier: Option<DependencyBarrier>,
+    ) -> Poll<eyre::Result<()>> {
+        loop {
+            let max_queued = self.replay_throttle.max_queued_notifications;
+            let catch_up = match self.catch_up.as_mut() {
+                // the catch-up notifications are still being produced, nothing else may be sent
+                // before them
+                Some(catch_up) => ready!(catch_up.poll_next(cx, max_queued)),
+                None => None,
+            };
+            let (notification, from_catch_up) = match catch_up {
+                Some(notification) => (notification, true),
+                None => {
+                    self.catch_up = None;
+                    match ready!(self.poll_dequeue(cx))? {
+                        Some(notification) => (notification, false),
+                        None => return Poll::Ready(Ok(())),
+                    }
+                }
+            };
+
+            let blocks = notification_blocks(&notification.1);
+            let allowed = from_catch_up ||
+                barrier.map_or(true, |barrier| barrier.allows(notification.0, &notification.1));
+            let ready = allowed &&
+                match self.replay_limiter.as_mut() {
+                    Some(limiter) => limiter.poll_ready(cx, blocks).is_ready(),
+                    None => true,
+                };
+            let result = if ready { self.send(cx, &notification) } else { Poll::Pending };
+
+            match result {
+                Poll::Ready(Ok(())) => {
+                    if let Some(limiter) = self.replay_limiter.as_mut() {
+                        limiter.consume(blocks);
+                    }
+                }
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                Poll::Pending => {
+                    if from_catch_up {
+                        self.catch_up.as_mut().expect("catch-up exists").push_front(notification);
+                    } else {
+                        let (notification_id, notification) = notification;
+                        let notification = QueuedNotification::Full(notification);
+                        self.queued_bytes += notification.size();
+                        self.queued.push_front((notification_id, notification));
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// sends the notifications the `ExEx` has not seen yet, first the ones held outside of the
+    /// manager's buffer, then as many of the buffered ones as the channel accepts.
+    ///
+    /// this never waits on the `ExEx`: once its channel is full, the waker is registered with
+    /// the channel and the `ExEx` is polled again when it has capacity. `min_id` and `next_id`
+    /// are the IDs of the first notification in `buffer` and of the next one to be buffered.
+    ///
+    /// returns an error if the channel was closed or a queued notification failed to decompress.
+    fn poll_send_all(
+        &mut self,
+        cx: &mut Context<'_>,
+        buffer: &VecDeque<(usize, ExExNotification)>,
+        (min_id, next_id): (usize, usize),
+        barrier: Option<DependencyBarrier>,
+    ) -> eyre::Result<()> {
+        // Calculate the notification index for this ExEx handle
+        let mut notification_index = self
+            .next_notification_id
+            .checked_sub(min_id)
+            .expect("exex expected notification ID outside the manager's range");
+        if self.backpressure == ExExBackpressure::Unbounded {
+            // Move everything the ExEx has not seen yet into its own queue, so it never holds
+            // back the shared buffer
+            for notification in buffer.iter().skip(notification_index) {
+                self.enqueue(notification.clone());
+            }
+            self.next_notification_id = next_id;
+        }
+
+        // Send the catch-up and queued notifications first
+        if self.poll_send_replay(cx, barrier)?.is_pending() {
+            return Ok(())
+        }
+
+        // Send as many buffered notifications as the channel accepts
+        while let Some(notification) = buffer.get(notification_index) {
+            // Wait for the dependencies to acknowledge the notification first
+            if barrier.is_some_and(|barrier| !barrier.allows(notification.0, &notification.1)) {
+                break
+            }
+            // Attempt to send the notification
+            match self.send(cx, notification) {
+                Poll::Ready(Ok(())) => {
+                    self.next_notification_id = notification.0 + 1;
+                    self.send_deadline = None;
+                    notification_index += 1;
+                }
+                // If the channel was closed, return an error
+                Poll::Ready(Err(err)) => return Err(err.into()),
+                Poll::Pending => {
+                    if self.poll_send_timeout(cx) &&
+                        self.backpressure == ExExBackpressure::DropOldest
+                    {
+                        // Skip the notification the ExEx did not accept in time
+                        self.next_notification_id = notification.0 + 1;
+                        self.metrics.notifications_dropped_total.increment(1);
+                        notification_index += 1;
+                        continue
+                    }
+                    break
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// polls the send timeout of the notification that is waiting for the `ExEx` to accept it.
+    ///
+    /// returns `true` if the timeout elapsed, in which case the `ExEx` is marked as unhealthy and
+    /// the timeout is restarted.
+    fn poll_send_timeout(&mut self, cx: &mut Context<'_>) -> bool {
+        let Some(timeout) = self.send_timeout else { return false };
+        let deadline = self.send_deadline.get_or_insert_with(|| Box::pin(sleep(timeout)));
+        if deadline.as_mut().poll(cx).is_pending() {
+            return false
+        }
+
+        warn!(exex_id = %self.id, ?timeout, "ExEx did not accept notification in time");
+        self.metrics.send_timeouts_total.increment(1);
+        self.metrics.unhealthy.set(1.0);
+        self.unhealthy_reason = Some(format!("notification not accepted within {timeout:?}"));
+        self.send_deadline = None;
+        true
+    }
+
+    /// returns the height up to which the `ExEx` acknowledged the notifications sent to it.
+    ///
+    /// notifications skipped because of the filter count as acknowledged once the `ExEx`
+    /// acknowledged everything sent to it before them.
+    fn acknowledged_height(&self) -> Option<BlockNumber> {
+        let caught_up = self
+            .delivered_tip
+            .map_or(true, |tip| self.finished_height.is_some_and(|height| height >= tip));
+        if caught_up {
+            self.finished_height.max(self.skipped_tip)
+        } else {
+            self.finished_height
+        }
+    }
+
+    /// returns the reason the `ExEx` last reported itself as unhealthy, or `None` if it is
+    /// healthy.
+    pub fn unhealthy_reason(&self) -> Option<&str> {
+        self.unhealthy_reason.as_deref()
+    }
+
     /// reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
-    /// whe n the notification is sent, it is considered delivered.
+    /// whe n the notification is sent (or skipped), it is considered delivered. advancing
+    /// `next_notification_id` is left to the caller.
     fn send(
         &mut self,
         cx: &mut Context<'_>,
@@ -102,7 +631,6 @@ impl ExExHandle {
                             "Skipping notification"
                         );
 
-                        self.next_notification_id = notification_id + 1;
                         return Poll::Ready(Ok(()))
                     }
                 }
@@ -114,25 +642,38 @@ impl ExExHandle {
             }
         }
 
-        debug!(
+        if !self.filter.matches(notification) {
+            debug!(
+                exex_id = %self.id,
+                %notification_id,
+                "Skipping notification not matching the filter"
+            );
+            self.skipped_tip = Some(notification.sequence().number);
+            return Poll::Ready(Ok(()))
+        }
+
+        let _span = debug_span!(
+            target: "exex::manager",
+            "send_notification",
             exex_id = %self.id,
             %notification_id,
-            "Reserving slot for notification"
-        );
+            committed = ?notification.committed_chain().map(|chain| chain.range()),
+            reverted = ?notification.reverted_chain().map(|chain| chain.range()),
+        )
+        .entered();
+
+        debug!("Reserving slot for notification");
         match self.sender.poll_reserve(cx) {
             Poll::Ready(Ok(())) => (),
             other => return other,
         }
 
-        debug!(
-            exex_id = %self.id,
-            %notification_id,
-            "Sending notification"
-        );
+        debug!("Sending notification");
         match self.sender.send_item(notification.clone()) {
             Ok(()) => {
-                self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
+                self.delivered_tip = Some(notification.sequence().number);
+                self.skipped_tip = None;
                 Poll::Ready(Ok(()))
             }
             Err(err) => Poll::Ready(Err(err)),
@@ -152,8 +693,12 @@ pub struct ExExManagerMetrics {
     ///
     /// note that this might be slightly bigger than the maximum capacity in some cases.
     buffer_size: Gauge,
+    /// estimated number of bytes of the notifications in the internal state notifications buffer.
+    buffer_bytes: Gauge,
     /// current number of `ExEx`'s on the node.
     num_exexs: Gauge,
+    /// the tip of the canonical chain according to the notifications received by the manager.
+    tip: Gauge,
 }
 
 /// The execution extension manager.
@@ -172,16 +717,32 @@ pub struct ExExManager {
 
     /// [`ExExNotification`] channel from the [`ExExManagerHandle`]s.
     handle_rx: UnboundedReceiver<ExExNotification>,
+    /// Channel of [`ExExHandle`]s registered through the [`ExExManagerHandle`]s while the node
+    /// is running.
+    new_exex_rx: UnboundedReceiver<ExExHandle>,
+    /// Channel of shutdown requests from the [`ExExManagerHandle`]s, carrying the shutdown
+    /// timeout.
+    shutdown_rx: UnboundedReceiver<Duration>,
+    /// Channel of replay requests from the [`ExExManagerHandle`]s.
+    replay_rx: UnboundedReceiver<ReplayRequest>,
+    /// Deadline for the `ExEx`'s to complete their shutdown, set once shutdown was requested.
+    shutdown_deadline: Option<Pin<Box<Sleep>>>,
 
     /// The minimum notification ID currently present in the buffer.
     min_id: usize,
     /// Monotonically increasing ID for [`ExExNotification`]s.
     next_id: usize,
+    /// The tip of the canonical chain according to the notifications received so far.
+    ///
+    /// Used to compute how far behind each `ExEx` is.
+    tip: Option<BlockNumber>,
     /// Internal buffer of [`ExExNotification`]s.
     ///
     /// The first element of the tuple is a monotonically increasing ID unique to the notification
     /// (the second element of the tuple).
     buffer: VecDeque<(usize, ExExNotification)>,
+    /// The estimated number of bytes of the notifications in the buffer.
+    buffer_bytes: usize,
     /// Max size of the internal state notifications buffer.
     max_capacity: usize,
     /// Current state notifications buffer capacity.
@@ -209,10 +770,18 @@ impl ExExManager {
     ///
     /// When the capacity is exceeded (which can happen if an `ExEx` is slow) no one can send
     /// notifications over [`ExExManagerHandle`]s until there is capacity again.
-    pub fn new(handles: Vec<ExExHandle>, max_capacity: usize) -> Self {
+    ///
+    /// Returns an error if an `ExEx` depends on an unknown `ExEx` or on itself, or if the
+    /// dependencies form a cycle.
+    pub fn new(mut handles: Vec<ExExHandle>, max_capacity: usize) -> eyre::Result<Self> {
+        validate_dependencies(&handles)?;
         let num_exexs = handles.len();
+        sort_by_priority(&mut handles);
 
         let (handle_tx, handle_rx) = mpsc::unbounded_channel();
+        let (new_exex_tx, new_exex_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = mpsc::unbounded_channel();
+        let (replay_tx, replay_rx) = mpsc::unbounded_channel();
         let (is_ready_tx, is_ready_rx) = watch::channel(true);
         let (finished_height_tx, finished_height_rx) = watch::channel(if num_exexs == 0 {
             FinishedExExHeight::NoExExs
@@ -221,19 +790,29 @@ impl ExExManager {
         });
 
         let current_capacity = Arc::new(AtomicUsize::new(max_capacity));
+        let resource_usage = handles
+            .iter()
+            .map(|exex| (exex.id.clone(), exex.resource_usage.clone()))
+            .collect::<Vec<_>>();
 
         let metrics = ExExManagerMetrics::default();
         metrics.max_capacity.set(max_capacity as f64);
         metrics.num_exexs.set(num_exexs as f64);
 
-        Self {
+        Ok(Self {
             exex_handles: handles,
 
             handle_rx,
+            new_exex_rx,
+            shutdown_rx,
+            replay_rx,
+            shutdown_deadline: None,
 
             min_id: 0,
             next_id: 0,
+            tip: None,
             buffer: VecDeque::with_capacity(max_capacity),
+            buffer_bytes: 0,
             max_capacity,
             current_capacity: Arc::clone(&current_capacity),
 
@@ -242,14 +821,18 @@ impl ExExManager {
 
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
-                num_exexs,
+                new_exex_tx,
+                shutdown_tx,
+                replay_tx,
+                num_exexs: Arc::new(AtomicUsize::new(num_exexs)),
                 is_ready_receiver: is_ready_rx.clone(),
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
                 finished_height: finished_height_rx,
+                resource_usage: Arc::new(Mutex::new(resource_usage)),
             },
             metrics,
-        }
+        })
     }
 
     /// Returns the handle to the manager.
@@ -264,16 +847,90 @@ impl ExExManager {
         self.current_capacity.store(capacity, Ordering::Relaxed);
         self.metrics.current_capacity.set(capacity as f64);
         self.metrics.buffer_size.set(self.buffer.len() as f64);
+        self.metrics.buffer_bytes.set(self.buffer_bytes as f64);
 
         // we can safely ignore if the channel is closed, since the manager always holds it open
         // internally
         let _ = self.is_ready.send(capacity > 0);
     }
 
+    /// Adds an `ExEx` registered while the node is running.
+    ///
+    /// The `ExEx` receives its catch-up notifications first, and then every notification that
+    /// arrives at the manager after its registration.
+    fn add_exex(&mut self, mut exex: ExExHandle) {
+        // The registered ExEx can only depend on running ones, which can not depend on it
+        if let Some(dependency) = exex
+            .dependencies
+            .iter()
+            .find(|id| **id == exex.id || !self.exex_handles.iter().any(|other| other.id == **id))
+        {
+            // Dropping the handle closes the notifications channel of the ExEx
+            error!(exex_id = %exex.id, %dependency, "Rejecting ExEx with unknown dependency");
+            return
+        }
+
+        debug!(exex_id = %exex.id, catch_up = exex.catch_up.is_some(), "Registering new ExEx");
+        exex.next_notification_id = self.next_id;
+        if self.shutdown_deadline.is_some() {
+            exex.shutdown.cancel();
+        }
+        self.handle
+            .resource_usage
+            .lock()
+            .expect("resource usage lock poisoned")
+            .push((exex.id.clone(), exex.resource_usage.clone()));
+        self.exex_handles.push(exex);
+        sort_by_priority(&mut self.exex_handles);
+
+        let num_exexs = self.exex_handles.len();
+        self.handle.num_exexs.store(num_exexs, Ordering::Relaxed);
+        self.metrics.num_exexs.set(num_exexs as f64);
+
+        // The new ExEx has not emitted a finished height yet, so nothing may be pruned until it
+        // does
+        let _ = self.finished_height.send(FinishedExExHeight::NotReady);
+    }
+
+    /// Queues the notifications of a replay request after the catch-up notifications of the
+    /// requested `ExEx`.
+    fn start_replay(&mut self, request: ReplayRequest) {
+        let ReplayRequest { exex_id, notifications, response } = request;
+        let Some(exex) = self.exex_handles.iter_mut().find(|exex| exex.id == exex_id) else {
+            let _ = response.send(Err(eyre::eyre!("unknown ExEx {exex_id}")));
+            return
+        };
+
+        debug!(%exex_id, "Replaying notifications to ExEx");
+        match &mut exex.catch_up {
+            Some(catch_up) => catch_up.append(notifications),
+            None => exex.catch_up = Some(CatchUp::new(notifications)),
+        }
+        let _ = response.send(Ok(()));
+    }
+
+    /// Requests all `ExEx`'s to shut down by cancelling their shutdown tokens, and starts the
+    /// shutdown deadline.
+    fn start_shutdown(&mut self, timeout: Duration) {
+        debug!(?timeout, "Requesting ExEx's to shut down");
+        for exex in &self.exex_handles {
+            exex.shutdown.cancel();
+        }
+        self.shutdown_deadline = Some(Box::pin(sleep(timeout)));
+    }
+
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
     /// unique ID.
     fn push_notification(&mut self, notification: ExExNotification) {
+        if let Some(new) = notification.committed_chain() {
+            self.tip = Some(new.tip().number);
+        } else if let Some(old) = notification.reverted_chain() {
+            self.tip = Some(old.first().number.saturating_sub(1));
+        }
+        self.metrics.tip.set(self.tip.unwrap_or_default() as f64);
+
         let next_id = self.next_id;
+        self.buffer_bytes += notification_size(&notification);
         self.buffer.push_back((next_id, notification));
         self.next_id += 1;
     }
@@ -283,6 +940,23 @@ impl Future for ExExManager {
     type Output = eyre::Result<()>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Register ExEx's added while the node is running
+        while let Poll::Ready(Some(exex)) = self.new_exex_rx.poll_recv(cx) {
+            self.add_exex(exex);
+        }
+
+        // Queue the notifications replayed on request
+        while let Poll::Ready(Some(request)) = self.replay_rx.poll_recv(cx) {
+            self.start_replay(request);
+        }
+
+        // Start shutting down the ExEx's if requested
+        if self.shutdown_deadline.is_none() {
+            if let Poll::Ready(Some(timeout)) = self.shutdown_rx.poll_recv(cx) {
+                self.start_shutdown(timeout);
+            }
+        }
+
         // Drain handle notifications while the buffer is not full
         while self.buffer.len() < self.max_capacity {
             if let Poll::Ready(Some(notification)) = self.handle_rx.poll_recv(cx) {
@@ -302,50 +976,139 @@ impl Future for ExExManager {
         // Update the buffer capacity after adding new notifications
         self.update_capacity();
 
-        // Advance all poll senders for each ExEx handle
-        let mut min_id = usize::MAX;
-        for idx in (0..self.exex_handles.len()).rev() {
-            let mut exex = self.exex_handles.swap_remove(idx);
-
-            // Calculate the notification index for this ExEx handle
-            let notification_index = exex
-                .next_notification_id
-                .checked_sub(self.min_id)
-                .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = self.buffer.get(notification_index) {
-                // Attempt to send the notification
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
-                    // If the channel was closed, return an error
-                    return Poll::Ready(Err(err.into()))
-                }
+        // Advance the poll sender of each ExEx handle independently: an ExEx whose channel is
+        // full or closed does not hold back the fan-out to the others. The handles are sorted by
+        // priority, so higher priority ExEx's are sent notifications first
+        let mut min_id = self.next_id;
+        let mut exex_handles = std::mem::take(&mut self.exex_handles);
+        let barriers = exex_handles
+            .iter()
+            .map(|exex| DependencyBarrier::new(exex, &exex_handles))
+            .collect::<Vec<_>>();
+        let mut send_error = None;
+        for (exex, barrier) in exex_handles.iter_mut().zip(barriers.iter().copied()) {
+            // An ExEx that completed its shutdown may have dropped its notifications receiver, so
+            // it is not sent anything anymore and does not hold back the buffer
+            if self.shutdown_deadline.is_some() &&
+                (exex.shutdown_complete || exex.sender.is_closed())
+            {
+                exex.next_notification_id = self.next_id;
+                continue
+            }
+
+            let ids = (self.min_id, self.next_id);
+            if let Err(err) = exex.poll_send_all(cx, &self.buffer, ids, barrier) {
+                error!(exex_id = %exex.id, %err, "Failed to send notifications to ExEx");
+                send_error.get_or_insert(err);
             }
             // Update the minimum notification ID seen so far
             min_id = min_id.min(exex.next_notification_id);
-            self.exex_handles.push(exex);
+        }
+        self.exex_handles = exex_handles;
+        if let Some(err) = send_error {
+            return Poll::Ready(Err(err))
+        }
+
+        // If the buffer is full, drop the oldest notification for the ExEx's that hold it back and
+        // opted into dropping notifications
+        if self.buffer.len() >= self.max_capacity {
+            let mut new_min_id = self.next_id;
+            for exex in &mut self.exex_handles {
+                if exex.backpressure == ExExBackpressure::DropOldest &&
+                    exex.next_notification_id == min_id
+                {
+                    debug!(exex_id = %exex.id, notification_id = %min_id, "Dropping notification");
+                    exex.next_notification_id += 1;
+                    exex.metrics.notifications_dropped_total.increment(1);
+                }
+                new_min_id = new_min_id.min(exex.next_notification_id);
+            }
+            min_id = new_min_id;
         }
 
         // Remove processed notifications from the buffer
         debug!(%min_id, "Updating lowest notification id in buffer");
-        self.buffer.retain(|&(id, _)| id >= min_id);
+        while self.buffer.front().is_some_and(|&(id, _)| id < min_id) {
+            let (_, notification) = self.buffer.pop_front().expect("buffer is not empty");
+            self.buffer_bytes = self.buffer_bytes.saturating_sub(notification_size(&notification));
+        }
         self.min_id = min_id;
 
         // Update the buffer capacity after removing processed notifications
         self.update_capacity();
 
         // Handle incoming events from each ExEx handle
+        let mut finished_heights_updated = false;
         for exex in &mut self.exex_handles {
-            while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
+            while let Poll::Ready(event) = exex.receiver.poll_recv(cx) {
+                let Some(event) = event else {
+                    // The ExEx dropped its event sender, so it can not complete its shutdown
+                    // any further
+                    exex.shutdown_complete = true;
+                    break
+                };
+
                 // Log the received event from the ExEx handle
                 debug!(exex_id = %exex.id, ?event, "Received event from exex");
                 // Increment the total events sent metric
                 exex.metrics.events_sent_total.increment(1);
-                // Update the finished height if the event contains a new height
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    // Update the finished height and consider the ExEx healthy again
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.update_finished_height(height);
+                        finished_heights_updated = true;
+                        if exex.unhealthy_reason.take().is_some() {
+                            exex.metrics.unhealthy.set(0.0);
+                        }
+                    }
+                    ExExEvent::Unhealthy(reason) => {
+                        warn!(exex_id = %exex.id, %reason, "ExEx reported unhealthy");
+                        exex.metrics.unhealthy.set(1.0);
+                        exex.unhealthy_reason = Some(reason);
+                    }
+                    ExExEvent::FatalError(err) => {
+                        error!(
+                            exex_id = %exex.id,
+                            %err,
+                            critical = exex.critical,
+                            "ExEx reported fatal error"
+                        );
+                        exex.metrics.fatal_errors_total.increment(1);
+                        exex.metrics.unhealthy.set(1.0);
+                        exex.unhealthy_reason = Some(err.to_string());
+                        if exex.critical {
+                            // Resolving the manager with an error shuts down the node
+                            return Poll::Ready(Err(eyre::eyre!(
+                                "critical ExEx {} reported a fatal error: {err}",
+                                exex.id
+                            )))
+                        }
+                    }
+                    ExExEvent::ShutdownComplete => {
+                        debug!(exex_id = %exex.id, "ExEx completed shutdown");
+                        exex.shutdown_complete = true;
+                    }
                 }
             }
         }
 
+        // Notifications held back by a dependency may be sent now, which only happens on the next
+        // poll
+        if finished_heights_updated && barriers.iter().any(Option::is_some) {
+            cx.waker().wake_by_ref();
+        }
+
+        // Update the queue depth and lag of each ExEx handle
+        for exex in &self.exex_handles {
+            let buffered = self.next_id.saturating_sub(exex.next_notification_id);
+            let catch_up = exex.catch_up.as_ref().map_or(0, |catch_up| catch_up.len());
+            exex.metrics.queue_depth.set((buffered + exex.queued.len() + catch_up) as f64);
+            exex.metrics.queued_bytes.set(exex.queued_bytes as f64);
+            if let (Some(tip), Some(finished_height)) = (self.tip, exex.finished_height) {
+                exex.metrics.lag_blocks.set(tip.saturating_sub(finished_height) as f64);
+            }
+        }
+
         // Update the watch channel with the minimum finished height across all ExEx handles
         let finished_height = self.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
             let height = match exex.finished_height {
@@ -363,19 +1126,59 @@ impl Future for ExExManager {
             let _ = self.finished_height.send(FinishedExExHeight::Height(finished_height));
         }
 
+        // Resolve once all ExEx's completed their shutdown, or the shutdown timed out
+        if let Some(deadline) = &mut self.shutdown_deadline {
+            if self.exex_handles.iter().all(|exex| exex.shutdown_complete) {
+                debug!("All ExEx's completed shutdown");
+                return Poll::Ready(Ok(()))
+            }
+            if deadline.as_mut().poll(cx).is_ready() {
+                let pending = self
+                    .exex_handles
+                    .iter()
+                    .filter(|exex| !exex.shutdown_complete)
+                    .map(|exex| exex.id.as_str())
+                    .collect::<Vec<_>>();
+                warn!(?pending, "ExEx's did not complete shutdown in time");
+                return Poll::Ready(Ok(()))
+            }
+        }
+
         // Indicate that the future is not yet complete and should be polled again
         Poll::Pending
     }
 }
 
 
+/// A request to replay notifications to an `ExEx`, see [`ExExManagerHandle::replay`].
+struct ReplayRequest {
+    /// the ID of the `ExEx`.
+    exex_id: String,
+    /// the notifications to replay.
+    notifications: Box<dyn Iterator<Item = ExExNotification> + Send>,
+    /// channel to report whether the replay was started.
+    response: oneshot::Sender<eyre::Result<()>>,
+}
+
+impl std::fmt::Debug for ReplayRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayRequest").field("exex_id", &self.exex_id).finish_non_exhaustive()
+    }
+}
+
 /// A handle to communicate with the [`ExExManager`].
 #[derive(Debug)]
 pub struct ExExManagerHandle {
     /// Channel to send notifications to the `ExEx` manager.
     exex_tx: UnboundedSender<ExExNotification>,
+    /// Channel to register new `ExEx`'s with the `ExEx` manager.
+    new_exex_tx: UnboundedSender<ExExHandle>,
+    /// Channel to request the `ExEx` manager to shut down all `ExEx`'s.
+    shutdown_tx: UnboundedSender<Duration>,
+    /// Channel to request the `ExEx` manager to replay notifications to an `ExEx`.
+    replay_tx: UnboundedSender<ReplayRequest>,
     /// The number of `ExEx`'s running on the node.
-    num_exexs: usize,
+    num_exexs: Arc<AtomicUsize>,
     /// A watch channel denoting whether the manager is ready for new notifications or not.
     ///
     /// This is stored internally alongside a `ReusableBoxFuture` representation of the same value.
@@ -389,6 +1192,8 @@ pub struct ExExManagerHandle {
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Receiver<FinishedExExHeight>,
+    /// The resource usage of each `ExEx`, keyed by ID.
+    resource_usage: Arc<Mutex<Vec<(String, ExExResourceUsage)>>>,
 }
 
 impl ExExManagerHandle {
@@ -399,16 +1204,23 @@ impl ExExManagerHandle {
     /// The handle will always be ready, and have a capacity of 0.
     pub fn empty() -> Self {
         let (exex_tx, _) = mpsc::unbounded_channel();
+        let (new_exex_tx, _) = mpsc::unbounded_channel();
+        let (shutdown_tx, _) = mpsc::unbounded_channel();
+        let (replay_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
 
         Self {
             exex_tx,
-            num_exexs: 0,
+            new_exex_tx,
+            shutdown_tx,
+            replay_tx,
+            num_exexs: Arc::new(AtomicUsize::new(0)),
             is_ready_receiver: is_ready_rx.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
             finished_height: finished_height_rx,
+            resource_usage: Default::default(),
         }
     }
 
@@ -445,8 +1257,62 @@ impl ExExManagerHandle {
     }
 
     /// Returns `true` if there are `ExEx`'s installed in the node.
-    pub const fn has_exexs(&self) -> bool {
-        self.num_exexs > 0
+    pub fn has_exexs(&self) -> bool {
+        self.num_exexs.load(Ordering::Relaxed) > 0
+    }
+
+    /// Registers a new `ExEx` with the manager while the node is running.
+    ///
+    /// The `ExEx` receives the catch-up notifications set with [`ExExHandle::with_catch_up`]
+    /// first, followed by all notifications the manager receives after the registration. The
+    /// channels returned alongside the handle by [`ExExHandle::new`] should be given to the `ExEx`,
+    /// which is expected to be spawned by the caller.
+    ///
+    /// The manager rejects the `ExEx` by dropping the handle if it depends on an `ExEx` that is
+    /// not running.
+    ///
+    /// Returns the handle back if the manager is not running.
+    pub fn register_exex(&self, exex: ExExHandle) -> Result<(), SendError<ExExHandle>> {
+        self.new_exex_tx.send(exex)
+    }
+
+    /// Requests the manager to gracefully shut down all `ExEx`'s.
+    ///
+    /// The manager cancels the shutdown token of every `ExEx` and resolves once all of them
+    /// emitted [`ExExEvent::ShutdownComplete`], or once the timeout elapsed. Only after that
+    /// should the `ExEx` tasks be aborted.
+    pub fn shutdown(&self, timeout: Duration) -> Result<(), SendError<Duration>> {
+        self.shutdown_tx.send(timeout)
+    }
+
+    /// Re-sends the blocks of the backfill job to the `ExEx` with the given ID, e.g. when an
+    /// indexer discovers a processing bug and needs to re-run a specific range.
+    ///
+    /// This is meant to be exposed through an admin RPC endpoint. The blocks are sent as
+    /// [`ExExNotification::ChainReorged`] notifications with the same old and new chain, so the
+    /// `ExEx` drops what it derived from the blocks before processing them again. They are sent
+    /// like catch-up notifications, before any other notification and subject to the
+    /// [`ReplayThrottle`] of the `ExEx`. The job executes the blocks on a blocking task, so the
+    /// replay does not hold up the notifications of the other `ExEx`'s.
+    ///
+    /// Returns an error if the manager is not running or there is no `ExEx` with the given ID.
+    pub async fn replay<E, P>(
+        &self,
+        exex_id: impl Into<String>,
+        job: BackfillJob<E, P>,
+    ) -> eyre::Result<()>
+    where
+        E: BlockExecutorProvider,
+        P: BlockReader + HeaderProvider + StateProviderFactory + Send + 'static,
+    {
+        let (response, rx) = oneshot::channel();
+        let request = ReplayRequest {
+            exex_id: exex_id.into(),
+            notifications: Box::new(replay_notifications(job)),
+            response,
+        };
+        self.replay_tx.send(request).map_err(|_| eyre::eyre!("ExEx manager is not running"))?;
+        rx.await.map_err(|_| eyre::eyre!("ExEx manager is not running"))?
     }
 
     /// The finished height of all `ExEx`'s.
@@ -454,6 +1320,19 @@ impl ExExManagerHandle {
         self.finished_height.clone()
     }
 
+    /// Returns the resource usage of every `ExEx`, by ID.
+    ///
+    /// This is meant for debugging which `ExEx` slows down the node, e.g. from an admin RPC
+    /// endpoint.
+    pub fn resource_usage(&self) -> Vec<(String, ExExResourceSnapshot)> {
+        self.resource_usage
+            .lock()
+            .expect("resource usage lock poisoned")
+            .iter()
+            .map(|(id, usage)| (id.clone(), usage.snapshot()))
+            .collect()
+    }
+
     /// Wait until the manager is ready for new notifications.
     pub async fn ready(&mut self) {
         poll_fn(|cx| self.poll_ready(cx)).await
@@ -467,6 +1346,103 @@ impl ExExManagerHandle {
     }
 }
 
+/// The progress of the dependencies of an `ExEx`, which holds back notifications they have not
+/// acknowledged yet.
+#[derive(Debug, Clone, Copy)]
+struct DependencyBarrier {
+    /// the lowest ID of the next notification to send to any of the dependencies.
+    next_notification_id: usize,
+    /// the lowest acknowledged height of the dependencies, `None` if any of them has not
+    /// acknowledged a notification yet.
+    finished_height: Option<BlockNumber>,
+}
+
+impl DependencyBarrier {
+    /// Creates the barrier of the `ExEx` from the state of its dependencies among the handles.
+    ///
+    /// Returns `None` if the `ExEx` does not depend on any of the handles.
+    fn new(exex: &ExExHandle, handles: &[ExExHandle]) -> Option<Self> {
+        let mut dependencies = handles
+            .iter()
+            .filter(|handle| handle.id != exex.id && exex.dependencies.contains(&handle.id))
+            .peekable();
+        dependencies.peek()?;
+
+        let mut barrier =
+            Self { next_notification_id: usize::MAX, finished_height: Some(BlockNumber::MAX) };
+        for dependency in dependencies {
+            barrier.next_notification_id =
+                barrier.next_notification_id.min(dependency.next_notification_id);
+            barrier.finished_height =
+                barrier.finished_height.min(dependency.acknowledged_height());
+        }
+        Some(barrier)
+    }
+
+    /// Returns `true` if the dependencies acknowledged the notification with the given ID.
+    fn allows(&self, notification_id: usize, notification: &ExExNotification) -> bool {
+        notification_id < self.next_notification_id &&
+            self.finished_height.is_some_and(|height| height >= notification.sequence().number)
+    }
+}
+
+/// Ensures the dependencies of the handles refer to other handles and do not form a cycle.
+fn validate_dependencies(handles: &[ExExHandle]) -> eyre::Result<()> {
+    let index = |id: &str| handles.iter().position(|handle| handle.id == id);
+    for handle in handles {
+        for dependency in &handle.dependencies {
+            if *dependency == handle.id {
+                eyre::bail!("ExEx {} depends on itself", handle.id)
+            }
+            if index(dependency).is_none() {
+                eyre::bail!("ExEx {} depends on unknown ExEx {dependency}", handle.id)
+            }
+        }
+    }
+
+    /// the state of a handle in the depth-first search.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Visit {
+        New,
+        InProgress,
+        Done,
+    }
+
+    fn visit(
+        handles: &[ExExHandle],
+        index: &impl Fn(&str) -> Option<usize>,
+        visits: &mut [Visit],
+        handle: usize,
+    ) -> eyre::Result<()> {
+        match visits[handle] {
+            Visit::Done => return Ok(()),
+            Visit::InProgress => {
+                eyre::bail!("dependency cycle through ExEx {}", handles[handle].id)
+            }
+            Visit::New => {}
+        }
+        visits[handle] = Visit::InProgress;
+        for dependency in &handles[handle].dependencies {
+            let dependency = index(dependency).expect("dependencies are known");
+            visit(handles, index, visits, dependency)?;
+        }
+        visits[handle] = Visit::Done;
+        Ok(())
+    }
+
+    let mut visits = vec![Visit::New; handles.len()];
+    for handle in 0..handles.len() {
+        visit(handles, &index, &mut visits, handle)?;
+    }
+    Ok(())
+}
+
+/// Sorts the handles by descending priority, keeping the registration order of handles with the
+/// same priority.
+fn sort_by_priority(handles: &mut [ExExHandle]) {
+    handles.sort_by_key(|exex| std::cmp::Reverse(exex.priority));
+}
+
 /// Creates a future that resolves once the given watch channel receiver is true.
 async fn make_wait_future(mut rx: watch::Receiver<bool>) -> watch::Receiver<bool> {
     // NOTE(onbjerg): We can ignore the error here, because if the channel is closed, the node
@@ -480,11 +1456,15 @@ impl Clone for ExExManagerHandle {
     ///
     /// This method creates a new instance of `ExExManagerHandle` with cloned fields:
     /// - `exex_tx`: Clones the unbounded sender for `ExExNotification`.
-    /// - `num_exexs`: Copies the number of `ExEx` instances.
+    /// - `new_exex_tx`: Clones the unbounded sender for new `ExExHandle`s.
+    /// - `shutdown_tx`: Clones the unbounded sender for shutdown requests.
+    /// - `replay_tx`: Clones the unbounded sender for replay requests.
+    /// - `num_exexs`: Clones the atomic integer tracking the number of `ExEx` instances.
     /// - `is_ready_receiver`: Clones the watch channel receiver indicating manager readiness.
     /// - `is_ready`: Initializes a new `ReusableBoxFuture` waiting on `is_ready_receiver`.
     /// - `current_capacity`: Clones the atomic integer tracking buffer capacity.
     /// - `finished_height`: Clones the watch channel for `FinishedExExHeight`.
+    /// - `resource_usage`: Clones the shared resource usage of the `ExEx`'s.
     ///
     /// # Returns
     ///
@@ -492,17 +1472,25 @@ impl Clone for ExExManagerHandle {
     fn clone(&self) -> Self {
         Self {
             exex_tx: self.exex_tx.clone(),
-            num_exexs: self.num_exexs,
+            new_exex_tx: self.new_exex_tx.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+            replay_tx: self.replay_tx.clone(),
+            num_exexs: self.num_exexs.clone(),
             is_ready_receiver: self.is_ready_receiver.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
             finished_height: self.finished_height.clone(),
+            resource_usage: self.resource_usage.clone(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use reth_primitives::{Address, Header, SealedBlock, SealedBlockWithSenders};
+    use reth_provider::{Chain, ExecutionOutcome};
+
     // Define asynchronous tests using `tokio::test` attribute
 
     #[tokio::test]
@@ -524,5 +1512,100 @@ mod tests {
     #[tokio::test]
     async fn is_ready() {}
         // Test function for checking manager readiness
-    
+
+    /// Creates a committed chain of a single empty block.
+    fn committed(number: BlockNumber) -> ExExNotification {
+        let header = Header { number, ..Default::default() }.seal_slow();
+        let block = SealedBlockWithSenders {
+            block: SealedBlock { header, ..Default::default() },
+            senders: Vec::new(),
+        };
+        let outcome = ExecutionOutcome { first_block: number, ..Default::default() };
+        ExExNotification::ChainCommitted { new: Arc::new(Chain::new(vec![block], outcome, None)) }
+    }
+
+    fn handle(id: &str, dependencies: &[&str]) -> ExExHandle {
+        dependencies
+            .iter()
+            .fold(ExExHandle::new(id.to_string()).0, |handle, id| handle.with_dependency(*id))
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_dependencies() {
+        assert!(ExExManager::new(vec![handle("a", &[]), handle("b", &["a"])], 1).is_ok());
+
+        for handles in [
+            vec![handle("a", &["a"])],
+            vec![handle("a", &[]), handle("b", &["c"])],
+            vec![handle("a", &["b"]), handle("b", &["a"])],
+            vec![handle("a", &["c"]), handle("b", &["a"]), handle("c", &["b"])],
+        ] {
+            assert!(ExExManager::new(handles, 1).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn closed_exex_does_not_hold_back_others() {
+        let (closed, _closed_events, closed_notifications) = ExExHandle::new("closed".to_string());
+        let (open, _open_events, mut open_notifications) = ExExHandle::new("open".to_string());
+        drop(closed_notifications);
+
+        let mut manager = ExExManager::new(vec![closed, open], 4).unwrap();
+        manager.handle().send(committed(1)).unwrap();
+
+        // the ExEx after the closed one is still sent the notification, then the manager fails
+        let result = poll_fn(|cx| Poll::Ready(Pin::new(&mut manager).poll(cx))).await;
+        assert!(matches!(result, Poll::Ready(Err(_))));
+        assert_eq!(open_notifications.try_recv().unwrap(), committed(1));
+        assert_eq!(manager.exex_handles.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn skips_exex_that_completed_shutdown() {
+        let (done, done_events, done_notifications) = ExExHandle::new("done".to_string());
+        let (running, _running_events, mut running_notifications) =
+            ExExHandle::new("running".to_string());
+
+        let mut manager = ExExManager::new(vec![done, running], 4).unwrap();
+        let handle = manager.handle();
+        handle.shutdown(Duration::from_secs(60)).unwrap();
+        done_events.send(ExExEvent::ShutdownComplete).unwrap();
+        drop(done_notifications);
+        handle.send(committed(1)).unwrap();
+
+        // the notification is only sent to the ExEx that is still running
+        let result = poll_fn(|cx| Poll::Ready(Pin::new(&mut manager).poll(cx))).await;
+        assert!(result.is_pending());
+        assert_eq!(running_notifications.try_recv().unwrap(), committed(1));
+    }
+
+    #[tokio::test]
+    async fn filtered_notifications_pass_dependency_barrier() {
+        let (dependency, _events, mut notifications) = ExExHandle::new("dependency".to_string());
+        let mut dependency =
+            dependency.with_notification_filter(NotificationFilter::new().address(Address::ZERO));
+        let dependent = handle("dependent", &["dependency"]);
+
+        // the first notification is sent, the second one is skipped by the filter
+        let first = (0, committed(1));
+        let second = (1, committed(2));
+        dependency.delivered_tip = Some(1);
+        poll_fn(|cx| dependency.send(cx, &second)).await.unwrap();
+        dependency.next_notification_id = 2;
+        assert_eq!(dependency.skipped_tip, Some(2));
+        assert!(notifications.try_recv().is_err());
+
+        // the skipped notification waits for the sent one to be acknowledged
+        let handles = [dependency, dependent];
+        let barrier = DependencyBarrier::new(&handles[1], &handles).unwrap();
+        assert!(!barrier.allows(first.0, &first.1));
+        assert!(!barrier.allows(second.0, &second.1));
+
+        let [mut dependency, dependent] = handles;
+        dependency.finished_height = Some(1);
+        let handles = [dependency, dependent];
+        let barrier = DependencyBarrier::new(&handles[1], &handles).unwrap();
+        assert!(barrier.allows(first.0, &first.1));
+        assert!(barrier.allows(second.0, &second.1));
+    }
 }
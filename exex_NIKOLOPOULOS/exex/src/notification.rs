@@ -1,5 +1,11 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
 
+use reth_primitives::{
+    revm_primitives::AccountInfo, Address, BlockNumHash, Receipt, TxHash, U256,
+};
 use reth_provider::{CanonStateNotification, Chain};
 
 /// notifications sent to an `ExEx`
@@ -46,6 +52,180 @@ impl ExExNotification {
             Self::ChainCommitted { .. } => None,
         }
     }
+
+    /// Returns the sequence number of the notification, which is the number and hash of the tip
+    /// of the chain after the notification is applied.
+    ///
+    /// Unlike the IDs the manager assigns to buffered notifications, the sequence number is derived
+    /// from the blocks themselves, so a notification replayed after a restart or by a backfill has
+    /// the same sequence number as the original one.
+    pub fn sequence(&self) -> BlockNumHash {
+        match self {
+            Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => {
+                new.tip().num_hash()
+            }
+            // After a revert, the parent of the first reverted block is the tip.
+            Self::ChainReverted { old } => {
+                let first = old.first();
+                BlockNumHash::new(first.number.saturating_sub(1), first.parent_hash)
+            }
+        }
+    }
+
+    /// Returns the minimal [`StateDiff`] between the state before and after the notification.
+    ///
+    /// For [`Self::ChainReorged`] this only contains what actually differs between the tips of
+    /// the old and the new chain, instead of everything touched by either chain.
+    pub fn state_diff(&self) -> StateDiff {
+        StateDiff::new(self.reverted_chain().as_deref(), self.committed_chain().as_deref())
+    }
+}
+
+/// The change of an account between two states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The account before the change, `None` if it did not exist.
+    pub old: Option<AccountInfo>,
+    /// The account after the change, `None` if it does not exist anymore.
+    pub new: Option<AccountInfo>,
+}
+
+/// The change of a storage slot between two states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlotDiff {
+    /// The value before the change.
+    pub old: U256,
+    /// The value after the change.
+    pub new: U256,
+}
+
+/// The minimal difference between the tips of a reverted and a committed chain that fork off the
+/// same parent block.
+///
+/// Rollup derivation pipelines use this to apply a reorg without replaying both chains in full:
+/// accounts, storage slots and receipts that are the same on both sides are left out.
+///
+/// # Limitations
+///
+/// Storage wiped by a `SELFDESTRUCT` is not reported slot by slot, only the slots written by
+/// either chain are compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Accounts that differ, keyed by address.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+    /// Storage slots that differ, keyed by address and slot.
+    pub storage: BTreeMap<Address, BTreeMap<U256, StorageSlotDiff>>,
+    /// Receipts of the reverted chain that are not part of the committed chain.
+    pub reverted_receipts: Vec<(TxHash, Receipt)>,
+    /// Receipts of the committed chain that were not part of the reverted chain.
+    pub committed_receipts: Vec<(TxHash, Receipt)>,
+}
+
+impl StateDiff {
+    /// Computes the diff between the tip of the `old` chain and the tip of the `new` chain.
+    ///
+    /// Both chains must fork off the same parent block. A missing chain stands for the state of
+    /// that parent block.
+    pub fn new(old: Option<&Chain>, new: Option<&Chain>) -> Self {
+        let old_state = old.map(|chain| &chain.execution_outcome().bundle.state);
+        let new_state = new.map(|chain| &chain.execution_outcome().bundle.state);
+
+        let mut diff = Self::default();
+
+        let addresses = old_state
+            .into_iter()
+            .chain(new_state)
+            .flat_map(|state| state.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        for address in addresses {
+            let old_account = old_state.and_then(|state| state.get(&address));
+            let new_account = new_state.and_then(|state| state.get(&address));
+
+            // An account not touched by one of the chains still has its value from before the
+            // fork, which is the original value recorded by the other chain.
+            let old_info = match (old_account, new_account) {
+                (Some(account), _) => account.info.clone(),
+                (None, Some(account)) => account.original_info.clone(),
+                (None, None) => continue,
+            };
+            let new_info = match (new_account, old_account) {
+                (Some(account), _) => account.info.clone(),
+                (None, Some(account)) => account.original_info.clone(),
+                (None, None) => continue,
+            };
+            if old_info != new_info {
+                diff.accounts.insert(address, AccountDiff { old: old_info, new: new_info });
+            }
+
+            let slots = old_account
+                .into_iter()
+                .chain(new_account)
+                .flat_map(|account| account.storage.keys())
+                .copied()
+                .collect::<BTreeSet<_>>();
+            for slot in slots {
+                let old_slot = old_account.and_then(|account| account.storage.get(&slot));
+                let new_slot = new_account.and_then(|account| account.storage.get(&slot));
+
+                let (old_value, new_value) = match (old_slot, new_slot) {
+                    (Some(old), Some(new)) => (old.present_value, new.present_value),
+                    (Some(old), None) => (old.present_value, old.previous_or_original_value),
+                    (None, Some(new)) => (new.previous_or_original_value, new.present_value),
+                    (None, None) => continue,
+                };
+                if old_value != new_value {
+                    diff.storage
+                        .entry(address)
+                        .or_default()
+                        .insert(slot, StorageSlotDiff { old: old_value, new: new_value });
+                }
+            }
+        }
+
+        let old_receipts = old.map(receipts_by_hash).unwrap_or_default();
+        let new_receipts = new.map(receipts_by_hash).unwrap_or_default();
+        diff.reverted_receipts = old_receipts
+            .iter()
+            .filter(|(hash, receipt)| new_receipts.get(*hash) != Some(*receipt))
+            .map(|(hash, receipt)| (*hash, (*receipt).clone()))
+            .collect();
+        diff.committed_receipts = new_receipts
+            .iter()
+            .filter(|(hash, receipt)| old_receipts.get(*hash) != Some(*receipt))
+            .map(|(hash, receipt)| (*hash, (*receipt).clone()))
+            .collect();
+        diff.reverted_receipts.sort_unstable_by_key(|(hash, _)| *hash);
+        diff.committed_receipts.sort_unstable_by_key(|(hash, _)| *hash);
+
+        diff
+    }
+
+    /// Computes the diff of a [`ExExNotification::ChainReverted`] followed by a
+    /// [`ExExNotification::ChainCommitted`] on top of the same parent block.
+    pub fn from_pair(reverted: &ExExNotification, committed: &ExExNotification) -> Self {
+        Self::new(reverted.reverted_chain().as_deref(), committed.committed_chain().as_deref())
+    }
+
+    /// Returns `true` if nothing differs between the two states.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() &&
+            self.storage.is_empty() &&
+            self.reverted_receipts.is_empty() &&
+            self.committed_receipts.is_empty()
+    }
+}
+
+/// Returns the receipts of the chain keyed by the hash of their transaction.
+fn receipts_by_hash(chain: &Chain) -> HashMap<TxHash, &Receipt> {
+    chain
+        .blocks_and_receipts()
+        .flat_map(|(block, receipts)| {
+            block.body.iter().zip(receipts.iter()).filter_map(|(tx, receipt)| {
+                receipt.as_ref().map(|receipt| (tx.hash(), receipt))
+            })
+        })
+        .collect()
 }
 
 impl From<CanonStateNotification> for ExExNotification {
@@ -62,3 +242,113 @@ impl From<CanonStateNotification> for ExExNotification {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{SealedBlock, SealedBlockWithSenders};
+    use reth_provider::ExecutionOutcome;
+    use reth_revm::db::BundleState;
+
+    /// an account with its original and present info, and the original and present values of its
+    /// storage slots.
+    type AccountState =
+        (Address, Option<AccountInfo>, Option<AccountInfo>, Vec<(U256, (U256, U256))>);
+
+    fn account(balance: u64) -> Option<AccountInfo> {
+        Some(AccountInfo { balance: U256::from(balance), ..Default::default() })
+    }
+
+    /// Creates a chain of a single empty block whose bundle contains the given accounts.
+    fn chain(accounts: Vec<AccountState>) -> Chain {
+        let state = accounts.into_iter().map(|(address, original, present, storage)| {
+            (address, original, present, storage.into_iter().collect())
+        });
+        let bundle = BundleState::new(
+            state,
+            Vec::<Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>>::new(),
+            Vec::new(),
+        );
+        let block = SealedBlockWithSenders { block: SealedBlock::default(), senders: Vec::new() };
+        Chain::new(vec![block], ExecutionOutcome { bundle, ..Default::default() }, None)
+    }
+
+    #[test]
+    fn commit() {
+        let address = Address::with_last_byte(1);
+        let new = chain(vec![(
+            address,
+            None,
+            account(1),
+            vec![(U256::from(1), (U256::ZERO, U256::from(5)))],
+        )]);
+
+        let diff = StateDiff::new(None, Some(&new));
+        assert_eq!(diff.accounts[&address], AccountDiff { old: None, new: account(1) });
+        assert_eq!(
+            diff.storage[&address][&U256::from(1)],
+            StorageSlotDiff { old: U256::ZERO, new: U256::from(5) }
+        );
+    }
+
+    #[test]
+    fn revert() {
+        let address = Address::with_last_byte(1);
+        let old = chain(vec![(
+            address,
+            account(1),
+            account(2),
+            vec![(U256::from(1), (U256::from(3), U256::from(5)))],
+        )]);
+
+        let diff = StateDiff::new(Some(&old), None);
+        assert_eq!(diff.accounts[&address], AccountDiff { old: account(2), new: account(1) });
+        assert_eq!(
+            diff.storage[&address][&U256::from(1)],
+            StorageSlotDiff { old: U256::from(5), new: U256::from(3) }
+        );
+    }
+
+    #[test]
+    fn reorg_leaves_out_identical_changes() {
+        let (changed, unchanged) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (only_old, only_new, both) = (U256::from(1), U256::from(2), U256::from(3));
+        let old = chain(vec![
+            (
+                changed,
+                account(1),
+                account(2),
+                vec![(only_old, (U256::ZERO, U256::from(5))), (both, (U256::ZERO, U256::from(9)))],
+            ),
+            (unchanged, None, account(7), Vec::new()),
+        ]);
+        let new = chain(vec![
+            (
+                changed,
+                account(1),
+                account(3),
+                vec![(only_new, (U256::ZERO, U256::from(6))), (both, (U256::ZERO, U256::from(9)))],
+            ),
+            (unchanged, None, account(7), Vec::new()),
+        ]);
+
+        let diff = StateDiff::new(Some(&old), Some(&new));
+        assert_eq!(
+            diff.accounts,
+            BTreeMap::from([(changed, AccountDiff { old: account(2), new: account(3) })])
+        );
+        assert_eq!(
+            diff.storage,
+            BTreeMap::from([(
+                changed,
+                BTreeMap::from([
+                    (only_old, StorageSlotDiff { old: U256::from(5), new: U256::ZERO }),
+                    (only_new, StorageSlotDiff { old: U256::ZERO, new: U256::from(6) }),
+                ])
+            )])
+        );
+
+        // the same chain on both sides has no diff
+        assert!(StateDiff::new(Some(&new), Some(&new)).is_empty());
+    }
+}
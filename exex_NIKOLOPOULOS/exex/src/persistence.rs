@@ -0,0 +1,168 @@
+use reth_primitives::BlockNumber;
+use reth_tracing::tracing::warn;
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// The name of the file the finished height is stored in.
+const FINISHED_HEIGHT_FILE_NAME: &str = "finished_height";
+
+/// Persistent storage of the last `FinishedHeight` emitted by an `ExEx`.
+///
+/// The height is stored as a decimal number in `<root>/<exex id>/finished_height`, and is
+/// replaced atomically on every update, so a crash never leaves a partially written file behind.
+#[derive(Debug, Clone)]
+pub struct FinishedHeightStore {
+    /// path of the file the finished height is stored in.
+    path: PathBuf,
+    /// the height waiting to be written by [`Self::save_in_background`].
+    pending: Arc<Mutex<PendingSave>>,
+}
+
+/// A finished height waiting to be written in the background.
+#[derive(Debug, Default)]
+struct PendingSave {
+    /// the latest height that was not written yet.
+    height: Option<BlockNumber>,
+    /// whether a blocking task is writing heights.
+    writing: bool,
+}
+
+impl PartialEq for FinishedHeightStore {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for FinishedHeightStore {}
+
+impl FinishedHeightStore {
+    /// Creates a store for the `ExEx` with the given ID under the `root` directory, e.g.
+    /// `<datadir>/exex`.
+    ///
+    /// Returns an error if the ID is not a plain directory name, e.g. if it contains a path
+    /// separator or is `..`, since the store would end up outside of `root`.
+    pub fn new(root: impl AsRef<Path>, exex_id: &str) -> io::Result<Self> {
+        ensure_valid_id(exex_id)?;
+        Ok(Self {
+            path: root.as_ref().join(exex_id).join(FINISHED_HEIGHT_FILE_NAME),
+            pending: Default::default(),
+        })
+    }
+
+    /// Returns the path of the file the finished height is stored in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the persisted finished height.
+    ///
+    /// Returns `None` if no finished height was persisted yet.
+    pub fn load(&self) -> io::Result<Option<BlockNumber>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        contents
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Persists the finished height, replacing the previous one.
+    ///
+    /// The height is written to a temporary file that is synced to disk before it replaces the
+    /// previous file.
+    pub fn save(&self, height: BlockNumber) -> io::Result<()> {
+        let parent = self.path.parent().expect("store path has a parent");
+        fs::create_dir_all(parent)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        io::Write::write_all(&mut file, height.to_string().as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(tmp_path, &self.path)?;
+
+        // persist the rename itself
+        #[cfg(unix)]
+        fs::File::open(parent)?.sync_all()?;
+        Ok(())
+    }
+
+    /// Persists the finished height on a blocking task, so the caller doesn't wait for the disk.
+    ///
+    /// Heights saved while a previous write is in progress are coalesced, only the latest one is
+    /// written. Failed writes are logged.
+    pub fn save_in_background(&self, height: BlockNumber) {
+        let mut pending = self.pending.lock().expect("pending save lock poisoned");
+        pending.height = Some(height);
+        if pending.writing {
+            return
+        }
+        pending.writing = true;
+        drop(pending);
+
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || loop {
+            let mut pending = this.pending.lock().expect("pending save lock poisoned");
+            let Some(height) = pending.height.take() else {
+                pending.writing = false;
+                return
+            };
+            drop(pending);
+
+            if let Err(err) = this.save(height) {
+                warn!(path = ?this.path, %height, %err, "Failed to persist finished height");
+            }
+        });
+    }
+}
+
+/// Ensures the ID of an `ExEx` can be used as a directory name, without escaping the directory it
+/// is joined to.
+pub(crate) fn ensure_valid_id(exex_id: &str) -> io::Result<()> {
+    let mut components = Path::new(exex_id).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) if name == exex_id => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid ExEx ID {exex_id:?}, must be a plain directory name"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load() {
+        let root = tempfile::tempdir().unwrap();
+        let store = FinishedHeightStore::new(root.path(), "my-exex").unwrap();
+        assert_eq!(store.path(), root.path().join("my-exex").join(FINISHED_HEIGHT_FILE_NAME));
+
+        assert_eq!(store.load().unwrap(), None);
+        store.save(10).unwrap();
+        assert_eq!(store.load().unwrap(), Some(10));
+        store.save(5).unwrap();
+        assert_eq!(store.load().unwrap(), Some(5));
+        assert!(!store.path().with_extension("tmp").exists());
+
+        // a new store for the same ExEx sees the persisted height
+        let reopened = FinishedHeightStore::new(root.path(), "my-exex").unwrap();
+        assert_eq!(reopened.load().unwrap(), Some(5));
+    }
+
+    #[test]
+    fn rejects_ids_escaping_the_root() {
+        let root = tempfile::tempdir().unwrap();
+        for id in ["", ".", "..", "../exex", "exex/..", "a/b", "/tmp/exex", "./exex"] {
+            assert!(FinishedHeightStore::new(root.path(), id).is_err(), "{id:?} was accepted");
+        }
+    }
+}
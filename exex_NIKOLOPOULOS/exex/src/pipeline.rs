@@ -0,0 +1,295 @@
+use crate::{persistence::ensure_valid_id, ExExNotification, FinishedHeightStore};
+use eyre::WrapErr;
+use reth_primitives::{BlockNumHash, BlockNumber};
+use reth_tracing::tracing::{debug, trace};
+use std::path::Path;
+
+/// A single step of an [`ExExPipeline`].
+///
+/// Stages are executed in order for every notification, and the output of a stage is the input of
+/// the next one. A stage receives its input by reference, so that it can be retried with the same
+/// input if it fails.
+pub trait Stage {
+    /// The input of the stage.
+    type Input;
+    /// The output of the stage, passed to the next stage.
+    type Output;
+
+    /// Returns the ID of the stage, used to name its checkpoint and in errors.
+    fn id(&self) -> &'static str;
+
+    /// Executes the stage for the notification that ends at block `height`.
+    fn execute(&mut self, height: BlockNumber, input: &Self::Input) -> eyre::Result<Self::Output>;
+}
+
+/// The checkpoint of a single stage.
+#[derive(Debug)]
+struct StageCheckpoint<O> {
+    /// the height of the last notification the stage has completed.
+    height: Option<BlockNumber>,
+    /// the output of the stage for the notification that is currently being processed, keyed by
+    /// its sequence, kept until all downstream stages have completed.
+    output: Option<(BlockNumHash, O)>,
+    /// the store the checkpoint height is persisted in.
+    store: Option<FinishedHeightStore>,
+}
+
+impl<O> StageCheckpoint<O> {
+    const fn new() -> Self {
+        Self { height: None, output: None, store: None }
+    }
+
+    /// Returns the cached output of the stage for the notification with the given sequence.
+    ///
+    /// The sequence includes the hash of the tip, so the output for a chain that was reorged away
+    /// is not reused for the new chain at the same height.
+    fn output(&self, sequence: BlockNumHash) -> Option<&O> {
+        self.output.as_ref().filter(|(output, _)| *output == sequence).map(|(_, output)| output)
+    }
+
+    /// Records that the stage has completed the notification with the given sequence.
+    fn complete(&mut self, sequence: BlockNumHash, output: O) -> eyre::Result<()> {
+        if let Some(store) = &self.store {
+            store.save(sequence.number)?;
+        }
+        self.height = Some(sequence.number);
+        self.output = Some((sequence, output));
+        Ok(())
+    }
+}
+
+/// A runner of a [`Stage`] that keeps track of its checkpoint.
+#[derive(Debug)]
+struct StageRunner<S: Stage> {
+    stage: S,
+    checkpoint: StageCheckpoint<S::Output>,
+}
+
+impl<S: Stage> StageRunner<S> {
+    const fn new(stage: S) -> Self {
+        Self { stage, checkpoint: StageCheckpoint::new() }
+    }
+
+    /// Returns the output of the stage for the notification with the given sequence, executing the
+    /// stage only if there is no cached output for it.
+    fn run(&mut self, sequence: BlockNumHash, input: &S::Input) -> eyre::Result<&S::Output> {
+        if self.checkpoint.output(sequence).is_none() {
+            let id = self.stage.id();
+            let height = sequence.number;
+            trace!(target: "exex::pipeline", stage = id, height, "Executing stage");
+
+            let output = self
+                .stage
+                .execute(height, input)
+                .wrap_err_with(|| format!("stage {id} failed at height {height}"))?;
+            self.checkpoint
+                .complete(sequence, output)
+                .wrap_err_with(|| format!("failed to save checkpoint of stage {id}"))?;
+        }
+
+        Ok(self.checkpoint.output(sequence).expect("output was just set"))
+    }
+}
+
+/// A pipeline of three stages that an `ExEx` can be composed of: a decode stage that extracts the
+/// data the `ExEx` is interested in from a notification, a transform stage, and a sink stage that
+/// writes the result to its destination.
+///
+/// Every stage has its own checkpoint. If a stage fails, the outputs of the stages before it are
+/// kept, and processing the same notification again only re-runs the failed stage and the ones
+/// after it. With [`ExExPipeline::with_checkpoints`], the checkpoints are also persisted, and
+/// committed notifications already written by the sink are skipped after a restart.
+#[derive(Debug)]
+pub struct ExExPipeline<D, T, S>
+where
+    D: Stage<Input = ExExNotification>,
+    T: Stage<Input = D::Output>,
+    S: Stage<Input = T::Output, Output = ()>,
+{
+    decode: StageRunner<D>,
+    transform: StageRunner<T>,
+    sink: StageRunner<S>,
+}
+
+impl<D, T, S> ExExPipeline<D, T, S>
+where
+    D: Stage<Input = ExExNotification>,
+    T: Stage<Input = D::Output>,
+    S: Stage<Input = T::Output, Output = ()>,
+{
+    /// Creates a new pipeline out of the given stages.
+    pub const fn new(decode: D, transform: T, sink: S) -> Self {
+        Self {
+            decode: StageRunner::new(decode),
+            transform: StageRunner::new(transform),
+            sink: StageRunner::new(sink),
+        }
+    }
+
+    /// Persists the stage checkpoints in `<root>/<exex id>/stages/<stage id>`, and loads the
+    /// previously persisted ones.
+    pub fn with_checkpoints(mut self, root: impl AsRef<Path>, exex_id: &str) -> eyre::Result<Self> {
+        ensure_valid_id(exex_id)?;
+        let root = root.as_ref().join(exex_id).join("stages");
+
+        let decode = FinishedHeightStore::new(&root, self.decode.stage.id())?;
+        self.decode.checkpoint.height = decode.load()?;
+        self.decode.checkpoint.store = Some(decode);
+
+        let transform = FinishedHeightStore::new(&root, self.transform.stage.id())?;
+        self.transform.checkpoint.height = transform.load()?;
+        self.transform.checkpoint.store = Some(transform);
+
+        let sink = FinishedHeightStore::new(&root, self.sink.stage.id())?;
+        self.sink.checkpoint.height = sink.load()?;
+        self.sink.checkpoint.store = Some(sink);
+
+        Ok(self)
+    }
+
+    /// Returns the height of the last notification written by the sink stage.
+    ///
+    /// This is the height an `ExEx` can report with `ExExEvent::FinishedHeight`.
+    pub const fn finished_height(&self) -> Option<BlockNumber> {
+        self.sink.checkpoint.height
+    }
+
+    /// Returns the checkpoints of the decode, transform and sink stages, in that order.
+    pub const fn checkpoints(&self) -> [Option<BlockNumber>; 3] {
+        [
+            self.decode.checkpoint.height,
+            self.transform.checkpoint.height,
+            self.sink.checkpoint.height,
+        ]
+    }
+
+    /// Runs the notification through all stages.
+    ///
+    /// Returns the height of the notification once the sink stage has completed, or `None` if the
+    /// notification was already written by the sink.
+    ///
+    /// If a stage fails, the error is returned and the same notification should be passed again to
+    /// retry it.
+    pub fn process(
+        &mut self,
+        notification: &ExExNotification,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        let sequence = notification.sequence();
+        let height = sequence.number;
+
+        // reorgs and reverts move the checkpoints backwards, so only committed notifications can
+        // be skipped
+        if matches!(notification, ExExNotification::ChainCommitted { .. }) &&
+            self.sink.checkpoint.height.is_some_and(|sink| sink >= height)
+        {
+            debug!(target: "exex::pipeline", height, "Skipping already processed notification");
+            return Ok(None)
+        }
+
+        let decoded = self.decode.run(sequence, notification)?;
+        let transformed = self.transform.run(sequence, decoded)?;
+        self.sink.run(sequence, transformed)?;
+
+        // drop the intermediate outputs, they are not needed anymore
+        self.decode.checkpoint.output = None;
+        self.transform.checkpoint.output = None;
+        self.sink.checkpoint.output = None;
+
+        Ok(Some(height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders, B256};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use std::sync::Arc;
+
+    /// Creates a chain of a single empty block, `fork` makes the hash differ from other forks.
+    fn chain(number: BlockNumber, fork: u64) -> Arc<Chain> {
+        let header = Header { number, timestamp: fork, ..Default::default() }.seal_slow();
+        let block = SealedBlockWithSenders {
+            block: SealedBlock { header, ..Default::default() },
+            senders: Vec::new(),
+        };
+        let outcome = ExecutionOutcome { first_block: number, ..Default::default() };
+        Arc::new(Chain::new(vec![block], outcome, None))
+    }
+
+    /// Decodes the hash of the tip of the committed chain.
+    struct Decode;
+
+    impl Stage for Decode {
+        type Input = ExExNotification;
+        type Output = B256;
+
+        fn id(&self) -> &'static str {
+            "decode"
+        }
+
+        fn execute(&mut self, _: BlockNumber, input: &ExExNotification) -> eyre::Result<B256> {
+            Ok(input.sequence().hash)
+        }
+    }
+
+    /// Passes the input through, failing the given number of times first.
+    struct Transform {
+        failures: usize,
+    }
+
+    impl Stage for Transform {
+        type Input = B256;
+        type Output = B256;
+
+        fn id(&self) -> &'static str {
+            "transform"
+        }
+
+        fn execute(&mut self, _: BlockNumber, input: &B256) -> eyre::Result<B256> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                eyre::bail!("transform failed")
+            }
+            Ok(*input)
+        }
+    }
+
+    /// Records the written hashes.
+    #[derive(Default)]
+    struct Sink {
+        written: Vec<(BlockNumber, B256)>,
+    }
+
+    impl Stage for Sink {
+        type Input = B256;
+        type Output = ();
+
+        fn id(&self) -> &'static str {
+            "sink"
+        }
+
+        fn execute(&mut self, height: BlockNumber, input: &B256) -> eyre::Result<()> {
+            self.written.push((height, *input));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reorg_does_not_reuse_outputs_of_old_chain() {
+        let mut pipeline = ExExPipeline::new(Decode, Transform { failures: 1 }, Sink::default());
+
+        // the transform stage fails after the decode stage completed the old chain
+        let old = chain(1, 0);
+        let committed = ExExNotification::ChainCommitted { new: old.clone() };
+        assert!(pipeline.process(&committed).is_err());
+        assert_eq!(pipeline.checkpoints(), [Some(1), None, None]);
+
+        // the old chain is reorged to a new chain at the same height
+        let new = chain(1, 1);
+        let reorged = ExExNotification::ChainReorged { old, new: new.clone() };
+        assert_eq!(pipeline.process(&reorged).unwrap(), Some(1));
+        assert_eq!(pipeline.sink.stage.written, vec![(1, new.tip().hash())]);
+        assert_eq!(pipeline.checkpoints(), [Some(1); 3]);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{BackfillJob, ExExNotification};
+use reth_evm::execute::BlockExecutorProvider;
+use reth_provider::{BlockReader, HeaderProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::mpsc,
+    time::{sleep, Sleep},
+};
+
+/// The default maximum number of catch-up notifications held in memory for a single `ExEx`.
+pub const DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS: usize = 16;
+
+/// Throttling of the notifications replayed to an `ExEx` that is catching up.
+///
+/// Replayed notifications are the catch-up notifications given to
+/// [`ExExHandle::with_catch_up`](crate::ExExHandle::with_catch_up) and, with
+/// [`ExExBackpressure::Unbounded`](crate::ExExBackpressure::Unbounded), the notifications queued
+/// for a lagging `ExEx`. Live notifications from the manager's buffer are never throttled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayThrottle {
+    /// The maximum number of blocks replayed per second, `None` for no limit.
+    pub blocks_per_second: Option<u64>,
+    /// The maximum number of catch-up notifications produced ahead of delivery.
+    ///
+    /// This bounds the number of catch-up notifications held in memory, independent of the length
+    /// of the catch-up range. The memory used depends on the size of the notifications, e.g. on
+    /// the batch size of a [`BackfillJob`].
+    pub max_queued_notifications: usize,
+}
+
+impl Default for ReplayThrottle {
+    fn default() -> Self {
+        Self {
+            blocks_per_second: None,
+            max_queued_notifications: DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS,
+        }
+    }
+}
+
+impl ReplayThrottle {
+    /// Limits the replay to the given number of blocks per second.
+    pub const fn with_blocks_per_second(mut self, blocks_per_second: u64) -> Self {
+        self.blocks_per_second = Some(blocks_per_second);
+        self
+    }
+
+    /// Sets the maximum number of catch-up notifications held in memory.
+    pub const fn with_max_queued_notifications(mut self, max_queued_notifications: usize) -> Self {
+        self.max_queued_notifications = max_queued_notifications;
+        self
+    }
+}
+
+/// The catch-up notifications of an `ExEx`, produced on a blocking task.
+///
+/// Producing catch-up notifications can be expensive, e.g. the notifications of a
+/// [`BackfillJob`] execute blocks, so the iterators are driven on a blocking task that stays at
+/// most [`ReplayThrottle::max_queued_notifications`] notifications ahead of delivery.
+pub(crate) struct CatchUp {
+    /// a notification that was received but could not be sent.
+    front: Option<(usize, ExExNotification)>,
+    /// the notifications produced from the iterator that is being drained.
+    current: Option<mpsc::Receiver<ExExNotification>>,
+    /// the iterators drained after the current one, in order.
+    remaining: VecDeque<Box<dyn Iterator<Item = ExExNotification> + Send>>,
+    /// the position of the next notification received from the producer.
+    ///
+    /// the IDs are the position in the catch-up sequence and only used for logging.
+    next_id: usize,
+}
+
+impl fmt::Debug for CatchUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchUp")
+            .field("queued", &self.len())
+            .field("remaining", &self.remaining.len())
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
+
+impl CatchUp {
+    /// Creates a new catch-up from the given notifications.
+    pub(crate) fn new(
+        notifications: impl Iterator<Item = ExExNotification> + Send + 'static,
+    ) -> Self {
+        Self {
+            front: None,
+            current: None,
+            remaining: VecDeque::from([
+                Box::new(notifications) as Box<dyn Iterator<Item = ExExNotification> + Send>
+            ]),
+            next_id: 0,
+        }
+    }
+
+    /// Polls the next catch-up notification, starting to produce the next iterator on a blocking
+    /// task once the current one is drained.
+    ///
+    /// Returns `None` once all notifications were received.
+    pub(crate) fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+        max_queued_notifications: usize,
+    ) -> Poll<Option<(usize, ExExNotification)>> {
+        if let Some(notification) = self.front.take() {
+            return Poll::Ready(Some(notification))
+        }
+
+        loop {
+            if let Some(current) = self.current.as_mut() {
+                match ready!(current.poll_recv(cx)) {
+                    Some(notification) => {
+                        let id = self.next_id;
+                        self.next_id += 1;
+                        return Poll::Ready(Some((id, notification)))
+                    }
+                    None => self.current = None,
+                }
+            }
+
+            let Some(notifications) = self.remaining.pop_front() else { return Poll::Ready(None) };
+            self.current = Some(produce(notifications, max_queued_notifications));
+        }
+    }
+
+    /// Appends more notifications, which are sent after the remaining ones.
+    pub(crate) fn append(
+        &mut self,
+        notifications: impl Iterator<Item = ExExNotification> + Send + 'static,
+    ) {
+        self.remaining.push_back(Box::new(notifications));
+    }
+
+    /// Puts back a notification that could not be sent.
+    pub(crate) fn push_front(&mut self, notification: (usize, ExExNotification)) {
+        debug_assert!(self.front.is_none(), "only one notification is put back");
+        self.front = Some(notification);
+    }
+
+    /// Returns the number of notifications held in memory.
+    pub(crate) fn len(&self) -> usize {
+        usize::from(self.front.is_some()) + self.current.as_ref().map_or(0, |current| current.len())
+    }
+}
+
+/// Drains the iterator on a blocking task, holding at most `max_queued_notifications`
+/// notifications in the returned channel.
+///
+/// The task stops once the receiver is dropped.
+fn produce(
+    notifications: Box<dyn Iterator<Item = ExExNotification> + Send>,
+    max_queued_notifications: usize,
+) -> mpsc::Receiver<ExExNotification> {
+    let (tx, rx) = mpsc::channel(max_queued_notifications.max(1));
+    tokio::task::spawn_blocking(move || {
+        for notification in notifications {
+            if tx.blocking_send(notification).is_err() {
+                break
+            }
+        }
+    });
+    rx
+}
+
+/// Token bucket limiting the number of blocks replayed per second.
+///
+/// The bucket holds at most one second worth of blocks, so the replay can burst up to
+/// `blocks_per_second` blocks at once. A notification with more blocks than that is let through
+/// once the bucket is full, and leaves the bucket in debt.
+#[derive(Debug)]
+pub(crate) struct ReplayRateLimiter {
+    /// blocks added to the bucket per second.
+    rate: f64,
+    /// the current number of blocks in the bucket, negative when in debt.
+    tokens: f64,
+    /// the last time the bucket was refilled.
+    last_refill: Instant,
+    /// timer waking up the manager once enough blocks are in the bucket.
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl ReplayRateLimiter {
+    /// Creates a full bucket refilled with `blocks_per_second` blocks per second.
+    pub(crate) fn new(blocks_per_second: u64) -> Self {
+        let rate = blocks_per_second.max(1) as f64;
+        Self { rate, tokens: rate, last_refill: Instant::now(), delay: None }
+    }
+
+    /// Returns [`Poll::Ready`] once `blocks` blocks may be replayed.
+    ///
+    /// The blocks are only taken out of the bucket by [`Self::consume`].
+    pub(crate) fn poll_ready(&mut self, cx: &mut Context<'_>, blocks: u64) -> Poll<()> {
+        loop {
+            let now = Instant::now();
+            let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+            self.last_refill = now;
+
+            let required = (blocks as f64).min(self.rate);
+            if self.tokens >= required {
+                self.delay = None;
+                return Poll::Ready(())
+            }
+
+            let wait = Duration::from_secs_f64((required - self.tokens) / self.rate);
+            let delay = self.delay.get_or_insert_with(|| Box::pin(sleep(wait)));
+            delay.as_mut().reset(tokio::time::Instant::now() + wait);
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending
+            }
+        }
+    }
+
+    /// Takes the replayed blocks out of the bucket.
+    pub(crate) fn consume(&mut self, blocks: u64) {
+        self.tokens -= blocks as f64;
+    }
+}
+
+/// Turns a backfill job into the notifications replaying its range to an `ExEx`.
+///
+/// Every batch of blocks is sent as a [`ExExNotification::ChainReorged`] with the same old and new
+/// chain, so the `ExEx` drops what it derived from the blocks before processing them again, and
+/// the blocks are not skipped as already delivered by
+/// [`ExExNotifications`](crate::ExExNotifications). The replay stops at the first batch that
+/// fails to execute.
+///
+/// The iterator executes blocks, so it must only be drained off the manager's task, see
+/// [`CatchUp`].
+pub(crate) fn replay_notifications<E, P>(
+    job: BackfillJob<E, P>,
+) -> impl Iterator<Item = ExExNotification> + Send + 'static
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + HeaderProvider + StateProviderFactory + Send + 'static,
+{
+    job.map_while(|notification| match notification {
+        Ok(ExExNotification::ChainCommitted { new }) => {
+            Some(ExExNotification::ChainReorged { old: new.clone(), new })
+        }
+        Ok(notification) => Some(notification),
+        Err(err) => {
+            warn!(target: "exex::replay", %err, "Failed to execute replayed blocks");
+            None
+        }
+    })
+}
+
+/// Returns the number of blocks a notification covers, counting both the reverted and the
+/// committed chain.
+pub(crate) fn notification_blocks(notification: &ExExNotification) -> u64 {
+    let reverted = notification.reverted_chain().map_or(0, |chain| chain.len());
+    let committed = notification.committed_chain().map_or(0, |chain| chain.len());
+    (reverted + committed) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use std::{
+        future::poll_fn,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    fn notification(number: u64) -> ExExNotification {
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header: Header { number, ..Default::default() }.seal_slow(),
+                ..Default::default()
+            },
+            senders: Vec::new(),
+        };
+        let execution_outcome = ExecutionOutcome { first_block: number, ..Default::default() };
+        ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], execution_outcome, None)),
+        }
+    }
+
+    fn tip(notification: &ExExNotification) -> u64 {
+        notification.committed_chain().expect("committed chain").tip().number
+    }
+
+    #[tokio::test]
+    async fn catch_up_in_order_and_bounded() {
+        let produced = Arc::new(AtomicUsize::new(0));
+        let counter = produced.clone();
+        let notifications = (1..=10).map(move |number| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            notification(number)
+        });
+        let mut catch_up = CatchUp::new(notifications);
+        catch_up.append((11..=12).map(notification));
+
+        let (id, first) = poll_fn(|cx| catch_up.poll_next(cx, 2)).await.unwrap();
+        assert_eq!((id, tip(&first)), (0, 1));
+
+        // the producer is at most two notifications ahead, plus the one it is blocked on
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(produced.load(Ordering::SeqCst) <= 4);
+        assert!(catch_up.len() <= 2);
+
+        // a notification that could not be sent is delivered first
+        catch_up.push_front((id, first));
+        let mut tips = Vec::new();
+        while let Some((_, notification)) = poll_fn(|cx| catch_up.poll_next(cx, 2)).await {
+            tips.push(tip(&notification));
+        }
+        assert_eq!(tips, (1..=12).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_waits_for_refill() {
+        let mut limiter = ReplayRateLimiter::new(1_000);
+
+        // the bucket starts full
+        let ready = poll_fn(|cx| Poll::Ready(limiter.poll_ready(cx, 1_000))).await;
+        assert!(ready.is_ready());
+        limiter.consume(1_000);
+
+        // 100 blocks are refilled after 100ms
+        let start = Instant::now();
+        poll_fn(|cx| limiter.poll_ready(cx, 100)).await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn counts_reverted_and_committed_blocks() {
+        let ExExNotification::ChainCommitted { new } = notification(1) else { unreachable!() };
+        assert_eq!(notification_blocks(&ExExNotification::ChainCommitted { new: new.clone() }), 1);
+        assert_eq!(notification_blocks(&ExExNotification::ChainReverted { old: new.clone() }), 1);
+        assert_eq!(
+            notification_blocks(&ExExNotification::ChainReorged { old: new.clone(), new }),
+            2
+        );
+    }
+}
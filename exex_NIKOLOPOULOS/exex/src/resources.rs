@@ -0,0 +1,311 @@
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    ptr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    /// the counters of the `ExEx` that is being polled on this thread, if any.
+    static CURRENT: Cell<*const ResourceCounters> = const { Cell::new(ptr::null()) };
+}
+
+/// metrics for the resource usage of an `ExEx`.
+#[derive(Metrics)]
+#[metrics(scope = "exex")]
+struct ExExResourceMetrics {
+    /// the total number of times the future of an `ExEx` was polled.
+    polls_total: Counter,
+    /// the time a single poll of the future of an `ExEx` took, in seconds.
+    poll_duration_seconds: Histogram,
+    /// the total time spent polling the future of an `ExEx`, in microseconds.
+    poll_time_micros_total: Counter,
+    /// the total number of bytes allocated while polling the future of an `ExEx`.
+    ///
+    /// only recorded if the node uses the [`ExExAllocator`].
+    allocated_bytes_total: Counter,
+    /// the number of bytes allocated minus the number of bytes freed while polling the future of
+    /// an `ExEx`.
+    ///
+    /// memory allocated by an `ExEx` but freed by another task is not accounted for.
+    net_allocated_bytes: Gauge,
+}
+
+/// The counters updated while an `ExEx` is polled.
+#[derive(Debug, Default)]
+struct ResourceCounters {
+    polls: AtomicU64,
+    poll_time_nanos: AtomicU64,
+    max_poll_time_nanos: AtomicU64,
+    allocated_bytes: AtomicU64,
+    deallocated_bytes: AtomicU64,
+}
+
+/// The resource usage of an `ExEx`, shared between its [`ExExHandle`](crate::ExExHandle) and the
+/// task running its future.
+///
+/// The poll time is measured by running the future of the `ExEx` through [`Self::track`]. Memory
+/// allocations are only attributed to the `ExEx` if the node installs the [`ExExAllocator`] as its
+/// global allocator. Work the `ExEx` does in tasks it spawns itself is not accounted for.
+#[derive(Clone)]
+pub struct ExExResourceUsage {
+    /// the counters of the `ExEx`.
+    counters: Arc<ResourceCounters>,
+    /// the metrics of the `ExEx`.
+    metrics: Arc<ExExResourceMetrics>,
+}
+
+impl fmt::Debug for ExExResourceUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExResourceUsage").field("usage", &self.snapshot()).finish()
+    }
+}
+
+impl ExExResourceUsage {
+    /// Creates the resource usage of the `ExEx` with the given ID.
+    pub fn new(exex_id: &str) -> Self {
+        Self {
+            counters: Default::default(),
+            metrics: Arc::new(ExExResourceMetrics::new_with_labels(&[(
+                "exex",
+                exex_id.to_string(),
+            )])),
+        }
+    }
+
+    /// Wraps the future of the `ExEx`, measuring the time spent polling it and the memory it
+    /// allocates.
+    pub fn track<F: Future>(&self, future: F) -> TrackedExEx<F> {
+        TrackedExEx { future: Box::pin(future), usage: self.clone() }
+    }
+
+    /// Returns the resource usage of the `ExEx` so far.
+    pub fn snapshot(&self) -> ExExResourceSnapshot {
+        let counters = &self.counters;
+        ExExResourceSnapshot {
+            polls: counters.polls.load(Ordering::Relaxed),
+            poll_time: Duration::from_nanos(counters.poll_time_nanos.load(Ordering::Relaxed)),
+            max_poll_time: Duration::from_nanos(
+                counters.max_poll_time_nanos.load(Ordering::Relaxed),
+            ),
+            allocated_bytes: counters.allocated_bytes.load(Ordering::Relaxed),
+            deallocated_bytes: counters.deallocated_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Records a single poll of the future of the `ExEx`.
+    fn record_poll(&self, elapsed: Duration, allocated: u64, deallocated: u64) {
+        let nanos = elapsed.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.counters.polls.fetch_add(1, Ordering::Relaxed);
+        self.counters.poll_time_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.counters.max_poll_time_nanos.fetch_max(nanos, Ordering::Relaxed);
+
+        self.metrics.polls_total.increment(1);
+        self.metrics.poll_duration_seconds.record(elapsed.as_secs_f64());
+        self.metrics.poll_time_micros_total.increment(elapsed.as_micros() as u64);
+        self.metrics.allocated_bytes_total.increment(allocated);
+        self.metrics.net_allocated_bytes.increment(allocated as f64);
+        self.metrics.net_allocated_bytes.decrement(deallocated as f64);
+    }
+}
+
+/// A snapshot of the resource usage of an `ExEx`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExExResourceSnapshot {
+    /// The number of times the future of the `ExEx` was polled.
+    pub polls: u64,
+    /// The total time spent polling the future of the `ExEx`.
+    pub poll_time: Duration,
+    /// The longest time a single poll took.
+    pub max_poll_time: Duration,
+    /// The total number of bytes allocated while polling the future of the `ExEx`.
+    pub allocated_bytes: u64,
+    /// The total number of bytes freed while polling the future of the `ExEx`.
+    pub deallocated_bytes: u64,
+}
+
+/// The future of an `ExEx` with its resource usage being tracked, see
+/// [`ExExResourceUsage::track`].
+pub struct TrackedExEx<F> {
+    /// the future of the `ExEx`.
+    future: Pin<Box<F>>,
+    /// where the resource usage is recorded.
+    usage: ExExResourceUsage,
+}
+
+impl<F> fmt::Debug for TrackedExEx<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedExEx").field("usage", &self.usage).finish_non_exhaustive()
+    }
+}
+
+impl<F: Future> Future for TrackedExEx<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let counters = &this.usage.counters;
+        let allocated = counters.allocated_bytes.load(Ordering::Relaxed);
+        let deallocated = counters.deallocated_bytes.load(Ordering::Relaxed);
+
+        // The counters outlive the poll, since `this.usage` holds on to them
+        let guard = CurrentGuard::enter(counters);
+        let started_at = Instant::now();
+        let poll = this.future.as_mut().poll(cx);
+        let elapsed = started_at.elapsed();
+        drop(guard);
+
+        this.usage.record_poll(
+            elapsed,
+            counters.allocated_bytes.load(Ordering::Relaxed) - allocated,
+            counters.deallocated_bytes.load(Ordering::Relaxed) - deallocated,
+        );
+        poll
+    }
+}
+
+/// Sets the counters of the `ExEx` that is being polled on this thread, and restores the previous
+/// ones on drop, including when the future of the `ExEx` panics.
+struct CurrentGuard {
+    /// the counters that were set before.
+    previous: *const ResourceCounters,
+}
+
+impl CurrentGuard {
+    fn enter(counters: &Arc<ResourceCounters>) -> Self {
+        Self { previous: CURRENT.with(|current| current.replace(Arc::as_ptr(counters))) }
+    }
+}
+
+impl Drop for CurrentGuard {
+    fn drop(&mut self) {
+        // `try_with` fails while the thread local is destroyed, there is nothing to restore then
+        let _ = CURRENT.try_with(|current| current.set(self.previous));
+    }
+}
+
+/// A global allocator that attributes the memory allocated while the future of an `ExEx` is
+/// polled to that `ExEx`, see [`ExExResourceUsage`].
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: ExExAllocator<std::alloc::System> = ExExAllocator::new(std::alloc::System);
+/// ```
+#[derive(Debug, Default)]
+pub struct ExExAllocator<A> {
+    /// the allocator that does the actual allocations.
+    inner: A,
+}
+
+impl<A> ExExAllocator<A> {
+    /// Wraps the given allocator.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+/// Calls `f` with the counters of the `ExEx` that is being polled on this thread, if any.
+fn with_current(f: impl FnOnce(&ResourceCounters)) {
+    // `try_with` fails while the thread local is destroyed, allocations are not attributed then
+    if let Ok(current) = CURRENT.try_with(Cell::get) {
+        // SAFETY: the pointer is only set while the `TrackedExEx` holding the counters is polled
+        if let Some(counters) = unsafe { current.as_ref() } {
+            f(counters)
+        }
+    }
+}
+
+// SAFETY: all allocations are forwarded to the inner allocator.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for ExExAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        with_current(|counters| {
+            counters.allocated_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        });
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        with_current(|counters| {
+            counters.deallocated_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        });
+        self.inner.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        with_current(|counters| {
+            counters.allocated_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        });
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        with_current(|counters| {
+            counters.deallocated_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+            counters.allocated_bytes.fetch_add(new_size as u64, Ordering::Relaxed);
+        });
+        self.inner.realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::System;
+
+    #[tokio::test]
+    async fn track_poll_time() {
+        let usage = ExExResourceUsage::new("test");
+        usage
+            .track(async {
+                tokio::task::yield_now().await;
+                std::thread::sleep(Duration::from_millis(10));
+            })
+            .await;
+
+        let snapshot = usage.snapshot();
+        assert_eq!(snapshot.polls, 2);
+        assert!(snapshot.max_poll_time >= Duration::from_millis(10));
+        assert!(snapshot.poll_time >= snapshot.max_poll_time);
+    }
+
+    #[test]
+    fn attribute_allocations_to_current_exex() {
+        let alloc = ExExAllocator::new(System);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let (outer, inner) = (Arc::new(ResourceCounters::default()), Arc::default());
+
+        let outer_guard = CurrentGuard::enter(&outer);
+        // SAFETY: the layout has a non-zero size, and the memory is freed with the same layout
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            let inner_guard = CurrentGuard::enter(&inner);
+            alloc.dealloc(ptr, layout);
+            // the counters of the outer `ExEx` are restored
+            drop(inner_guard);
+            let ptr = alloc.alloc_zeroed(layout);
+            alloc.dealloc(ptr, layout);
+        }
+        drop(outer_guard);
+
+        // allocations outside of an `ExEx` are not attributed
+        // SAFETY: see above
+        unsafe { alloc.dealloc(alloc.alloc(layout), layout) };
+
+        assert_eq!(outer.allocated_bytes.load(Ordering::Relaxed), 128);
+        assert_eq!(outer.deallocated_bytes.load(Ordering::Relaxed), 64);
+        assert_eq!(inner.allocated_bytes.load(Ordering::Relaxed), 0);
+        assert_eq!(inner.deallocated_bytes.load(Ordering::Relaxed), 64);
+    }
+}
@@ -0,0 +1,226 @@
+use reth_primitives::BlockNumber;
+use reth_tracing::tracing::{debug, warn};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The default number of blocks between two snapshots of an `ExEx`.
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 10_000;
+
+/// The default number of snapshots kept on disk per `ExEx`.
+pub const DEFAULT_SNAPSHOTS_RETAINED: usize = 2;
+
+/// The file extension of snapshot files.
+const SNAPSHOT_FILE_EXTENSION: &str = "snapshot";
+
+/// Callbacks an `ExEx` registers to snapshot and restore its derived state.
+///
+/// The callbacks are invoked on a blocking task spawned by the [`ExExManager`](crate::ExExManager),
+/// so the state they capture is usually shared with the `ExEx` task (e.g. behind an
+/// `Arc<Mutex<_>>`).
+pub trait ExExSnapshotHooks: Send + 'static {
+    /// Serializes the derived state of the `ExEx` as of the given finished height.
+    fn snapshot(&mut self, height: BlockNumber) -> eyre::Result<Vec<u8>>;
+
+    /// Restores the derived state of the `ExEx` from a snapshot taken at the given height.
+    fn restore(&mut self, height: BlockNumber, data: Vec<u8>) -> eyre::Result<()>;
+}
+
+/// On-disk storage of the snapshots of a single `ExEx`.
+///
+/// Snapshots are stored as `<dir>/<height>.snapshot`, written atomically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotStore {
+    /// directory the snapshots are stored in.
+    dir: PathBuf,
+    /// how many snapshots to keep, older ones are removed.
+    retain: usize,
+}
+
+impl SnapshotStore {
+    /// Creates a store in the given directory, e.g. `<datadir>/exex/<id>/snapshots`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), retain: DEFAULT_SNAPSHOTS_RETAINED }
+    }
+
+    /// Sets how many snapshots are kept on disk. At least one snapshot is always kept.
+    pub fn with_retain(mut self, retain: usize) -> Self {
+        self.retain = retain.max(1);
+        self
+    }
+
+    /// Returns the directory the snapshots are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes a snapshot taken at the given height, and removes snapshots exceeding the retention.
+    ///
+    /// The snapshot is written to a temporary file that is synced to disk before it is renamed, so
+    /// a crash never leaves a partially written snapshot behind.
+    pub fn save(&self, height: BlockNumber, data: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(height);
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        io::Write::write_all(&mut file, data)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(tmp_path, path)?;
+
+        // persist the rename itself
+        #[cfg(unix)]
+        fs::File::open(&self.dir)?.sync_all()?;
+
+        let heights = self.heights()?;
+        for height in heights.iter().rev().skip(self.retain) {
+            fs::remove_file(self.path(*height))?;
+        }
+        Ok(())
+    }
+
+    /// Loads the most recent snapshot, returning the height it was taken at and its contents.
+    pub fn load_latest(&self) -> io::Result<Option<(BlockNumber, Vec<u8>)>> {
+        let Some(height) = self.heights()?.last().copied() else { return Ok(None) };
+        Ok(Some((height, fs::read(self.path(height))?)))
+    }
+
+    /// Returns the heights of the stored snapshots in ascending order.
+    fn heights(&self) -> io::Result<Vec<BlockNumber>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut heights = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_FILE_EXTENSION) {
+                continue
+            }
+            if let Some(height) = path.file_stem().and_then(|stem| stem.to_str()?.parse().ok()) {
+                heights.push(height);
+            }
+        }
+        heights.sort_unstable();
+        Ok(heights)
+    }
+
+    fn path(&self, height: BlockNumber) -> PathBuf {
+        self.dir.join(format!("{height}.{SNAPSHOT_FILE_EXTENSION}"))
+    }
+}
+
+/// Snapshot support of a single `ExEx`, shared between its [`ExExContext`](crate::ExExContext)
+/// and its [`ExExHandle`](crate::ExExHandle).
+///
+/// The `ExEx` registers its hooks with [`ExExContext::register_snapshot_hooks`], after which the
+/// manager takes a snapshot whenever the finished height of the `ExEx` crosses a multiple of the
+/// interval.
+///
+/// [`ExExContext::register_snapshot_hooks`]: crate::ExExContext::register_snapshot_hooks
+#[derive(Clone)]
+pub struct ExExSnapshots {
+    /// where the snapshots are stored.
+    store: SnapshotStore,
+    /// the number of blocks between two snapshots.
+    interval: u64,
+    /// the hooks registered by the `ExEx`, if any.
+    hooks: Arc<Mutex<Option<Box<dyn ExExSnapshotHooks>>>>,
+    /// whether a snapshot is being taken.
+    in_progress: Arc<AtomicBool>,
+}
+
+impl fmt::Debug for ExExSnapshots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExSnapshots")
+            .field("store", &self.store)
+            .field("interval", &self.interval)
+            .field("hooks", &"...")
+            .finish()
+    }
+}
+
+impl ExExSnapshots {
+    /// Creates snapshot support taking a snapshot every `interval` blocks.
+    ///
+    /// An interval of `0` is treated as `1`.
+    pub fn new(store: SnapshotStore, interval: u64) -> Self {
+        Self {
+            store,
+            interval: interval.max(1),
+            hooks: Default::default(),
+            in_progress: Default::default(),
+        }
+    }
+
+    /// Returns the store the snapshots are kept in.
+    pub const fn store(&self) -> &SnapshotStore {
+        &self.store
+    }
+
+    /// Returns the number of blocks between two snapshots.
+    pub const fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Registers the hooks and restores the latest snapshot, if any.
+    ///
+    /// Returns the height the restored snapshot was taken at.
+    pub fn register(
+        &self,
+        mut hooks: Box<dyn ExExSnapshotHooks>,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        let restored = match self.store.load_latest()? {
+            Some((height, data)) => {
+                debug!(target: "exex::snapshot", %height, "Restoring snapshot");
+                hooks.restore(height, data)?;
+                Some(height)
+            }
+            None => None,
+        };
+        *self.hooks.lock().expect("snapshot hooks lock poisoned") = Some(hooks);
+        Ok(restored)
+    }
+
+    /// Takes a snapshot on a blocking task if the finished height moved from `previous` across a
+    /// multiple of the interval.
+    ///
+    /// The snapshot is skipped if the previous one is still being taken.
+    pub(crate) fn on_finished_height(&self, previous: Option<BlockNumber>, height: BlockNumber) {
+        if height / self.interval <= previous.unwrap_or_default() / self.interval {
+            return
+        }
+
+        if self.in_progress.swap(true, Ordering::AcqRel) {
+            debug!(target: "exex::snapshot", %height, "Previous snapshot in progress, skipping");
+            return
+        }
+
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            this.snapshot(height);
+            this.in_progress.store(false, Ordering::Release);
+        });
+    }
+
+    /// Takes a snapshot at the given height with the registered hooks, if any.
+    fn snapshot(&self, height: BlockNumber) {
+        let mut hooks = self.hooks.lock().expect("snapshot hooks lock poisoned");
+        let Some(hooks) = hooks.as_mut() else { return };
+
+        debug!(target: "exex::snapshot", %height, "Taking snapshot");
+        let result = hooks
+            .snapshot(height)
+            .and_then(|data| self.store.save(height, &data).map_err(Into::into));
+        if let Err(err) = result {
+            warn!(target: "exex::snapshot", %height, %err, "Failed to take snapshot");
+        }
+    }
+}
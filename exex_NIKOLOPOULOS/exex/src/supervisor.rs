@@ -0,0 +1,462 @@
+use crate::{
+    replay::CatchUp, ExExContext, ExExEvent, ExExNotification, ExExNotifications,
+    DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS,
+};
+use futures::FutureExt;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_node_api::FullNodeComponents;
+use reth_primitives::BlockNumber;
+use reth_tracing::tracing::{debug, error, warn};
+use std::{
+    any::Any,
+    future::{poll_fn, Future},
+    panic::AssertUnwindSafe,
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::PollSender;
+
+/// metrics for a supervised `ExEx`.
+#[derive(Metrics)]
+#[metrics(scope = "exex")]
+struct ExExSupervisorMetrics {
+    /// the total number of times a supervised `ExEx` failed or panicked.
+    failures_total: Counter,
+    /// the total number of times a supervised `ExEx` was restarted.
+    restarts_total: Counter,
+}
+
+/// What an [`ExExSupervisor`] does when the `ExEx` it supervises fails or panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExRestartPolicy {
+    /// the `ExEx` is never restarted, the supervisor emits an [`ExExEvent::FatalError`] with the
+    /// error of the `ExEx` and resolves with its message.
+    #[default]
+    Never,
+    /// the `ExEx` is restarted indefinitely, waiting `initial` before the first restart and
+    /// doubling the delay for every consecutive failure, up to `max`.
+    ///
+    /// failures are consecutive if the `ExEx` did not emit a higher finished height in between.
+    ExponentialBackoff {
+        /// the delay before the first restart.
+        initial: Duration,
+        /// the maximum delay between two restarts.
+        max: Duration,
+    },
+    /// like [`ExExRestartPolicy::ExponentialBackoff`], but the `ExEx` is given up on after
+    /// `max_retries` consecutive restarts.
+    ///
+    /// the supervisor then emits an [`ExExEvent::FatalError`], which shuts down the node if the
+    /// `ExEx` is [critical](crate::ExExHandle::critical).
+    MaxRetries {
+        /// the number of restarts before giving up.
+        max_retries: u32,
+        /// the delay before the first restart.
+        initial: Duration,
+        /// the maximum delay between two restarts.
+        max: Duration,
+    },
+}
+
+impl ExExRestartPolicy {
+    /// Returns the delay before the given restart, starting at `1`, or `None` if the `ExEx`
+    /// should not be restarted anymore.
+    pub fn backoff(&self, restart: u32) -> Option<Duration> {
+        let (initial, max) = match *self {
+            Self::Never => return None,
+            Self::ExponentialBackoff { initial, max } => (initial, max),
+            Self::MaxRetries { max_retries, initial, max } => {
+                if restart > max_retries {
+                    return None
+                }
+                (initial, max)
+            }
+        };
+        let factor = 1u32.checked_shl(restart.saturating_sub(1)).unwrap_or(u32::MAX);
+        Some(initial.saturating_mul(factor).min(max))
+    }
+}
+
+/// Runs an `ExEx` and restarts it according to an [`ExExRestartPolicy`] when it fails or panics.
+///
+/// The supervisor is installed in place of the `ExEx` and receives its [`ExExContext`]. Every run
+/// of the `ExEx` gets its own context with fresh channels, which the supervisor connects to the
+/// channels of the manager. A restarted `ExEx` first receives the blocks after its last finished
+/// height that were already sent to the failed run, re-executed with a
+/// [`BackfillJob`](crate::BackfillJob), followed by the live notifications it has not received
+/// yet. A live notification the failed run did not accept is sent right after the catch-up
+/// blocks, since it builds on top of them.
+///
+/// The catch-up blocks are re-executed on a blocking task, at most
+/// [`DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS`] notifications ahead of delivery.
+pub struct ExExSupervisor<Node: FullNodeComponents, F> {
+    /// the context given to the supervisor by the node.
+    ctx: ExExContext<Node>,
+    /// launches a run of the `ExEx`.
+    launch: F,
+    /// the restart policy.
+    policy: ExExRestartPolicy,
+    /// metrics for the supervised `ExEx`.
+    metrics: ExExSupervisorMetrics,
+
+    /// the last finished height emitted by the `ExEx`.
+    finished_height: Option<BlockNumber>,
+    /// the tip of the chain after the last notification sent to the `ExEx`.
+    delivered_tip: Option<BlockNumber>,
+    /// the notification received from the manager but not yet accepted by the `ExEx`.
+    pending: Option<ExExNotification>,
+    /// whether `pending` was re-executed for the catch-up, rather than received from the manager.
+    pending_from_catch_up: bool,
+}
+
+impl<Node: FullNodeComponents, F> std::fmt::Debug for ExExSupervisor<Node, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExExSupervisor")
+            .field("ctx", &self.ctx)
+            .field("policy", &self.policy)
+            .field("finished_height", &self.finished_height)
+            .field("delivered_tip", &self.delivered_tip)
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node, F, Fut, E> ExExSupervisor<Node, F>
+where
+    Node: FullNodeComponents,
+    F: FnMut(ExExContext<Node>) -> Fut,
+    Fut: Future<Output = eyre::Result<E>>,
+    E: Future<Output = eyre::Result<()>>,
+{
+    /// Creates a supervisor that launches the `ExEx` with the same closure it is installed with.
+    ///
+    /// Defaults to [`ExExRestartPolicy::Never`].
+    pub fn new(ctx: ExExContext<Node>, launch: F) -> Self {
+        let metrics = ExExSupervisorMetrics::new_with_labels(&[("exex", ctx.id.clone())]);
+        Self {
+            ctx,
+            launch,
+            policy: ExExRestartPolicy::default(),
+            metrics,
+            finished_height: None,
+            delivered_tip: None,
+            pending: None,
+            pending_from_catch_up: false,
+        }
+    }
+
+    /// Sets the restart policy.
+    pub const fn with_restart_policy(mut self, policy: ExExRestartPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Runs the `ExEx` until it resolves successfully, or fails and is not restarted anymore.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let mut restarts = 0;
+        let mut replay = RunReplay::default();
+        loop {
+            let finished_height = self.finished_height;
+            let catch_up_tip = replay.catch_up_tip;
+            let err = match self.run_once(&mut replay).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            self.metrics.failures_total.increment(1);
+
+            // The ExEx made progress since the last failure, so back off from the start again
+            if self.finished_height > finished_height {
+                restarts = 0;
+            }
+
+            // Nothing is restarted while the node is shutting down
+            if self.ctx.shutdown.is_cancelled() {
+                return Err(err)
+            }
+
+            restarts += 1;
+            let Some(delay) = self.policy.backoff(restarts) else {
+                error!(exex_id = %self.ctx.id, %err, restarts, "ExEx failed, giving up");
+                // The source error goes to the manager, the supervisor resolves with its message
+                let message = format!("{err:#}");
+                let _ = self.ctx.events.send(ExExEvent::FatalError(err.into()));
+                eyre::bail!("ExEx {} failed: {message}", self.ctx.id)
+            };
+            warn!(exex_id = %self.ctx.id, %err, ?delay, restarts, "ExEx failed, restarting");
+            tokio::time::sleep(delay).await;
+            self.metrics.restarts_total.increment(1);
+
+            // Resume from the last finished height
+            let finished_height = self
+                .finished_height
+                .or_else(|| self.ctx.last_finished_height())
+                .unwrap_or(self.ctx.head.number);
+            // The catch-up of the failed run may not have been sent completely
+            let catch_up_tip =
+                self.delivered_tip.max(catch_up_tip).filter(|tip| *tip > finished_height);
+            // The live notification the failed run did not accept is sent after the catch-up,
+            // a catch-up notification is re-executed by the new catch-up
+            let deferred = self
+                .pending
+                .take()
+                .filter(|_| !self.pending_from_catch_up)
+                .or_else(|| replay.deferred.take());
+            replay = match catch_up_tip {
+                Some(tip) => {
+                    RunReplay::new(self.ctx.backfill(finished_height + 1..=tip), tip, deferred)
+                }
+                None => RunReplay { deferred, ..Default::default() },
+            };
+            self.delivered_tip = Some(finished_height);
+        }
+    }
+
+    /// Launches the `ExEx` once and connects it to the manager until it resolves.
+    async fn run_once(&mut self, replay: &mut RunReplay) -> eyre::Result<()> {
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        let ctx = ExExContext {
+            id: self.ctx.id.clone(),
+            head: self.ctx.head,
+            config: self.ctx.config.clone(),
+            reth_config: self.ctx.reth_config.clone(),
+            events: events_tx,
+            notifications: ExExNotifications::new(self.ctx.id.clone(), notifications_rx),
+            shutdown: self.ctx.shutdown.clone(),
+            finished_height_store: self.ctx.finished_height_store.clone(),
+            messages: self.ctx.messages.clone(),
+            snapshots: self.ctx.snapshots.clone(),
+            components: self.ctx.components.clone(),
+        };
+        let mut notifications_tx = Some(PollSender::new(notifications_tx));
+
+        debug!(exex_id = %self.ctx.id, catch_up_tip = ?replay.catch_up_tip, "Launching ExEx");
+        let launch = (self.launch)(ctx);
+        let exex = AssertUnwindSafe(async move { launch.await?.await }).catch_unwind();
+        let mut exex = pin!(exex);
+
+        let result = poll_fn(|cx| {
+            while let Poll::Ready(Some(event)) = events_rx.poll_recv(cx) {
+                self.forward_event(event);
+            }
+            if let Err(err) = self.forward_notifications(cx, &mut notifications_tx, replay) {
+                return Poll::Ready(Err(err))
+            }
+            exex.as_mut().poll(cx).map(|result| result.unwrap_or_else(|panic| Err(panicked(panic))))
+        })
+        .await;
+
+        // Forward the events the ExEx emitted right before it resolved
+        while let Ok(event) = events_rx.try_recv() {
+            self.forward_event(event);
+        }
+        result
+    }
+
+    /// Forwards an event of the `ExEx` to the manager, keeping track of its finished height.
+    fn forward_event(&mut self, event: ExExEvent) {
+        if let Some(height) = event.as_finished_height() {
+            self.finished_height = Some(height);
+        }
+        let _ = self.ctx.events.send(event);
+    }
+
+    /// Sends the replayed notifications, followed by the notifications of the manager, to the
+    /// `ExEx` for as long as it accepts them.
+    ///
+    /// The sender is dropped once the manager closed its channel, so the `ExEx` sees it closed as
+    /// well.
+    fn forward_notifications(
+        &mut self,
+        cx: &mut Context<'_>,
+        notifications: &mut Option<PollSender<ExExNotification>>,
+        replay: &mut RunReplay,
+    ) -> eyre::Result<()> {
+        while let Some(sender) = notifications {
+            if self.pending.is_none() {
+                let Poll::Ready(replayed) = replay.poll_next(cx)? else { break };
+                if let Some(notification) = replayed {
+                    self.pending_from_catch_up = replay.catch_up.is_some();
+                    self.pending = Some(notification);
+                } else {
+                    self.pending = match self.ctx.notifications.poll_recv(cx) {
+                        Poll::Ready(Some(notification)) => Some(notification),
+                        Poll::Ready(None) => {
+                            *notifications = None;
+                            break
+                        }
+                        Poll::Pending => break,
+                    };
+                    self.pending_from_catch_up = false;
+                }
+            }
+
+            match sender.poll_reserve(cx) {
+                Poll::Ready(Ok(())) => {
+                    let notification = self.pending.take().expect("pending notification");
+                    self.delivered_tip = Some(notification.sequence().number);
+                    // the ExEx dropped its receiver, it is not interested in notifications
+                    // anymore
+                    if sender.send_item(notification).is_err() {
+                        *notifications = None;
+                    }
+                }
+                // the ExEx dropped its receiver, the notification is kept for the next run
+                Poll::Ready(Err(_)) => *notifications = None,
+                Poll::Pending => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The notifications sent to a restarted run of the `ExEx` before any live notification.
+#[derive(Debug, Default)]
+struct RunReplay {
+    /// the blocks that were sent to the failed run after its last finished height, re-executed
+    /// on a blocking task.
+    catch_up: Option<CatchUp>,
+    /// the tip of the catch-up blocks, `None` once all of them were received.
+    catch_up_tip: Option<BlockNumber>,
+    /// the error that stopped the re-execution of the catch-up blocks, if any.
+    catch_up_error: Arc<Mutex<Option<eyre::Report>>>,
+    /// the live notification the failed run did not accept, sent after the catch-up.
+    deferred: Option<ExExNotification>,
+}
+
+impl RunReplay {
+    /// Creates a replay that re-executes the catch-up blocks up to `catch_up_tip` with the given
+    /// iterator, followed by the deferred notification.
+    ///
+    /// The catch-up stops at the first block that fails to execute.
+    fn new<I, E>(
+        catch_up: I,
+        catch_up_tip: BlockNumber,
+        deferred: Option<ExExNotification>,
+    ) -> Self
+    where
+        I: Iterator<Item = Result<ExExNotification, E>> + Send + 'static,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let catch_up_error = Arc::<Mutex<Option<eyre::Report>>>::default();
+        let error = catch_up_error.clone();
+        let catch_up = catch_up.map_while(move |notification| match notification {
+            Ok(notification) => Some(notification),
+            Err(err) => {
+                *error.lock().expect("catch-up error lock poisoned") = Some(err.into());
+                None
+            }
+        });
+        Self {
+            catch_up: Some(CatchUp::new(catch_up)),
+            catch_up_tip: Some(catch_up_tip),
+            catch_up_error,
+            deferred,
+        }
+    }
+
+    /// Polls the next notification to send before the live notifications.
+    ///
+    /// Returns `None` once all of them were sent, or an error if a catch-up block failed to
+    /// execute.
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<eyre::Result<Option<ExExNotification>>> {
+        if let Some(catch_up) = &mut self.catch_up {
+            let max_queued = DEFAULT_REPLAY_MAX_QUEUED_NOTIFICATIONS;
+            if let Some((_, notification)) = ready!(catch_up.poll_next(cx, max_queued)) {
+                return Poll::Ready(Ok(Some(notification)))
+            }
+            self.catch_up = None;
+            self.catch_up_tip = None;
+            // the producer stores the error before it stops
+            let err = self.catch_up_error.lock().expect("catch-up error lock poisoned").take();
+            if let Some(err) = err {
+                return Poll::Ready(Err(err))
+            }
+        }
+        Poll::Ready(Ok(self.deferred.take()))
+    }
+}
+
+/// Converts the payload of a panic into an error.
+fn panicked(panic: Box<dyn Any + Send>) -> eyre::Report {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    eyre::eyre!("ExEx panicked: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use std::{io, sync::Arc};
+
+    /// Creates a notification committing a single empty block.
+    fn committed(number: BlockNumber) -> ExExNotification {
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header: Header { number, ..Default::default() }.seal_slow(),
+                ..Default::default()
+            },
+            senders: Vec::new(),
+        };
+        let execution_outcome = ExecutionOutcome { first_block: number, ..Default::default() };
+        ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], execution_outcome, None)),
+        }
+    }
+
+    #[test]
+    fn backoff() {
+        let initial = Duration::from_secs(1);
+        let max = Duration::from_secs(10);
+
+        assert_eq!(ExExRestartPolicy::Never.backoff(1), None);
+
+        let policy = ExExRestartPolicy::ExponentialBackoff { initial, max };
+        assert_eq!(policy.backoff(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_secs(2)));
+        assert_eq!(policy.backoff(4), Some(Duration::from_secs(8)));
+        assert_eq!(policy.backoff(5), Some(max));
+        // the delay does not overflow
+        assert_eq!(policy.backoff(u32::MAX), Some(max));
+
+        let policy = ExExRestartPolicy::MaxRetries { max_retries: 2, initial, max };
+        assert_eq!(policy.backoff(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_secs(2)));
+        assert_eq!(policy.backoff(3), None);
+    }
+
+    async fn next(replay: &mut RunReplay) -> eyre::Result<Option<ExExNotification>> {
+        poll_fn(|cx| replay.poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn replay_sends_catch_up_before_deferred_notification() {
+        let catch_up = vec![Ok::<_, io::Error>(committed(1)), Ok(committed(2))];
+        let mut replay = RunReplay::new(catch_up.into_iter(), 2, Some(committed(3)));
+
+        assert_eq!(next(&mut replay).await.unwrap(), Some(committed(1)));
+        assert!(replay.catch_up.is_some());
+        assert_eq!(next(&mut replay).await.unwrap(), Some(committed(2)));
+        assert_eq!(next(&mut replay).await.unwrap(), Some(committed(3)));
+        assert!(replay.catch_up.is_none());
+        assert_eq!(next(&mut replay).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn replay_fails_on_catch_up_error() {
+        let catch_up = vec![Err(io::Error::other("execution failed")), Ok(committed(2))];
+        let mut replay = RunReplay::new(catch_up.into_iter(), 2, Some(committed(3)));
+
+        assert!(next(&mut replay).await.is_err());
+        // the deferred notification is kept for the next run
+        assert_eq!(replay.deferred, Some(committed(3)));
+    }
+}
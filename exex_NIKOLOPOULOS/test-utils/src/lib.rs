@@ -10,6 +10,7 @@
 //! - Creating a test node (`TestNode`) that configures components for testing.
 //! - Setting up an `ExExContext` for testing with various configurations (`test_exex_context_with_chain_spec`).
 //! - Helpers for sending notifications and assertions on `ExEx` events (`TestExExHandle`).
+//! - Scripting sequences of commit/revert notifications (`NotificationScript`).
 //! - Extension traits and utilities for polling `ExEx` futures (`PollOnce`).
 //!
 //! # Warning
@@ -25,6 +26,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod script;
+pub use script::*;
+
 use futures_util::FutureExt;
 use reth_blockchain_tree::noop::NoopBlockchainTree;
 use reth_chainspec::{ChainSpec, MAINNET};
@@ -33,7 +37,9 @@ use reth_db::{test_utils::TempDatabase, DatabaseEnv};
 use reth_db_common::init::init_genesis;
 use reth_evm::test_utils::MockExecutorProvider;
 use reth_execution_types::Chain;
-use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_exex::{
+    ExExContext, ExExEvent, ExExMessageBus, ExExNotification, ExExNotifications,
+};
 use reth_network::{config::SecretKey, NetworkConfigBuilder, NetworkManager};
 use reth_node_api::{FullNodeTypes, FullNodeTypesAdapter, NodeTypes};
 use reth_node_builder::{
@@ -65,6 +71,7 @@ use std::{
 };
 use thiserror::Error;
 use tokio::sync::mpsc::{Sender, UnboundedReceiver};
+use tokio_util::sync::CancellationToken;
 
 /// A test [`PoolBuilder`] that builds a [`TestPool`].
 #[derive(Debug, Default, Clone, Copy)]
@@ -179,6 +186,8 @@ pub struct TestExExHandle {
     pub events_rx: UnboundedReceiver<ExExEvent>,
     /// Channel for sending notifications to the Execution Extension
     pub notifications_tx: Sender<ExExNotification>,
+    /// Token to request the Execution Extension to shut down
+    pub shutdown: CancellationToken,
     /// Node task manager
     pub tasks: TaskManager,
 }
@@ -212,6 +221,19 @@ impl TestExExHandle {
         Ok(())
     }
 
+    /// Requests the Execution Extension to shut down
+    pub fn request_shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Asserts that the Execution Extension emitted a `ShutdownComplete` event.
+    #[track_caller]
+    pub fn assert_event_shutdown_complete(&mut self) -> eyre::Result<()> {
+        let event = self.events_rx.try_recv()?;
+        assert!(matches!(event, ExExEvent::ShutdownComplete), "unexpected event: {event:?}");
+        Ok(())
+    }
+
     /// Asserts that the Execution Extension did not emit any events.
     #[track_caller]
     pub fn assert_events_empty(&self) {
@@ -223,7 +245,7 @@ impl TestExExHandle {
     #[track_caller]
     pub fn assert_event_finished_height(&mut self, height: u64) -> eyre::Result<()> {
         let event = self.events_rx.try_recv()?;
-        assert_eq!(event, ExExEvent::FinishedHeight(height));
+        assert_eq!(event.as_finished_height(), Some(height), "unexpected event: {event:?}");
         Ok(())
     }
 }
@@ -306,14 +328,20 @@ pub async fn test_exex_context_with_chain_spec(
     // Create channels for events and notifications
     let (events_tx, events_rx) = tokio::sync::mpsc::unbounded_channel();
     let (notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(1);
+    let shutdown = CancellationToken::new();
 
     // Construct the Execution Extension context
     let ctx = ExExContext {
+        id: "test-exex".to_string(),
         head,
         config: NodeConfig::test(),
         reth_config: reth_config::Config::default(),
         events: events_tx,
-        notifications: notifications_rx,
+        notifications: ExExNotifications::new("test-exex", notifications_rx),
+        shutdown: shutdown.clone(),
+        finished_height_store: None,
+        messages: ExExMessageBus::default(),
+        snapshots: None,
         components,
     };
 
@@ -325,6 +353,7 @@ pub async fn test_exex_context_with_chain_spec(
             provider_factory,
             events_rx,
             notifications_tx,
+            shutdown,
             tasks,
         },
     ))
@@ -0,0 +1,209 @@
+//! Scripted notification sequences for testing Execution Extensions.
+
+use crate::{PollOnce, TestExExHandle};
+use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_exex::{ExExEvent, ExExNotification};
+use reth_primitives::{BlockNumber, Header, SealedBlock, SealedBlockWithSenders, B256};
+use std::{collections::BTreeMap, future::Future, ops::RangeInclusive, sync::Arc};
+
+/// A builder for a sequence of notifications sent to an Execution Extension.
+///
+/// # Example
+///
+/// ```ignore
+/// let script = NotificationScript::new()
+///     .commit_blocks(1..=2)
+///     .revert_blocks(2..=2)
+///     .commit_blocks(2..=3);
+/// handle.run_script(&mut exex, script).await?;
+/// handle.assert_finished_heights(&[2, 1, 3])?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NotificationScript {
+    notifications: Vec<ExExNotification>,
+    /// the block hashes of the chain after the scripted notifications, by block number.
+    canonical: BTreeMap<BlockNumber, B256>,
+}
+
+impl NotificationScript {
+    /// Creates an empty script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a notification that the chain has been committed
+    pub fn commit(mut self, chain: Chain) -> Self {
+        self.apply_commit(&chain);
+        self.notifications.push(ExExNotification::ChainCommitted { new: Arc::new(chain) });
+        self
+    }
+
+    /// Appends a notification that the chain has been reorged
+    pub fn reorg(mut self, old: Chain, new: Chain) -> Self {
+        self.apply_revert(&old);
+        self.apply_commit(&new);
+        self.notifications
+            .push(ExExNotification::ChainReorged { old: Arc::new(old), new: Arc::new(new) });
+        self
+    }
+
+    /// Appends a notification that the chain has been reverted
+    pub fn revert(mut self, chain: Chain) -> Self {
+        self.apply_revert(&chain);
+        self.notifications.push(ExExNotification::ChainReverted { old: Arc::new(chain) });
+        self
+    }
+
+    /// Appends a notification that empty blocks in the range have been committed.
+    ///
+    /// The first block is a child of the block before the range on the scripted chain, if any.
+    pub fn commit_blocks(self, blocks: RangeInclusive<BlockNumber>) -> Self {
+        let parent_hash = self.parent_hash(*blocks.start());
+        self.commit(empty_chain(blocks, parent_hash))
+    }
+
+    /// Appends a notification that empty blocks in the range have been reverted.
+    ///
+    /// The reverted blocks are the ones committed with [`Self::commit_blocks`] for the same
+    /// numbers.
+    pub fn revert_blocks(self, blocks: RangeInclusive<BlockNumber>) -> Self {
+        let parent_hash = self.parent_hash(*blocks.start());
+        self.revert(empty_chain(blocks, parent_hash))
+    }
+
+    /// Returns the hash of the parent of the given block on the scripted chain, or
+    /// [`B256::ZERO`] if the parent is not part of it.
+    fn parent_hash(&self, number: BlockNumber) -> B256 {
+        number
+            .checked_sub(1)
+            .and_then(|parent| self.canonical.get(&parent).copied())
+            .unwrap_or(B256::ZERO)
+    }
+
+    /// Makes the blocks of the chain the tip of the scripted chain.
+    fn apply_commit(&mut self, chain: &Chain) {
+        self.canonical.split_off(&chain.first().number);
+        self.canonical.extend(chain.blocks_iter().map(|block| (block.number, block.hash())));
+    }
+
+    /// Removes the blocks of the chain and all blocks after them from the scripted chain.
+    fn apply_revert(&mut self, chain: &Chain) {
+        self.canonical.split_off(&chain.first().number);
+    }
+
+    /// Returns the scripted notifications.
+    pub fn notifications(&self) -> &[ExExNotification] {
+        &self.notifications
+    }
+}
+
+impl IntoIterator for NotificationScript {
+    type Item = ExExNotification;
+    type IntoIter = std::vec::IntoIter<ExExNotification>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.notifications.into_iter()
+    }
+}
+
+/// Creates a chain of empty blocks with the given numbers on top of the given parent, without any
+/// execution outcome.
+///
+/// The blocks are linked by their parent hashes, which is enough for Execution Extensions that
+/// only look at block numbers and hashes.
+pub fn empty_chain(blocks: RangeInclusive<BlockNumber>, mut parent_hash: B256) -> Chain {
+    let first_block = *blocks.start();
+    let blocks = blocks
+        .map(|number| {
+            let header = Header { number, parent_hash, ..Default::default() }.seal_slow();
+            parent_hash = header.hash();
+            SealedBlockWithSenders {
+                block: SealedBlock { header, ..Default::default() },
+                senders: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Chain::new(blocks, ExecutionOutcome { first_block, ..Default::default() }, None)
+}
+
+impl TestExExHandle {
+    /// Sends the scripted notifications to the Execution Extension one by one, polling the
+    /// Execution Extension once after each of them.
+    pub async fn run_script<F>(&self, exex: &mut F, script: NotificationScript) -> eyre::Result<()>
+    where
+        F: Future<Output = eyre::Result<()>> + Unpin + Send,
+    {
+        for notification in script {
+            self.notifications_tx.send(notification).await?;
+            exex.poll_once().await?;
+        }
+        Ok(())
+    }
+
+    /// Returns all events the Execution Extension emitted so far.
+    pub fn drain_events(&mut self) -> Vec<ExExEvent> {
+        std::iter::from_fn(|| self.events_rx.try_recv().ok()).collect()
+    }
+
+    /// Asserts that the Execution Extension emitted exactly the `FinishedHeight` events with the
+    /// given heights, in order, and no other events.
+    #[track_caller]
+    pub fn assert_finished_heights(&mut self, heights: &[BlockNumber]) -> eyre::Result<()> {
+        let events = self.drain_events();
+        let finished_heights =
+            events.iter().map(ExExEvent::as_finished_height).collect::<Option<Vec<_>>>();
+        assert_eq!(finished_heights.as_deref(), Some(heights), "unexpected events: {events:?}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_exex_context;
+
+    #[test]
+    fn script_links_blocks_to_scripted_chain() {
+        let script = NotificationScript::new()
+            .commit_blocks(1..=3)
+            .revert_blocks(2..=3)
+            .commit_blocks(2..=2);
+        let [commit, revert, recommit] = script.notifications() else {
+            panic!("unexpected notifications {:?}", script.notifications())
+        };
+        let hashes =
+            |chain: &Chain| chain.blocks_iter().map(|block| block.hash()).collect::<Vec<_>>();
+        let committed = commit.committed_chain().unwrap();
+
+        // the reverted blocks are the committed ones
+        let reverted = revert.reverted_chain().unwrap();
+        assert_eq!(hashes(&reverted), hashes(&committed)[1..]);
+
+        // the recommitted block is a child of the block that was not reverted
+        let recommitted = recommit.committed_chain().unwrap();
+        assert_eq!(recommitted.first().parent_hash, committed.first().hash());
+    }
+
+    #[tokio::test]
+    async fn run_script_and_assert_finished_heights() -> eyre::Result<()> {
+        let (mut ctx, mut handle) = test_exex_context().await?;
+        let mut exex = Box::pin(async move {
+            while let Some(notification) = ctx.notifications.recv().await {
+                let height = match notification.committed_chain() {
+                    Some(chain) => chain.tip().number,
+                    None => notification.reverted_chain().unwrap().first().number - 1,
+                };
+                ctx.events.send(ExExEvent::FinishedHeight(height))?;
+            }
+            Ok::<_, eyre::Report>(())
+        });
+
+        let script = NotificationScript::new()
+            .commit_blocks(1..=2)
+            .revert_blocks(2..=2)
+            .commit_blocks(2..=3);
+        handle.run_script(&mut exex, script).await?;
+        handle.assert_finished_heights(&[2, 1, 3])
+    }
+}
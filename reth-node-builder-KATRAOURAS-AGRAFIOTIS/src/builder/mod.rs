@@ -30,7 +30,7 @@ use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, TransactionPool};
 pub use states::*;
-use std::{str::FromStr, sync::Arc};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 mod states;
 
@@ -382,7 +382,8 @@ where
     ///
     /// # Note
     ///
-    /// The ExEx ID must be unique.
+    /// The ExEx ID must be unique, and `reth-txpool` is reserved for the ExEx of the transaction
+    /// pool.
     pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<RethFullAdapter<DB, T>, CB::Components>>) -> R
@@ -398,6 +399,29 @@ where
         }
     }
 
+    /// Sets the number of blocks between two snapshots of the derived state of the ExExs.
+    ///
+    /// See also [`NodeBuilderWithComponents::with_exex_snapshot_interval`].
+    pub fn with_exex_snapshot_interval(self, interval: u64) -> Self {
+        Self {
+            builder: self.builder.with_exex_snapshot_interval(interval),
+            task_executor: self.task_executor,
+            data_dir: self.data_dir,
+        }
+    }
+
+    /// Persists the pending, queued and basefee transactions of the pool to the given file on
+    /// shutdown and restores them on the next launch.
+    ///
+    /// See also [`NodeBuilderWithComponents::with_pool_transactions_backup`].
+    pub fn with_pool_transactions_backup(self, transactions_path: impl Into<PathBuf>) -> Self {
+        Self {
+            builder: self.builder.with_pool_transactions_backup(transactions_path),
+            task_executor: self.task_executor,
+            data_dir: self.data_dir,
+        }
+    }
+
     /// Launches the node and returns a handle to it.
     pub async fn launch(
         self,
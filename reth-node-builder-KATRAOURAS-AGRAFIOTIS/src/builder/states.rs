@@ -13,13 +13,14 @@ use crate::{
     rpc::{RethRpcServerHandles, RpcContext, RpcHooks},
     FullNode,
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, DEFAULT_SNAPSHOT_INTERVAL};
 use reth_network::NetworkHandle;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_tasks::TaskExecutor;
-use std::{fmt, future::Future};
+use reth_transaction_pool::maintain::PoolTransactionsBackupConfig;
+use std::{fmt, future::Future, path::PathBuf};
 
 /// A node builder that also has the configured types.
 pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
@@ -50,6 +51,8 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
                 hooks: NodeHooks::default(),
                 rpc: RpcHooks::new(),
                 exexs: Vec::new(),
+                pool_backup: PoolTransactionsBackupConfig::default(),
+                exex_snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
             },
         }
     }
@@ -201,7 +204,8 @@ impl<T: FullNodeTypes, CB: NodeComponentsBuilder<T>> NodeBuilderWithComponents<T
     ///
     /// # Note
     ///
-    /// The ExEx ID must be unique.
+    /// The ExEx ID must be unique, and `reth-txpool` is reserved for the ExEx of the transaction
+    /// pool.
     pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
@@ -212,6 +216,24 @@ impl<T: FullNodeTypes, CB: NodeComponentsBuilder<T>> NodeBuilderWithComponents<T
         self
     }
 
+    /// Sets the number of blocks between two snapshots of the derived state of the ExExs.
+    ///
+    /// Defaults to [`DEFAULT_SNAPSHOT_INTERVAL`].
+    pub fn with_exex_snapshot_interval(mut self, interval: u64) -> Self {
+        self.add_ons.exex_snapshot_interval = interval;
+        self
+    }
+
+    /// Persists the pending, queued and basefee transactions of the pool to the given file on
+    /// shutdown and restores them on the next launch.
+    ///
+    /// Restored transactions are validated again before they are inserted into the pool.
+    pub fn with_pool_transactions_backup(mut self, transactions_path: impl Into<PathBuf>) -> Self {
+        self.add_ons.pool_backup =
+            PoolTransactionsBackupConfig::with_pool_backup(transactions_path.into());
+        self
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -236,4 +258,8 @@ pub(crate) struct NodeAddOns<Node: FullNodeComponents> {
     pub(crate) rpc: RpcHooks<Node>,
     /// The ExExs (execution extensions) of the node.
     pub(crate) exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// Settings for persisting the transactions of the pool across restarts.
+    pub(crate) pool_backup: PoolTransactionsBackupConfig,
+    /// The number of blocks between two snapshots of the derived state of the ExExs.
+    pub(crate) exex_snapshot_interval: u64,
 }
\ No newline at end of file
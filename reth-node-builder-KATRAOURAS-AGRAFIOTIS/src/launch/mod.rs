@@ -18,7 +18,10 @@ use reth_blockchain_tree::{
     TreeExternals,
 };
 use reth_consensus::Consensus;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{
+    ExExContext, ExExEvent, ExExHandle, ExExManager, ExExMessageBus, ExExNotification,
+    ExExNotifications, ExExSnapshots, FinishedHeightStore, SnapshotStore,
+};
 use reth_network::NetworkEvents;
 use reth_node_api::{FullNodeComponents, FullNodeTypes};
 use reth_node_core::{
@@ -33,15 +36,24 @@ use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
 use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::engine::ClientVersionV1;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
-use reth_transaction_pool::TransactionPool;
-use std::{future::Future, sync::Arc};
+use reth_tracing::tracing::{debug, error, info};
+use reth_transaction_pool::{
+    maintain::{backup_pool_transactions_task, reinject_reverted_transactions_task},
+    TransactionPool, TransactionPoolExt,
+};
+use std::{future::Future, sync::Arc, time::Duration};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 
 pub mod common;
 pub use common::LaunchContext;
 
+/// How long the ExEx's are given to complete their shutdown before they are aborted.
+const EXEX_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The ID of the built-in ExEx that re-injects the transactions of reverted blocks into the pool.
+const TXPOOL_EXEX_ID: &str = "reth-txpool";
+
 /// A general-purpose trait that launches a new node of any kind.
 ///
 /// Acts as a node factory and encapsulates the launch logic for a node.
@@ -82,6 +94,7 @@ impl<T, CB> LaunchNode<NodeBuilderWithComponents<T, CB>> for DefaultNodeLauncher
 where
     T: FullNodeTypes<Provider = BlockchainProvider<<T as FullNodeTypes>::DB>>,
     CB: NodeComponentsBuilder<T>,
+    <CB::Components as NodeComponents<T>>::Pool: TransactionPoolExt,
 {
     type Node = NodeHandle<NodeAdapter<T, CB::Components>>;
 
@@ -102,7 +115,13 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: NodeAddOns { hooks, rpc, exexs: installed_exex },
+            add_ons: NodeAddOns {
+                hooks,
+                rpc,
+                exexs: installed_exex,
+                pool_backup,
+                exex_snapshot_interval,
+            },
             config,
         } = target;
 
@@ -201,23 +220,51 @@ where
         debug!(target: "reth::cli", "Calling on_component_initialized hook");
         on_component_initialized.on_event(node_adapter.clone())?;
 
+        if pool_backup.transactions_path.is_some() {
+            let pool = node_adapter.components.pool().clone();
+            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "txpool backup task",
+                |shutdown| backup_pool_transactions_task(shutdown, pool, pool_backup),
+            );
+        }
+
         // Spawn ExExs
+        if installed_exex.iter().any(|(id, _)| id == TXPOOL_EXEX_ID) {
+            eyre::bail!("ExEx ID {TXPOOL_EXEX_ID} is reserved for the transaction pool")
+        }
         let mut exex_handles = Vec::with_capacity(installed_exex.len());
         let mut exexs = Vec::with_capacity(installed_exex.len());
+        let exex_message_bus = ExExMessageBus::default();
         for (id, exex) in installed_exex {
             // Create a new ExEx handle
+            let exex_dir = ctx.data_dir().data_dir().join("exex");
+            let finished_height_store = FinishedHeightStore::new(&exex_dir, &id)?;
+            let snapshots = ExExSnapshots::new(
+                SnapshotStore::new(exex_dir.join(&id).join("snapshots")),
+                exex_snapshot_interval,
+            );
             let (handle, events, notifications) = ExExHandle::new(id.clone());
+            let handle = handle
+                .with_finished_height_store(finished_height_store.clone())?
+                .with_snapshots(snapshots.clone());
+            let shutdown = handle.shutdown_token();
+            let resource_usage = handle.resource_usage();
             exex_handles.push(handle);
 
             // Create the launch context for the ExEx
             let context = ExExContext {
+                id: id.clone(),
                 head,
                 data_dir: ctx.data_dir().clone(),
                 config: ctx.node_config().clone(),
                 reth_config: ctx.toml_config().clone(),
                 components: node_adapter.clone(),
                 events,
-                notifications,
+                notifications: ExExNotifications::new(id.clone(), notifications),
+                shutdown: shutdown.clone(),
+                finished_height_store: Some(finished_height_store),
+                messages: exex_message_bus.clone(),
+                snapshots: Some(snapshots),
             };
 
             let executor = ctx.task_executor().clone();
@@ -232,7 +279,10 @@ where
                 // Spawn it as a critical task
                 executor.spawn_critical("exex", async move {
                     info!(target: "reth::cli", "ExEx started");
-                    match exex.await {
+                    match resource_usage.track(exex).await {
+                        Ok(_) if shutdown.is_cancelled() => {
+                            info!(target: "reth::cli", "ExEx shut down")
+                        }
                         Ok(_) => panic!("ExEx {id} finished. ExEx's should run indefinitely"),
                         Err(err) => panic!("ExEx {id} crashed: {err}"),
                     }
@@ -243,35 +293,79 @@ where
         future::join_all(exexs).await;
 
         // Spawn ExEx manager
-        let exex_manager_handle = if !exex_handles.is_empty() {
-            debug!(target: "reth::cli", "Spawning ExEx manager");
-            // TODO: Remove magic number
-            let exex_manager = ExExManager::new(exex_handles, 1024);
-            let exex_manager_handle = exex_manager.handle();
-            ctx.task_executor().spawn_critical("exex manager", async move {
-                exex_manager.await.expect("ExEx manager crashed");
+        //
+        // Reverts without a new chain only reach the node through the ExEx manager, so the manager
+        // always runs and the pool gets an ExEx of its own to re-inject the transactions of
+        // reverted blocks, even if no other ExEx is installed
+        let (handle, events, notifications) = ExExHandle::new(TXPOOL_EXEX_ID.to_string());
+        let reverted = ReceiverStream::new(notifications)
+            .take_until(handle.shutdown_token().cancelled_owned())
+            .filter_map(move |notification| {
+                // the pool does not need the blocks afterwards, so they can be pruned
+                let _ = events.send(ExExEvent::FinishedHeight(notification.sequence().number));
+                future::ready(match notification {
+                    ExExNotification::ChainReverted { old } => Some(old),
+                    _ => None,
+                })
             });
+        exex_handles.push(handle);
+        ctx.task_executor().spawn_critical(
+            "txpool reverted transactions reinjection",
+            reinject_reverted_transactions_task(
+                blockchain_db.clone(),
+                node_adapter.components.pool().clone(),
+                Box::pin(reverted),
+            ),
+        );
 
-            // Send notifications from the blockchain tree to ExEx manager
-            let mut canon_state_notifications = blockchain_db.subscribe_to_canonical_state();
-            let mut handle = exex_manager_handle.clone();
-            ctx.task_executor().spawn_critical(
-                "exex manager blockchain tree notifications",
-                async move {
-                    while let Ok(notification) = canon_state_notifications.recv().await {
-                        handle.send_async(notification.into()).await.expect(
-                            "Blockchain tree notification could not be sent to ExEx manager",
-                        );
+        debug!(target: "reth::cli", "Spawning ExEx manager");
+        // TODO: Remove magic number
+        let exex_manager = ExExManager::new(exex_handles, 1024)?;
+        let exex_manager_handle = exex_manager.handle();
+        let shutdown_handle = exex_manager.handle();
+        ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "exex manager",
+            |shutdown| async move {
+                let mut exex_manager = std::pin::pin!(exex_manager);
+                tokio::select! {
+                    res = &mut exex_manager => {
+                        // A failed critical task shuts down the node
+                        if let Err(err) = res {
+                            panic!("ExEx manager crashed: {err:?}")
+                        }
                     }
-                },
-            );
+                    guard = shutdown => {
+                        // Give the ExEx's a chance to persist their state before the node
+                        // aborts their tasks
+                        let _ = shutdown_handle.shutdown(EXEX_SHUTDOWN_TIMEOUT);
+                        if let Err(err) = exex_manager.await {
+                            error!(
+                                target: "reth::cli",
+                                %err,
+                                "ExEx manager failed during shutdown"
+                            );
+                        }
+                        drop(guard);
+                    }
+                }
+            },
+        );
 
-            info!(target: "reth::cli", "ExEx Manager started");
+        // Send notifications from the blockchain tree to ExEx manager
+        let mut canon_state_notifications = blockchain_db.subscribe_to_canonical_state();
+        let mut handle = exex_manager_handle.clone();
+        ctx.task_executor().spawn_critical(
+            "exex manager blockchain tree notifications",
+            async move {
+                while let Ok(notification) = canon_state_notifications.recv().await {
+                    handle.send_async(notification.into()).await.expect(
+                        "Blockchain tree notification could not be sent to ExEx manager",
+                    );
+                }
+            },
+        );
 
-            Some(exex_manager_handle)
-        } else {
-            None
-        };
+        info!(target: "reth::cli", "ExEx Manager started");
 
         // Create pipeline
         let network_client = node_adapter.network().fetch_client().await?;
@@ -298,8 +392,7 @@ where
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
         // Configure the pipeline
-        let pipeline_exex_handle =
-            exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
+        let pipeline_exex_handle = exex_manager_handle.clone();
         let (pipeline, client) = if ctx.is_dev() {
             info!(target: "reth::cli", "Starting Reth in dev mode");
 
@@ -369,14 +462,11 @@ where
 
         let initial_target = ctx.initial_pipeline_target();
 
-        let mut pruner_builder =
-            ctx.pruner_builder().max_reorg_depth(tree_config.max_reorg_depth() as usize);
-        if let Some(exex_manager_handle) = &exex_manager_handle {
-            pruner_builder =
-                pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
-        }
-
-        let pruner = pruner_builder.build(ctx.provider_factory().clone());
+        let pruner = ctx
+            .pruner_builder()
+            .max_reorg_depth(tree_config.max_reorg_depth() as usize)
+            .finished_exex_height(exex_manager_handle.finished_height())
+            .build(ctx.provider_factory().clone());
 
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
@@ -0,0 +1,171 @@
+#![allow(missing_docs)]
+
+// Benchmarks the insertion of blob transactions into the blob pool and of their sidecars into the
+// blob store
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use pprof::criterion::{Output, PProfProfiler};
+use proptest::{
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{RngAlgorithm, TestRng, TestRunner},
+};
+use reth_primitives::{hex_literal::hex, Address, BlobTransactionSidecar, B256};
+use reth_transaction_pool::{
+    blobstore::{BlobStore, InMemoryBlobStore},
+    pool::BlobTransactions,
+    test_utils::{random_blob_sidecar, MockTransaction, MockTransactionFactory, MockValidTx},
+    PoolTransaction,
+};
+use std::sync::Arc;
+
+// constant seed to use for the rng, represented as a 32-byte hexadecimal array
+const SEED: [u8; 32] = hex!("1337133713371337133713371337133713371337133713371337133713371337");
+
+/// Generates blob transactions for `senders` unique senders, with up to `max_depth` transactions
+/// per sender. Its values are generated using [Arbitrary].
+///
+/// All transactions share the given sidecar, because computing the commitments and proofs of a
+/// sidecar is expensive.
+fn generate_blob_transactions(
+    senders: usize,
+    max_depth: usize,
+    sidecar: &BlobTransactionSidecar,
+) -> Vec<MockTransaction> {
+    let config = ProptestConfig::default();
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &SEED);
+    let mut runner = TestRunner::new_with_rng(config, rng);
+
+    let mut txs = Vec::new();
+    for idx in 0..senders {
+        // modulo max_depth so we know it is bounded, plus one so the minimum is always 1
+        let depth = any::<usize>().new_tree(&mut runner).unwrap().current() % max_depth + 1;
+
+        // pad the sender index with 12 bytes of zeros, which makes debugging easier
+        let addr_slice = [0u8; 12].into_iter().chain(idx.to_be_bytes()).collect::<Vec<_>>();
+        let sender = Address::from_slice(&addr_slice);
+
+        let mut sender_txs =
+            prop::collection::vec(MockTransaction::arbitrary_eip4844(sidecar.clone()), depth)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+        for (nonce, tx) in sender_txs.iter_mut().enumerate() {
+            tx.set_sender(sender);
+            tx.set_nonce(nonce as u64);
+        }
+        txs.extend(sender_txs);
+    }
+
+    txs
+}
+
+/// Main function to run benchmarks for inserting blob transactions
+fn txpool_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Transaction Pool Insert");
+
+    // every transaction carries a full blob, so the number of transactions is kept moderate
+    let sidecar = random_blob_sidecar(1);
+    for senders in [10, 100, 500] {
+        for max_depth in [1, 4] {
+            let txs = generate_blob_transactions(senders, max_depth, &sidecar);
+            insert_blob_pool(&mut group, "BlobPool", &txs, senders, max_depth);
+            insert_blob_store(&mut group, "BlobStore", &txs, senders, max_depth);
+        }
+    }
+}
+
+/// Benchmark function for inserting transactions into the blob pool.
+///
+/// # Arguments
+/// * `group` - The `BenchmarkGroup` to add the benchmarks to.
+/// * `description` - A description for the benchmark.
+/// * `txs` - The blob transactions to insert.
+/// * `senders` - The number of unique senders.
+/// * `max_depth` - The maximum number of transactions per sender.
+fn insert_blob_pool(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    description: &str,
+    txs: &[MockTransaction],
+    senders: usize,
+    max_depth: usize,
+) {
+    // validate the transactions once, so that the setup only clones the arcs
+    let mut f = MockTransactionFactory::default();
+    let txs = txs.iter().map(|tx| f.validated_arc(tx.clone())).collect::<Vec<Arc<MockValidTx>>>();
+
+    let group_id = format!(
+        "txpool | total txs: {} | total senders: {} | max depth: {} | {}",
+        txs.len(),
+        senders,
+        max_depth,
+        description,
+    );
+
+    group.bench_function(group_id, |b| {
+        b.iter_with_setup(
+            || txs.clone(),
+            |txs| {
+                let mut txpool = BlobTransactions::default();
+                for tx in txs {
+                    txpool.add_transaction(tx);
+                }
+                std::hint::black_box(txpool);
+            },
+        );
+    });
+}
+
+/// Benchmark function for inserting the sidecars of blob transactions into the blob store.
+///
+/// # Arguments
+/// * `group` - The `BenchmarkGroup` to add the benchmarks to.
+/// * `description` - A description for the benchmark.
+/// * `txs` - The blob transactions whose sidecars are inserted.
+/// * `senders` - The number of unique senders.
+/// * `max_depth` - The maximum number of transactions per sender.
+fn insert_blob_store(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    description: &str,
+    txs: &[MockTransaction],
+    senders: usize,
+    max_depth: usize,
+) {
+    let sidecars = txs
+        .iter()
+        .map(|tx| {
+            let MockTransaction::Eip4844 { sidecar, .. } = tx else {
+                unreachable!("only blob transactions are generated")
+            };
+            (*tx.hash(), sidecar.clone())
+        })
+        .collect::<Vec<(B256, BlobTransactionSidecar)>>();
+
+    let group_id = format!(
+        "txpool | total txs: {} | total senders: {} | max depth: {} | {}",
+        txs.len(),
+        senders,
+        max_depth,
+        description,
+    );
+
+    group.bench_function(group_id, |b| {
+        b.iter_with_setup(
+            || sidecars.clone(),
+            |sidecars| {
+                let store = InMemoryBlobStore::default();
+                store.insert_all(sidecars).unwrap();
+                std::hint::black_box(store);
+            },
+        );
+    });
+}
+
+criterion_group! {
+    name = insert;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = txpool_insert
+}
+criterion_main!(insert);
@@ -13,9 +13,9 @@ use proptest::{
     strategy::ValueTree,
     test_runner::{RngAlgorithm, TestRng, TestRunner},
 };
-use reth_primitives::{hex_literal::hex, Address};
+use reth_primitives::{hex_literal::hex, Address, BlobTransactionSidecar};
 use reth_transaction_pool::{
-    pool::{BasefeeOrd, ParkedPool, PendingPool, QueuedOrd},
+    pool::{BasefeeOrd, BlobTransactions, ParkedPool, PendingPool, QueuedOrd},
     test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
     SubPoolLimit,
 };
@@ -30,6 +30,7 @@ const SEED: [u8; 32] = hex!("133713371337133713371337133713371337133713371337133
 /// * `runner` - A Proptest `TestRunner` for generating transactions
 /// * `sender` - The `Address` of the sender for all transactions
 /// * `depth` - The number of transactions to generate
+/// * `only_blobs` - Whether to generate only EIP-4844 transactions
 ///
 /// # Returns
 /// A vector of `MockTransaction` instances
@@ -38,23 +39,27 @@ fn create_transactions_for_sender(
     mut runner: TestRunner,
     sender: Address,
     depth: usize,
+    only_blobs: bool,
 ) -> Vec<MockTransaction> {
-    // TODO: for blob truncate, this would need a flag for _only_ generating 4844 mock transactions
-
     // assert that depth is always greater than zero, since empty vecs do not really make sense in
     // this context
     assert!(depth > 0);
 
+    // blob transactions use the default (empty) sidecar to keep the memory usage of the benchmark
+    // reasonable
+    let strategy = if only_blobs {
+        MockTransaction::arbitrary_eip4844(BlobTransactionSidecar::default())
+    } else {
+        any::<MockTransaction>()
+    };
+
     // make sure these are all post-eip-1559 transactions
     // Generate a vector of transactions
-    let mut txs = prop::collection::vec(any::<MockTransaction>(), depth)
-        .new_tree(&mut runner)
-        .unwrap()
-        .current();
+    let mut txs = prop::collection::vec(strategy, depth).new_tree(&mut runner).unwrap().current();
 
     for (nonce, tx) in txs.iter_mut().enumerate() {
-        // reject pre-eip1559 tx types, if there is a legacy tx, replace it with an eip1559 tx
         if tx.is_legacy() || tx.is_eip2930() {
+            // reject pre-eip1559 tx types, if there is a legacy tx, replace it with an eip1559 tx
             *tx = MockTransaction::eip1559();
 
             // set fee values using arbitrary
@@ -81,11 +86,16 @@ fn create_transactions_for_sender(
 /// # Arguments
 /// * `senders` - The number of unique senders
 /// * `max_depth` - The maximum number of transactions per sender
+/// * `only_blobs` - Whether to generate only EIP-4844 transactions
 ///
 /// # Returns
 /// A vector of `MockTransaction` instances
 
-fn generate_many_transactions(senders: usize, max_depth: usize) -> Vec<MockTransaction> {
+fn generate_many_transactions(
+    senders: usize,
+    max_depth: usize,
+    only_blobs: bool,
+) -> Vec<MockTransaction> {
     // Configure the Proptest and initialize RNG with a fixed seed
     let config = ProptestConfig::default();
     let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &SEED);
@@ -105,7 +115,7 @@ fn generate_many_transactions(senders: usize, max_depth: usize) -> Vec<MockTrans
 
         let sender = Address::from_slice(&addr_slice);
         // Generate transactions for each sender and append to the transaction vector
-        txs.extend(create_transactions_for_sender(runner.clone(), sender, depth));
+        txs.extend(create_transactions_for_sender(runner.clone(), sender, depth, only_blobs));
     }
 
     txs
@@ -120,7 +130,7 @@ fn generate_many_transactions(senders: usize, max_depth: usize) -> Vec<MockTrans
 
 fn benchmark_pools(group: &mut BenchmarkGroup<'_, WallTime>, senders: usize, max_depth: usize) {
     println!("Generating transactions for benchmark with {senders} unique senders and a max depth of {max_depth}...");
-    let txs = generate_many_transactions(senders, max_depth);
+    let txs = generate_many_transactions(senders, max_depth, false);
 
     // benchmark parked pool
     truncate_basefee(group, "BasefeePool", txs.clone(), senders, max_depth);
@@ -131,7 +141,9 @@ fn benchmark_pools(group: &mut BenchmarkGroup<'_, WallTime>, senders: usize, max
     // benchmark queued pool
     truncate_queued(group, "QueuedPool", txs, senders, max_depth);
 
-    // TODO: benchmark blob truncate
+    // benchmark blob pool
+    let blob_txs = generate_many_transactions(senders, max_depth, true);
+    truncate_blob(group, "BlobPool", blob_txs, senders, max_depth);
 }
 
 /// Main function to run benchmarks for the transaction pool truncate functionality
@@ -307,6 +319,51 @@ fn truncate_basefee(
     });
 }
 
+/// Benchmark function for truncating the blob pool.
+///
+/// # Arguments
+/// * `group` - The `BenchmarkGroup` to add the benchmarks to.
+/// * `description` - A description for the benchmark.
+/// * `seed` - The seed blob transactions to initialize the pool.
+/// * `senders` - The number of unique senders.
+/// * `max_depth` - The maximum number of transactions per sender.
+
+fn truncate_blob(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    description: &str,
+    seed: Vec<MockTransaction>,
+    senders: usize,
+    max_depth: usize,
+) {
+    let setup = || {
+        // Initialize the blob pool and transaction factory
+        let mut txpool = BlobTransactions::default();
+        let mut f = MockTransactionFactory::default();
+
+        // Add seed transactions to the pool
+        for tx in &seed {
+            txpool.add_transaction(f.validated_arc(tx.clone()));
+        }
+        txpool
+    };
+
+    let group_id = format!(
+        "txpool | total txs: {} | total senders: {} | max depth: {} | {}",
+        seed.len(),
+        senders,
+        max_depth,
+        description,
+    );
+
+    // for now we just use the default SubPoolLimit
+    group.bench_function(group_id, |b| {
+        b.iter_with_setup(setup, |mut txpool| {
+            txpool.truncate_pool(SubPoolLimit::default());
+            std::hint::black_box(());
+        });
+    });
+}
+
 // Define a criterion group for the truncate benchmarks
 
 criterion_group! {
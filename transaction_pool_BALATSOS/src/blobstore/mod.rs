@@ -10,11 +10,13 @@ use std::{
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
 };
+pub use sidecars::{BlobAndProof, BlobSidecarStore, DEFAULT_BLOB_SIDECAR_RETENTION_BLOCKS};
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
 pub mod disk;
 mod mem;
 mod noop;
+mod sidecars;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
@@ -0,0 +1,347 @@
+//! A blob store that retains the sidecars of mined transactions.
+
+use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError};
+use alloy_eips::eip4844::{Blob, Bytes48};
+use parking_lot::RwLock;
+use reth_primitives::{BlobTransactionSidecar, BlockNumber, B256};
+use reth_provider::ChainBlocks;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The number of blocks the sidecars of mined transactions are retained for by default.
+///
+/// This is `MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS` (4096 epochs of 32 slots), the window in which
+/// blob sidecars must be served by consensus clients.
+pub const DEFAULT_BLOB_SIDECAR_RETENTION_BLOCKS: u64 = 4096 * 32;
+
+/// A blob and its KZG proof, see [`BlobSidecarStore::get_blobs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob.
+    pub blob: Blob,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
+/// A [`BlobStore`] that retains the sidecars of mined transactions for the blob retention window
+/// and serves blobs by their versioned hash.
+///
+/// This wraps the blob store of the pool. Sidecars of transactions that were mined in the last
+/// [`DEFAULT_BLOB_SIDECAR_RETENTION_BLOCKS`] blocks are not deleted from the wrapped store, even if
+/// the pool deletes them, e.g. once the transaction is finalized. Mined transactions are tracked
+/// with [`Self::on_new_chain_blocks`], which also prunes sidecars that fell out of the retention
+/// window, see also [`maintain_blob_sidecar_store`](crate::maintain::maintain_blob_sidecar_store).
+#[derive(Debug, Clone)]
+pub struct BlobSidecarStore<S> {
+    /// The wrapped store.
+    store: S,
+    /// The index of the stored sidecars.
+    index: Arc<RwLock<SidecarIndex>>,
+    /// The number of blocks the sidecars of mined transactions are retained for.
+    retention: u64,
+}
+
+impl<S: BlobStore> BlobSidecarStore<S> {
+    /// Wraps the given store, retaining the sidecars of mined transactions for
+    /// [`DEFAULT_BLOB_SIDECAR_RETENTION_BLOCKS`].
+    ///
+    /// Note: sidecars that are already in the store are not indexed.
+    pub fn new(store: S) -> Self {
+        Self { store, index: Default::default(), retention: DEFAULT_BLOB_SIDECAR_RETENTION_BLOCKS }
+    }
+
+    /// Sets the number of blocks the sidecars of mined transactions are retained for.
+    pub const fn with_retention(mut self, retention: u64) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Returns the wrapped store.
+    pub const fn inner(&self) -> &S {
+        &self.store
+    }
+
+    /// Records the blob transactions mined in the given block, whose sidecars are retained until
+    /// the block falls out of the retention window.
+    ///
+    /// Note: this replaces the transactions previously recorded for the block, e.g. on a reorg.
+    pub fn on_mined(&self, block_number: BlockNumber, txs: impl IntoIterator<Item = B256>) {
+        let txs = txs.into_iter().collect::<Vec<_>>();
+        let mut index = self.index.write();
+        for tx in &txs {
+            index.retained.insert(*tx, block_number);
+        }
+        if let Some(previous) = index.mined.insert(block_number, txs) {
+            for tx in previous {
+                if index.retained.get(&tx) == Some(&block_number) {
+                    index.retained.remove(&tx);
+                }
+            }
+        }
+    }
+
+    /// Records the blob transactions of the given canonical blocks, see [`Self::on_mined`], and
+    /// prunes all sidecars that fell out of the retention window of the new tip.
+    ///
+    /// Returns the number of pruned sidecars.
+    pub fn on_new_chain_blocks(&self, blocks: &ChainBlocks<'_>) -> Result<usize, BlobStoreError> {
+        for (number, block) in blocks.iter() {
+            let txs = block.body.iter().filter(|tx| tx.transaction.is_eip4844()).map(|tx| tx.hash);
+            self.on_mined(*number, txs);
+        }
+        self.prune(blocks.tip().number)
+    }
+
+    /// Deletes the sidecars of all transactions that were mined more than the retention window
+    /// before the given tip.
+    ///
+    /// Returns the number of pruned sidecars.
+    pub fn prune(&self, tip: BlockNumber) -> Result<usize, BlobStoreError> {
+        let cutoff = tip.saturating_sub(self.retention);
+        let mut pruned = Vec::new();
+        {
+            let mut index = self.index.write();
+            while let Some(entry) = index.mined.first_entry() {
+                if *entry.key() >= cutoff {
+                    break
+                }
+                let (number, txs) = entry.remove_entry();
+                for tx in txs {
+                    if index.retained.get(&tx) == Some(&number) {
+                        index.retained.remove(&tx);
+                        index.remove(&tx);
+                        pruned.push(tx);
+                    }
+                }
+            }
+        }
+
+        let num_pruned = pruned.len();
+        self.store.delete_all(pruned)?;
+        Ok(num_pruned)
+    }
+
+    /// Returns the sidecar of the given transaction.
+    pub fn get_blob(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.store.get(tx)
+    }
+
+    /// Returns the blobs and their proofs for the given versioned hashes, in the order they were
+    /// requested.
+    ///
+    /// `None` is returned for blobs that are not in the store.
+    pub fn get_blobs(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let locations = {
+            let index = self.index.read();
+            versioned_hashes
+                .iter()
+                .map(|hash| index.by_versioned_hash.get(hash).copied())
+                .collect::<Vec<_>>()
+        };
+
+        let mut sidecars = HashMap::new();
+        let mut blobs = Vec::with_capacity(locations.len());
+        for location in locations {
+            let Some((tx, idx)) = location else {
+                blobs.push(None);
+                continue
+            };
+            if !sidecars.contains_key(&tx) {
+                sidecars.insert(tx, self.store.get(tx)?);
+            }
+            let blob = sidecars[&tx].as_ref().and_then(|sidecar| {
+                Some(BlobAndProof {
+                    blob: *sidecar.blobs.get(idx)?,
+                    proof: *sidecar.proofs.get(idx)?,
+                })
+            });
+            blobs.push(blob);
+        }
+
+        Ok(blobs)
+    }
+
+    /// Returns the transactions of the given ones whose sidecars are not retained.
+    fn not_retained(&self, txs: Vec<B256>) -> Vec<B256> {
+        let mut index = self.index.write();
+        txs.into_iter()
+            .filter(|tx| {
+                let retained = index.retained.contains_key(tx);
+                if !retained {
+                    index.remove(tx);
+                }
+                !retained
+            })
+            .collect()
+    }
+}
+
+impl<S: BlobStore> BlobStore for BlobSidecarStore<S> {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+        self.index.write().insert(tx, &data);
+        self.store.insert(tx, data)
+    }
+
+    fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+        {
+            let mut index = self.index.write();
+            for (tx, data) in &txs {
+                index.insert(*tx, data);
+            }
+        }
+        self.store.insert_all(txs)
+    }
+
+    fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
+        if self.not_retained(vec![tx]).is_empty() {
+            return Ok(())
+        }
+        self.store.delete(tx)
+    }
+
+    fn delete_all(&self, txs: Vec<B256>) -> Result<(), BlobStoreError> {
+        self.store.delete_all(self.not_retained(txs))
+    }
+
+    fn cleanup(&self) -> BlobStoreCleanupStat {
+        self.store.cleanup()
+    }
+
+    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.store.get(tx)
+    }
+
+    fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
+        self.store.contains(tx)
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+        self.store.get_all(txs)
+    }
+
+    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+        self.store.get_exact(txs)
+    }
+
+    fn data_size_hint(&self) -> Option<usize> {
+        self.store.data_size_hint()
+    }
+
+    fn blobs_len(&self) -> usize {
+        self.store.blobs_len()
+    }
+}
+
+/// The index of a [`BlobSidecarStore`].
+#[derive(Debug, Default)]
+struct SidecarIndex {
+    /// The transaction and the index of the blob in its sidecar, by versioned hash.
+    by_versioned_hash: HashMap<B256, (B256, usize)>,
+    /// The versioned hashes of the blobs of each transaction.
+    versioned_hashes: HashMap<B256, Vec<B256>>,
+    /// The retained transactions, by the block they were mined in.
+    mined: BTreeMap<BlockNumber, Vec<B256>>,
+    /// The block each retained transaction was mined in.
+    retained: HashMap<B256, BlockNumber>,
+}
+
+impl SidecarIndex {
+    /// Indexes the blobs of the given sidecar.
+    fn insert(&mut self, tx: B256, sidecar: &BlobTransactionSidecar) {
+        let hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        for (idx, hash) in hashes.iter().enumerate() {
+            self.by_versioned_hash.insert(*hash, (tx, idx));
+        }
+        self.versioned_hashes.insert(tx, hashes);
+    }
+
+    /// Removes the blobs of the given transaction from the index.
+    fn remove(&mut self, tx: &B256) {
+        for hash in self.versioned_hashes.remove(tx).unwrap_or_default() {
+            // the same blob can be part of multiple transactions
+            if self.by_versioned_hash.get(&hash).is_some_and(|(other, _)| other == tx) {
+                self.by_versioned_hash.remove(&hash);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blobstore::InMemoryBlobStore;
+
+    fn sidecar(num_blobs: u8) -> BlobTransactionSidecar {
+        BlobTransactionSidecar {
+            blobs: (0..num_blobs).map(Blob::repeat_byte).collect(),
+            commitments: (0..num_blobs).map(Bytes48::repeat_byte).collect(),
+            proofs: (0..num_blobs).map(|i| Bytes48::repeat_byte(i + 1)).collect(),
+        }
+    }
+
+    #[test]
+    fn get_blobs_by_versioned_hash() {
+        let store = BlobSidecarStore::new(InMemoryBlobStore::default());
+        let tx = B256::random();
+        let data = sidecar(2);
+        let hashes = data.versioned_hashes().collect::<Vec<_>>();
+        store.insert(tx, data.clone()).unwrap();
+
+        let blobs = store.get_blobs(&[hashes[1], B256::random(), hashes[0]]).unwrap();
+        assert_eq!(
+            blobs,
+            vec![
+                Some(BlobAndProof { blob: data.blobs[1], proof: data.proofs[1] }),
+                None,
+                Some(BlobAndProof { blob: data.blobs[0], proof: data.proofs[0] }),
+            ]
+        );
+
+        store.delete(tx).unwrap();
+        assert_eq!(store.get_blobs(&hashes).unwrap(), vec![None, None]);
+        assert!(store.get_blob(tx).unwrap().is_none());
+    }
+
+    #[test]
+    fn retain_mined_sidecars() {
+        let store = BlobSidecarStore::new(InMemoryBlobStore::default()).with_retention(10);
+        let mined = B256::random();
+        let pooled = B256::random();
+        store.insert_all(vec![(mined, sidecar(1)), (pooled, sidecar(1))]).unwrap();
+        store.on_mined(5, [mined]);
+
+        // the pool deletes both, but the mined sidecar is retained
+        store.delete_all(vec![mined, pooled]).unwrap();
+        assert!(store.contains(mined).unwrap());
+        assert!(!store.contains(pooled).unwrap());
+
+        // still within the retention window
+        assert_eq!(store.prune(15).unwrap(), 0);
+        assert!(store.contains(mined).unwrap());
+
+        assert_eq!(store.prune(16).unwrap(), 1);
+        assert!(!store.contains(mined).unwrap());
+        assert_eq!(store.blobs_len(), 0);
+    }
+
+    #[test]
+    fn reorged_sidecars_are_not_retained() {
+        let store = BlobSidecarStore::new(InMemoryBlobStore::default());
+        let tx = B256::random();
+        store.insert(tx, sidecar(1)).unwrap();
+        store.on_mined(1, [tx]);
+
+        // the block was replaced by a block without the transaction
+        store.on_mined(1, []);
+        store.delete(tx).unwrap();
+        assert!(!store.contains(tx).unwrap());
+    }
+}
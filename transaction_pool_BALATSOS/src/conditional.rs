@@ -0,0 +1,167 @@
+//! Conditions of transactions that were submitted with `eth_sendRawTransactionConditional`.
+
+use reth_primitives::{Address, Header, B256, U256};
+use reth_provider::{ProviderResult, StateProvider};
+use std::collections::HashMap;
+
+/// The expected state of an account of a [`TransactionConditional`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KnownAccount {
+    /// The expected storage root of the account.
+    StorageRoot(B256),
+    /// The expected values of storage slots of the account.
+    Slots(HashMap<B256, B256>),
+}
+
+/// The conditions under which a transaction may be included, see
+/// [`add_conditional_transaction`](crate::TransactionPool::add_conditional_transaction).
+///
+/// The pool re-checks the conditions on each canonical state change and drops the transaction as
+/// soon as they can no longer be satisfied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionConditional {
+    /// The expected state of accounts.
+    pub known_accounts: HashMap<Address, KnownAccount>,
+    /// The min block number the transaction can be included in.
+    pub block_number_min: Option<u64>,
+    /// The max block number the transaction can be included in.
+    pub block_number_max: Option<u64>,
+    /// The min timestamp of the block the transaction can be included in.
+    pub timestamp_min: Option<u64>,
+    /// The max timestamp of the block the transaction can be included in.
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// Returns true if the transaction can be included in a block with the given number and
+    /// timestamp.
+    ///
+    /// Note: this does not check the [`Self::known_accounts`].
+    pub fn matches_block(&self, number: u64, timestamp: u64) -> bool {
+        self.block_number_min.map_or(true, |min| number >= min) &&
+            self.block_number_max.map_or(true, |max| number <= max) &&
+            self.timestamp_min.map_or(true, |min| timestamp >= min) &&
+            self.timestamp_max.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Checks whether the block number range can still be satisfied by a block on top of the tip
+    /// with the given number.
+    pub fn check_block_range(&self, tip_number: u64) -> Result<(), ConditionViolation> {
+        let next_block = tip_number + 1;
+        if self.block_number_max.is_some_and(|max| next_block > max) {
+            return Err(ConditionViolation::BlockNumber(next_block))
+        }
+        Ok(())
+    }
+
+    /// Checks whether the conditions can still be satisfied by a block on top of the given tip.
+    ///
+    /// The min bounds can't be violated, because the block number and timestamp of future blocks
+    /// only increase. The known accounts are checked against the given state of the tip.
+    pub fn check<S>(
+        &self,
+        tip: &Header,
+        state: &S,
+    ) -> ProviderResult<Result<(), ConditionViolation>>
+    where
+        S: StateProvider + ?Sized,
+    {
+        if let Err(violation) = self.check_block_range(tip.number) {
+            return Ok(Err(violation))
+        }
+        // the timestamp of the next block is higher than the timestamp of the tip
+        if self.timestamp_max.is_some_and(|max| tip.timestamp >= max) {
+            return Ok(Err(ConditionViolation::Timestamp(tip.timestamp)))
+        }
+
+        for (address, account) in &self.known_accounts {
+            match account {
+                KnownAccount::StorageRoot(root) => {
+                    if state.proof(*address, &[])?.storage_root != *root {
+                        return Ok(Err(ConditionViolation::StorageRoot(*address)))
+                    }
+                }
+                KnownAccount::Slots(slots) => {
+                    for (slot, value) in slots {
+                        let current = state.storage(*address, *slot)?.unwrap_or_default();
+                        if current != U256::from_be_bytes(value.0) {
+                            return Ok(Err(ConditionViolation::StorageSlot(*address, *slot)))
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Ok(()))
+    }
+}
+
+/// A condition of a [`TransactionConditional`] that can no longer be satisfied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ConditionViolation {
+    /// The next block number exceeds the max block number.
+    #[error("block number {0} exceeds the max block number")]
+    BlockNumber(u64),
+    /// The timestamp of the tip reached the max timestamp.
+    #[error("timestamp {0} reached the max timestamp")]
+    Timestamp(u64),
+    /// The storage root of the account changed.
+    #[error("storage root of {0} changed")]
+    StorageRoot(Address),
+    /// A storage slot of the account changed.
+    #[error("storage slot {1} of {0} changed")]
+    StorageSlot(Address, B256),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn check_conditions() {
+        let provider = MockEthProvider::default();
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO).extend_storage([(slot, U256::from(2))]),
+        );
+        let tip = Header { number: 10, timestamp: 100, ..Default::default() };
+
+        let mut conditional = TransactionConditional {
+            known_accounts: HashMap::from([(
+                address,
+                KnownAccount::Slots(HashMap::from([(slot, B256::with_last_byte(2))])),
+            )]),
+            block_number_min: Some(20),
+            block_number_max: Some(11),
+            timestamp_max: Some(101),
+            ..Default::default()
+        };
+        assert_eq!(conditional.check(&tip, &provider).unwrap(), Ok(()));
+        assert!(!conditional.matches_block(11, 101));
+
+        conditional.block_number_max = Some(10);
+        assert_eq!(
+            conditional.check(&tip, &provider).unwrap(),
+            Err(ConditionViolation::BlockNumber(11))
+        );
+
+        conditional.block_number_max = None;
+        conditional.timestamp_max = Some(100);
+        assert_eq!(
+            conditional.check(&tip, &provider).unwrap(),
+            Err(ConditionViolation::Timestamp(100))
+        );
+
+        conditional.timestamp_max = None;
+        conditional
+            .known_accounts
+            .insert(address, KnownAccount::Slots(HashMap::from([(slot, B256::ZERO)])));
+        assert_eq!(
+            conditional.check(&tip, &provider).unwrap(),
+            Err(ConditionViolation::StorageSlot(address, slot))
+        );
+    }
+}
@@ -1,15 +1,57 @@
-use crate::{PoolSize, TransactionOrigin};
+use crate::{
+    EthReplacementPolicy, PoolSize, PoolTransaction, ReplacementPolicy, ReputationConfig,
+    TransactionFilter, TransactionOrigin, ValidPoolTransaction,
+};
 use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// The default maximum number of transactions of a single peer that are kept once the pool exceeds
+/// its limits.
+pub const TXPOOL_MAX_TRANSACTIONS_PER_PEER_DEFAULT: usize = 2_048;
+
+/// The default maximum number of local transactions of a sub-pool that are exempt from eviction,
+/// see [`LocalTransactionConfig::max_exempt_transactions`].
+pub const TXPOOL_MAX_EXEMPT_LOCAL_TRANSACTIONS_DEFAULT: usize = 1_024;
+
 /// The default maximum allowed number of transactions in the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 
 /// The default maximum allowed size of the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 
+/// The default maximum allowed encoded size of the pending, basefee and queued subpools.
+pub const TXPOOL_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT: usize = 64;
+
+/// The default maximum allowed encoded size of the blob subpool, including the blob sidecars.
+///
+/// This leaves room for [`TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT`] blobs with their commitments and
+/// proofs.
+pub const TXPOOL_BLOB_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT: usize = 288;
+
+/// The default maximum allowed number of blobs in the blob subpool.
+///
+/// A blob is 128KiB, so this bounds the blob data of the subpool to 256MiB.
+pub const TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT: usize = 2048;
+
+/// The estimated average size (in bytes) of a non-blob transaction, which is used to derive the
+/// max number of transactions from a memory budget, see [`SubPoolLimit::for_memory_budget`].
+pub const TXPOOL_AVERAGE_TX_SIZE_BYTES: usize = 2 * 1024;
+
+/// The encoded size (in bytes) of a blob with its commitment and proof.
+pub const TXPOOL_BLOB_SIZE_BYTES: usize = 128 * 1024 + 2 * 48;
+
+/// The default share (in %) of a memory budget that is assigned to the blob sub-pool, see
+/// [`PoolConfig::with_memory_budget`].
+///
+/// This matches the ratio of the default encoded size limits.
+pub const TXPOOL_BLOB_MEMORY_SHARE_DEFAULT: usize = 60;
+
+/// The bounds of the share (in %) of a memory budget that is assigned to the blob sub-pool when the
+/// budget is rebalanced, see [`PoolConfig::rebalance_memory_budget`].
+pub const TXPOOL_BLOB_MEMORY_SHARE_BOUNDS: (usize, usize) = (25, 85);
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -29,23 +71,154 @@ pub struct PoolConfig {
     pub queued_limit: SubPoolLimit,
     /// Max number of transactions in the blob sub-pool
     pub blob_limit: SubPoolLimit,
+    /// Max number of blobs of all transactions in the blob sub-pool.
+    ///
+    /// The size of the blob sidecars is only part of the encoded size of a transaction, see
+    /// [`Self::max_encoded_size`].
+    pub max_blob_count: usize,
+    /// Max combined encoded size (in bytes) of the transactions of each sub-pool.
+    pub max_encoded_size: EncodedSizeLimits,
     /// Max number of executable transaction slots guaranteed per account
+    ///
+    /// This can be changed at runtime, see [`TransactionPoolExt`](crate::TransactionPoolExt).
     pub max_account_slots: usize,
+    /// Max number of transactions received from a single peer that are kept once the pool exceeds
+    /// its limits.
+    ///
+    /// When the pool is truncated, the worst transactions of peers above their quota are evicted
+    /// first, so that a single peer can't crowd out the transactions of other peers or local
+    /// transactions. See [`TransactionSource::Peer`](crate::TransactionSource).
+    pub max_transactions_per_peer: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
+    /// Custom policy for replacing transactions with the same sender and nonce.
+    ///
+    /// If not set, the [`EthReplacementPolicy`] with the configured `price_bumps` is used.
+    pub replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
+    /// Filter that runs before transactions are validated, e.g. to drop spam cheaply.
+    ///
+    /// If not set, all transactions are validated.
+    pub transaction_filter: Option<Arc<dyn TransactionFilter>>,
     /// How to handle locally received transactions:
     /// [`TransactionOrigin::Local`](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// Max time a transaction can be parked in the queued or basefee sub-pool before it is
+    /// evicted on the next canonical state change.
+    ///
+    /// Local transactions are exempt. If not set, parked transactions are only evicted when the
+    /// sub-pools exceed their limits.
+    pub transaction_lifetime: Option<Duration>,
+    /// Whether the sub-pool limits and the [`Self::transaction_lifetime`] are only enforced by the
+    /// maintenance task, in bounded steps.
+    ///
+    /// This keeps inserts and canonical state changes cheap during spikes, but the sub-pools can
+    /// temporarily exceed their limits. See
+    /// [`pool_maintenance_task`](crate::maintain::pool_maintenance_task).
+    pub deferred_maintenance: bool,
+    /// Penalties of senders of rejected transactions.
+    ///
+    /// Transactions of senders with a worse reputation are validated and gossiped last.
+    pub reputation: ReputationConfig,
+    /// Total memory budget (in bytes) the sub-pool limits are derived from.
+    ///
+    /// If set, the budget is split between the blob sub-pool and the other sub-pools according to
+    /// how much of the pool is occupied by blob transactions, which is rebalanced on every
+    /// canonical state change. See [`Self::with_memory_budget`].
+    pub memory_budget: Option<usize>,
 }
 
 impl PoolConfig {
+    /// Derives all sub-pool limits from the given total memory budget (in bytes).
+    ///
+    /// Initially [`TXPOOL_BLOB_MEMORY_SHARE_DEFAULT`] percent of the budget is assigned to the
+    /// blob sub-pool. Since blob transactions are much larger than other transactions, the split
+    /// is adjusted to the actual usage on every canonical state change, see
+    /// [`Self::rebalance_memory_budget`].
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self.split_memory_budget(memory_budget, TXPOOL_BLOB_MEMORY_SHARE_DEFAULT);
+        self
+    }
+
+    /// Splits the memory budget between the sub-pools, assigning `blob_share` percent to the blob
+    /// sub-pool.
+    ///
+    /// Of the remaining budget, half is assigned to the pending sub-pool and a quarter each to the
+    /// basefee and queued sub-pools.
+    pub fn split_memory_budget(&mut self, memory_budget: usize, blob_share: usize) {
+        let blob_budget = memory_budget / 100 * blob_share.min(100);
+        let remaining = memory_budget - blob_budget;
+        let pending_budget = remaining / 2;
+        let basefee_budget = remaining / 4;
+
+        self.pending_limit = SubPoolLimit::for_memory_budget(pending_budget);
+        self.basefee_limit = SubPoolLimit::for_memory_budget(basefee_budget);
+        self.queued_limit =
+            SubPoolLimit::for_memory_budget(remaining - pending_budget - basefee_budget);
+        self.blob_limit = SubPoolLimit::for_blob_memory_budget(blob_budget);
+        self.max_blob_count = blob_budget / TXPOOL_BLOB_SIZE_BYTES;
+        self.max_encoded_size = EncodedSizeLimits {
+            pending: self.pending_limit.max_size,
+            basefee: self.basefee_limit.max_size,
+            queued: self.queued_limit.max_size,
+            blob: self.blob_limit.max_size,
+        };
+    }
+
+    /// Re-splits the configured [`Self::memory_budget`] based on the share of the encoded size of
+    /// the pool that is occupied by blob transactions, bounded by
+    /// [`TXPOOL_BLOB_MEMORY_SHARE_BOUNDS`].
+    ///
+    /// Returns `false` if no memory budget is configured.
+    pub fn rebalance_memory_budget(&mut self, pool_size: PoolSize) -> bool {
+        let Some(memory_budget) = self.memory_budget else { return false };
+
+        let (min_share, max_share) = TXPOOL_BLOB_MEMORY_SHARE_BOUNDS;
+        let total_encoded_size = pool_size.pending_encoded_size +
+            pool_size.basefee_encoded_size +
+            pool_size.queued_encoded_size +
+            pool_size.blob_encoded_size;
+        let blob_share = if total_encoded_size == 0 {
+            TXPOOL_BLOB_MEMORY_SHARE_DEFAULT
+        } else {
+            (pool_size.blob_encoded_size * 100 / total_encoded_size).clamp(min_share, max_share)
+        };
+
+        self.split_memory_budget(memory_budget, blob_share);
+        true
+    }
+
+    /// Returns the policy for replacing transactions with the same sender and nonce.
+    pub fn replacement_policy(&self) -> Arc<dyn ReplacementPolicy> {
+        self.replacement_policy
+            .clone()
+            .unwrap_or_else(|| Arc::new(EthReplacementPolicy::new(self.price_bumps)))
+    }
+
     /// Returns whether or not the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
         self.blob_limit.is_exceeded(pool_size.blob, pool_size.blob_size) ||
+            self.max_blob_count < pool_size.blob_count ||
             self.pending_limit.is_exceeded(pool_size.pending, pool_size.pending_size) ||
             self.basefee_limit.is_exceeded(pool_size.basefee, pool_size.basefee_size) ||
-            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size)
+            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size) ||
+            self.max_encoded_size.is_exceeded(pool_size)
+    }
+
+    /// Returns the fullness of the fullest of the pending, basefee and queued sub-pools, between
+    /// `0.0` for empty and `1.0` for full sub-pools.
+    ///
+    /// The blob sub-pool is not considered, since it is limited separately.
+    pub fn fullness(&self, pool_size: PoolSize) -> f64 {
+        let max_encoded_size = self.max_encoded_size;
+        self.pending_limit
+            .fullness(pool_size.pending, pool_size.pending_size)
+            .max(fullness(pool_size.pending_encoded_size, max_encoded_size.pending))
+            .max(self.basefee_limit.fullness(pool_size.basefee, pool_size.basefee_size))
+            .max(fullness(pool_size.basefee_encoded_size, max_encoded_size.basefee))
+            .max(self.queued_limit.fullness(pool_size.queued, pool_size.queued_size))
+            .max(fullness(pool_size.queued_encoded_size, max_encoded_size.queued))
     }
 }
 
@@ -56,9 +229,18 @@ impl Default for PoolConfig {
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
             blob_limit: Default::default(),
+            max_blob_count: TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT,
+            max_encoded_size: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_transactions_per_peer: TXPOOL_MAX_TRANSACTIONS_PER_PEER_DEFAULT,
             price_bumps: Default::default(),
+            replacement_policy: None,
+            transaction_filter: None,
             local_transactions_config: Default::default(),
+            transaction_lifetime: None,
+            deferred_maintenance: false,
+            reputation: Default::default(),
+            memory_budget: None,
         }
     }
 }
@@ -78,11 +260,39 @@ impl SubPoolLimit {
         Self { max_txs, max_size }
     }
 
+    /// Creates limits for a sub-pool of non-blob transactions that uses at most `memory_budget`
+    /// bytes.
+    ///
+    /// The max number of transactions is derived from [`TXPOOL_AVERAGE_TX_SIZE_BYTES`].
+    pub fn for_memory_budget(memory_budget: usize) -> Self {
+        Self {
+            max_txs: (memory_budget / TXPOOL_AVERAGE_TX_SIZE_BYTES).max(1),
+            max_size: memory_budget,
+        }
+    }
+
+    /// Creates limits for the blob sub-pool that uses at most `memory_budget` bytes, including the
+    /// blob sidecars.
+    ///
+    /// Every blob transaction carries at least one blob, so the max number of transactions is
+    /// derived from [`TXPOOL_BLOB_SIZE_BYTES`].
+    pub fn for_blob_memory_budget(memory_budget: usize) -> Self {
+        Self {
+            max_txs: (memory_budget / TXPOOL_BLOB_SIZE_BYTES).max(1),
+            max_size: memory_budget,
+        }
+    }
+
     /// Returns whether the size or amount constraint is violated.
     #[inline]
     pub const fn is_exceeded(&self, txs: usize, size: usize) -> bool {
         self.max_txs < txs || self.max_size < size
     }
+
+    /// Returns how close the size or amount is to its limit, between `0.0` and `1.0`.
+    pub fn fullness(&self, txs: usize, size: usize) -> f64 {
+        fullness(txs, self.max_txs).max(fullness(size, self.max_size))
+    }
 }
 
 impl Default for SubPoolLimit {
@@ -95,6 +305,54 @@ impl Default for SubPoolLimit {
     }
 }
 
+/// Maximum combined encoded size (in bytes) of the transactions of each sub-pool.
+///
+/// The encoded size is the sum of [`PoolTransaction::encoded_length`], which includes the blob
+/// sidecar of blob transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodedSizeLimits {
+    /// Maximum encoded size of the pending sub-pool.
+    pub pending: usize,
+    /// Maximum encoded size of the basefee sub-pool.
+    pub basefee: usize,
+    /// Maximum encoded size of the queued sub-pool.
+    pub queued: usize,
+    /// Maximum encoded size of the blob sub-pool.
+    pub blob: usize,
+}
+
+impl EncodedSizeLimits {
+    /// Returns whether the encoded size of any sub-pool exceeds its limit.
+    #[inline]
+    pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
+        self.pending < pool_size.pending_encoded_size ||
+            self.basefee < pool_size.basefee_encoded_size ||
+            self.queued < pool_size.queued_encoded_size ||
+            self.blob < pool_size.blob_encoded_size
+    }
+}
+
+impl Default for EncodedSizeLimits {
+    fn default() -> Self {
+        let max_encoded_size = TXPOOL_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT * 1024 * 1024;
+        Self {
+            pending: max_encoded_size,
+            basefee: max_encoded_size,
+            queued: max_encoded_size,
+            blob: TXPOOL_BLOB_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT * 1024 * 1024,
+        }
+    }
+}
+
+/// Returns how close `used` is to `limit`, between `0.0` and `1.0`.
+fn fullness(used: usize, limit: usize) -> f64 {
+    if limit == 0 {
+        1.0
+    } else {
+        (used as f64 / limit as f64).min(1.0)
+    }
+}
+
 /// Price bump config (in %) for the transaction pool underpriced check.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct PriceBumpConfig {
@@ -139,6 +397,13 @@ pub struct LocalTransactionConfig {
     pub local_addresses: HashSet<Address>,
     /// Flag indicating whether local transactions should be propagated.
     pub propagate_local_transactions: bool,
+    /// The maximum number of local transactions of a sub-pool that are exempt from eviction when
+    /// the sub-pool is truncated.
+    ///
+    /// If a sub-pool that exceeds its limits holds more local transactions, they are evicted like
+    /// any other transaction, so the memory of the pool stays bounded even if every transaction is
+    /// submitted locally, e.g. over a public RPC endpoint.
+    pub max_exempt_transactions: usize,
 }
 
 impl Default for LocalTransactionConfig {
@@ -147,6 +412,7 @@ impl Default for LocalTransactionConfig {
             no_exemptions: false,
             local_addresses: HashSet::default(),
             propagate_local_transactions: true,
+            max_exempt_transactions: TXPOOL_MAX_EXEMPT_LOCAL_TRANSACTIONS_DEFAULT,
         }
     }
 }
@@ -175,6 +441,13 @@ impl LocalTransactionConfig {
         origin.is_local() || self.contains_local_address(sender)
     }
 
+    /// Returns whether the transaction is exempt from eviction when a sub-pool is truncated, see
+    /// [`Self::max_exempt_transactions`].
+    #[inline]
+    pub fn is_exempt<T: PoolTransaction>(&self, tx: &ValidPoolTransaction<T>) -> bool {
+        self.is_local(tx.origin, tx.sender())
+    }
+
     /// Sets toggle to propagate transactions received locally by this client (e.g
     /// transactions from `eth_sendTransaction` to this nodes' RPC server)
     ///
@@ -219,10 +492,87 @@ mod tests {
             queued_size: config.queued_limit.max_size + 1,
             blob: config.blob_limit.max_txs + 1,
             blob_size: config.blob_limit.max_size + 1,
+            blob_count: config.max_blob_count + 1,
             ..Default::default()
         };
 
         // now this should be above the limits
         assert!(config.is_exceeded(pool_size));
+
+        // the blob count alone exceeds the limits
+        let pool_size = PoolSize { blob_count: config.max_blob_count + 1, ..Default::default() };
+        assert!(config.is_exceeded(pool_size));
+
+        // the encoded size alone exceeds the limits
+        let pool_size = PoolSize {
+            blob_encoded_size: config.max_encoded_size.blob + 1,
+            ..Default::default()
+        };
+        assert!(config.is_exceeded(pool_size));
+        let pool_size = PoolSize {
+            pending_encoded_size: config.max_encoded_size.pending + 1,
+            ..Default::default()
+        };
+        assert!(config.is_exceeded(pool_size));
+    }
+
+    #[test]
+    fn test_pool_fullness() {
+        let config = PoolConfig::default();
+        assert_eq!(config.fullness(PoolSize::default()), 0.0);
+
+        // the fullest sub-pool determines the fullness
+        let pool_size = PoolSize {
+            pending: config.pending_limit.max_txs / 4,
+            queued: config.queued_limit.max_txs / 2,
+            ..Default::default()
+        };
+        assert_eq!(config.fullness(pool_size), 0.5);
+
+        // the blob sub-pool is not considered
+        let pool_size = PoolSize { blob: config.blob_limit.max_txs, ..Default::default() };
+        assert_eq!(config.fullness(pool_size), 0.0);
+
+        let pool_size =
+            PoolSize { basefee_size: config.basefee_limit.max_size * 2, ..Default::default() };
+        assert_eq!(config.fullness(pool_size), 1.0);
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let memory_budget = 1024 * 1024 * 1024;
+        let mut config = PoolConfig::default().with_memory_budget(memory_budget);
+
+        let total = |config: &PoolConfig| {
+            config.max_encoded_size.pending +
+                config.max_encoded_size.basefee +
+                config.max_encoded_size.queued +
+                config.max_encoded_size.blob
+        };
+        assert_eq!(total(&config), memory_budget);
+        assert_eq!(config.max_encoded_size.blob, memory_budget / 100 * 60);
+        assert_eq!(config.max_encoded_size.pending, config.max_encoded_size.basefee * 2);
+        assert_eq!(
+            config.pending_limit.max_txs,
+            config.pending_limit.max_size / TXPOOL_AVERAGE_TX_SIZE_BYTES
+        );
+        assert_eq!(config.max_blob_count, config.max_encoded_size.blob / TXPOOL_BLOB_SIZE_BYTES);
+
+        // blob transactions dominate, so the blob sub-pool gets the max share
+        let blob_limit = config.blob_limit;
+        let pool_size =
+            PoolSize { pending_encoded_size: 1, blob_encoded_size: 99, ..Default::default() };
+        assert!(config.rebalance_memory_budget(pool_size));
+        assert_eq!(total(&config), memory_budget);
+        assert_eq!(config.max_encoded_size.blob, memory_budget / 100 * 85);
+        assert!(config.blob_limit.max_txs > blob_limit.max_txs);
+
+        // no blob transactions, so the blob sub-pool gets the min share
+        let pool_size = PoolSize { pending_encoded_size: 1, ..Default::default() };
+        assert!(config.rebalance_memory_budget(pool_size));
+        assert_eq!(config.max_encoded_size.blob, memory_budget / 100 * 25);
+
+        // nothing to rebalance without a budget
+        assert!(!PoolConfig::default().rebalance_memory_budget(pool_size));
     }
 }
@@ -1,6 +1,9 @@
 //! Transaction pool errors
 
-use reth_primitives::{Address, BlobTransactionValidationError, InvalidTransactionError, TxHash};
+use crate::{ConditionViolation, TransactionFilterError};
+use reth_primitives::{
+    Address, BlobTransactionValidationError, Bytes, InvalidTransactionError, TxHash,
+};
 
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
@@ -59,6 +62,13 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if the sender is the authority of a pending EIP-7702 authorization and already has
+    /// an in-flight transaction.
+    #[error("in-flight transaction limit reached for delegated account {0}")]
+    InflightTxLimitReached(Address),
+    /// Thrown if an authority of an EIP-7702 transaction already has in-flight transactions.
+    #[error("authority already reserved")]
+    AuthorityReserved,
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -133,6 +143,11 @@ impl PoolError {
                 // exclusivity (blob vs normal tx) for all senders
                 false
             }
+            PoolErrorKind::InflightTxLimitReached(_) | PoolErrorKind::AuthorityReserved => {
+                // this depends on the pending authorizations in the pool, which the sender could
+                // not know about
+                false
+            }
         }
     }
 }
@@ -168,6 +183,18 @@ pub enum Eip4844PoolTransactionError {
     Eip4844NonceGap,
 }
 
+/// Represents all errors that can happen when validating transactions for the pool for EIP-7702
+/// transactions
+#[derive(Debug, thiserror::Error)]
+pub enum Eip7702PoolTransactionError {
+    /// Thrown if an EIP-7702 transaction without any authorizations arrives
+    #[error("EIP7702 transaction with empty authorization list")]
+    MissingEip7702AuthorizationList,
+    /// Thrown if an EIP-7702 transaction is a contract creation
+    #[error("EIP7702 transaction cannot create a contract")]
+    Eip7702Create,
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [`TransactionValidator`](crate::TransactionValidator).
@@ -192,12 +219,32 @@ pub enum InvalidPoolTransactionError {
     /// Thrown if the transaction's fee is below the minimum fee
     #[error("transaction underpriced")]
     Underpriced,
+    /// Thrown if the transaction's priority fee is below the minimum priority fee currently
+    /// enforced by the pool, see [`MinimumPriorityFee`](crate::validate::MinimumPriorityFee).
+    #[error("transaction's priority fee is below the minimum priority fee {minimum_priority_fee}")]
+    PriorityFeeBelowMinimum {
+        /// The minimum priority fee at the time of validation.
+        minimum_priority_fee: u128,
+    },
     /// Thrown if the transaction's would require an account to be overdrawn
     #[error("transaction overdraws from account")]
     Overdraft,
     /// Eip-4844 related errors
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// Eip-7702 related errors
+    #[error(transparent)]
+    Eip7702(#[from] Eip7702PoolTransactionError),
+    /// Thrown if the transaction was rejected by the configured
+    /// [`TransactionFilter`](crate::TransactionFilter) before validation.
+    #[error(transparent)]
+    Filtered(#[from] TransactionFilterError),
+    /// Thrown if the conditions of a conditional transaction can't be satisfied on top of the
+    /// current head, see [`add_conditional_transaction`].
+    ///
+    /// [`add_conditional_transaction`]: crate::TransactionPool::add_conditional_transaction
+    #[error(transparent)]
+    ConditionViolated(#[from] ConditionViolation),
     /// Any other error that occurred while inserting/validating that is transaction specific
     #[error(transparent)]
     Other(Box<dyn PoolTransactionError>),
@@ -205,6 +252,13 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if a local transaction reverted when it was simulated against the latest state, see
+    /// [`PreInclusionSimulator`](crate::validate::PreInclusionSimulator).
+    #[error("transaction reverted in simulation: {output}")]
+    SimulationReverted {
+        /// The revert data returned by the transaction.
+        output: Bytes,
+    },
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -259,8 +313,24 @@ impl InvalidPoolTransactionError {
                 // local setting
                 false
             }
+            Self::PriorityFeeBelowMinimum { .. } => {
+                // local setting, which can change with the fullness of the pool
+                false
+            }
             Self::IntrinsicGasTooLow => true,
+            Self::SimulationReverted { .. } => {
+                // only local transactions are simulated, and the outcome depends on the state
+                false
+            }
             Self::Overdraft => false,
+            Self::Filtered(_) => {
+                // local policy
+                false
+            }
+            Self::ConditionViolated(_) => {
+                // the conditions depend on the chain the sender may not have seen yet
+                false
+            }
             Self::Other(err) => err.is_bad_transaction(),
             Self::Eip4844(eip4844_err) => {
                 match eip4844_err {
@@ -288,6 +358,13 @@ impl InvalidPoolTransactionError {
                     }
                 }
             }
+            Self::Eip7702(eip7702_err) => match eip7702_err {
+                Eip7702PoolTransactionError::MissingEip7702AuthorizationList |
+                Eip7702PoolTransactionError::Eip7702Create => {
+                    // this is a malformed transaction and should not be sent over the network
+                    true
+                }
+            },
         }
     }
 
@@ -0,0 +1,162 @@
+//! Fee suggestions derived from the pool contents and recent blocks, e.g. for `eth_feeHistory` and
+//! `eth_maxPriorityFeePerGas`.
+
+use crate::BlockInfo;
+use reth_primitives::{BlockNumber, SealedBlock};
+use std::collections::VecDeque;
+
+/// The number of recent blocks whose fees are tracked by the pool.
+pub const FEE_HISTORY_BLOCKS: usize = 20;
+
+/// The fees paid in a block, see [`FeeForecast::blocks`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockFees {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The base fee of the block.
+    pub base_fee_per_gas: u64,
+    /// The blob fee of the block, only after Cancun.
+    pub blob_fee: Option<u128>,
+    /// The ratio of the gas used to the gas limit of the block.
+    pub gas_used_ratio: f64,
+    /// The priority fees paid by the transactions of the block, in ascending order.
+    pub priority_fees: Vec<u128>,
+}
+
+impl BlockFees {
+    /// Returns the fees paid in the given block.
+    pub fn from_block(block: &SealedBlock) -> Self {
+        let base_fee_per_gas = block.base_fee_per_gas.unwrap_or_default();
+        let mut priority_fees = block
+            .body
+            .iter()
+            .filter_map(|tx| tx.effective_tip_per_gas(Some(base_fee_per_gas)))
+            .collect::<Vec<_>>();
+        priority_fees.sort_unstable();
+
+        let gas_used_ratio = if block.gas_limit == 0 {
+            0.0
+        } else {
+            block.gas_used as f64 / block.gas_limit as f64
+        };
+
+        Self {
+            number: block.number,
+            base_fee_per_gas,
+            blob_fee: block.blob_fee(),
+            gas_used_ratio,
+            priority_fees,
+        }
+    }
+
+    /// Returns the priority fees paid in the block at the given percentiles.
+    pub fn rewards(&self, percentiles: &[f64]) -> Vec<u128> {
+        percentiles.iter().map(|p| percentile(&self.priority_fees, *p)).collect()
+    }
+}
+
+/// Fee suggestions for the next block, see
+/// [`TransactionPool::fee_forecast`](crate::TransactionPool::fee_forecast).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeeForecast {
+    /// The base fee of the next block.
+    pub base_fee_per_gas: u64,
+    /// The blob fee of the next block, only after Cancun.
+    pub blob_fee: Option<u128>,
+    /// The suggested priority fees, one for each of the requested percentiles.
+    ///
+    /// These are the percentiles of the priority fees of the pending transactions in the pool and
+    /// of the transactions in the recent blocks.
+    pub priority_fees: Vec<u128>,
+    /// The fees paid in the recent blocks, in ascending order.
+    pub blocks: Vec<BlockFees>,
+}
+
+/// Tracks the fees paid in the recent blocks.
+#[derive(Debug, Default)]
+pub(crate) struct FeeHistory {
+    /// The fees of the last [`FEE_HISTORY_BLOCKS`] blocks, in ascending order.
+    blocks: VecDeque<BlockFees>,
+}
+
+impl FeeHistory {
+    /// Records the fees of a new canonical block.
+    ///
+    /// This forgets all tracked blocks with the same or a higher block number, which were reorged.
+    pub(crate) fn insert(&mut self, fees: BlockFees) {
+        while self.blocks.back().is_some_and(|block| block.number >= fees.number) {
+            self.blocks.pop_back();
+        }
+        self.blocks.push_back(fees);
+        while self.blocks.len() > FEE_HISTORY_BLOCKS {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// Returns the fee suggestions for the next block at the given percentiles, based on the
+    /// priority fees of the given pending transactions and the recent blocks.
+    ///
+    /// The percentiles are clamped to `[0, 100]`.
+    pub(crate) fn forecast(
+        &self,
+        block_info: BlockInfo,
+        pending_priority_fees: impl IntoIterator<Item = u128>,
+        percentiles: &[f64],
+    ) -> FeeForecast {
+        let mut priority_fees = pending_priority_fees.into_iter().collect::<Vec<_>>();
+        priority_fees.extend(self.blocks.iter().flat_map(|block| block.priority_fees.iter()));
+        priority_fees.sort_unstable();
+
+        FeeForecast {
+            base_fee_per_gas: block_info.pending_basefee,
+            blob_fee: block_info.pending_blob_fee,
+            priority_fees: percentiles.iter().map(|p| percentile(&priority_fees, *p)).collect(),
+            blocks: self.blocks.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Returns the value at the given percentile of the sorted values, or zero if there are none.
+fn percentile(sorted: &[u128], percentile: f64) -> u128 {
+    let Some(last) = sorted.len().checked_sub(1) else { return 0 };
+    let idx = (percentile.clamp(0.0, 100.0) / 100.0 * last as f64).round() as usize;
+    sorted[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: BlockNumber, priority_fees: Vec<u128>) -> BlockFees {
+        BlockFees { number, priority_fees, ..Default::default() }
+    }
+
+    #[test]
+    fn forecast_percentiles() {
+        let mut history = FeeHistory::default();
+        history.insert(block(1, vec![1, 2, 3]));
+        history.insert(block(2, vec![100]));
+        // block 2 was reorged
+        history.insert(block(2, vec![4, 5]));
+
+        let block_info = BlockInfo { pending_basefee: 7, ..Default::default() };
+        let forecast = history.forecast(block_info, [6, 7, 8, 9, 10], &[0.0, 50.0, 100.0, 200.0]);
+        assert_eq!(forecast.base_fee_per_gas, 7);
+        assert_eq!(forecast.priority_fees, vec![1, 6, 10, 10]);
+        assert_eq!(forecast.blocks.iter().map(|b| b.number).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(forecast.blocks[1].rewards(&[0.0, 100.0]), vec![4, 5]);
+
+        let empty = FeeHistory::default().forecast(block_info, [], &[50.0]);
+        assert_eq!(empty.priority_fees, vec![0]);
+    }
+
+    #[test]
+    fn bounded_history() {
+        let mut history = FeeHistory::default();
+        for number in 0..FEE_HISTORY_BLOCKS as u64 + 5 {
+            history.insert(block(number, vec![]));
+        }
+        assert_eq!(history.blocks.len(), FEE_HISTORY_BLOCKS);
+        assert_eq!(history.blocks.front().unwrap().number, 5);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{
+    traits::{PeerId, PoolTransaction, TransactionSource},
+    TransactionOrigin,
+};
+use parking_lot::Mutex;
+use reth_primitives::{Address, TxHash};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The fields of a transaction a [`TransactionFilter`] can inspect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilterInput {
+    /// Where the transaction originated from.
+    pub origin: TransactionOrigin,
+    /// Who submitted the transaction.
+    pub source: TransactionSource,
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The size of the calldata of the transaction.
+    pub input_len: usize,
+}
+
+impl FilterInput {
+    /// Returns the filter input of the given transaction.
+    pub fn new<T: PoolTransaction>(origin: TransactionOrigin, transaction: &T) -> Self {
+        Self {
+            origin,
+            source: origin.into(),
+            hash: *transaction.hash(),
+            sender: transaction.sender(),
+            to: transaction.to(),
+            gas_limit: transaction.gas_limit(),
+            input_len: transaction.input().len(),
+        }
+    }
+
+    /// Sets the submitter of the transaction.
+    pub const fn with_source(mut self, source: TransactionSource) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+/// Represents errors of a [`TransactionFilter`] that rejected a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TransactionFilterError {
+    /// The calldata of the transaction exceeds the limit.
+    #[error("calldata size {0} exceeds filter limit {1}")]
+    CalldataTooLarge(usize, usize),
+    /// The gas limit of the transaction exceeds the limit.
+    #[error("gas limit {0} exceeds filter limit {1}")]
+    GasLimitTooHigh(u64, u64),
+    /// The sender of the transaction is blacklisted.
+    #[error("sender {0} is blacklisted")]
+    BlacklistedSender(Address),
+    /// The recipient of the transaction is blacklisted.
+    #[error("recipient {0} is blacklisted")]
+    BlacklistedRecipient(Address),
+    /// The sender of the transaction exceeded its rate limit.
+    #[error("sender {0} exceeded its rate limit")]
+    RateLimited(Address),
+    /// The peer that sent the transaction exceeded its rate limit.
+    #[error("peer {0} exceeded its rate limit")]
+    PeerRateLimited(PeerId),
+}
+
+/// A cheap check that runs before a transaction is validated, which allows operators to drop
+/// unwanted transactions before the (expensive) validation.
+///
+/// The filter of a pool is configured with
+/// [`PoolConfig::transaction_filter`](crate::PoolConfig::transaction_filter). Transactions that
+/// are rejected by the filter are reported as
+/// [`InvalidPoolTransactionError::Filtered`](crate::error::InvalidPoolTransactionError::Filtered).
+pub trait TransactionFilter: fmt::Debug + Send + Sync + 'static {
+    /// Returns an error if the transaction should be rejected.
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError>;
+}
+
+/// Rejects transactions with a calldata size above the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxCalldataSizeFilter {
+    /// The max calldata size (in bytes).
+    max_size: usize,
+}
+
+impl MaxCalldataSizeFilter {
+    /// Creates a new filter with the given max calldata size (in bytes).
+    pub const fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl TransactionFilter for MaxCalldataSizeFilter {
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError> {
+        if transaction.input_len > self.max_size {
+            return Err(TransactionFilterError::CalldataTooLarge(
+                transaction.input_len,
+                self.max_size,
+            ))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with a gas limit above the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxGasFilter {
+    /// The max gas limit.
+    max_gas: u64,
+}
+
+impl MaxGasFilter {
+    /// Creates a new filter with the given max gas limit.
+    pub const fn new(max_gas: u64) -> Self {
+        Self { max_gas }
+    }
+}
+
+impl TransactionFilter for MaxGasFilter {
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError> {
+        if transaction.gas_limit > self.max_gas {
+            return Err(TransactionFilterError::GasLimitTooHigh(transaction.gas_limit, self.max_gas))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions from or to blacklisted addresses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlacklistFilter {
+    /// The blacklisted senders.
+    senders: HashSet<Address>,
+    /// The blacklisted recipients.
+    recipients: HashSet<Address>,
+}
+
+impl BlacklistFilter {
+    /// Blacklists the given senders.
+    pub fn with_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.senders.extend(senders);
+        self
+    }
+
+    /// Blacklists the given recipients.
+    pub fn with_recipients(mut self, recipients: impl IntoIterator<Item = Address>) -> Self {
+        self.recipients.extend(recipients);
+        self
+    }
+}
+
+impl TransactionFilter for BlacklistFilter {
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError> {
+        if self.senders.contains(&transaction.sender) {
+            return Err(TransactionFilterError::BlacklistedSender(transaction.sender))
+        }
+        if let Some(to) = transaction.to.filter(|to| self.recipients.contains(to)) {
+            return Err(TransactionFilterError::BlacklistedRecipient(to))
+        }
+        Ok(())
+    }
+}
+
+/// The number of tracked rate limit keys above which expired rate limit windows are pruned.
+const RATE_LIMIT_PRUNE_THRESHOLD: usize = 10_000;
+
+/// What a [`RateLimitFilter`] counts transactions by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RateLimitKey {
+    /// Transactions received from a peer are counted by the peer.
+    Peer(PeerId),
+    /// Transactions from an unknown source are counted by their sender.
+    Sender(Address),
+}
+
+/// Limits the number of external transactions per peer in a time window.
+///
+/// Transactions received from a peer are rate limited by the peer's id, so that a single peer
+/// can't flood the pool by spreading transactions over many senders. Transactions from an unknown
+/// source are rate limited by their sender. Local transactions are not rate limited.
+#[derive(Debug)]
+pub struct RateLimitFilter {
+    /// The max number of transactions per peer (or sender) in a window.
+    max_txs: usize,
+    /// The length of a window.
+    window: Duration,
+    /// The start of the current window and the number of transactions in it, by key.
+    windows: Mutex<HashMap<RateLimitKey, (Instant, usize)>>,
+}
+
+impl RateLimitFilter {
+    /// Creates a new filter that allows `max_txs` transactions per peer every `window`.
+    pub fn new(max_txs: usize, window: Duration) -> Self {
+        Self { max_txs, window, windows: Default::default() }
+    }
+}
+
+impl TransactionFilter for RateLimitFilter {
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError> {
+        if transaction.origin.is_local() {
+            return Ok(())
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        if windows.len() >= RATE_LIMIT_PRUNE_THRESHOLD {
+            // forget windows that expired, so that the map does not grow unbounded
+            windows.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+        }
+
+        let key = match transaction.source.peer() {
+            Some(peer) => RateLimitKey::Peer(*peer),
+            None => RateLimitKey::Sender(transaction.sender),
+        };
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= self.window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max_txs {
+            return Err(match key {
+                RateLimitKey::Peer(peer) => TransactionFilterError::PeerRateLimited(peer),
+                RateLimitKey::Sender(sender) => TransactionFilterError::RateLimited(sender),
+            })
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Runs all of the given filters, rejecting a transaction if any filter rejects it.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilters {
+    /// The filters, in the order they are run.
+    filters: Vec<Arc<dyn TransactionFilter>>,
+}
+
+impl TransactionFilters {
+    /// Adds the given filter.
+    pub fn with(mut self, filter: impl TransactionFilter) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+}
+
+impl TransactionFilter for TransactionFilters {
+    fn check(&self, transaction: &FilterInput) -> Result<(), TransactionFilterError> {
+        self.filters.iter().try_for_each(|filter| filter.check(transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn filter_chain() {
+        let tx = MockTransaction::eip1559();
+        let input = FilterInput::new(TransactionOrigin::External, &tx);
+
+        let filters = TransactionFilters::default()
+            .with(MaxGasFilter::new(tx.get_gas_limit()))
+            .with(BlacklistFilter::default().with_recipients([Address::random()]));
+        assert_eq!(filters.check(&input), Ok(()));
+
+        let filters = filters.with(BlacklistFilter::default().with_senders([tx.get_sender()]));
+        assert_eq!(
+            filters.check(&input),
+            Err(TransactionFilterError::BlacklistedSender(tx.get_sender()))
+        );
+
+        let filters = TransactionFilters::default().with(MaxGasFilter::new(tx.get_gas_limit() - 1));
+        assert!(matches!(filters.check(&input), Err(TransactionFilterError::GasLimitTooHigh(..))));
+    }
+
+    #[test]
+    fn rate_limit_external() {
+        let filter = RateLimitFilter::new(2, Duration::from_secs(60));
+        let tx = MockTransaction::eip1559();
+        let external = FilterInput::new(TransactionOrigin::External, &tx);
+        let local = FilterInput::new(TransactionOrigin::Local, &tx);
+
+        assert_eq!(filter.check(&external), Ok(()));
+        assert_eq!(filter.check(&external), Ok(()));
+        assert_eq!(
+            filter.check(&external),
+            Err(TransactionFilterError::RateLimited(tx.get_sender()))
+        );
+        assert_eq!(filter.check(&local), Ok(()));
+
+        let other = FilterInput::new(TransactionOrigin::External, &MockTransaction::eip1559());
+        assert_eq!(filter.check(&other), Ok(()));
+    }
+
+    #[test]
+    fn rate_limit_by_peer() {
+        let filter = RateLimitFilter::new(1, Duration::from_secs(60));
+        let peer = TransactionSource::Peer(PeerId::random());
+        let tx = MockTransaction::eip1559();
+        let input = FilterInput::new(TransactionOrigin::External, &tx);
+
+        assert_eq!(filter.check(&input.with_source(peer)), Ok(()));
+        // the same sender is counted separately for another peer
+        let other_peer = TransactionSource::Peer(PeerId::random());
+        assert_eq!(filter.check(&input.with_source(other_peer)), Ok(()));
+
+        // another sender shares the bucket of the peer
+        let other = FilterInput::new(TransactionOrigin::External, &MockTransaction::eip1559());
+        assert_eq!(
+            filter.check(&other.with_source(peer)),
+            Err(TransactionFilterError::PeerRateLimited(*peer.peer().unwrap()))
+        );
+    }
+}
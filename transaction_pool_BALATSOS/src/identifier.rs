@@ -61,6 +61,12 @@ impl SenderId {
     pub const fn start_bound(self) -> std::ops::Bound<TransactionId> {
         std::ops::Bound::Included(TransactionId::new(self, 0))
     }
+
+    /// Returns the index of the shard this sender belongs to, if the senders are split into
+    /// `shards` shards.
+    pub(crate) const fn shard(self, shards: usize) -> usize {
+        (self.0 % shards as u64) as usize
+    }
 }
 
 impl From<u64> for SenderId {
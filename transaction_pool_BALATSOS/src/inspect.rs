@@ -0,0 +1,247 @@
+//! Structured views of the pool contents, e.g. for the `txpool` RPC namespace.
+
+use crate::{
+    AllPoolTransactions, PoolTransaction, TransactionPool, TransactionSource, ValidPoolTransaction,
+};
+use reth_primitives::{keccak256, Address, B256, U256};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+/// Values keyed by the sender and the nonce of a transaction.
+pub type BySenderAndNonce<V> = BTreeMap<Address, BTreeMap<u64, V>>;
+
+/// The transactions in the pool keyed by sender and nonce, see [`PoolInspect::content`].
+///
+/// This is the content of `txpool_content`, extended by the parked blob transactions.
+#[derive(Debug)]
+pub struct PoolContent<T: PoolTransaction> {
+    /// Transactions that are ready for inclusion in the next block.
+    pub pending: BySenderAndNonce<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions that are parked in the queued or basefee sub-pool.
+    pub queued: BySenderAndNonce<Arc<ValidPoolTransaction<T>>>,
+    /// Blob transactions that are parked in the blob sub-pool.
+    pub blob: BySenderAndNonce<Arc<ValidPoolTransaction<T>>>,
+}
+
+impl<T: PoolTransaction> PoolContent<T> {
+    /// Returns the content of the given transactions that satisfy the predicate.
+    fn filtered(
+        transactions: AllPoolTransactions<T>,
+        mut predicate: impl FnMut(&ValidPoolTransaction<T>) -> bool,
+    ) -> Self {
+        let mut by_sender = |txs: Vec<Arc<ValidPoolTransaction<T>>>| {
+            let mut content = BySenderAndNonce::new();
+            for tx in txs.into_iter().filter(|tx| predicate(tx)) {
+                content.entry(tx.sender()).or_insert_with(BTreeMap::new).insert(tx.nonce(), tx);
+            }
+            content
+        };
+        Self {
+            pending: by_sender(transactions.pending),
+            queued: by_sender(transactions.queued),
+            blob: by_sender(transactions.blob),
+        }
+    }
+
+    /// Returns the summaries of all transactions, see [`PoolInspect::inspect`].
+    pub fn summary(&self) -> PoolInspectSummary {
+        let summarize = |content: &BySenderAndNonce<Arc<ValidPoolTransaction<T>>>| {
+            content
+                .iter()
+                .map(|(sender, txs)| {
+                    let txs = txs
+                        .iter()
+                        .map(|(nonce, tx)| (*nonce, TransactionSummary::new(tx)))
+                        .collect();
+                    (*sender, txs)
+                })
+                .collect()
+        };
+        PoolInspectSummary {
+            pending: summarize(&self.pending),
+            queued: summarize(&self.queued),
+            blob: summarize(&self.blob),
+        }
+    }
+}
+
+impl<T: PoolTransaction> From<AllPoolTransactions<T>> for PoolContent<T> {
+    fn from(transactions: AllPoolTransactions<T>) -> Self {
+        Self::filtered(transactions, |_| true)
+    }
+}
+
+/// Returns the keccak256 hash of the hashes of the given transactions, ordered by sender and
+/// nonce, see [`PoolInspect::pool_fingerprint`].
+fn fingerprint<T: PoolTransaction>(mut transactions: Vec<Arc<ValidPoolTransaction<T>>>) -> B256 {
+    transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+    let hashes = transactions.iter().flat_map(|tx| tx.hash().0).collect::<Vec<_>>();
+    keccak256(hashes)
+}
+
+/// A summary of a transaction in the pool.
+///
+/// This displays like the entries of `txpool_inspect`:
+/// `<to>: <value> wei + <gas limit> gas × <gas price> wei`, the source is not displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TransactionSummary {
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The value transferred by the transaction.
+    pub value: U256,
+    /// The gas limit of the transaction.
+    pub gas: u64,
+    /// The max fee per gas, or the gas price for legacy transactions.
+    pub gas_price: u128,
+    /// Who submitted the transaction.
+    pub source: TransactionSource,
+}
+
+impl TransactionSummary {
+    /// Returns the summary of the given transaction.
+    pub fn new<T: PoolTransaction>(transaction: &ValidPoolTransaction<T>) -> Self {
+        Self {
+            to: transaction.to(),
+            value: transaction.transaction.value(),
+            gas: transaction.transaction.gas_limit(),
+            gas_price: transaction.transaction.max_fee_per_gas(),
+            source: transaction.source,
+        }
+    }
+}
+
+impl fmt::Display for TransactionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to {
+            Some(to) => write!(f, "{to}: ")?,
+            None => write!(f, "contract creation: ")?,
+        }
+        write!(f, "{} wei + {} gas × {} wei", self.value, self.gas, self.gas_price)
+    }
+}
+
+/// The summaries of the transactions in the pool keyed by sender and nonce, see
+/// [`PoolInspect::inspect`].
+///
+/// This is the content of `txpool_inspect`, extended by the parked blob transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PoolInspectSummary {
+    /// Transactions that are ready for inclusion in the next block.
+    pub pending: BySenderAndNonce<TransactionSummary>,
+    /// Transactions that are parked in the queued or basefee sub-pool.
+    pub queued: BySenderAndNonce<TransactionSummary>,
+    /// Blob transactions that are parked in the blob sub-pool.
+    pub blob: BySenderAndNonce<TransactionSummary>,
+}
+
+/// The number of transactions in the pool, see [`PoolInspect::status`].
+///
+/// This is the content of `txpool_status`, extended by the parked blob transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PoolStatus {
+    /// Number of transactions that are ready for inclusion in the next block.
+    pub pending: usize,
+    /// Number of transactions that are parked in the queued or basefee sub-pool.
+    pub queued: usize,
+    /// Number of blob transactions that are parked in the blob sub-pool.
+    pub blob: usize,
+}
+
+/// Structured views of the pool contents that can be returned by a `txpool` RPC namespace as is.
+///
+/// This is implemented for all [`TransactionPool`]s.
+pub trait PoolInspect: TransactionPool {
+    /// Returns all transactions in the pool keyed by sender and nonce.
+    fn content(&self) -> PoolContent<Self::Transaction> {
+        self.all_transactions().into()
+    }
+
+    /// Returns the transactions of the given sender keyed by nonce.
+    fn content_from(&self, sender: Address) -> PoolContent<Self::Transaction> {
+        PoolContent::filtered(self.all_transactions(), |tx| tx.sender() == sender)
+    }
+
+    /// Returns the transactions submitted by the given source keyed by sender and nonce.
+    fn content_by_source(&self, source: TransactionSource) -> PoolContent<Self::Transaction> {
+        PoolContent::filtered(self.all_transactions(), |tx| tx.source == source)
+    }
+
+    /// Returns the summaries of all transactions in the pool keyed by sender and nonce.
+    fn inspect(&self) -> PoolInspectSummary {
+        self.content().summary()
+    }
+
+    /// Returns the number of transactions in the pool.
+    fn status(&self) -> PoolStatus {
+        let size = self.pool_size();
+        PoolStatus { pending: size.pending, queued: size.basefee + size.queued, blob: size.blob }
+    }
+
+    /// Returns a deterministic hash over the pending transactions, ordered by sender and nonce.
+    ///
+    /// Nodes with the same pending transactions have the same fingerprint, regardless of the order
+    /// the transactions arrived in, so this can be compared between nodes to check whether their
+    /// pools converged.
+    fn pool_fingerprint(&self) -> B256 {
+        fingerprint(self.pending_transactions())
+    }
+}
+
+impl<P: TransactionPool> PoolInspect for P {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn content_by_sender_and_nonce() {
+        let mut f = MockTransactionFactory::default();
+        let tx = MockTransaction::eip1559();
+        let pending = Arc::new(f.validated(tx.clone()));
+        let queued = Arc::new(f.validated(tx.next().next()));
+        let other = Arc::new(f.validated(MockTransaction::legacy()));
+
+        let all = AllPoolTransactions {
+            pending: vec![pending.clone(), other],
+            queued: vec![queued.clone()],
+            blob: vec![],
+        };
+        let content = PoolContent::filtered(all, |tx| tx.sender() == pending.sender());
+
+        assert_eq!(content.pending.len(), 1);
+        assert_eq!(content.pending[&pending.sender()][&0].hash(), pending.hash());
+        assert_eq!(content.queued[&pending.sender()][&2].hash(), queued.hash());
+        assert!(content.blob.is_empty());
+
+        let summary = content.summary();
+        let entry = summary.pending[&pending.sender()][&0];
+        let expected = format!(
+            "{}: 0 wei + {} gas × {} wei",
+            tx.to().unwrap(),
+            tx.gas_limit(),
+            tx.max_fee_per_gas()
+        );
+        assert_eq!(entry.to_string(), expected);
+        assert_eq!(entry.source, TransactionSource::Unknown);
+    }
+
+    #[test]
+    fn fingerprint_is_order_independent() {
+        let mut f = MockTransactionFactory::default();
+        let tx = MockTransaction::eip1559();
+        let first = Arc::new(f.validated(tx.clone()));
+        let second = Arc::new(f.validated(tx.next()));
+        let other = Arc::new(f.validated(MockTransaction::legacy()));
+
+        let fingerprint_a = fingerprint(vec![first.clone(), second.clone(), other.clone()]);
+        let fingerprint_b = fingerprint(vec![other, second, first.clone()]);
+        assert_eq!(fingerprint_a, fingerprint_b);
+        assert_ne!(fingerprint_a, fingerprint(vec![first]));
+        assert_eq!(fingerprint::<MockTransaction>(vec![]), reth_primitives::KECCAK_EMPTY);
+    }
+}
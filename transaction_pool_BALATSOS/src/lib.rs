@@ -140,6 +140,8 @@
 //!
 //! - `serde` (default): Enable serde support
 //! - `test-utils`: Export utilities for testing
+//! - `canonical-state`: Let the pool subscribe to the canonical state notifications itself, see
+//!   `Pool::subscribe_to_canonical_state`
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -162,15 +164,36 @@ use tracing::{instrument, trace};
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        EncodedSizeLimits, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_AVERAGE_TX_SIZE_BYTES,
+        TXPOOL_BLOB_MEMORY_SHARE_BOUNDS, TXPOOL_BLOB_MEMORY_SHARE_DEFAULT, TXPOOL_BLOB_SIZE_BYTES,
+        TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT, TXPOOL_BLOB_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_EXEMPT_LOCAL_TRANSACTIONS_DEFAULT,
+        TXPOOL_MAX_TRANSACTIONS_PER_PEER_DEFAULT, TXPOOL_SUBPOOL_MAX_ENCODED_SIZE_MB_DEFAULT,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
+    conditional::{ConditionViolation, KnownAccount, TransactionConditional},
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    fees::{BlockFees, FeeForecast, FEE_HISTORY_BLOCKS},
+    filter::{
+        BlacklistFilter, FilterInput, MaxCalldataSizeFilter, MaxGasFilter, RateLimitFilter,
+        TransactionFilter, TransactionFilterError, TransactionFilters,
+    },
+    inspect::{
+        BySenderAndNonce, PoolContent, PoolInspect, PoolInspectSummary, PoolStatus,
+        TransactionSummary,
+    },
+    ordering::{
+        BoxedOrdering, CoinbasePaymentOrdering, CoinbaseTipOrdering, OrderingRegistry,
+        Priority, PriorityFeeOrdering, TransactionOrdering, COINBASE_PAYMENT_ORDERING,
+        COINBASE_TIP_ORDERING, PRIORITY_FEE_ORDERING,
+    },
+    replacement::{EthReplacementPolicy, OpReplacementPolicy, ReplacementFees, ReplacementPolicy},
+    reputation::{Offense, ReputationConfig, SenderReputation},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DropReason,
+        FullTransactionEvent, PoolLifecycleEvent, PoolLifecycleEvents, TransactionEvent,
+        TransactionEvents,
     },
     traits::*,
     validate::{
@@ -187,9 +210,15 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+mod conditional;
 mod config;
+mod fees;
+mod filter;
 pub mod identifier;
+mod inspect;
 mod ordering;
+mod replacement;
+mod reputation;
 mod traits;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -233,26 +262,113 @@ where
         self.inner().config()
     }
 
+    /// Subscribes the pool to the canonical state notifications of the client.
+    ///
+    /// This spawns a task that removes mined transactions and updates the nonces, balances and fees
+    /// tracked by the pool on every new block or reorg, see
+    /// [`spawn_canonical_state_maintenance`](maintain::spawn_canonical_state_maintenance).
+    #[cfg(feature = "canonical-state")]
+    pub fn subscribe_to_canonical_state<Client, Tasks>(
+        &self,
+        client: Client,
+        task_spawner: Tasks,
+        config: maintain::MaintainPoolConfig,
+    ) where
+        Self: TransactionPoolExt + 'static,
+        Client: reth_provider::CanonStateSubscriptions
+            + StateProviderFactory
+            + reth_provider::BlockReaderIdExt
+            + reth_provider::ChainSpecProvider
+            + Clone
+            + Send
+            + 'static,
+        Tasks: reth_tasks::TaskSpawner + Clone + 'static,
+    {
+        maintain::spawn_canonical_state_maintenance(client, self.clone(), task_spawner, config)
+    }
+
     /// Returns future that validates all transaction in the given iterator.
     ///
-    /// This returns the validated transactions in the iterator's order.
+    /// Transactions of senders with a better reputation are validated first, see
+    /// [`SenderReputation`]. This returns the validated transactions in the iterator's order.
     async fn validate_all(
         &self,
+        source: TransactionSource,
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = V::Transaction>,
     ) -> Vec<(TxHash, TransactionValidationOutcome<V::Transaction>)> {
-        futures_util::future::join_all(transactions.into_iter().map(|tx| self.validate(origin, tx)))
-            .await
+        // validate transactions of senders with a better reputation first
+        let mut transactions = transactions.into_iter().enumerate().collect::<Vec<_>>();
+        self.pool.reputation().sort_by_score(&mut transactions, |(_, tx)| tx.sender());
+
+        let mut validated = futures_util::future::join_all(
+            transactions.into_iter().map(|(idx, tx)| async move {
+                (idx, self.validate(source, origin, tx).await)
+            }),
+        )
+        .await;
+        validated.sort_unstable_by_key(|(idx, _)| *idx);
+        validated.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    /// Runs the configured [`TransactionFilter`] on the given transaction.
+    fn filter(
+        &self,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transaction: &V::Transaction,
+    ) -> Result<(), TransactionFilterError> {
+        let Some(filter) = &self.config().transaction_filter else { return Ok(()) };
+        let input = FilterInput::new(origin, transaction).with_source(source);
+        filter.check(&input).inspect_err(|err| {
+            trace!(target: "txpool", hash=%transaction.hash(), %err, "Transaction rejected by filter");
+        })
+    }
+
+    /// Validates all given transactions with a single call to the validator, see
+    /// [`TransactionValidator::validate_transactions`].
+    ///
+    /// This returns the outcomes in the order of the given transactions.
+    async fn validate_batch(
+        &self,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transactions: Vec<V::Transaction>,
+    ) -> Vec<TransactionValidationOutcome<V::Transaction>> {
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut unfiltered = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            if let Err(err) = self.filter(source, origin, &transaction) {
+                outcomes.push(Some(TransactionValidationOutcome::Invalid(transaction, err.into())));
+            } else {
+                outcomes.push(None);
+                unfiltered.push((origin, transaction));
+            }
+        }
+
+        let mut validated =
+            self.pool.validator().validate_transactions(unfiltered).await.into_iter();
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| validated.next().expect("one outcome per transaction"))
+            })
+            .collect()
     }
 
     /// Validates the given transaction
     async fn validate(
         &self,
+        source: TransactionSource,
         origin: TransactionOrigin,
         transaction: V::Transaction,
     ) -> (TxHash, TransactionValidationOutcome<V::Transaction>) {
         let hash = *transaction.hash();
 
+        if let Err(err) = self.filter(source, origin, &transaction) {
+            return (hash, TransactionValidationOutcome::Invalid(transaction, err.into()))
+        }
+
         let outcome = self.pool.validator().validate_transaction(origin, transaction).await;
 
         (hash, outcome)
@@ -274,6 +390,27 @@ where
     }
 }
 
+impl<V, S> Pool<V, BoxedOrdering<V::Transaction>, S>
+where
+    V: TransactionValidator,
+    S: BlobStore,
+{
+    /// Returns a new [Pool] that orders its pending transactions with the ordering registered
+    /// under the given name in the [`OrderingRegistry`].
+    ///
+    /// Returns `None` if no ordering is registered under the name.
+    pub fn with_registered_ordering(
+        validator: V,
+        registry: &OrderingRegistry<V::Transaction>,
+        ordering: &str,
+        blob_store: S,
+        config: PoolConfig,
+    ) -> Option<Self> {
+        let ordering = registry.get(ordering)?;
+        Some(Self::new(validator, ordering, blob_store, config))
+    }
+}
+
 impl<Client, S> EthTransactionPool<Client, S>
 where
     Client: StateProviderFactory + reth_provider::BlockReaderIdExt + Clone + 'static,
@@ -338,7 +475,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
-        let (_, tx) = self.validate(origin, transaction).await;
+        let (_, tx) = self.validate(origin.into(), origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
 
@@ -347,8 +484,8 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
-        let (_, tx) = self.validate(origin, transaction).await;
-        let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
+        let (_, tx) = self.validate(origin.into(), origin, transaction).await;
+        let mut results = self.pool.add_transactions(origin.into(), origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
     }
 
@@ -356,13 +493,64 @@ where
         &self,
         origin: TransactionOrigin,
         transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        self.add_transactions_from(origin.into(), origin, transactions).await
+    }
+
+    async fn add_transactions_from(
+        &self,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let validated = self.validate_all(source, origin, transactions).await;
+
+        self.pool.add_transactions(source, origin, validated.into_iter().map(|(_, tx)| tx))
+    }
+
+    async fn add_peer_transactions(
+        &self,
+        peer: PeerId,
+        transactions: Vec<Self::Transaction>,
     ) -> Vec<PoolResult<TxHash>> {
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
+        let source = TransactionSource::Peer(peer);
+        let origin = TransactionOrigin::External;
+        let validated = self.validate_batch(source, origin, transactions).await;
 
-        self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+        self.pool.add_transactions_batch(source, origin, validated)
+    }
+
+    async fn add_transactions_batch(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let validated = self.validate_batch(origin.into(), origin, transactions).await;
+
+        self.pool.add_transactions_batch(origin.into(), origin, validated)
+    }
+
+    async fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(origin.into(), origin, transaction).await;
+        self.pool.add_conditional_transaction(origin, tx, conditional)
+    }
+
+    fn get_conditional(&self, tx_hash: &TxHash) -> Option<TransactionConditional> {
+        self.pool.get_conditional(tx_hash)
     }
 
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
@@ -373,6 +561,10 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn lifecycle_event_listener(&self) -> PoolLifecycleEvents<Self::Transaction> {
+        self.pool.add_lifecycle_event_listener()
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -425,6 +617,10 @@ where
         Box::new(self.pool.best_transactions())
     }
 
+    fn best_transactions_stream(&self) -> BestTransactionsStream<Self::Transaction> {
+        Box::pin(self.pool.best_transactions().into_stream())
+    }
+
     fn best_transactions_with_base_fee(
         &self,
         base_fee: u64,
@@ -447,6 +643,22 @@ where
         self.pool.queued_transactions()
     }
 
+    fn pending_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<Self::Transaction> {
+        self.pool.pending_transactions_page(cursor, limit)
+    }
+
+    fn queued_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<Self::Transaction> {
+        self.pool.queued_transactions_page(cursor, limit)
+    }
+
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction> {
         self.pool.all_transactions()
     }
@@ -501,10 +713,29 @@ where
         self.pool.get_transactions_by_origin(origin)
     }
 
+    fn get_transactions_by_source(
+        &self,
+        source: TransactionSource,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.get_transactions_by_source(source)
+    }
+
     fn unique_senders(&self) -> HashSet<Address> {
         self.pool.unique_senders()
     }
 
+    fn sender_state(&self, sender: Address) -> SenderState {
+        self.pool.sender_state(sender)
+    }
+
+    fn sender_spam_score(&self, sender: Address) -> f64 {
+        self.pool.reputation().score(&sender)
+    }
+
+    fn fee_forecast(&self, percentiles: &[f64]) -> FeeForecast {
+        self.pool.fee_forecast(percentiles)
+    }
+
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get(tx_hash)
     }
@@ -555,6 +786,32 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn max_account_slots(&self) -> usize {
+        self.pool.max_account_slots()
+    }
+
+    #[instrument(skip(self), target = "txpool")]
+    fn set_max_account_slots(&self, max_account_slots: usize) {
+        trace!(target: "txpool", "updating max account slots");
+        self.pool.set_max_account_slots(max_account_slots)
+    }
+
+    fn rebroadcast_local_transactions(&self) -> Vec<TxHash> {
+        self.pool.rebroadcast_local_transactions()
+    }
+
+    fn conditional_transactions(&self) -> Vec<(TxHash, TransactionConditional)> {
+        self.pool.conditional_transactions()
+    }
+
+    fn remove_violated_conditionals(&self, hashes: Vec<TxHash>) {
+        self.pool.remove_violated_conditionals(hashes);
+    }
+
+    fn run_maintenance(&self, budget: usize) -> PoolMaintenanceStats {
+        self.pool.run_maintenance(budget)
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
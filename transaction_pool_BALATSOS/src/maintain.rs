@@ -1,22 +1,25 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{BlobSidecarStore, BlobStore, BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo,
+    traits::{
+        CanonicalStateUpdate, ChangedAccount, TransactionListenerKind, TransactionPool,
+        TransactionPoolExt,
+    },
+    BlockInfo, PoolTransaction, TransactionOrigin, ValidPoolTransaction,
 };
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
-use reth_execution_types::ExecutionOutcome;
+use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, SealedBlock,
+    TransactionSigned, TransactionSignedEcRecovered, TryFromRecoveredTransaction,
 };
 use reth_provider::{
     BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ProviderError,
@@ -27,7 +30,10 @@ use std::{
     borrow::Borrow,
     collections::HashSet,
     hash::{Hash, Hasher},
+    io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -52,6 +58,27 @@ impl Default for MaintainPoolConfig {
     }
 }
 
+/// Settings for the pool maintenance task.
+///
+/// See [`pool_maintenance_task`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolMaintenanceConfig {
+    /// How often the maintenance runs.
+    ///
+    /// Default: 1 second
+    pub interval: Duration,
+    /// Max number of transactions removed per run, which bounds how long the pool is locked.
+    ///
+    /// Default: 1000
+    pub budget: usize,
+}
+
+impl Default for PoolMaintenanceConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(1), budget: 1000 }
+    }
+}
+
 /// Settings for local transaction backup task
 #[derive(Debug, Clone, Default)]
 pub struct LocalTransactionBackupConfig {
@@ -66,6 +93,58 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Settings for the transaction pool backup task.
+///
+/// Unlike [`LocalTransactionBackupConfig`], this persists the pending, queued and basefee
+/// transactions of all senders.
+#[derive(Debug, Clone, Default)]
+pub struct PoolTransactionsBackupConfig {
+    /// Path to the transactions backup file
+    pub transactions_path: Option<PathBuf>,
+}
+
+impl PoolTransactionsBackupConfig {
+    /// Receive path to the pool backup and return initialized config
+    pub const fn with_pool_backup(transactions_path: PathBuf) -> Self {
+        Self { transactions_path: Some(transactions_path) }
+    }
+}
+
+/// Settings for the local transactions journal task.
+///
+/// See [`local_transactions_journal_task`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalTransactionsJournalConfig {
+    /// Path to the journal file, the journal is disabled if this is not set.
+    pub journal_path: Option<PathBuf>,
+    /// How often the journal is rewritten from the local transactions in the pool, which drops
+    /// included and discarded transactions.
+    ///
+    /// Default: 1 hour
+    pub rejournal_interval: Duration,
+    /// How often pending local transactions are rebroadcast to peers.
+    ///
+    /// Default: 5 minutes
+    pub rebroadcast_interval: Duration,
+}
+
+impl LocalTransactionsJournalConfig {
+    /// Receive path to the journal and return initialized config
+    pub fn with_journal(journal_path: PathBuf) -> Self {
+        Self { journal_path: Some(journal_path), ..Default::default() }
+    }
+}
+
+impl Default for LocalTransactionsJournalConfig {
+    fn default() -> Self {
+        Self {
+            journal_path: None,
+            rejournal_interval: Duration::from_secs(60 * 60),
+            rebroadcast_interval: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -86,6 +165,35 @@ where
     .boxed()
 }
 
+/// Subscribes to the canonical state notifications of the client and spawns a task that maintains
+/// the state of the transaction pool with them, see [`maintain_transaction_pool`].
+///
+/// This lets the pool keep itself up to date, e.g. when it is embedded in standalone tooling or
+/// tests, instead of requiring the node to feed it with the canonical state.
+#[cfg(feature = "canonical-state")]
+pub fn spawn_canonical_state_maintenance<Client, P, Tasks>(
+    client: Client,
+    pool: P,
+    task_spawner: Tasks,
+    config: MaintainPoolConfig,
+) where
+    Client: reth_provider::CanonStateSubscriptions
+        + StateProviderFactory
+        + BlockReaderIdExt
+        + ChainSpecProvider
+        + Clone
+        + Send
+        + 'static,
+    P: TransactionPoolExt + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
+{
+    let events = client.canonical_state_stream();
+    task_spawner.spawn_critical(
+        "txpool maintenance task",
+        maintain_transaction_pool_future(client, pool, events, task_spawner.clone(), config),
+    );
+}
+
 /// Maintains the state of the transaction pool by handling new blocks and reorgs.
 ///
 /// This listens for any new blocks and reorgs and updates the transaction pool's state accordingly
@@ -317,31 +425,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 let pruned_old_transactions = old_blocks
                     .transactions_ecrecovered()
                     .filter(|tx| !new_mined_transactions.contains(&tx.hash))
-                    .filter_map(|tx| {
-                        if tx.is_eip4844() {
-                            // reorged blobs no longer include the blob, which is necessary for
-                            // validating the transaction. Even though the transaction could have
-                            // been validated previously, we still need the blob in order to
-                            // accurately set the transaction's
-                            // encoded-length which is propagated over the network.
-                            pool.get_blob(tx.hash)
-                                .ok()
-                                .flatten()
-                                .and_then(|sidecar| {
-                                    PooledTransactionsElementEcRecovered::try_from_blob_transaction(
-                                        tx, sidecar,
-                                    )
-                                    .ok()
-                                })
-                                .map(
-                                    <P as TransactionPool>::Transaction::from_recovered_pooled_transaction,
-                                )
-                        } else {
-                            <P as TransactionPool>::Transaction::try_from_recovered_transaction(
-                                tx,
-                            ).ok()
-                        }
-                    })
+                    .filter_map(|tx| to_pool_transaction(&pool, tx))
                     .collect::<Vec<_>>();
 
                 // update the pool first
@@ -354,6 +438,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions: new_blocks.transaction_hashes().collect(),
                 };
                 pool.on_canonical_state_change(update);
+                remove_violated_conditionals(&client, &pool, &new_tip.block);
 
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
@@ -435,6 +520,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions,
                 };
                 pool.on_canonical_state_change(update);
+                remove_violated_conditionals(&client, &pool, &tip.block);
 
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -443,6 +529,159 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     }
 }
 
+/// Converts a transaction of a reorged or reverted block into a pool transaction.
+///
+/// Returns `None` for blob transactions whose sidecar is no longer in the blob store.
+fn to_pool_transaction<P>(pool: &P, tx: TransactionSignedEcRecovered) -> Option<P::Transaction>
+where
+    P: TransactionPool,
+{
+    #[cfg(feature = "optimism")]
+    if tx.is_deposit() {
+        // deposits are derived from L1 and are never pooled, the sender nonce they consumed is
+        // already reflected in the changed accounts
+        return None
+    }
+
+    if tx.is_eip4844() {
+        // reorged blobs no longer include the blob, which is necessary for validating the
+        // transaction. Even though the transaction could have been validated previously, we still
+        // need the blob in order to accurately set the transaction's encoded-length which is
+        // propagated over the network.
+        pool.get_blob(tx.hash)
+            .ok()
+            .flatten()
+            .and_then(|sidecar| {
+                PooledTransactionsElementEcRecovered::try_from_blob_transaction(tx, sidecar).ok()
+            })
+            .map(<P as TransactionPool>::Transaction::from_recovered_pooled_transaction)
+    } else {
+        <P as TransactionPool>::Transaction::try_from_recovered_transaction(tx).ok()
+    }
+}
+
+/// Re-injects the transactions of a reverted chain into the pool, e.g. on an
+/// `ExExNotification::ChainReverted`.
+///
+/// Canonical state notifications only cover commits and reorgs, so the pool is not updated if
+/// blocks are reverted without a new chain. This moves the pool back to the parent of the first
+/// reverted block, reloads the accounts that were changed in the reverted blocks and re-validates
+/// the reverted transactions, which are inserted into the appropriate sub-pools subject to the
+/// usual replacement and limit rules.
+///
+/// Returns the number of re-injected transactions.
+pub async fn reinject_reverted_transactions<Client, P>(
+    client: &Client,
+    pool: &P,
+    reverted: &Chain,
+) -> usize
+where
+    Client: StateProviderFactory + Clone,
+    P: TransactionPoolExt,
+{
+    let (blocks, state) = reverted.inner();
+    let first = blocks.first();
+    let fork_block = first.parent_hash;
+
+    // the next block is built on the fork block, like the first reverted block, so it has the same
+    // fees
+    pool.set_block_info(BlockInfo {
+        last_seen_block_hash: fork_block,
+        last_seen_block_number: first.number.saturating_sub(1),
+        pending_basefee: first.base_fee_per_gas.unwrap_or_default(),
+        pending_blob_fee: first.blob_fee(),
+    });
+
+    let changed_accounts = state.accounts_iter().map(|(address, _)| address);
+    match load_accounts(client.clone(), fork_block, changed_accounts) {
+        Ok(LoadedAccounts { accounts, failed_to_load }) => {
+            if !failed_to_load.is_empty() {
+                debug!(
+                    target: "txpool",
+                    ?failed_to_load,
+                    "failed to load accounts changed in reverted blocks"
+                );
+            }
+            pool.update_accounts(accounts);
+        }
+        Err(err) => {
+            let (_, err) = *err;
+            debug!(target: "txpool", %err, ?fork_block, "failed to load accounts at fork block");
+        }
+    }
+
+    let transactions = blocks
+        .transactions_ecrecovered()
+        .filter_map(|tx| to_pool_transaction(pool, tx))
+        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        return 0
+    }
+
+    trace!(target: "txpool", count = transactions.len(), "re-injecting reverted transactions");
+    pool.add_external_transactions(transactions).await.iter().filter(|res| res.is_ok()).count()
+}
+
+/// Re-injects the transactions of every reverted chain yielded by the stream into the pool, see
+/// [`reinject_reverted_transactions`].
+///
+/// The stream is expected to yield the chains of `ExExNotification::ChainReverted`s, which the
+/// pool never sees through [`maintain_transaction_pool`].
+pub async fn reinject_reverted_transactions_task<Client, P, St>(
+    client: Client,
+    pool: P,
+    mut reverted: St,
+) where
+    Client: StateProviderFactory + Clone,
+    P: TransactionPoolExt,
+    St: Stream<Item = Arc<Chain>> + Send + Unpin,
+{
+    while let Some(chain) = reverted.next().await {
+        let count = reinject_reverted_transactions(&client, &pool, &chain).await;
+        debug!(target: "txpool", count, range = ?chain.range(), "re-injected transactions");
+    }
+}
+
+/// Removes all conditional transactions whose conditions can no longer be satisfied on top of the
+/// given tip.
+///
+/// Transactions whose conditions can't be checked, e.g. because the state of the tip is not
+/// available, are kept.
+fn remove_violated_conditionals<Client, P>(client: &Client, pool: &P, tip: &SealedBlock)
+where
+    Client: StateProviderFactory,
+    P: TransactionPoolExt,
+{
+    let conditionals = pool.conditional_transactions();
+    if conditionals.is_empty() {
+        return
+    }
+
+    let state = match client.history_by_block_hash(tip.hash()) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, "failed to load state for conditional transactions");
+            return
+        }
+    };
+
+    let violated = conditionals
+        .into_iter()
+        .filter_map(|(tx_hash, conditional)| match conditional.check(&tip.header, &*state) {
+            Ok(Ok(())) => None,
+            Ok(Err(violation)) => {
+                trace!(target: "txpool", %tx_hash, %violation, "dropping conditional transaction");
+                Some(tx_hash)
+            }
+            Err(err) => {
+                debug!(target: "txpool", %tx_hash, %err, "failed to check transaction conditions");
+                None
+            }
+        })
+        .collect();
+    pool.remove_violated_conditionals(violated);
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -587,7 +826,61 @@ where
     }
 
     let txs_signed: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let num_txs = reinsert_transactions(&pool, TransactionOrigin::Local, txs_signed).await;
+
+    info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Successfully reinserted local transactions from file");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Loads a pool backup written by [`save_pool_txs_backup`] and inserts the transactions into the
+/// transaction pool on node boot up.
+///
+/// The transactions are validated again against the current state, so transactions that were
+/// included or became invalid while the node was offline are discarded.
+/// The file is removed after the transactions have been successfully processed.
+async fn load_and_reinsert_pool_transactions<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
 
+    debug!(target: "txpool", txs_file =?file_path, "Check persistent storage for saved pool transactions");
+    let data = reth_fs_util::read(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    // the backup consists of the list of local transactions followed by the list of external ones
+    let mut buf = data.as_slice();
+    let local: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut buf)?;
+    let external: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut buf)?;
+
+    let num_local = reinsert_transactions(&pool, TransactionOrigin::Local, local).await;
+    let num_external = reinsert_transactions(&pool, TransactionOrigin::External, external).await;
+
+    info!(target: "txpool", txs_file =?file_path, %num_local, %num_external, "Successfully reinserted pool transactions from file");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Recovers the given transactions and adds them to the pool with the given origin.
+///
+/// Returns the number of transactions that were handed to the pool.
+async fn reinsert_transactions<P>(
+    pool: &P,
+    origin: TransactionOrigin,
+    txs_signed: Vec<TransactionSigned>,
+) -> usize
+where
+    P: TransactionPool,
+{
     let pool_transactions = txs_signed
         .into_iter()
         .filter_map(|tx| tx.try_ecrecovered())
@@ -597,11 +890,11 @@ where
         })
         .collect::<Vec<_>>();
 
-    let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    if pool_transactions.is_empty() {
+        return 0
+    }
 
-    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
-    reth_fs_util::remove_file(file_path)?;
-    Ok(())
+    pool.add_transactions(origin, pool_transactions).await.len()
 }
 
 fn save_local_txs_backup<P>(pool: P, file_path: &Path)
@@ -635,6 +928,119 @@ where
     }
 }
 
+/// Writes the pending, queued and basefee transactions of the pool to the given file.
+///
+/// Private transactions are not persisted, because they would be propagated once they are
+/// reinserted as external transactions. Blob transactions are not persisted either, because the
+/// backup does not include their sidecars.
+fn save_pool_txs_backup<P>(pool: P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let all = pool.all_transactions();
+
+    let mut local = Vec::new();
+    let mut external = Vec::new();
+    for tx in all.pending.iter().chain(all.queued.iter()).filter(|tx| !tx.is_eip4844()) {
+        match tx.origin {
+            TransactionOrigin::Local => local.push(to_signed(tx)),
+            TransactionOrigin::External => external.push(to_signed(tx)),
+            TransactionOrigin::Private => {}
+        }
+    }
+
+    if local.is_empty() && external.is_empty() {
+        trace!(target: "txpool", "no pool transactions to save");
+        return
+    }
+
+    let mut buf = Vec::new();
+    alloy_rlp::encode_list(&local, &mut buf);
+    alloy_rlp::encode_list(&external, &mut buf);
+    info!(target: "txpool", txs_file =?file_path, num_local=%local.len(), num_external=%external.len(), "Saving current pool transactions");
+    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
+
+    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+        Ok(_) => {
+            info!(target: "txpool", txs_file=?file_path, "Wrote pool transactions to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write pool transactions to file");
+        }
+    }
+}
+
+/// Loads the transactions of a journal written by [`append_to_journal`] and
+/// [`rotate_local_transactions_journal`].
+///
+/// A journal entry that was only partially written, e.g. because the node crashed while appending
+/// to the journal, is ignored together with any entries that follow it.
+fn load_local_transactions_journal(
+    file_path: &Path,
+) -> Result<Vec<TransactionSigned>, TransactionsBackupError> {
+    if !file_path.exists() {
+        return Ok(Vec::new())
+    }
+
+    let data = reth_fs_util::read(file_path)?;
+    let mut buf = data.as_slice();
+    let mut txs = Vec::new();
+    while !buf.is_empty() {
+        match <TransactionSigned as alloy_rlp::Decodable>::decode(&mut buf) {
+            Ok(tx) => txs.push(tx),
+            Err(err) => {
+                warn!(target: "txpool", %err, txs_file=?file_path, "Failed to decode journal entry");
+                break
+            }
+        }
+    }
+
+    Ok(txs)
+}
+
+/// Appends a new local transaction to the journal.
+fn append_to_journal(file_path: &Path, tx: &TransactionSigned) -> std::io::Result<()> {
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut buf = Vec::new();
+    alloy_rlp::Encodable::encode(tx, &mut buf);
+
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(file_path)?;
+    file.write_all(&buf)
+}
+
+/// Rewrites the journal with the local transactions that are currently in the pool.
+///
+/// Blob transactions are not journaled, because the journal does not include their sidecars.
+fn rotate_local_transactions_journal<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let mut buf = Vec::new();
+    let mut num_txs = 0;
+    for tx in pool.get_local_transactions().iter().filter(|tx| !tx.is_eip4844()) {
+        alloy_rlp::Encodable::encode(&to_signed(tx), &mut buf);
+        num_txs += 1;
+    }
+
+    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
+    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+        Ok(_) => {
+            debug!(target: "txpool", txs_file=?file_path, %num_txs, "Rotated local transactions journal");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to rotate local transactions journal");
+        }
+    }
+}
+
+/// Converts a pool transaction into the signed transaction that is persisted.
+fn to_signed<T: PoolTransaction>(tx: &Arc<ValidPoolTransaction<T>>) -> TransactionSigned {
+    tx.to_recovered_transaction().into_signed()
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
@@ -675,16 +1081,169 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Task which manages saving the pending, queued and basefee transactions of the pool to the
+/// persistent file in case of shutdown.
+/// Reloads the transactions from the file on the boot up, revalidates them and inserts them into
+/// the pool.
+pub async fn backup_pool_transactions_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: PoolTransactionsBackupConfig,
+) where
+    P: TransactionPool + Clone,
+{
+    let Some(transactions_path) = config.transactions_path else {
+        // nothing to do
+        return
+    };
+
+    if let Err(err) = load_and_reinsert_pool_transactions(pool.clone(), &transactions_path).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let graceful_guard = shutdown.await;
+
+    // write transactions to disk
+    save_pool_txs_backup(pool, &transactions_path);
+
+    drop(graceful_guard)
+}
+
+/// Task which journals local transactions to disk and rebroadcasts them until they are included.
+///
+/// On boot up, the transactions of the journal are reinserted into the pool as local
+/// transactions. Every new local transaction is appended to the journal, and the journal is
+/// rewritten from the pool every [`LocalTransactionsJournalConfig::rejournal_interval`] and on
+/// shutdown, which drops transactions that were included or discarded in the meantime.
+///
+/// Pending local transactions are rebroadcast every
+/// [`LocalTransactionsJournalConfig::rebroadcast_interval`], see
+/// [`TransactionPoolExt::rebroadcast_local_transactions`].
+pub async fn local_transactions_journal_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: LocalTransactionsJournalConfig,
+) where
+    P: TransactionPoolExt + Clone,
+{
+    let Some(journal_path) = config.journal_path else {
+        // nothing to do
+        return
+    };
+
+    match load_local_transactions_journal(&journal_path) {
+        Ok(txs) => {
+            let num_txs = reinsert_transactions(&pool, TransactionOrigin::Local, txs).await;
+            info!(target: "txpool", txs_file=?journal_path, %num_txs, "Reinserted journaled local transactions");
+        }
+        Err(err) => error!(target: "txpool", "{}", err),
+    }
+
+    // subscribe before rewriting the journal, so that no new local transaction is missed
+    let mut new_transactions = pool.new_transactions_listener_for(TransactionListenerKind::All);
+    rotate_local_transactions_journal(&pool, &journal_path);
+
+    let mut rejournal = tokio::time::interval(config.rejournal_interval);
+    let mut rebroadcast = tokio::time::interval(config.rebroadcast_interval);
+    // the first tick completes immediately
+    rejournal.tick().await;
+    rebroadcast.tick().await;
+
+    let mut shutdown = std::pin::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            graceful_guard = &mut shutdown => {
+                rotate_local_transactions_journal(&pool, &journal_path);
+                drop(graceful_guard);
+                break
+            }
+            event = new_transactions.recv() => {
+                let Some(event) = event else { break };
+                let tx = event.transaction;
+                if tx.origin.is_local() && !tx.is_eip4844() {
+                    if let Err(err) = append_to_journal(&journal_path, &to_signed(&tx)) {
+                        warn!(target: "txpool", %err, txs_file=?journal_path, "Failed to journal local transaction");
+                    }
+                }
+            }
+            _ = rejournal.tick() => {
+                rotate_local_transactions_journal(&pool, &journal_path);
+            }
+            _ = rebroadcast.tick() => {
+                let hashes = pool.rebroadcast_local_transactions();
+                trace!(target: "txpool", num_txs=%hashes.len(), "Rebroadcast local transactions");
+            }
+        }
+    }
+}
+
+/// Task which removes expired transactions and enforces the sub-pool limits every
+/// [`PoolMaintenanceConfig::interval`], removing at most [`PoolMaintenanceConfig::budget`]
+/// transactions per run.
+///
+/// This is required if [`PoolConfig::deferred_maintenance`](crate::PoolConfig) is set, otherwise
+/// the pool does this on inserts and canonical state changes. Promotions and demotions due to base
+/// fee changes are still done on canonical state changes.
+pub async fn pool_maintenance_task<P>(pool: P, config: PoolMaintenanceConfig)
+where
+    P: TransactionPoolExt,
+{
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        let stats = pool.run_maintenance(config.budget);
+        if stats.removed() > 0 {
+            trace!(
+                target: "txpool",
+                expired = stats.expired,
+                discarded = stats.discarded,
+                "Removed transactions in pool maintenance"
+            );
+        }
+    }
+}
+
+/// Task which records the blob transactions of new canonical blocks in the given
+/// [`BlobSidecarStore`], so that their sidecars are retained for the blob retention window, and
+/// prunes the sidecars that fell out of the window.
+///
+/// The store is expected to be the blob store of the pool.
+pub async fn maintain_blob_sidecar_store<S, St>(store: BlobSidecarStore<S>, mut events: St)
+where
+    S: BlobStore,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        let new = match event {
+            CanonStateNotification::Commit { new } => new,
+            CanonStateNotification::Reorg { new, .. } => new,
+        };
+        let (blocks, _) = new.inner();
+        match store.on_new_chain_blocks(&blocks) {
+            Ok(num_pruned) => {
+                trace!(target: "txpool", %num_pruned, "Pruned blob sidecars");
+            }
+            Err(err) => {
+                warn!(target: "txpool", %err, "Failed to prune blob sidecars");
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore,
+        validate::{EthTransactionValidator, EthTransactionValidatorBuilder},
         CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
     };
     use reth_chainspec::MAINNET;
     use reth_fs_util as fs;
-    use reth_primitives::{hex, PooledTransactionsElement, U256};
+    use reth_primitives::{hex, Header, PooledTransactionsElement, SealedBlockWithSenders, U256};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -748,4 +1307,123 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    type TestPool = Pool<
+        EthTransactionValidator<MockEthProvider, EthPooledTransaction>,
+        CoinbaseTipOrdering<EthPooledTransaction>,
+        InMemoryBlobStore,
+    >;
+
+    /// Returns the test transaction and a pool whose validator sees the sender of the transaction
+    /// with a state nonce `nonce_offset` above the nonce of the transaction.
+    fn setup(nonce_offset: u64) -> (EthPooledTransaction, MockEthProvider, TestPool) {
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
+            tx.try_into_ecrecovered().unwrap(),
+        );
+        let provider = MockEthProvider::default();
+        let state_nonce = transaction.nonce() + nonce_offset;
+        provider.add_account(transaction.sender(), ExtendedAccount::new(state_nonce, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        (transaction, provider, pool)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_restore_pool_txs_backup_revalidates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let (transaction, _, txpool) = setup(0);
+
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        save_pool_txs_backup(txpool, &transactions_path);
+
+        // the transaction is still valid against the current state
+        let (_, _, restored) = setup(0);
+        load_and_reinsert_pool_transactions(restored.clone(), &transactions_path).await.unwrap();
+        let restored_tx = restored.get(transaction.hash()).expect("transaction was restored");
+        assert_eq!(restored_tx.origin, TransactionOrigin::External);
+        assert!(!transactions_path.exists());
+
+        // the transaction was included while the node was offline, so revalidation discards it
+        save_pool_txs_backup(restored, &transactions_path);
+        let (_, _, stale) = setup(1);
+        load_and_reinsert_pool_transactions(stale.clone(), &transactions_path).await.unwrap();
+        assert!(stale.get(transaction.hash()).is_none());
+        assert!(stale.is_empty());
+        assert!(!transactions_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_local_transactions_journal() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let (transaction, _, txpool) = setup(0);
+        let signed = transaction.to_recovered_transaction().into_signed();
+
+        // the journal ends with a partially written entry, which is ignored
+        append_to_journal(&journal_path, &signed).unwrap();
+        let mut file = std::fs::OpenOptions::new().append(true).open(&journal_path).unwrap();
+        file.write_all(&[0xf8]).unwrap();
+        drop(file);
+
+        let mut pending = txpool.pending_transactions_listener();
+        let handle = tokio::runtime::Handle::current();
+        let manager = TaskManager::new(handle);
+        let config = LocalTransactionsJournalConfig {
+            rebroadcast_interval: Duration::from_millis(10),
+            ..LocalTransactionsJournalConfig::with_journal(journal_path.clone())
+        };
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            local_transactions_journal_task(shutdown, txpool.clone(), config)
+        });
+
+        // the journaled transaction is reinserted as a local transaction
+        let timeout = Duration::from_secs(5);
+        let hash = tokio::time::timeout(timeout, pending.recv()).await.unwrap();
+        assert_eq!(hash, Some(*transaction.hash()));
+        let restored_tx = txpool.get(transaction.hash()).expect("transaction was restored");
+        assert_eq!(restored_tx.origin, TransactionOrigin::Local);
+
+        // and rebroadcast while it is pending
+        let hash = tokio::time::timeout(timeout, pending.recv()).await.unwrap();
+        assert_eq!(hash, Some(*transaction.hash()));
+
+        // the journal is rewritten from the pool on shutdown, which drops the partial entry
+        manager.graceful_shutdown();
+        assert_eq!(fs::read(&journal_path).unwrap(), alloy_rlp::encode(&signed));
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reinject_reverted_transactions() {
+        let (transaction, provider, txpool) = setup(0);
+        let tx = transaction.to_recovered_transaction();
+
+        // the transaction was mined in a block, so it is not in the pool
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header: Header { number: 1, ..Default::default() }.seal_slow(),
+                body: vec![tx.clone().into_signed()],
+                ..Default::default()
+            },
+            senders: vec![tx.signer()],
+        };
+        let chain = Chain::new(vec![block], ExecutionOutcome::default(), None);
+        assert!(!txpool.contains(&tx.hash()));
+
+        // the block is reverted
+        let reverted = futures_util::stream::iter([Arc::new(chain)]);
+        reinject_reverted_transactions_task(provider, txpool.clone(), reverted).await;
+
+        let reinjected = txpool.get(&tx.hash()).expect("transaction was re-injected");
+        assert_eq!(reinjected.origin, TransactionOrigin::External);
+    }
 }
@@ -1,9 +1,11 @@
 //! Transaction pool metrics.
 
+use crate::SubPool;
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use std::time::Duration;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -16,31 +18,103 @@ pub struct TxPoolMetrics {
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
 
+    /// Number of transactions added to the pending sub-pool, including promoted transactions
+    pub(crate) pending_pool_added_transactions: Counter,
+    /// Number of transactions removed from the pending sub-pool, including demoted transactions
+    pub(crate) pending_pool_removed_transactions: Counter,
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
     /// Total amount of memory used by the transactions in the pending sub-pool in bytes
     pub(crate) pending_pool_size_bytes: Gauge,
+    /// Total encoded size of the transactions in the pending sub-pool in bytes
+    pub(crate) pending_pool_encoded_size_bytes: Gauge,
 
+    /// Number of transactions added to the basefee sub-pool
+    pub(crate) basefee_pool_added_transactions: Counter,
+    /// Number of transactions removed from the basefee sub-pool
+    pub(crate) basefee_pool_removed_transactions: Counter,
     /// Number of transactions in the basefee sub-pool
     pub(crate) basefee_pool_transactions: Gauge,
     /// Total amount of memory used by the transactions in the basefee sub-pool in bytes
     pub(crate) basefee_pool_size_bytes: Gauge,
+    /// Total encoded size of the transactions in the basefee sub-pool in bytes
+    pub(crate) basefee_pool_encoded_size_bytes: Gauge,
 
+    /// Number of transactions added to the queued sub-pool
+    pub(crate) queued_pool_added_transactions: Counter,
+    /// Number of transactions removed from the queued sub-pool
+    pub(crate) queued_pool_removed_transactions: Counter,
     /// Number of transactions in the queued sub-pool
     pub(crate) queued_pool_transactions: Gauge,
     /// Total amount of memory used by the transactions in the queued sub-pool in bytes
     pub(crate) queued_pool_size_bytes: Gauge,
+    /// Total encoded size of the transactions in the queued sub-pool in bytes
+    pub(crate) queued_pool_encoded_size_bytes: Gauge,
 
+    /// Number of transactions added to the blob sub-pool
+    pub(crate) blob_pool_added_transactions: Counter,
+    /// Number of transactions removed from the blob sub-pool
+    pub(crate) blob_pool_removed_transactions: Counter,
     /// Number of transactions in the blob sub-pool
     pub(crate) blob_pool_transactions: Gauge,
     /// Total amount of memory used by the transactions in the blob sub-pool in bytes
     pub(crate) blob_pool_size_bytes: Gauge,
+    /// Total encoded size of the transactions in the blob sub-pool in bytes
+    pub(crate) blob_pool_encoded_size_bytes: Gauge,
+    /// Number of blobs of the transactions in the blob sub-pool
+    pub(crate) blob_pool_blobs: Gauge,
 
     /// Number of all transactions of all sub-pools: pending + basefee + queued
     pub(crate) total_transactions: Gauge,
 
     /// How often the pool was updated after the canonical state changed
     pub(crate) performed_state_updates: Counter,
+
+    /// Number of transactions promoted to the pending sub-pool
+    pub(crate) promoted_transactions: Counter,
+    /// Number of transactions demoted from the pending sub-pool
+    pub(crate) demoted_transactions: Counter,
+    /// How long promoted transactions were in the pool before they were promoted, in seconds
+    pub(crate) promotion_latency_seconds: Histogram,
+
+    /// Number of transactions discarded to enforce the limits of the sub-pools
+    pub(crate) truncated_transactions: Counter,
+    /// How long it took to enforce the limits of the sub-pools, in seconds
+    pub(crate) truncation_duration_seconds: Histogram,
+}
+
+impl TxPoolMetrics {
+    #[inline]
+    pub(crate) fn inc_added(&self, pool: SubPool) {
+        match pool {
+            SubPool::Pending => self.pending_pool_added_transactions.increment(1),
+            SubPool::BaseFee => self.basefee_pool_added_transactions.increment(1),
+            SubPool::Queued => self.queued_pool_added_transactions.increment(1),
+            SubPool::Blob => self.blob_pool_added_transactions.increment(1),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn inc_removed(&self, pool: SubPool) {
+        match pool {
+            SubPool::Pending => self.pending_pool_removed_transactions.increment(1),
+            SubPool::BaseFee => self.basefee_pool_removed_transactions.increment(1),
+            SubPool::Queued => self.queued_pool_removed_transactions.increment(1),
+            SubPool::Blob => self.blob_pool_removed_transactions.increment(1),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record_promotion(&self, in_pool: Duration) {
+        self.promoted_transactions.increment(1);
+        self.promotion_latency_seconds.record(in_pool.as_secs_f64());
+    }
+
+    #[inline]
+    pub(crate) fn record_truncation(&self, truncated: usize, duration: Duration) {
+        self.truncated_transactions.increment(truncated as u64);
+        self.truncation_duration_seconds.record(duration.as_secs_f64());
+    }
 }
 
 /// Transaction pool blobstore metrics
@@ -6,15 +6,18 @@
 use crate::{
     blobstore::BlobStoreError,
     error::PoolError,
+    identifier::TransactionId,
     traits::{
-        BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
-        TransactionListenerKind,
+        BestTransactionsAttributes, BestTransactionsStream, GetPooledTransactionLimit,
+        NewBlobSidecar, TransactionListenerKind,
     },
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
-    EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize, PoolTransaction,
-    PooledTransactionsElement, PropagatedTransactions, TransactionEvents, TransactionOrigin,
-    TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    EthPooledTransaction, FeeForecast, NewTransactionEvent, PoolLifecycleEvents, PoolResult,
+    PoolSize, PoolTransaction, PooledTransactionsElement, PropagatedTransactions, SenderState,
+    TransactionConditional, TransactionEvents, TransactionOrigin, TransactionPool,
+    TransactionSource, TransactionValidationOutcome, TransactionValidator, TransactionsPage,
+    ValidPoolTransaction,
 };
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{Address, BlobTransactionSidecar, TxHash, U256};
@@ -77,6 +80,29 @@ impl TransactionPool for NoopTransactionPool {
             .collect()
     }
 
+    async fn add_transactions_from(
+        &self,
+        _source: TransactionSource,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        self.add_transactions(origin, transactions).await
+    }
+
+    async fn add_conditional_transaction(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        _conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
+    fn get_conditional(&self, _tx_hash: &TxHash) -> Option<TransactionConditional> {
+        None
+    }
+
     fn transaction_event_listener(&self, _tx_hash: TxHash) -> Option<TransactionEvents> {
         None
     }
@@ -85,6 +111,10 @@ impl TransactionPool for NoopTransactionPool {
         AllTransactionsEvents::new(mpsc::channel(1).1)
     }
 
+    fn lifecycle_event_listener(&self) -> PoolLifecycleEvents<Self::Transaction> {
+        PoolLifecycleEvents::new(mpsc::channel(1).1)
+    }
+
     fn pending_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
@@ -147,6 +177,10 @@ impl TransactionPool for NoopTransactionPool {
         Box::new(std::iter::empty())
     }
 
+    fn best_transactions_stream(&self) -> BestTransactionsStream<Self::Transaction> {
+        Box::pin(futures_util::stream::empty())
+    }
+
     fn best_transactions_with_base_fee(
         &self,
         _: u64,
@@ -169,6 +203,22 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn pending_transactions_page(
+        &self,
+        _cursor: Option<TransactionId>,
+        _limit: usize,
+    ) -> TransactionsPage<Self::Transaction> {
+        TransactionsPage::default()
+    }
+
+    fn queued_transactions_page(
+        &self,
+        _cursor: Option<TransactionId>,
+        _limit: usize,
+    ) -> TransactionsPage<Self::Transaction> {
+        TransactionsPage::default()
+    }
+
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction> {
         AllPoolTransactions::default()
     }
@@ -218,10 +268,29 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn get_transactions_by_source(
+        &self,
+        _source: TransactionSource,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn unique_senders(&self) -> HashSet<Address> {
         Default::default()
     }
 
+    fn sender_state(&self, sender: Address) -> SenderState {
+        SenderState { sender, ..Default::default() }
+    }
+
+    fn sender_spam_score(&self, _sender: Address) -> f64 {
+        0.0
+    }
+
+    fn fee_forecast(&self, percentiles: &[f64]) -> FeeForecast {
+        FeeForecast { priority_fees: vec![0; percentiles.len()], ..Default::default() }
+    }
+
     fn get_blob(&self, _tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         Ok(None)
     }
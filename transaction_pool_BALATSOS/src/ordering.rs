@@ -1,6 +1,15 @@
 use crate::traits::PoolTransaction;
 use reth_primitives::U256;
-use std::{fmt, marker::PhantomData};
+use std::{collections::HashMap, fmt, marker::PhantomData, sync::Arc};
+
+/// The name of [`CoinbaseTipOrdering`] in the default [`OrderingRegistry`].
+pub const COINBASE_TIP_ORDERING: &str = "coinbase-tip";
+
+/// The name of [`PriorityFeeOrdering`] in the default [`OrderingRegistry`].
+pub const PRIORITY_FEE_ORDERING: &str = "priority-fee";
+
+/// The name of [`CoinbasePaymentOrdering`] in the default [`OrderingRegistry`].
+pub const COINBASE_PAYMENT_ORDERING: &str = "coinbase-payment";
 
 /// Priority of the transaction that can be missing.
 ///
@@ -79,3 +88,220 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// Orders transactions by their max priority fee per gas, or their gas price for legacy
+/// transactions.
+///
+/// Unlike [`CoinbaseTipOrdering`], this ignores the base fee, so a transaction with a high priority
+/// fee but a max fee close to the base fee is ranked higher than its effective tip.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PriorityFeeOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for PriorityFeeOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(U256::from(transaction.priority_fee_or_price()))
+    }
+}
+
+impl<T> Default for PriorityFeeOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for PriorityFeeOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders transactions by the total tip they pay to the fee recipient of the block.
+///
+/// The payment is simulated as the effective tip per gas times the gas limit of the transaction,
+/// which is the most the fee recipient can receive. Unlike [`CoinbaseTipOrdering`], this prefers a
+/// transaction that uses a lot of gas over one that pays a slightly higher tip per gas.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CoinbasePaymentOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for CoinbasePaymentOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        transaction
+            .effective_tip_per_gas(base_fee)
+            .map(|tip| U256::from(tip) * U256::from(transaction.gas_limit()))
+            .into()
+    }
+}
+
+impl<T> Default for CoinbasePaymentOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for CoinbasePaymentOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// A type erased [`TransactionOrdering`] with a [`U256`] priority.
+///
+/// This allows selecting the ordering of a pool at runtime, see [`OrderingRegistry`].
+pub struct BoxedOrdering<T> {
+    inner: Arc<dyn TransactionOrdering<PriorityValue = U256, Transaction = T>>,
+}
+
+impl<T: PoolTransaction> BoxedOrdering<T> {
+    /// Wraps the given ordering.
+    pub fn new<O>(ordering: O) -> Self
+    where
+        O: TransactionOrdering<PriorityValue = U256, Transaction = T>,
+    {
+        Self { inner: Arc::new(ordering) }
+    }
+}
+
+impl<T: PoolTransaction> TransactionOrdering for BoxedOrdering<T> {
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.inner.priority(transaction, base_fee)
+    }
+}
+
+impl<T> Clone for BoxedOrdering<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T> fmt::Debug for BoxedOrdering<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedOrdering").finish_non_exhaustive()
+    }
+}
+
+/// A registry of named [`TransactionOrdering`]s, to select the ordering of a pool by name, e.g.
+/// from a config.
+///
+/// The default registry contains [`CoinbaseTipOrdering`] ([`COINBASE_TIP_ORDERING`]),
+/// [`PriorityFeeOrdering`] ([`PRIORITY_FEE_ORDERING`]) and [`CoinbasePaymentOrdering`]
+/// ([`COINBASE_PAYMENT_ORDERING`]). Custom orderings can be added with [`Self::register`].
+///
+/// See also [`Pool::with_registered_ordering`](crate::Pool::with_registered_ordering).
+#[derive(Debug, Clone)]
+pub struct OrderingRegistry<T> {
+    orderings: HashMap<String, BoxedOrdering<T>>,
+}
+
+impl<T: PoolTransaction> OrderingRegistry<T> {
+    /// Creates a registry without any orderings.
+    pub fn empty() -> Self {
+        Self { orderings: HashMap::new() }
+    }
+
+    /// Registers the ordering under the given name.
+    ///
+    /// Returns the ordering that was previously registered under the name, if any.
+    pub fn register<O>(&mut self, name: impl Into<String>, ordering: O) -> Option<BoxedOrdering<T>>
+    where
+        O: TransactionOrdering<PriorityValue = U256, Transaction = T>,
+    {
+        self.orderings.insert(name.into(), BoxedOrdering::new(ordering))
+    }
+
+    /// Returns the ordering registered under the given name.
+    pub fn get(&self, name: &str) -> Option<BoxedOrdering<T>> {
+        self.orderings.get(name).cloned()
+    }
+
+    /// Returns the names of all registered orderings.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.orderings.keys().map(String::as_str)
+    }
+}
+
+impl<T: PoolTransaction> Default for OrderingRegistry<T> {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(COINBASE_TIP_ORDERING, CoinbaseTipOrdering::default());
+        registry.register(PRIORITY_FEE_ORDERING, PriorityFeeOrdering::default());
+        registry.register(COINBASE_PAYMENT_ORDERING, CoinbasePaymentOrdering::default());
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn coinbase_payment_prefers_total_tip() {
+        let ordering = CoinbasePaymentOrdering::default();
+        let base_fee = 10;
+
+        let small = MockTransaction::eip1559()
+            .with_max_fee(100)
+            .with_priority_fee(20)
+            .with_gas_limit(21_000);
+        let large = MockTransaction::eip1559()
+            .with_max_fee(100)
+            .with_priority_fee(10)
+            .with_gas_limit(100_000);
+
+        assert_eq!(ordering.priority(&small, base_fee), Priority::Value(U256::from(20 * 21_000)));
+        assert!(ordering.priority(&large, base_fee) > ordering.priority(&small, base_fee));
+
+        // the tip per gas decides for the coinbase tip ordering
+        let ordering = CoinbaseTipOrdering::default();
+        assert!(ordering.priority(&large, base_fee) < ordering.priority(&small, base_fee));
+    }
+
+    #[test]
+    fn registry_lookup() {
+        let mut registry = OrderingRegistry::<MockTransaction>::default();
+        let mut names = registry.names().collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [COINBASE_PAYMENT_ORDERING, COINBASE_TIP_ORDERING, PRIORITY_FEE_ORDERING]
+        );
+
+        let tx = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(50);
+        let ordering = registry.get(PRIORITY_FEE_ORDERING).unwrap();
+        assert_eq!(ordering.priority(&tx, 80), Priority::Value(U256::from(50)));
+        assert!(registry.get("unknown").is_none());
+
+        assert!(registry.register(PRIORITY_FEE_ORDERING, CoinbaseTipOrdering::default()).is_some());
+        let ordering = registry.get(PRIORITY_FEE_ORDERING).unwrap();
+        assert_eq!(ordering.priority(&tx, 80), Priority::Value(U256::from(20)));
+    }
+}
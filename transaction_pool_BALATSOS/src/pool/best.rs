@@ -20,17 +20,21 @@
 /// and `BestTransactions` iterators are central to this functionality.
 ///
 use crate::{
-    identifier::TransactionId, pool::pending::PendingTransaction, PoolTransaction,
-    TransactionOrdering, ValidPoolTransaction,
+    identifier::TransactionId, pool::pending::PendingTransaction, traits::InvalidSlots,
+    PoolTransaction, TransactionOrdering, ValidPoolTransaction,
 };
 use core::fmt;
+use futures_util::{stream, Stream};
 use reth_primitives::B256 as TxHash;
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
     sync::Arc,
 };
 
-use tokio::sync::broadcast::{error::TryRecvError, Receiver};
+use tokio::sync::broadcast::{
+    error::{RecvError, TryRecvError},
+    Receiver,
+};
 use tracing::debug;
 
 /// An iterator that returns transactions that can be executed on the current state (*best*
@@ -50,6 +54,10 @@ impl<T: TransactionOrdering> crate::traits::BestTransactions for BestTransaction
         BestTransactions::mark_invalid(&mut self.best, tx)
     }
 
+    fn mark_invalid_slots(&mut self, slots: &InvalidSlots) {
+        self.best.invalid_slots.extend(slots)
+    }
+
     fn no_updates(&mut self) {
         self.best.no_updates()
     }
@@ -105,6 +113,8 @@ pub(crate) struct BestTransactions<T: TransactionOrdering> {
     pub(crate) independent: BTreeSet<PendingTransaction<T>>,
     /// There might be the case where a yielded transactions is invalid, this will track it.
     pub(crate) invalid: HashSet<TxHash>,
+    /// Slots that were marked as invalid, their transactions and all descendants are skipped.
+    pub(crate) invalid_slots: InvalidSlots,
     /// Used to receive any new pending transactions that have been added to the pool after this
     /// iterator was static fileted
     ///
@@ -156,15 +166,44 @@ impl<T: TransactionOrdering> BestTransactions<T> {
     /// created and inserts them
     fn add_new_transactions(&mut self) {
         while let Some(pending_tx) = self.try_recv() {
-            let tx = pending_tx.transaction.clone();
-            //  same logic as PendingPool::add_transaction/PendingPool::best_with_unlocked
-            let tx_id = *tx.id();
-            if self.ancestor(&tx_id).is_none() {
-                self.independent.insert(pending_tx.clone());
-            }
-            self.all.insert(tx_id, pending_tx);
+            self.add_new_transaction(pending_tx);
         }
     }
+
+    /// Inserts a new pending transaction
+    fn add_new_transaction(&mut self, pending_tx: PendingTransaction<T>) {
+        let tx = pending_tx.transaction.clone();
+        //  same logic as PendingPool::add_transaction/PendingPool::best_with_unlocked
+        let tx_id = *tx.id();
+        if self.ancestor(&tx_id).is_none() {
+            self.independent.insert(pending_tx.clone());
+        }
+        self.all.insert(tx_id, pending_tx);
+    }
+
+    /// Turns the iterator into a stream that doesn't end once all transactions were yielded, but
+    /// waits for new pending transactions instead.
+    ///
+    /// The stream ends once the pool is dropped.
+    pub(crate) fn into_stream(
+        self,
+    ) -> impl Stream<Item = Arc<ValidPoolTransaction<T::Transaction>>> + Send {
+        stream::unfold(self, |mut best| async move {
+            loop {
+                if let Some(tx) = best.next() {
+                    return Some((tx, best))
+                }
+
+                // wait until a new transaction is promoted to the pending pool
+                match best.new_transaction_receiver.as_mut()?.recv().await {
+                    Ok(pending_tx) => best.add_new_transaction(pending_tx),
+                    // skipped transactions can't be recovered, continue with the next one
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
 }
 
 impl<T: TransactionOrdering> crate::traits::BestTransactions for BestTransactions<T> {
@@ -172,6 +211,10 @@ impl<T: TransactionOrdering> crate::traits::BestTransactions for BestTransaction
         Self::mark_invalid(self, tx)
     }
 
+    fn mark_invalid_slots(&mut self, slots: &InvalidSlots) {
+        self.invalid_slots.extend(slots)
+    }
+
     fn no_updates(&mut self) {
         self.new_transaction_receiver.take();
     }
@@ -205,6 +248,16 @@ impl<T: TransactionOrdering> Iterator for BestTransactions<T> {
                 continue
             }
 
+            // skip transactions in invalid slots, without unlocking their descendants
+            if self.invalid_slots.contains(&best.transaction.sender(), best.transaction.nonce()) {
+                debug!(
+                    target: "txpool",
+                    "[{:?}] skipping transaction in invalid slot",
+                    hash
+                );
+                continue
+            }
+
             // Insert transactions that just got unlocked.
             if let Some(unlocked) = self.all.get(&best.unlocks()) {
                 self.independent.insert(unlocked.clone());
@@ -266,6 +319,10 @@ where
         crate::traits::BestTransactions::mark_invalid(&mut self.best, tx)
     }
 
+    fn mark_invalid_slots(&mut self, slots: &InvalidSlots) {
+        crate::traits::BestTransactions::mark_invalid_slots(&mut self.best, slots)
+    }
+
     fn no_updates(&mut self) {
         self.best.no_updates()
     }
@@ -342,4 +399,34 @@ mod tests {
         // iterator is empty
         assert!(best.next().is_none());
     }
+
+    #[test]
+    fn test_best_iter_invalid_slots() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        // 3 gapless transactions of two senders each
+        let tx = MockTransaction::eip1559();
+        let other = MockTransaction::eip1559();
+        for nonce in 0..3 {
+            for tx in [&tx, &other] {
+                let valid_tx = f.validated(tx.clone().rng_hash().with_nonce(nonce));
+                pool.add_transaction(Arc::new(valid_tx), 0);
+            }
+        }
+
+        let mut best = pool.best();
+        let first = best.next().unwrap();
+        assert_eq!(first.nonce(), 0);
+
+        // the builder failed to execute the first transaction, the already unlocked descendant of
+        // the sender is skipped as well
+        let mut slots = InvalidSlots::default();
+        slots.insert_sender(first.sender());
+        crate::traits::BestTransactions::mark_invalid_slots(&mut best, &slots);
+
+        let rest = best.collect::<Vec<_>>();
+        assert_eq!(rest.len(), 3);
+        assert!(rest.iter().all(|tx| tx.sender() != first.sender()));
+    }
 }
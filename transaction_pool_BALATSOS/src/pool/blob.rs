@@ -1,7 +1,8 @@
 use super::txpool::PendingFees;
 use crate::{
     identifier::TransactionId, pool::size::SizeTracker, traits::BestTransactionsAttributes,
-    PoolTransaction, SubPoolLimit, ValidPoolTransaction,
+    LocalTransactionConfig, PoolTransaction, SubPoolLimit, ValidPoolTransaction,
+    TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT,
 };
 use std::{
     cmp::Ordering,
@@ -14,9 +15,12 @@ use std::{
 /// The purpose of this pool is keep track of blob transactions that are queued and to evict the
 /// worst blob transactions once the sub-pool is full.
 ///
+/// In addition to the [`SubPoolLimit`], the pool is bounded by the number of blobs of its
+/// transactions, because the blob sidecars are not part of the reported size of a transaction.
+///
 /// This expects that certain constraints are met:
 ///   - blob transactions are always gap less
-pub(crate) struct BlobTransactions<T: PoolTransaction> {
+pub struct BlobTransactions<T: PoolTransaction> {
     /// Keeps track of transactions inserted in the pool.
     ///
     /// This way we can determine when transactions were submitted to the pool.
@@ -31,25 +35,68 @@ pub(crate) struct BlobTransactions<T: PoolTransaction> {
     ///
     /// See also [`PoolTransaction::size`].
     size_of: SizeTracker,
+    /// Keeps track of the encoded size of this pool, including the blob sidecars.
+    ///
+    /// See also [`PoolTransaction::encoded_length`].
+    encoded_size_of: SizeTracker,
+    /// Number of blobs of all transactions in the pool.
+    ///
+    /// See also [`PoolTransaction::blob_count`].
+    blob_count: usize,
+    /// Max number of blobs of all transactions in the pool.
+    max_blob_count: usize,
+    /// Max encoded size of all transactions in the pool, unbounded by default.
+    max_encoded_size: usize,
 }
 
 // === impl BlobTransactions ===
 
 impl<T: PoolTransaction> BlobTransactions<T> {
+    /// Creates a new pool that holds at most `max_blob_count` blobs.
+    pub fn new(max_blob_count: usize) -> Self {
+        Self {
+            submission_id: 0,
+            by_id: Default::default(),
+            all: Default::default(),
+            size_of: Default::default(),
+            encoded_size_of: Default::default(),
+            pending_fees: Default::default(),
+            blob_count: 0,
+            max_blob_count,
+            max_encoded_size: usize::MAX,
+        }
+    }
+
+    /// Updates the max number of blobs of all transactions in the pool.
+    ///
+    /// If the limit was lowered, transactions are only removed once the pool is truncated.
+    pub(crate) fn set_max_blob_count(&mut self, max_blob_count: usize) {
+        self.max_blob_count = max_blob_count;
+    }
+
+    /// Updates the max encoded size of all transactions in the pool.
+    ///
+    /// If the limit was lowered, transactions are only removed once the pool is truncated.
+    pub(crate) fn set_max_encoded_size(&mut self, max_encoded_size: usize) {
+        self.max_encoded_size = max_encoded_size;
+    }
+
     /// Adds a new transactions to the pending queue.
     ///
     /// # Panics
     ///
     ///   - If the transaction is not a blob tx.
     ///   - If the transaction is already included.
-    pub(crate) fn add_transaction(&mut self, tx: Arc<ValidPoolTransaction<T>>) {
+    pub fn add_transaction(&mut self, tx: Arc<ValidPoolTransaction<T>>) {
         assert!(tx.is_eip4844(), "transaction is not a blob tx");
         let id = *tx.id();
         assert!(!self.contains(&id), "transaction already included {:?}", self.get(&id).unwrap());
         let submission_id = self.next_id();
 
-        // keep track of size
+        // keep track of size and blobs
         self.size_of += tx.size();
+        self.encoded_size_of += tx.encoded_length();
+        self.blob_count += tx.transaction.blob_count();
 
         // set transaction, which will also calculate priority based on current pending fees
         let transaction = BlobTransaction::new(tx, submission_id, &self.pending_fees);
@@ -74,8 +121,10 @@ impl<T: PoolTransaction> BlobTransactions<T> {
 
         self.all.remove(&tx);
 
-        // keep track of size
+        // keep track of size and blobs
         self.size_of -= tx.transaction.size();
+        self.encoded_size_of -= tx.transaction.encoded_length();
+        self.blob_count -= tx.transaction.transaction.blob_count();
 
         Some(tx.transaction)
     }
@@ -118,10 +167,13 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         transactions
     }
 
-    /// Returns true if the pool exceeds the given limit
+    /// Returns true if the pool exceeds the given limit, or holds more blobs or encoded bytes than
+    /// allowed.
     #[inline]
     pub(crate) fn exceeds(&self, limit: &SubPoolLimit) -> bool {
-        limit.is_exceeded(self.len(), self.size())
+        limit.is_exceeded(self.len(), self.size()) ||
+            self.blob_count > self.max_blob_count ||
+            self.encoded_size() > self.max_encoded_size
     }
 
     /// The reported size of all transactions in this pool.
@@ -129,11 +181,26 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         self.size_of.into()
     }
 
+    /// The encoded size of all transactions in this pool, including the blob sidecars.
+    pub(crate) fn encoded_size(&self) -> usize {
+        self.encoded_size_of.into()
+    }
+
+    /// Number of blobs of all transactions in this pool.
+    pub(crate) const fn blob_count(&self) -> usize {
+        self.blob_count
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.by_id.len()
     }
 
+    /// Returns an iterator over all transactions in the pool
+    pub(crate) fn all(&self) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.by_id.values().map(|tx| tx.transaction.clone())
+    }
+
     /// Returns whether the pool is empty
     #[cfg(test)]
     #[allow(dead_code)]
@@ -212,27 +279,75 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         removed
     }
 
-    /// Removes transactions until the pool satisfies its [`SubPoolLimit`].
+    /// Removes transactions until the pool satisfies its [`SubPoolLimit`] and its blob limit.
     ///
     /// This is done by removing transactions according to their ordering in the pool, defined by
-    /// the [`BlobOrd`] struct.
+    /// the `BlobOrd` struct.
+    ///
+    /// Local transactions are exempt from truncation with the default [`LocalTransactionConfig`],
+    /// see [`Self::truncate_pool_with_locals`].
     ///
     /// Removed transactions are returned in the order they were removed.
-    pub(crate) fn truncate_pool(
+    pub fn truncate_pool(
+        &mut self,
+        limit: SubPoolLimit,
+    ) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        self.truncate_pool_with_locals(limit, &LocalTransactionConfig::default())
+    }
+
+    /// Same as [`Self::truncate_pool`], but local transactions are exempt according to the given
+    /// config.
+    ///
+    /// If the pool still exceeds the limits after all non-local transactions were removed and it
+    /// holds more than [`LocalTransactionConfig::max_exempt_transactions`] local transactions,
+    /// local transactions are removed as well.
+    pub(crate) fn truncate_pool_with_locals(
         &mut self,
         limit: SubPoolLimit,
+        locals: &LocalTransactionConfig,
     ) -> Vec<Arc<ValidPoolTransaction<T>>> {
         let mut removed = Vec::new();
+        if !self.exceeds(&limit) {
+            return removed
+        }
 
-        while self.exceeds(&limit) {
-            let tx = self.all.last().expect("pool is not empty");
-            let id = *tx.transaction.id();
-            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        self.remove_worst(&limit, Some(locals), &mut removed);
+        if !self.exceeds(&limit) {
+            return removed
+        }
+
+        let exempt = self.by_id.values().filter(|tx| locals.is_exempt(&tx.transaction)).count();
+        if exempt > locals.max_exempt_transactions {
+            self.remove_worst(&limit, None, &mut removed);
         }
 
         removed
     }
 
+    /// Removes the worst transactions until the pool is under the given limits, skipping
+    /// transactions that are exempt according to `locals`, if set.
+    fn remove_worst(
+        &mut self,
+        limit: &SubPoolLimit,
+        locals: Option<&LocalTransactionConfig>,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T>>>,
+    ) {
+        let worst = self
+            .all
+            .iter()
+            .rev()
+            .filter(|tx| !locals.is_some_and(|locals| locals.is_exempt(&tx.transaction)))
+            .map(|tx| *tx.transaction.id())
+            .collect::<Vec<_>>();
+
+        for id in worst {
+            if !self.exceeds(limit) {
+                break
+            }
+            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        }
+    }
+
     /// Returns `true` if the transaction with the given id is already included in this pool.
     pub(crate) fn contains(&self, id: &TransactionId) -> bool {
         self.by_id.contains_key(id)
@@ -252,13 +367,7 @@ impl<T: PoolTransaction> BlobTransactions<T> {
 
 impl<T: PoolTransaction> Default for BlobTransactions<T> {
     fn default() -> Self {
-        Self {
-            submission_id: 0,
-            by_id: Default::default(),
-            all: Default::default(),
-            size_of: Default::default(),
-            pending_fees: Default::default(),
-        }
+        Self::new(TXPOOL_BLOB_SUBPOOL_MAX_BLOBS_DEFAULT)
     }
 }
 
@@ -453,6 +562,7 @@ impl Ord for BlobOrd {
 mod tests {
     use super::*;
     use crate::test_utils::{MockTransaction, MockTransactionFactory};
+    use reth_primitives::BlobTransactionSidecar;
 
     /// Represents the fees for a single transaction, which will be built inside of a test.
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -673,6 +783,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn truncate_by_blob_count() {
+        let mut factory = MockTransactionFactory::default();
+        let mut pool = BlobTransactions::new(3);
+
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Default::default(); 2],
+            commitments: vec![],
+            proofs: vec![],
+        };
+        let first = factory.validated_arc(MockTransaction::eip4844_with_sidecar(sidecar.clone()));
+        let second = factory.validated_arc(MockTransaction::eip4844_with_sidecar(sidecar));
+
+        pool.add_transaction(first.clone());
+        assert_eq!(pool.blob_count(), 2);
+        assert!(!pool.exceeds(&SubPoolLimit::default()));
+
+        pool.add_transaction(second.clone());
+        assert_eq!(pool.blob_count(), 4);
+        assert!(pool.exceeds(&SubPoolLimit::default()));
+
+        // both have the same priority, so the later submission is evicted
+        let removed = pool.truncate_pool(SubPoolLimit::default());
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id(), second.id());
+        assert!(pool.contains(first.id()));
+        assert_eq!(pool.blob_count(), 2);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn priority_tests() {
         // Test vectors from:
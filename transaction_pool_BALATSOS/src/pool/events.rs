@@ -16,9 +16,11 @@
 // 9) Discarded(TxHash) -> Indicates the transaction was dropped due to configured limits
 // 10) Invalid(TxHash) -> Indicates the transaction became invalid indefinitely
 // 11) Propagated(Arc<Vec<PropagateKind>>) -> Indicates the transaction was propagated to peers, wrapped in Arc
+// 12) PoolLifecycleEvent -> Lifecycle events of a transaction (Added, Promoted, Demoted, Replaced, Dropped, Mined)
+// 13) DropReason -> Why a transaction was dropped from the pool
 
 
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{pool::state::SubPool, traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
 use reth_primitives::{TxHash, B256};
 use std::sync::Arc;
 
@@ -103,3 +105,112 @@ impl TransactionEvent {
         matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
     }
 }
+
+/// An event in the lifecycle of a transaction in the pool.
+///
+/// Unlike [`FullTransactionEvent`], this distinguishes new transactions from transactions that
+/// move between the sub-pools, and tells why a transaction was dropped from the pool.
+#[derive(Debug)]
+pub enum PoolLifecycleEvent<T: PoolTransaction> {
+    /// Transaction has been added to the given sub-pool.
+    Added {
+        /// The added transaction.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The sub-pool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// Transaction has been moved from a parked sub-pool to the pending sub-pool.
+    Promoted(TxHash),
+    /// Transaction has been moved from the pending sub-pool to the given parked sub-pool.
+    Demoted {
+        /// The hash of the demoted transaction.
+        tx_hash: TxHash,
+        /// The sub-pool the transaction was moved to.
+        subpool: SubPool,
+    },
+    /// Transaction has been replaced by the transaction belonging to the hash.
+    ///
+    /// E.g. same (sender + nonce) pair
+    Replaced {
+        /// The transaction that was replaced.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The transaction that replaced the event subject.
+        replaced_by: TxHash,
+    },
+    /// Transaction has been dropped from the pool.
+    Dropped {
+        /// The hash of the dropped transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was dropped.
+        reason: DropReason,
+    },
+    /// Transaction has been included in the block belonging to this hash.
+    Mined {
+        /// The hash of the mined transaction.
+        tx_hash: TxHash,
+        /// The hash of the mined block that contains the transaction.
+        block_hash: B256,
+    },
+}
+
+impl<T: PoolTransaction> PoolLifecycleEvent<T> {
+    /// Returns the hash of the transaction this event is about.
+    pub fn hash(&self) -> &TxHash {
+        match self {
+            Self::Added { transaction, .. } | Self::Replaced { transaction, .. } => {
+                transaction.hash()
+            }
+            Self::Promoted(tx_hash) |
+            Self::Demoted { tx_hash, .. } |
+            Self::Dropped { tx_hash, .. } |
+            Self::Mined { tx_hash, .. } => tx_hash,
+        }
+    }
+}
+
+impl<T: PoolTransaction> Clone for PoolLifecycleEvent<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added { transaction, subpool } => {
+                Self::Added { transaction: Arc::clone(transaction), subpool: *subpool }
+            }
+            Self::Promoted(hash) => Self::Promoted(*hash),
+            Self::Demoted { tx_hash, subpool } => {
+                Self::Demoted { tx_hash: *tx_hash, subpool: *subpool }
+            }
+            Self::Replaced { transaction, replaced_by } => {
+                Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
+            }
+            Self::Dropped { tx_hash, reason } => {
+                Self::Dropped { tx_hash: *tx_hash, reason: *reason }
+            }
+            Self::Mined { tx_hash, block_hash } => {
+                Self::Mined { tx_hash: *tx_hash, block_hash: *block_hash }
+            }
+        }
+    }
+}
+
+/// The reason a transaction was dropped from the pool, see [`PoolLifecycleEvent::Dropped`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DropReason {
+    /// The transaction is invalid and was never added to the pool.
+    Invalid,
+    /// The nonce of the transaction is lower than the nonce of its sender, e.g. because another
+    /// transaction with the same nonce was mined.
+    NonceTooLow,
+    /// The transaction was evicted because the pool exceeded its configured limits.
+    PoolLimit,
+    /// The transaction was evicted because its sender exceeded the max number of transaction slots
+    /// per sender.
+    AccountSlots,
+    /// The transaction was removed through the pool API.
+    Removed,
+    /// The transaction was parked for longer than the configured
+    /// [`PoolConfig::transaction_lifetime`](crate::PoolConfig::transaction_lifetime).
+    Expired,
+    /// The conditions the transaction was submitted with can no longer be satisfied, see
+    /// [`TransactionConditional`](crate::TransactionConditional).
+    ConditionViolated,
+}
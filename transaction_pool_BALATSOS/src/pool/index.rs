@@ -0,0 +1,170 @@
+//! A sharded index of all transactions in the pool, which can be read without the pool lock.
+
+use crate::{identifier::SenderId, PoolTransaction, ValidPoolTransaction};
+use parking_lot::RwLock;
+use reth_primitives::TxHash;
+use rustc_hash::FxHashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// The number of shards of a [`TransactionIndex`].
+const INDEX_SHARDS: usize = 16;
+
+/// A shard of the transactions by their hash.
+type HashShard<T> = RwLock<HashMap<TxHash, Arc<ValidPoolTransaction<T>>>>;
+
+/// A shard of the transactions of each sender by their nonce.
+type SenderShard<T> = RwLock<FxHashMap<SenderId, BTreeMap<u64, Arc<ValidPoolTransaction<T>>>>>;
+
+/// All transactions of the pool by their hash and by their sender.
+///
+/// The index is owned by [`AllTransactions`](crate::pool::txpool::AllTransactions), which updates
+/// it while holding the pool lock, and shared with the [`PoolInner`](crate::pool::PoolInner), which
+/// serves lookups from it without acquiring the pool lock. This way lookups do not contend with
+/// bulk inserts or the truncation of the pool, only with writes to the same shard.
+///
+/// Transactions are sharded by their hash and, for the lookup by sender, by their sender, see
+/// [`SenderId::shard`].
+pub(crate) struct TransactionIndex<T: PoolTransaction> {
+    /// The transactions by their hash.
+    by_hash: Box<[HashShard<T>]>,
+    /// The transactions of each sender by their nonce.
+    by_sender: Box<[SenderShard<T>]>,
+    /// The number of transactions in the index.
+    len: AtomicUsize,
+}
+
+// === impl TransactionIndex ===
+
+impl<T: PoolTransaction> TransactionIndex<T> {
+    /// Returns the shard of the given hash.
+    fn hash_shard(&self, tx_hash: &TxHash) -> &HashShard<T> {
+        &self.by_hash[tx_hash[0] as usize % self.by_hash.len()]
+    }
+
+    /// Returns the shard of the given sender.
+    fn sender_shard(&self, sender: SenderId) -> &SenderShard<T> {
+        &self.by_sender[sender.shard(self.by_sender.len())]
+    }
+
+    /// Returns the number of transactions in the index.
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the index contains the transaction with the given hash.
+    pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
+        self.hash_shard(tx_hash).read().contains_key(tx_hash)
+    }
+
+    /// Returns the transaction with the given hash.
+    pub(crate) fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T>>> {
+        self.hash_shard(tx_hash).read().get(tx_hash).cloned()
+    }
+
+    /// Returns all transactions of the given sender, ordered by their nonce.
+    pub(crate) fn by_sender(&self, sender: SenderId) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        self.sender_shard(sender)
+            .read()
+            .get(&sender)
+            .map(|txs| txs.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns all transactions, shard by shard.
+    pub(crate) fn transactions(&self) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        self.by_hash
+            .iter()
+            .flat_map(|shard| shard.read().values().cloned().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Inserts the transaction, replacing the transaction with the same hash.
+    pub(crate) fn insert(&self, tx: Arc<ValidPoolTransaction<T>>) {
+        self.sender_shard(tx.sender_id())
+            .write()
+            .entry(tx.sender_id())
+            .or_default()
+            .insert(tx.nonce(), tx.clone());
+        if self.hash_shard(tx.hash()).write().insert(*tx.hash(), tx).is_none() {
+            self.len.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Removes the transaction with the given hash.
+    pub(crate) fn remove(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T>>> {
+        let tx = self.hash_shard(tx_hash).write().remove(tx_hash)?;
+        self.len.fetch_sub(1, Ordering::Relaxed);
+
+        let sender = tx.sender_id();
+        let mut shard = self.sender_shard(sender).write();
+        if let Some(txs) = shard.get_mut(&sender) {
+            // the nonce may already be taken by a replacement
+            if txs.get(&tx.nonce()).is_some_and(|other| other.hash() == tx_hash) {
+                txs.remove(&tx.nonce());
+            }
+            if txs.is_empty() {
+                shard.remove(&sender);
+            }
+        }
+        Some(tx)
+    }
+}
+
+impl<T: PoolTransaction> Default for TransactionIndex<T> {
+    fn default() -> Self {
+        Self {
+            by_hash: (0..INDEX_SHARDS).map(|_| Default::default()).collect(),
+            by_sender: (0..INDEX_SHARDS).map(|_| Default::default()).collect(),
+            len: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for TransactionIndex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionIndex").field("len", &self.len()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn insert_replace_remove() {
+        let mut f = MockTransactionFactory::default();
+        let index = TransactionIndex::default();
+
+        let tx = f.validated_arc(MockTransaction::eip1559());
+        let next = f.validated_arc(tx.transaction.next());
+        index.insert(tx.clone());
+        index.insert(next.clone());
+        assert_eq!(index.len(), 2);
+        assert!(index.contains(tx.hash()));
+        assert_eq!(index.by_sender(tx.sender_id()).len(), 2);
+
+        // replace the first transaction
+        let replacement = f.validated_arc(tx.transaction.clone().rng_hash());
+        index.insert(replacement.clone());
+        index.remove(tx.hash());
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(tx.hash()));
+        let by_sender = index.by_sender(tx.sender_id());
+        assert_eq!(by_sender[0].hash(), replacement.hash());
+        assert_eq!(by_sender[1].hash(), next.hash());
+
+        index.remove(replacement.hash());
+        index.remove(next.hash());
+        assert_eq!(index.len(), 0);
+        assert!(index.by_sender(tx.sender_id()).is_empty());
+        assert!(index.transactions().is_empty());
+    }
+}
@@ -10,13 +10,20 @@
 // 5) replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) -> Notifies listeners that a transaction was replaced
 // 6) queued(&mut self, tx: &TxHash) -> Notifies listeners that a transaction was added to the queued pool
 // 7) propagated(&mut self, tx: &TxHash, peers: Vec<PropagateKind>) -> Notifies listeners that a transaction was propagated
-// 8) discarded(&mut self, tx: &TxHash) -> Notifies listeners that a transaction was discarded
+// 8) discarded(&mut self, tx: &TxHash, reason: DropReason) -> Notifies listeners that a transaction was discarded
 // 9) mined(&mut self, tx: &TxHash, block_hash: B256) -> Notifies listeners that a transaction was mined
+// 10) subscribe_lifecycle(&mut self) -> PoolLifecycleEvents<T> -> Creates a new subscription for the lifecycle events of all transactions
+// 11) added(&mut self, tx: &Arc<ValidPoolTransaction<T>>, subpool: SubPool) -> Notifies listeners that a transaction was added to a sub-pool
+// 12) promoted(&mut self, tx: &TxHash) -> Notifies listeners that a transaction was promoted to the pending pool
+// 13) demoted(&mut self, tx: &TxHash, subpool: SubPool) -> Notifies listeners that a transaction was moved out of the pending pool
 
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::{
+        events::{DropReason, FullTransactionEvent, PoolLifecycleEvent, TransactionEvent},
+        state::SubPool,
+    },
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
@@ -83,6 +90,28 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     }
 }
 
+/// A Stream that receives [`PoolLifecycleEvent`] for _all_ transactions.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PoolLifecycleEvents<T: PoolTransaction> {
+    pub(crate) events: Receiver<PoolLifecycleEvent<T>>,
+}
+
+impl<T: PoolTransaction> PoolLifecycleEvents<T> {
+    /// Create a new instance of this stream.
+    pub const fn new(events: Receiver<PoolLifecycleEvent<T>>) -> Self {
+        Self { events }
+    }
+}
+
+impl<T: PoolTransaction> Stream for PoolLifecycleEvents<T> {
+    type Item = PoolLifecycleEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
 /// A type that broadcasts [`TransactionEvent`] to installed listeners.
 ///
 /// This is essentially a multi-producer, multi-consumer channel where each event is broadcast to
@@ -90,7 +119,9 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
 #[derive(Debug)]
 pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     /// All listeners for all transaction events.
-    all_events_broadcaster: AllPoolEventsBroadcaster<T>,
+    all_events_broadcaster: AllPoolEventsBroadcaster<FullTransactionEvent<T>>,
+    /// All listeners for the lifecycle events of all transactions.
+    lifecycle_events_broadcaster: AllPoolEventsBroadcaster<PoolLifecycleEvent<T>>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
 }
@@ -99,6 +130,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
     fn default() -> Self {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
+            lifecycle_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
         }
     }
@@ -147,6 +179,32 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         AllTransactionsEvents::new(rx)
     }
 
+    /// Create a new subscription for the lifecycle events of all transactions.
+    pub(crate) fn subscribe_lifecycle(&mut self) -> PoolLifecycleEvents<T> {
+        let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
+        self.lifecycle_events_broadcaster.senders.push(tx);
+        PoolLifecycleEvents::new(rx)
+    }
+
+    /// Notify listeners about a transaction that was added to the given sub-pool.
+    pub(crate) fn added(&mut self, tx: &Arc<ValidPoolTransaction<T>>, subpool: SubPool) {
+        self.lifecycle_events_broadcaster
+            .broadcast(PoolLifecycleEvent::Added { transaction: Arc::clone(tx), subpool });
+    }
+
+    /// Notify listeners about a transaction that was promoted to the pending pool.
+    pub(crate) fn promoted(&mut self, tx: &TxHash) {
+        self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
+        self.lifecycle_events_broadcaster.broadcast(PoolLifecycleEvent::Promoted(*tx));
+    }
+
+    /// Notify listeners about a transaction that was moved from the pending pool to the given
+    /// parked sub-pool.
+    pub(crate) fn demoted(&mut self, tx: &TxHash, subpool: SubPool) {
+        self.lifecycle_events_broadcaster
+            .broadcast(PoolLifecycleEvent::Demoted { tx_hash: *tx, subpool });
+    }
+
     /// Notify listeners about a transaction that was added to the pending queue.
     pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
         self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
@@ -165,6 +223,8 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Replaced(replaced_by),
             FullTransactionEvent::Replaced { transaction, replaced_by },
         );
+        self.lifecycle_events_broadcaster
+            .broadcast(PoolLifecycleEvent::Replaced { transaction: tx, replaced_by });
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
@@ -182,9 +242,11 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         );
     }
 
-    /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
+    /// Notify listeners about a transaction that was discarded for the given reason.
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DropReason) {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+        self.lifecycle_events_broadcaster
+            .broadcast(PoolLifecycleEvent::Dropped { tx_hash: *tx, reason });
     }
 
     /// Notify listeners that the transaction was mined
@@ -194,6 +256,8 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Mined(block_hash),
             FullTransactionEvent::Mined { tx_hash: *tx, block_hash },
         );
+        self.lifecycle_events_broadcaster
+            .broadcast(PoolLifecycleEvent::Mined { tx_hash: *tx, block_hash });
     }
 }
 
@@ -201,20 +265,20 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 ///
 /// This mimics [`tokio::sync::broadcast`] but uses separate channels.
 #[derive(Debug)]
-struct AllPoolEventsBroadcaster<T: PoolTransaction> {
+struct AllPoolEventsBroadcaster<E> {
     /// Corresponding sender half(s) for event listener channel
-    senders: Vec<Sender<FullTransactionEvent<T>>>,
+    senders: Vec<Sender<E>>,
 }
 
-impl<T: PoolTransaction> Default for AllPoolEventsBroadcaster<T> {
+impl<E> Default for AllPoolEventsBroadcaster<E> {
     fn default() -> Self {
         Self { senders: Vec::new() }
     }
 }
 
-impl<T: PoolTransaction> AllPoolEventsBroadcaster<T> {
+impl<E: Clone> AllPoolEventsBroadcaster<E> {
     // Broadcast an event to all listeners. Dropped listeners are silently evicted.
-    fn broadcast(&mut self, event: FullTransactionEvent<T>) {
+    fn broadcast(&mut self, event: E) {
         self.senders.retain(|sender| match sender.try_send(event.clone()) {
             Ok(_) | Err(TrySendError::Full(_)) => true,
             Err(TrySendError::Closed(_)) => false,
@@ -66,22 +66,25 @@
 //!    category (2.) and become pending.
 
 use crate::{
-    error::{PoolError, PoolErrorKind, PoolResult},
+    error::{InvalidPoolTransactionError, PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
+        index::TransactionIndex,
         listener::PoolEventBroadcast,
+        pending::PendingSnapshot,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
-        PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        PoolTransaction, PropagatedTransactions, TransactionOrigin, TransactionSource,
+        TransactionsPage,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, ChangedAccount, PoolConfig, TransactionOrdering, TransactionValidator,
 };
 use best::BestTransactions;
-use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{
     Address, BlobTransaction, BlobTransactionSidecar, IntoRecoveredTransaction,
@@ -90,7 +93,11 @@ use reth_primitives::{
 use std::{
     collections::{HashMap, HashSet},
     fmt,
-    sync::Arc,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::Instant,
 };
 use tokio::sync::mpsc;
@@ -98,20 +105,27 @@ use tracing::{debug, trace, warn};
 mod events;
 use crate::{
     blobstore::BlobStore,
+    conditional::TransactionConditional,
+    fees::{BlockFees, FeeForecast, FeeHistory},
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
-    traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
+    reputation::SenderReputation,
+    traits::{
+        GetPooledTransactionLimit, NewBlobSidecar, PoolMaintenanceStats, SenderState,
+        TransactionListenerKind,
+    },
     validate::ValidTransaction,
 };
 pub use best::BestTransactionFilter;
-pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
-pub use listener::{AllTransactionsEvents, TransactionEvents};
+pub use blob::{blob_tx_priority, fee_delta, BlobTransactions};
+pub use events::{DropReason, FullTransactionEvent, PoolLifecycleEvent, TransactionEvent};
+pub use listener::{AllTransactionsEvents, PoolLifecycleEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 
 mod best;
 mod blob;
+mod index;
 mod listener;
 mod parked;
 pub(crate) mod pending;
@@ -136,7 +150,16 @@ where
     /// Storage for blob transactions
     blob_store: S,
     /// The internal pool that manages all transactions.
+    ///
+    /// Writes must go through [`Self::pool_mut`].
     pool: RwLock<TxPool<T>>,
+    /// Incremented whenever a write to the pool is done, see [`PoolWriteGuard`].
+    pool_generation: AtomicU64,
+    /// All transactions of the pool by their hash and sender, readable without the pool lock.
+    index: Arc<TransactionIndex<T::Transaction>>,
+    /// The last snapshot of the pending sub-pool and the pool generation it was taken at, see
+    /// [`Self::best_transactions`].
+    pending_snapshot: RwLock<Option<(u64, Arc<PendingSnapshot<T>>)>>,
     /// Pool settings.
     config: PoolConfig,
     /// Manages listeners for transaction state change events.
@@ -149,6 +172,12 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// The fees paid in the recent blocks.
+    fee_history: RwLock<FeeHistory>,
+    /// The conditions of the conditional transactions in the pool.
+    conditionals: RwLock<HashMap<TxHash, TransactionConditional>>,
+    /// The spam scores of senders of rejected transactions.
+    reputation: SenderReputation,
 }
 
 // === impl PoolInner ===
@@ -161,17 +190,24 @@ where
 {
     /// Create a new transaction pool instance.
     pub(crate) fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        let pool = TxPool::new(ordering, config.clone());
         Self {
             identifiers: Default::default(),
             validator,
             event_listener: Default::default(),
-            pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            index: pool.index(),
+            pool: RwLock::new(pool),
+            pool_generation: AtomicU64::new(0),
+            pending_snapshot: Default::default(),
+            reputation: SenderReputation::new(config.reputation),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            fee_history: Default::default(),
+            conditionals: Default::default(),
         }
     }
 
@@ -191,7 +227,21 @@ where
     }
     /// Returns the currently tracked block
     pub(crate) fn set_block_info(&self, info: BlockInfo) {
-        self.pool.write().set_block_info(info)
+        let UpdateOutcome { promoted, demoted, .. } = self.pool_mut().update_block_info(info);
+
+        let mut listener = self.event_listener.write();
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx.hash(), *subpool));
+    }
+
+    /// Returns the max number of transaction slots per sender that are currently enforced.
+    pub(crate) fn max_account_slots(&self) -> usize {
+        self.get_pool_data().max_account_slots()
+    }
+
+    /// Updates the max number of transaction slots per sender.
+    pub(crate) fn set_max_account_slots(&self, max_account_slots: usize) {
+        self.pool_mut().set_max_account_slots(max_account_slots)
     }
 
     /// Returns the internal `SenderId` for this address
@@ -264,9 +314,7 @@ where
         &self,
         tx_hash: TxHash,
     ) -> Option<TransactionEvents> {
-        self.get_pool_data()
-            .contains(&tx_hash)
-            .then(|| self.event_listener.write().subscribe(tx_hash))
+        self.index.contains(&tx_hash).then(|| self.event_listener.write().subscribe(tx_hash))
     }
 
     /// Adds a listener for all transaction events.
@@ -276,24 +324,42 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for the lifecycle events of all transactions.
+    pub(crate) fn add_lifecycle_event_listener(&self) -> PoolLifecycleEvents<T::Transaction> {
+        self.event_listener.write().subscribe_lifecycle()
+    }
+
     /// Returns a read lock to the pool's data.
     pub(crate) fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
     }
 
-    /// Returns hashes of _all_ transactions in the pool.
+    /// Returns a write lock to the pool's data.
+    ///
+    /// Releasing the lock outdates the snapshot of the pending sub-pool.
+    fn pool_mut(&self) -> PoolWriteGuard<'_, T> {
+        PoolWriteGuard { pool: self.pool.write(), generation: &self.pool_generation }
+    }
+
+    /// Returns hashes of _all_ transactions in the pool, see [`Self::pooled_transactions`].
     pub(crate) fn pooled_transactions_hashes(&self) -> Vec<TxHash> {
-        self.get_pool_data()
-            .all()
-            .transactions_iter()
-            .filter(|tx| tx.propagate)
-            .map(|tx| *tx.hash())
-            .collect()
+        self.pooled_transactions().iter().map(|tx| *tx.hash()).collect()
     }
 
     /// Returns _all_ transactions in the pool.
+    ///
+    /// Transactions of senders with a better reputation come first, so that they are gossiped
+    /// first.
     pub(crate) fn pooled_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().all().transactions_iter().filter(|tx| tx.propagate).collect()
+        let mut transactions =
+            self.index.transactions().into_iter().filter(|tx| tx.propagate).collect::<Vec<_>>();
+        self.reputation.sort_by_score(&mut transactions, |tx| tx.sender());
+        transactions
+    }
+
+    /// Returns the reputation tracker of the senders of transactions.
+    pub(crate) const fn reputation(&self) -> &SenderReputation {
+        &self.reputation
     }
 
     /// Returns the [`BlobTransaction`] for the given transaction if the sidecar exists.
@@ -373,21 +439,33 @@ where
         let block_info = update.block_info();
         let CanonicalStateUpdate { new_tip, changed_accounts, mined_transactions, .. } = update;
         self.validator.on_new_head_block(new_tip);
+        self.fee_history.write().insert(BlockFees::from_block(new_tip));
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
-        let outcome = self.pool.write().on_canonical_state_change(
+        let outcome = self.pool_mut().on_canonical_state_change(
             block_info,
             mined_transactions,
             changed_senders,
         );
 
         // This will discard outdated transactions based on the account's nonce
-        self.delete_discarded_blobs(outcome.discarded.iter());
+        self.delete_discarded_blobs(
+            outcome.discarded.iter().chain(&outcome.evicted).chain(&outcome.expired),
+        );
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+        self.update_pool_fullness();
+    }
+
+    /// Reports the current fullness of the pool to the validator, see
+    /// [`TransactionValidator::on_pool_fullness`].
+    fn update_pool_fullness(&self) {
+        // the limits of the pool can differ from the config if they are derived from a memory
+        // budget
+        self.validator.on_pool_fullness(self.get_pool_data().fullness());
     }
 
     /// Performs account updates on the pool.
@@ -395,12 +473,13 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub(crate) fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
-            self.pool.write().update_accounts(changed_senders);
+        let UpdateOutcome { promoted, demoted, discarded } =
+            self.pool_mut().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx.hash(), *subpool));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::NonceTooLow));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -411,10 +490,15 @@ where
     ///
     /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
     /// come in through that function, either as a batch or `std::iter::once`.
+    ///
+    /// The conditions of a conditional transaction are recorded while the pool is still locked for
+    /// the insert, so that the transaction is never visible without them.
     fn add_transaction(
         &self,
+        source: TransactionSource,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: Option<TransactionConditional>,
     ) -> PoolResult<TxHash> {
         match tx {
             TransactionValidationOutcome::Valid {
@@ -423,71 +507,133 @@ where
                 transaction,
                 propagate,
             } => {
-                let sender_id = self.get_sender_id(transaction.sender());
-                let transaction_id = TransactionId::new(sender_id, transaction.nonce());
-
-                // split the valid transaction and the blob sidecar if it has any
-                let (transaction, maybe_sidecar) = match transaction {
-                    ValidTransaction::Valid(tx) => (tx, None),
-                    ValidTransaction::ValidWithSidecar { transaction, sidecar } => {
-                        debug_assert!(
-                            transaction.is_eip4844(),
-                            "validator returned sidecar for non EIP-4844 transaction"
-                        );
-                        (transaction, Some(sidecar))
-                    }
-                };
-
-                let tx = ValidPoolTransaction {
+                let (tx, maybe_sidecar) = Self::to_pool_transaction(
+                    &mut self.identifiers.write(),
+                    source,
+                    origin,
                     transaction,
-                    transaction_id,
                     propagate,
-                    timestamp: Instant::now(),
-                    origin,
+                );
+
+                let sender = tx.sender();
+                let added = {
+                    let mut pool = self.pool_mut();
+                    let added = pool
+                        .add_transaction(tx, balance, state_nonce)
+                        .inspect_err(|err| self.reputation.record_error(sender, err))?;
+                    if let Some(conditional) = conditional {
+                        self.conditionals.write().insert(*added.hash(), conditional);
+                    }
+                    added
                 };
+                Ok(self.on_added_transaction(added, maybe_sidecar))
+            }
+            invalid => Err(self.on_invalid_transaction(invalid)),
+        }
+    }
 
-                let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
-                let hash = *added.hash();
+    /// Converts a validated transaction into the internal representation of the pool, splitting
+    /// off the blob sidecar if it has any.
+    fn to_pool_transaction(
+        identifiers: &mut SenderIdentifiers,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transaction: ValidTransaction<T::Transaction>,
+        propagate: bool,
+    ) -> (ValidPoolTransaction<T::Transaction>, Option<BlobTransactionSidecar>) {
+        let sender_id = identifiers.sender_id_or_create(transaction.sender());
+        let transaction_id = TransactionId::new(sender_id, transaction.nonce());
+        let authority_ids = transaction.transaction().authorities().map(|authorities| {
+            authorities
+                .iter()
+                .map(|authority| identifiers.sender_id_or_create(*authority))
+                .collect()
+        });
 
-                // transaction was successfully inserted into the pool
-                if let Some(sidecar) = maybe_sidecar {
-                    // notify blob sidecar listeners
-                    self.on_new_blob_sidecar(&hash, &sidecar);
-                    // store the sidecar in the blob store
-                    self.insert_blob(hash, sidecar);
-                }
+        // split the valid transaction and the blob sidecar if it has any
+        let (transaction, maybe_sidecar) = match transaction {
+            ValidTransaction::Valid(tx) => (tx, None),
+            ValidTransaction::ValidWithSidecar { transaction, sidecar } => {
+                debug_assert!(
+                    transaction.is_eip4844(),
+                    "validator returned sidecar for non EIP-4844 transaction"
+                );
+                (transaction, Some(sidecar))
+            }
+        };
 
-                if let Some(replaced) = added.replaced_blob_transaction() {
-                    // delete the replaced transaction from the blob store
-                    self.delete_blob(replaced);
-                }
+        let tx = ValidPoolTransaction {
+            transaction,
+            transaction_id,
+            authority_ids,
+            propagate,
+            timestamp: Instant::now(),
+            origin,
+            source,
+        };
+        (tx, maybe_sidecar)
+    }
 
-                // Notify about new pending transactions
-                if let Some(pending) = added.as_pending() {
-                    self.on_new_pending_transaction(pending);
-                }
+    /// Handles a transaction that was successfully inserted into the pool: stores its blob
+    /// sidecar and notifies all listeners.
+    fn on_added_transaction(
+        &self,
+        added: AddedTransaction<T::Transaction>,
+        maybe_sidecar: Option<BlobTransactionSidecar>,
+    ) -> TxHash {
+        let hash = *added.hash();
+
+        if let Some(sidecar) = maybe_sidecar {
+            // notify blob sidecar listeners
+            self.on_new_blob_sidecar(&hash, &sidecar);
+            // store the sidecar in the blob store
+            self.insert_blob(hash, sidecar);
+        }
 
-                // Notify tx event listeners
-                self.notify_event_listeners(&added);
+        if let Some(replaced) = added.replaced_blob_transaction() {
+            // delete the replaced transaction from the blob store
+            self.delete_blob(replaced);
+        }
 
-                if let Some(discarded) = added.discarded_transactions() {
-                    self.delete_discarded_blobs(discarded.iter());
-                }
+        // Notify about new pending transactions
+        if let Some(pending) = added.as_pending() {
+            self.on_new_pending_transaction(pending);
+        }
 
-                // Notify listeners for _all_ transactions
-                self.on_new_transaction(added.into_new_transaction_event());
+        // Notify tx event listeners
+        self.notify_event_listeners(&added);
 
-                Ok(hash)
-            }
+        if let Some(discarded) = added.discarded_transactions() {
+            self.delete_discarded_blobs(discarded.iter());
+        }
+
+        // Notify listeners for _all_ transactions
+        self.on_new_transaction(added.into_new_transaction_event());
+
+        hash
+    }
+
+    /// Handles a transaction that failed validation and returns the error for it.
+    ///
+    /// Note: this must not be called with a valid outcome.
+    fn on_invalid_transaction(
+        &self,
+        tx: TransactionValidationOutcome<T::Transaction>,
+    ) -> PoolError {
+        let mut listener = self.event_listener.write();
+        match tx {
             TransactionValidationOutcome::Invalid(tx, err) => {
-                let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
-                Err(PoolError::new(*tx.hash(), err))
+                listener.discarded(tx.hash(), DropReason::Invalid);
+                let err = PoolError::new(*tx.hash(), err);
+                self.reputation.record_error(tx.sender(), &err);
+                err
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
-                let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
-                Err(PoolError::other(tx_hash, err))
+                listener.discarded(&tx_hash, DropReason::Invalid);
+                PoolError::other(tx_hash, err)
+            }
+            TransactionValidationOutcome::Valid { .. } => {
+                unreachable!("valid transactions are inserted into the pool")
             }
         }
     }
@@ -501,7 +647,7 @@ where
             let mut listener = self.event_listener.write();
             listener.subscribe(tx.tx_hash())
         };
-        let mut results = self.add_transactions(origin, std::iter::once(tx));
+        let mut results = self.add_transactions(origin.into(), origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")?;
         Ok(listener)
     }
@@ -509,15 +655,116 @@ where
     /// Adds all transactions in the iterator to the pool, returning a list of results.
     pub fn add_transactions(
         &self,
+        source: TransactionSource,
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
     ) -> Vec<PoolResult<TxHash>> {
-        let mut added =
-            transactions.into_iter().map(|tx| self.add_transaction(origin, tx)).collect::<Vec<_>>();
+        let added = transactions
+            .into_iter()
+            .map(|tx| self.add_transaction(source, origin, tx, None))
+            .collect::<Vec<_>>();
+        self.enforce_limits(added)
+    }
+
+    /// Adds a transaction to the pool that may only be included while the given conditions hold.
+    ///
+    /// The transaction is rejected if the block number range of the conditions can't be satisfied
+    /// on top of the current head.
+    pub(crate) fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        if let Err(violation) =
+            conditional.check_block_range(self.block_info().last_seen_block_number)
+        {
+            let err = PoolError::new(tx.tx_hash(), InvalidPoolTransactionError::from(violation));
+            return Err(err)
+        }
+
+        let added = self.add_transaction(origin.into(), origin, tx, Some(conditional));
+        let mut results = self.enforce_limits(vec![added]);
+        results.pop().expect("result length is the same as the input")
+    }
+
+    /// Adds all transactions to the pool, returning a list of results.
+    ///
+    /// In contrast to [`Self::add_transactions`], the senders of all transactions are resolved at
+    /// once and all transactions are inserted with a single acquisition of the pool lock, which is
+    /// cheaper for large batches, e.g. transactions announced by peers.
+    pub fn add_transactions_batch(
+        &self,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transactions: Vec<TransactionValidationOutcome<T::Transaction>>,
+    ) -> Vec<PoolResult<TxHash>> {
+        let mut added = Vec::with_capacity(transactions.len());
+        let mut valid = Vec::with_capacity(transactions.len());
+        {
+            let mut identifiers = self.identifiers.write();
+            for (idx, tx) in transactions.into_iter().enumerate() {
+                match tx {
+                    TransactionValidationOutcome::Valid {
+                        balance,
+                        state_nonce,
+                        transaction,
+                        propagate,
+                    } => {
+                        let (tx, maybe_sidecar) = Self::to_pool_transaction(
+                            &mut identifiers,
+                            source,
+                            origin,
+                            transaction,
+                            propagate,
+                        );
+                        valid.push((idx, tx, balance, state_nonce, maybe_sidecar));
+                        added.push(None);
+                    }
+                    invalid => added.push(Some(Err(self.on_invalid_transaction(invalid)))),
+                }
+            }
+        }
 
-        // If at least one transaction was added successfully, then we enforce the pool size limits.
-        let discarded =
-            if added.iter().any(Result::is_ok) { self.discard_worst() } else { Default::default() };
+        let inserted = {
+            let mut pool = self.pool_mut();
+            valid
+                .into_iter()
+                .map(|(idx, tx, balance, state_nonce, maybe_sidecar)| {
+                    let sender = tx.sender();
+                    let res = pool
+                        .add_transaction(tx, balance, state_nonce)
+                        .inspect_err(|err| self.reputation.record_error(sender, err));
+                    (idx, res, maybe_sidecar)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (idx, res, maybe_sidecar) in inserted {
+            added[idx] = Some(res.map(|added| self.on_added_transaction(added, maybe_sidecar)));
+        }
+
+        let added = added
+            .into_iter()
+            .map(|res| res.expect("all valid transactions were inserted"))
+            .collect();
+        self.enforce_limits(added)
+    }
+
+    /// Enforces the size limits of the pool after transactions were added, and adjusts the
+    /// results of the added transactions that were discarded immediately.
+    fn enforce_limits(&self, mut added: Vec<PoolResult<TxHash>>) -> Vec<PoolResult<TxHash>> {
+        // If at least one transaction was added successfully, then we enforce the pool size limits,
+        // unless this is deferred to the maintenance task.
+        let any_added = added.iter().any(Result::is_ok);
+        let discarded = if any_added && !self.config.deferred_maintenance {
+            self.discard_worst()
+        } else {
+            Default::default()
+        };
+        if any_added {
+            self.update_pool_fullness();
+        }
 
         if discarded.is_empty() {
             return added
@@ -525,7 +772,7 @@ where
 
         {
             let mut listener = self.event_listener.write();
-            discarded.iter().for_each(|tx| listener.discarded(tx));
+            discarded.iter().for_each(|tx| listener.discarded(tx, DropReason::PoolLimit));
         }
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
@@ -558,6 +805,30 @@ where
         });
     }
 
+    /// Notifies the pending transaction listeners about all pending local transactions again, so
+    /// that they are announced to peers again.
+    ///
+    /// Transactions that must not be propagated are skipped. Returns the hashes of the rebroadcast
+    /// transactions.
+    pub(crate) fn rebroadcast_local_transactions(&self) -> Vec<TxHash> {
+        let hashes = self
+            .get_pool_data()
+            .pending()
+            .all()
+            .filter(|tx| tx.is_local() && tx.propagate)
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+
+        if hashes.is_empty() {
+            return hashes
+        }
+
+        let mut transaction_listeners = self.pending_transaction_listener.lock();
+        transaction_listeners.retain_mut(|listener| listener.send_all(hashes.iter().copied()));
+
+        hashes
+    }
+
     /// Notify all listeners about a newly inserted pending transaction.
     fn on_new_transaction(&self, event: NewTransactionEvent<T::Transaction>) {
         let mut transaction_listeners = self.transaction_listener.lock();
@@ -614,14 +885,25 @@ where
             })
         }
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome {
+            mined,
+            promoted,
+            demoted,
+            discarded,
+            evicted,
+            expired,
+            block_hash,
+        } = outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx.hash(), *subpool));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::NonceTooLow));
+        evicted.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::AccountSlots));
+        expired.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::Expired));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -630,24 +912,57 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction {
+                    transaction,
+                    promoted,
+                    demoted,
+                    discarded,
+                    replaced,
+                } = tx;
 
+                listener.added(transaction, SubPool::Pending);
                 listener.pending(transaction.hash(), replaced.clone());
-                promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+                demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx.hash(), *subpool));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DropReason::NonceTooLow));
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
+            AddedTransaction::Parked { transaction, replaced, subpool, demoted } => {
+                listener.added(transaction, *subpool);
                 listener.queued(transaction.hash());
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
                 }
+                demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx.hash(), *subpool));
             }
         }
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
+    ///
+    /// The iterator is created from a snapshot of the pending sub-pool, which is taken again once
+    /// the pool was written to. An outdated snapshot is never used, since it may contain
+    /// transactions that were already mined, so this waits for a write in progress if the pool
+    /// was written to since the last snapshot.
     pub(crate) fn best_transactions(&self) -> BestTransactions<T> {
-        self.get_pool_data().best_transactions()
+        let pool = self.pool.read();
+        // subscribe while no write can be in progress, so every transaction that becomes pending
+        // after the snapshot of this generation is yielded by the receiver
+        let new_transaction_receiver = pool.subscribe_pending();
+        let generation = self.pool_generation.load(Ordering::Acquire);
+        if let Some((snapshot_generation, snapshot)) = &*self.pending_snapshot.read() {
+            if *snapshot_generation == generation {
+                return snapshot.best(new_transaction_receiver)
+            }
+        }
+
+        let snapshot = Arc::new(pool.pending_snapshot());
+        drop(pool);
+
+        let best = snapshot.best(new_transaction_receiver);
+        *self.pending_snapshot.write() = Some((generation, snapshot));
+        best
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -670,12 +985,31 @@ where
         self.get_pool_data().queued_transactions()
     }
 
+    /// Returns a page of the transactions of the pending sub-pool.
+    pub(crate) fn pending_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<T::Transaction> {
+        self.get_pool_data().pending_transactions_page(cursor, limit)
+    }
+
+    /// Returns a page of the transactions of the parked pools.
+    pub(crate) fn queued_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<T::Transaction> {
+        self.get_pool_data().queued_transactions_page(cursor, limit)
+    }
+
     /// Returns all transactions in the pool
     pub(crate) fn all_transactions(&self) -> AllPoolTransactions<T::Transaction> {
         let pool = self.get_pool_data();
         AllPoolTransactions {
             pending: pool.pending_transactions(),
             queued: pool.queued_transactions(),
+            blob: pool.blob_transactions(),
         }
     }
 
@@ -687,12 +1021,51 @@ where
         if hashes.is_empty() {
             return Vec::new()
         }
-        let removed = self.pool.write().remove_transactions(hashes);
+        let removed = self.pool_mut().remove_transactions(hashes);
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::Removed));
+
+        removed
+    }
+
+    /// Returns the conditions of the given transaction, if it was submitted with conditions.
+    pub(crate) fn get_conditional(&self, tx_hash: &TxHash) -> Option<TransactionConditional> {
+        self.conditionals.read().get(tx_hash).cloned()
+    }
+
+    /// Returns the conditional transactions in the pool and their conditions.
+    ///
+    /// This forgets the conditions of transactions that are no longer in the pool.
+    pub(crate) fn conditional_transactions(&self) -> Vec<(TxHash, TransactionConditional)> {
+        let pool = self.get_pool_data();
+        let mut conditionals = self.conditionals.write();
+        conditionals.retain(|tx_hash, _| pool.contains(tx_hash));
+        conditionals.iter().map(|(tx_hash, conditional)| (*tx_hash, conditional.clone())).collect()
+    }
+
+    /// Removes the given transactions, whose conditions can no longer be satisfied.
+    pub(crate) fn remove_violated_conditionals(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        if hashes.is_empty() {
+            return Vec::new()
+        }
+        {
+            let mut conditionals = self.conditionals.write();
+            for tx_hash in &hashes {
+                conditionals.remove(tx_hash);
+            }
+        }
+        let removed = self.pool_mut().remove_transactions(hashes);
 
+        let mut listener = self.event_listener.write();
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::ConditionViolated));
+        drop(listener);
+
+        self.delete_discarded_blobs(removed.iter());
         removed
     }
 
@@ -713,7 +1086,7 @@ where
         &self,
         tx_hash: &TxHash,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().get(tx_hash)
+        self.index.get(tx_hash)
     }
 
     /// Returns all transactions of the address
@@ -722,7 +1095,25 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let sender_id = self.get_sender_id(sender);
-        self.get_pool_data().get_transactions_by_sender(sender_id)
+        self.index.by_sender(sender_id)
+    }
+
+    /// Returns the nonce state of the given sender.
+    pub(crate) fn sender_state(&self, sender: Address) -> SenderState {
+        let sender_id = self.get_sender_id(sender);
+        self.get_pool_data().sender_state(sender, sender_id)
+    }
+
+    /// Returns the fee suggestions for the next block at the given percentiles.
+    pub(crate) fn fee_forecast(&self, percentiles: &[f64]) -> FeeForecast {
+        let (block_info, pending) = {
+            let pool = self.get_pool_data();
+            (pool.block_info(), pool.pending_transactions())
+        };
+        let pending_priority_fees = pending
+            .iter()
+            .filter_map(|tx| tx.transaction.effective_tip_per_gas(block_info.pending_basefee));
+        self.fee_history.read().forecast(block_info, pending_priority_fees, percentiles)
     }
 
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
@@ -730,7 +1121,15 @@ where
         &self,
         origin: TransactionOrigin,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().all().transactions_iter().filter(|tx| tx.origin == origin).collect()
+        self.index.transactions().into_iter().filter(|tx| tx.origin == origin).collect()
+    }
+
+    /// Returns all transactions that were submitted by the given source.
+    pub(crate) fn get_transactions_by_source(
+        &self,
+        source: TransactionSource,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.index.transactions().into_iter().filter(|tx| tx.source == source).collect()
     }
 
     /// Returns all the transactions belonging to the hashes.
@@ -740,10 +1139,7 @@ where
         &self,
         txs: Vec<TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        if txs.is_empty() {
-            return Vec::new()
-        }
-        self.get_pool_data().get_all(txs).collect()
+        txs.into_iter().filter_map(|tx| self.index.get(&tx)).collect()
     }
 
     /// Notify about propagated transactions.
@@ -776,7 +1172,7 @@ where
     /// If some of the transactions are blob transactions, they are also removed from the blob
     /// store.
     pub(crate) fn discard_worst(&self) -> HashSet<TxHash> {
-        let discarded = self.pool.write().discard_worst();
+        let discarded = self.pool_mut().discard_worst();
 
         // delete any blobs associated with discarded blob transactions
         self.delete_discarded_blobs(discarded.iter());
@@ -785,6 +1181,33 @@ where
         discarded.into_iter().map(|tx| *tx.hash()).collect()
     }
 
+    /// Removes expired transactions and enforces the sub-pool limits, removing at most `budget`
+    /// transactions.
+    ///
+    /// The expired and the discarded transactions are removed under separate write locks.
+    pub(crate) fn run_maintenance(&self, budget: usize) -> PoolMaintenanceStats {
+        let expired = match self.config.transaction_lifetime {
+            Some(lifetime) => self.pool_mut().remove_expired(lifetime, budget),
+            None => Vec::new(),
+        };
+        let remaining = budget.saturating_sub(expired.len());
+        let discarded = if remaining > 0 {
+            self.pool_mut().discard_worst_bounded(remaining)
+        } else {
+            Vec::new()
+        };
+
+        {
+            let mut listener = self.event_listener.write();
+            expired.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::Expired));
+            discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DropReason::PoolLimit));
+        }
+        self.delete_discarded_blobs(expired.iter().chain(&discarded));
+        self.update_pool_fullness();
+
+        PoolMaintenanceStats { expired: expired.len(), discarded: discarded.len() }
+    }
+
     /// Inserts a blob transaction into the blob store
     fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
         if let Err(err) = self.blob_store.insert(hash, blob) {
@@ -838,6 +1261,37 @@ impl<V, T: TransactionOrdering, S> fmt::Debug for PoolInner<V, T, S> {
     }
 }
 
+/// A write lock to the pool's data, which increments the generation of the pool before the lock
+/// is released.
+///
+/// See [`PoolInner::pool_mut`].
+struct PoolWriteGuard<'a, T: TransactionOrdering> {
+    pool: RwLockWriteGuard<'a, TxPool<T>>,
+    generation: &'a AtomicU64,
+}
+
+impl<T: TransactionOrdering> Deref for PoolWriteGuard<'_, T> {
+    type Target = TxPool<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pool
+    }
+}
+
+impl<T: TransactionOrdering> DerefMut for PoolWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pool
+    }
+}
+
+impl<T: TransactionOrdering> Drop for PoolWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // the lock is released after this, so that readers that see the new generation also see
+        // the written data
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
 /// An active listener for new pending transactions.
 #[derive(Debug)]
 struct PendingTransactionHashListener {
@@ -928,6 +1382,8 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     replaced: Option<Arc<ValidPoolTransaction<T>>>,
     /// transactions promoted to the pending queue
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved from the pending queue to the given parked pool
+    demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
     /// transactions that failed and became discarded
     discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
@@ -1016,6 +1472,9 @@ pub enum AddedTransaction<T: PoolTransaction> {
         replaced: Option<Arc<ValidPoolTransaction<T>>>,
         /// The subpool it was moved to.
         subpool: SubPool,
+        /// Transactions moved from the pending pool to the given parked pool, e.g. because the
+        /// replacement of their ancestor is parked.
+        demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
     },
 }
 
@@ -1070,7 +1529,6 @@ impl<T: PoolTransaction> AddedTransaction<T> {
     }
 
     /// Returns the subpool this transaction was added to
-    #[cfg(test)]
     pub(crate) const fn subpool(&self) -> SubPool {
         match self {
             Self::Pending(_) => SubPool::Pending,
@@ -1097,8 +1555,16 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the pending pool.
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions moved from the pending pool to the given parked pool.
+    pub(crate) demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions that were evicted because their sender exceeds the max number of transaction
+    /// slots per sender.
+    pub(crate) evicted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions that were evicted because they were parked for longer than the configured
+    /// lifetime.
+    pub(crate) expired: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
@@ -1133,12 +1599,124 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 mod tests {
     use crate::{
         blobstore::{BlobStore, InMemoryBlobStore},
+        error::{InvalidPoolTransactionError, PoolErrorKind},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, ConditionViolation, PoolConfig, SubPoolLimit, TransactionConditional,
+        TransactionOrigin, TransactionSource, TransactionValidationOutcome, U256,
     };
     use reth_primitives::{kzg::Blob, transaction::generate_blob_sidecar};
-    use std::{fs, path::PathBuf};
+    use std::{fs, path::PathBuf, sync::mpsc, thread, time::Duration};
+
+    #[test]
+    fn test_reads_follow_writes() {
+        let test_pool = &TestPoolBuilder::default().pool;
+        let add = |tx: MockTransaction| {
+            test_pool
+                .add_transaction(
+                    TransactionSource::Unknown,
+                    TransactionOrigin::External,
+                    TransactionValidationOutcome::Valid {
+                        balance: U256::MAX,
+                        state_nonce: 0,
+                        transaction: ValidTransaction::Valid(tx),
+                        propagate: true,
+                    },
+                    None,
+                )
+                .unwrap()
+        };
+
+        let tx = MockTransaction::eip1559();
+        let next = tx.next();
+        add(tx.clone());
+        assert_eq!(test_pool.best_transactions().count(), test_pool.pending_transactions().len());
+
+        // the snapshot of the pending sub-pool is taken again after the write
+        add(next.clone());
+        assert_eq!(test_pool.best_transactions().count(), test_pool.pending_transactions().len());
+        assert!(test_pool.get(&next.get_hash()).is_some());
+        assert_eq!(test_pool.get_transactions_by_sender(tx.get_sender()).len(), 2);
+
+        test_pool.remove_transactions(vec![next.get_hash()]);
+        assert!(test_pool.get(&next.get_hash()).is_none());
+        assert_eq!(test_pool.get_transactions_by_sender(tx.get_sender()).len(), 1);
+        assert_eq!(test_pool.best_transactions().count(), test_pool.pending_transactions().len());
+    }
+
+    #[test]
+    fn test_add_conditional_transaction() {
+        let test_pool = TestPoolBuilder::default().pool;
+        test_pool.set_block_info(BlockInfo { last_seen_block_number: 10, ..Default::default() });
+        let valid = |tx: MockTransaction| TransactionValidationOutcome::Valid {
+            balance: U256::MAX,
+            state_nonce: 0,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+        };
+
+        // the next block exceeds the max block number
+        let tx = MockTransaction::eip1559();
+        let conditional =
+            TransactionConditional { block_number_max: Some(10), ..Default::default() };
+        let origin = TransactionOrigin::External;
+        let outcome = valid(tx.clone());
+        let err = test_pool.add_conditional_transaction(origin, outcome, conditional).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::ConditionViolated(
+                ConditionViolation::BlockNumber(11)
+            ))
+        ));
+        assert!(test_pool.get(&tx.get_hash()).is_none());
+
+        // the conditions are recorded with the insert
+        let conditional =
+            TransactionConditional { block_number_max: Some(11), ..Default::default() };
+        let outcome = valid(tx.clone());
+        let hash = test_pool.add_conditional_transaction(origin, outcome, conditional.clone());
+        assert_eq!(hash.unwrap(), tx.get_hash());
+        assert_eq!(test_pool.get_conditional(&tx.get_hash()), Some(conditional));
+    }
+
+    #[test]
+    fn test_best_transactions_wait_for_write() {
+        let test_pool = TestPoolBuilder::default().pool;
+        let tx = MockTransaction::eip1559();
+        test_pool
+            .add_transaction(
+                TransactionSource::Unknown,
+                TransactionOrigin::External,
+                TransactionValidationOutcome::Valid {
+                    balance: U256::MAX,
+                    state_nonce: 0,
+                    transaction: ValidTransaction::Valid(tx.clone()),
+                    propagate: true,
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(test_pool.best_transactions().count(), 1);
+
+        // outdate the snapshot, e.g. because the transaction was mined
+        test_pool.remove_transactions(vec![tx.get_hash()]);
+
+        // start another write, which is in progress while the best transactions are requested
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let writer = {
+            let test_pool = test_pool.clone();
+            thread::spawn(move || {
+                let _pool = test_pool.pool_mut();
+                locked_tx.send(()).unwrap();
+                thread::sleep(Duration::from_millis(50));
+            })
+        };
+        locked_rx.recv().unwrap();
+
+        // the removed transaction is not yielded from the outdated snapshot
+        assert_eq!(test_pool.best_transactions().count(), 0);
+        writer.join().unwrap();
+    }
 
     #[test]
     fn test_discard_blobs_on_blob_tx_eviction() {
@@ -1193,6 +1771,7 @@ mod tests {
             // Add the transaction to the pool with external origin and valid outcome.
             test_pool
                 .add_transaction(
+                    TransactionSource::Unknown,
                     TransactionOrigin::External,
                     TransactionValidationOutcome::Valid {
                         balance: U256::from(1_000),
@@ -1203,6 +1782,7 @@ mod tests {
                         },
                         propagate: true,
                     },
+                    None,
                 )
                 .unwrap();
 
@@ -1,8 +1,10 @@
 use crate::{
     identifier::{SenderId, TransactionId},
     pool::size::SizeTracker,
-    PoolTransaction, SubPoolLimit, ValidPoolTransaction, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    LocalTransactionConfig, PoolTransaction, SubPoolLimit, ValidPoolTransaction,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
 };
+use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
@@ -16,7 +18,10 @@ use std::{
 /// basefee, ancestor transactions, balance) that eventually move the transaction into the pending
 /// pool.
 ///
-/// This pool is a bijection: at all times each set (`best`, `by_id`) contains the same
+/// Transactions are sharded by their sender, so that the transactions of many senders can be
+/// removed in parallel when the pool is truncated.
+///
+/// Each shard is a bijection: at all times each set (`best`, `by_id`) of a shard contains the same
 /// transactions.
 ///
 /// Note: This type is generic over [`ParkedPool`] which enforces that the underlying transaction
@@ -27,12 +32,10 @@ pub struct ParkedPool<T: ParkedOrd> {
     ///
     /// This way we can determine when transactions were submitted to the pool.
     submission_id: u64,
-    /// _All_ Transactions that are currently inside the pool grouped by their identifier.
-    by_id: BTreeMap<TransactionId, ParkedPoolTransaction<T>>,
-    /// All transactions sorted by their order function.
+    /// _All_ Transactions that are currently inside the pool, sharded by their sender.
     ///
-    /// The higher, the better.
-    best: BTreeSet<ParkedPoolTransaction<T>>,
+    /// See also [`SenderId::shard`].
+    shards: Box<[ParkedShard<T>]>,
     /// Keeps track of last submission id for each sender.
     ///
     /// This are sorted in Reverse order, so the last (highest) submission id is first, and the
@@ -45,8 +48,23 @@ pub struct ParkedPool<T: ParkedOrd> {
     ///
     /// See also [`PoolTransaction::size`].
     size_of: SizeTracker,
+    /// Keeps track of the encoded size of this pool.
+    ///
+    /// See also [`PoolTransaction::encoded_length`].
+    encoded_size_of: SizeTracker,
+    /// Max encoded size of all transactions in the pool, unbounded by default.
+    max_encoded_size: usize,
 }
 
+/// The number of shards of a [`ParkedPool`].
+const PARKED_POOL_SHARDS: usize = 16;
+
+/// The minimum number of transactions [`ParkedPool::truncate_pool`] has to remove before the
+/// shards are truncated in parallel.
+///
+/// Below this, the overhead of spawning the tasks outweighs the gains.
+const PARALLEL_TRUNCATE_THRESHOLD: usize = 1024;
+
 // === impl ParkedPool ===
 
 impl<T: ParkedOrd> ParkedPool<T> {
@@ -66,13 +84,24 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.encoded_size_of += tx.encoded_length();
 
         // update or create sender entry
         self.add_sender_count(tx.sender_id(), submission_id);
         let transaction = ParkedPoolTransaction { submission_id, transaction: tx.into() };
 
-        self.by_id.insert(id, transaction.clone());
-        self.best.insert(transaction);
+        self.shard_mut(id.sender).insert(id, transaction);
+    }
+
+    /// Returns the shard of the given sender.
+    fn shard(&self, sender: SenderId) -> &ParkedShard<T> {
+        &self.shards[sender.shard(self.shards.len())]
+    }
+
+    /// Returns the mutable shard of the given sender.
+    fn shard_mut(&mut self, sender: SenderId) -> &mut ParkedShard<T> {
+        let shard = sender.shard(self.shards.len());
+        &mut self.shards[shard]
     }
 
     /// Increments the count of transactions for the given sender and updates the tracked submission
@@ -132,7 +161,10 @@ impl<T: ParkedOrd> ParkedPool<T> {
     pub(crate) fn all(
         &self,
     ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
-        self.by_id.values().map(|tx| tx.transaction.clone().into())
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.by_id.values())
+            .map(|tx| tx.transaction.clone().into())
     }
 
     /// Removes the transaction from the pool
@@ -141,12 +173,12 @@ impl<T: ParkedOrd> ParkedPool<T> {
         id: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
         // remove from queues
-        let tx = self.by_id.remove(id)?;
-        self.best.remove(&tx);
+        let tx = self.shard_mut(id.sender).remove(id)?;
         self.remove_sender_count(tx.transaction.sender_id());
 
         // keep track of size
         self.size_of -= tx.transaction.size();
+        self.encoded_size_of -= tx.transaction.encoded_length();
 
         Some(tx.transaction.into())
     }
@@ -157,7 +189,8 @@ impl<T: ParkedOrd> ParkedPool<T> {
         &self,
         sender: SenderId,
     ) -> SmallVec<[TransactionId; TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER]> {
-        self.by_id
+        self.shard(sender)
+            .by_id
             .range((sender.start_bound(), Unbounded))
             .take_while(move |(other, _)| sender == other.sender)
             .map(|(tx_id, _)| *tx_id)
@@ -171,51 +204,6 @@ impl<T: ParkedOrd> ParkedPool<T> {
         self.last_sender_submission.iter().cloned()
     }
 
-    /// Truncates the pool by removing transactions, until the given [`SubPoolLimit`] has been met.
-    ///
-    /// This is done by first ordering senders by the last time they have submitted a transaction
-    ///
-    /// Uses sender ids sorted by each sender's last submission id. Senders with older last
-    /// submission ids are first. Note that _last_ submission ids are the newest submission id for
-    /// that sender, so this sorts senders by the last time they submitted a transaction in
-    /// descending order. Senders that have least recently submitted a transaction are first.
-    ///
-    /// Then, for each sender, all transactions for that sender are removed, until the pool limits
-    /// have been met.
-    ///
-    /// Any removed transactions are returned.
-    pub fn truncate_pool(
-        &mut self,
-        limit: SubPoolLimit,
-    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        if !self.exceeds(&limit) {
-            // if we are below the limits, we don't need to drop anything
-            return Vec::new()
-        }
-
-        let mut removed = Vec::new();
-
-        while limit.is_exceeded(self.len(), self.size()) && !self.last_sender_submission.is_empty()
-        {
-            // NOTE: This will not panic due to `!last_sender_transaction.is_empty()`
-            let sender_id = self.last_sender_submission.last().expect("not empty").sender_id;
-            let list = self.get_txs_by_sender(sender_id);
-
-            // Drop transactions from this sender until the pool is under limits
-            for txid in list.into_iter().rev() {
-                if let Some(tx) = self.remove_transaction(&txid) {
-                    removed.push(tx);
-                }
-
-                if !self.exceeds(&limit) {
-                    break
-                }
-            }
-        }
-
-        removed
-    }
-
     fn next_id(&mut self) -> u64 {
         let id = self.submission_id;
         self.submission_id = self.submission_id.wrapping_add(1);
@@ -227,38 +215,63 @@ impl<T: ParkedOrd> ParkedPool<T> {
         self.size_of.into()
     }
 
+    /// The encoded size of all transactions in this pool.
+    pub(crate) fn encoded_size(&self) -> usize {
+        self.encoded_size_of.into()
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
-        self.by_id.len()
+        self.shards.iter().map(|shard| shard.by_id.len()).sum()
+    }
+
+    /// Updates the max encoded size of all transactions in the pool.
+    ///
+    /// If the limit was lowered, transactions are only removed once the pool is truncated.
+    pub(crate) fn set_max_encoded_size(&mut self, max_encoded_size: usize) {
+        self.max_encoded_size = max_encoded_size;
     }
 
-    /// Returns true if the pool exceeds the given limit
+    /// Returns true if the pool exceeds the given limit, or holds more encoded bytes than allowed.
     #[inline]
     pub(crate) fn exceeds(&self, limit: &SubPoolLimit) -> bool {
-        limit.is_exceeded(self.len(), self.size())
+        self.is_exceeded(limit, self.len(), self.size(), self.encoded_size())
+    }
+
+    /// Returns true if a pool of the given length, size and encoded size exceeds the given limit.
+    #[inline]
+    fn is_exceeded(&self, limit: &SubPoolLimit, len: usize, size: usize, encoded: usize) -> bool {
+        limit.is_exceeded(len, size) || encoded > self.max_encoded_size
     }
 
     /// Returns whether the pool is empty
     #[cfg(test)]
     #[allow(dead_code)]
     pub(crate) fn is_empty(&self) -> bool {
-        self.by_id.is_empty()
+        self.shards.iter().all(|shard| shard.by_id.is_empty())
     }
 
     /// Returns `true` if the transaction with the given id is already included in this pool.
     pub(crate) fn contains(&self, id: &TransactionId) -> bool {
-        self.by_id.contains_key(id)
+        self.shard(id.sender).by_id.contains_key(id)
     }
 
     /// Retrieves a transaction with the given ID from the pool, if it exists.
     fn get(&self, id: &TransactionId) -> Option<&ParkedPoolTransaction<T>> {
-        self.by_id.get(id)
+        self.shard(id.sender).by_id.get(id)
     }
 
-    /// Asserts that the bijection between `by_id` and `best` is valid.
+    /// Asserts that the bijection between `by_id` and `best` of each shard is valid, and that
+    /// each transaction is in the shard of its sender.
     #[cfg(any(test, feature = "test-utils"))]
     pub(crate) fn assert_invariants(&self) {
-        assert_eq!(self.by_id.len(), self.best.len(), "by_id.len() != best.len()");
+        for (idx, shard) in self.shards.iter().enumerate() {
+            assert_eq!(shard.by_id.len(), shard.best.len(), "by_id.len() != best.len()");
+            assert!(
+                shard.by_id.keys().all(|id| id.sender.shard(self.shards.len()) == idx),
+                "transaction in wrong shard"
+            );
+        }
 
         assert_eq!(
             self.last_sender_submission.len(),
@@ -288,8 +301,9 @@ impl<T: PoolTransaction> ParkedPool<BasefeeOrd<T>> {
     /// Returns all transactions that satisfy the given basefee.
     fn satisfy_base_fee_ids(&self, basefee: u64) -> Vec<TransactionId> {
         let mut transactions = Vec::new();
-        {
-            let mut iter = self.by_id.iter().peekable();
+        // the transactions of a sender are all in the same shard
+        for shard in self.shards.iter() {
+            let mut iter = shard.by_id.iter().peekable();
 
             while let Some((id, tx)) = iter.next() {
                 if tx.transaction.transaction.max_fee_per_gas() < basefee as u128 {
@@ -324,19 +338,246 @@ impl<T: PoolTransaction> ParkedPool<BasefeeOrd<T>> {
     }
 }
 
+// === impl ParkedPool truncation ===
+
+impl<T: ParkedOrd + Send + Sync> ParkedPool<T> {
+    /// Truncates the pool by removing transactions, until the given [`SubPoolLimit`] has been met.
+    ///
+    /// This is done by first ordering senders by the last time they have submitted a transaction
+    ///
+    /// Uses sender ids sorted by each sender's last submission id. Senders with older last
+    /// submission ids are first. Note that _last_ submission ids are the newest submission id for
+    /// that sender, so this sorts senders by the last time they submitted a transaction in
+    /// descending order. Senders that have least recently submitted a transaction are first.
+    ///
+    /// Then, for each sender, all transactions for that sender are removed, until the pool limits
+    /// have been met.
+    ///
+    /// Local transactions are exempt with the default [`LocalTransactionConfig`], see
+    /// [`Self::truncate_pool_with_locals`].
+    ///
+    /// The transactions to remove are selected up front, and are then removed from the shards in
+    /// parallel if there are enough of them.
+    ///
+    /// Any removed transactions are returned.
+    pub fn truncate_pool(
+        &mut self,
+        limit: SubPoolLimit,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.truncate_pool_with_locals(limit, &LocalTransactionConfig::default())
+    }
+
+    /// Same as [`Self::truncate_pool`], but local transactions are exempt according to the given
+    /// config.
+    ///
+    /// Exempt transactions are skipped: a sender's transactions are only removed down to its
+    /// highest exempt transaction, because removing a lower nonce would also evict the exempt one.
+    /// If the pool still exceeds the limits afterwards and holds more than
+    /// [`LocalTransactionConfig::max_exempt_transactions`] exempt transactions, the pool is
+    /// truncated again without exemptions.
+    pub(crate) fn truncate_pool_with_locals(
+        &mut self,
+        limit: SubPoolLimit,
+        locals: &LocalTransactionConfig,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        if !self.exceeds(&limit) {
+            // if we are below the limits, we don't need to drop anything
+            return Vec::new()
+        }
+
+        let mut removed = self.remove_truncation_victims(&limit, Some(locals));
+        if !self.exceeds(&limit) {
+            return removed
+        }
+
+        let exempt = self
+            .shards
+            .iter()
+            .flat_map(|shard| shard.by_id.values())
+            .filter(|tx| locals.is_exempt(&tx.transaction))
+            .count();
+        if exempt > locals.max_exempt_transactions {
+            removed.extend(self.remove_truncation_victims(&limit, None));
+        }
+
+        removed
+    }
+
+    /// Selects the transactions to remove with [`Self::truncation_victims`] and removes them.
+    fn remove_truncation_victims(
+        &mut self,
+        limit: &SubPoolLimit,
+        locals: Option<&LocalTransactionConfig>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let removed = self.truncation_victims(limit, locals);
+
+        // group the transactions by shard
+        let mut ids_by_shard = vec![Vec::new(); self.shards.len()];
+        for tx in &removed {
+            ids_by_shard[tx.sender_id().shard(self.shards.len())].push(*tx.id());
+        }
+
+        if removed.len() >= PARALLEL_TRUNCATE_THRESHOLD {
+            self.shards.par_iter_mut().zip(ids_by_shard.par_iter()).for_each(|(shard, ids)| {
+                ids.iter().for_each(|id| {
+                    shard.remove(id);
+                })
+            });
+        } else {
+            for (shard, ids) in self.shards.iter_mut().zip(&ids_by_shard) {
+                ids.iter().for_each(|id| {
+                    shard.remove(id);
+                });
+            }
+        }
+
+        for tx in &removed {
+            self.remove_sender_count(tx.sender_id());
+            self.size_of -= tx.size();
+            self.encoded_size_of -= tx.encoded_length();
+        }
+
+        removed
+    }
+
+    /// Returns the transactions [`Self::truncate_pool`] removes to meet the given limit, in the
+    /// order they are removed, skipping transactions that are exempt according to `locals`.
+    ///
+    /// The senders are processed in chunks, and the candidates of the senders of a chunk are
+    /// collected from the shards in parallel if the chunk is large enough.
+    ///
+    /// Note: this does _not_ remove the transactions.
+    fn truncation_victims(
+        &self,
+        limit: &SubPoolLimit,
+        locals: Option<&LocalTransactionConfig>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let mut victims = Vec::new();
+        let (mut len, mut size, mut encoded_size) = (self.len(), self.size(), self.encoded_size());
+
+        let senders =
+            self.last_sender_submission.iter().rev().map(|s| s.sender_id).collect::<Vec<_>>();
+        for senders in senders.chunks(PARALLEL_TRUNCATE_THRESHOLD) {
+            if !self.is_exceeded(limit, len, size, encoded_size) {
+                break
+            }
+
+            // group the senders by shard
+            let mut senders_by_shard = vec![Vec::new(); self.shards.len()];
+            for sender_id in senders {
+                senders_by_shard[sender_id.shard(self.shards.len())].push(*sender_id);
+            }
+
+            let collect = |(shard, senders): (&ParkedShard<T>, &Vec<SenderId>)| {
+                senders
+                    .iter()
+                    .map(|sender_id| (*sender_id, shard.truncation_candidates(*sender_id, locals)))
+                    .collect::<Vec<_>>()
+            };
+            let mut candidates = if senders.len() >= PARALLEL_TRUNCATE_THRESHOLD {
+                self.shards
+                    .par_iter()
+                    .zip(senders_by_shard.par_iter())
+                    .flat_map_iter(collect)
+                    .collect::<FxHashMap<_, _>>()
+            } else {
+                self.shards
+                    .iter()
+                    .zip(&senders_by_shard)
+                    .flat_map(collect)
+                    .collect::<FxHashMap<_, _>>()
+            };
+
+            // Drop transactions from each sender until the pool is under limits
+            'senders: for sender_id in senders {
+                let txs = candidates.remove(sender_id).unwrap_or_default();
+                for tx in txs {
+                    if !self.is_exceeded(limit, len, size, encoded_size) {
+                        break 'senders
+                    }
+
+                    len -= 1;
+                    size -= tx.size();
+                    encoded_size -= tx.encoded_length();
+                    victims.push(tx);
+                }
+            }
+        }
+
+        victims
+    }
+}
+
 impl<T: ParkedOrd> Default for ParkedPool<T> {
     fn default() -> Self {
         Self {
             submission_id: 0,
-            by_id: Default::default(),
-            best: Default::default(),
+            shards: (0..PARKED_POOL_SHARDS).map(|_| ParkedShard::default()).collect(),
             last_sender_submission: Default::default(),
             sender_transaction_count: Default::default(),
             size_of: Default::default(),
+            encoded_size_of: Default::default(),
+            max_encoded_size: usize::MAX,
         }
     }
 }
 
+/// A shard of a [`ParkedPool`], holding the transactions of a subset of the senders.
+#[derive(Debug, Clone)]
+struct ParkedShard<T: ParkedOrd> {
+    /// All transactions of the shard grouped by their identifier.
+    by_id: BTreeMap<TransactionId, ParkedPoolTransaction<T>>,
+    /// All transactions of the shard sorted by their order function.
+    ///
+    /// The higher, the better.
+    best: BTreeSet<ParkedPoolTransaction<T>>,
+}
+
+impl<T: ParkedOrd> ParkedShard<T> {
+    /// Inserts the transaction into the shard.
+    fn insert(&mut self, id: TransactionId, transaction: ParkedPoolTransaction<T>) {
+        self.by_id.insert(id, transaction.clone());
+        self.best.insert(transaction);
+    }
+
+    /// Removes the transaction from the shard.
+    fn remove(&mut self, id: &TransactionId) -> Option<ParkedPoolTransaction<T>> {
+        let tx = self.by_id.remove(id)?;
+        self.best.remove(&tx);
+        Some(tx)
+    }
+
+    /// Returns the transactions of the sender that truncating the pool may remove, starting with
+    /// the highest nonce.
+    ///
+    /// If `locals` is set, these are all transactions above the highest exempt transaction of the
+    /// sender, because removing a lower nonce would also evict the exempt one.
+    fn truncation_candidates(
+        &self,
+        sender_id: SenderId,
+        locals: Option<&LocalTransactionConfig>,
+    ) -> SmallVec<[Arc<ValidPoolTransaction<T::Transaction>>; TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER]>
+    {
+        let txs = self
+            .by_id
+            .range((sender_id.start_bound(), Unbounded))
+            .take_while(|(id, _)| id.sender == sender_id)
+            .map(|(_, tx)| &tx.transaction)
+            .collect::<SmallVec<[_; TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER]>>();
+        txs.into_iter()
+            .rev()
+            .take_while(|tx| !locals.is_some_and(|locals| locals.is_exempt(tx)))
+            .map(|tx| Arc::clone(tx))
+            .collect()
+    }
+}
+
+impl<T: ParkedOrd> Default for ParkedShard<T> {
+    fn default() -> Self {
+        Self { by_id: Default::default(), best: Default::default() }
+    }
+}
+
 /// Keeps track of the number of transactions and the latest submission id for each sender.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 struct SenderTransactionCount {
@@ -417,6 +658,9 @@ impl PartialOrd for SubmissionSenderId {
 /// Helper trait used for custom `Ord` wrappers around a transaction.
 ///
 /// This is effectively a wrapper for `Arc<ValidPoolTransaction>` with custom `Ord` implementation.
+///
+/// Truncating a [`ParkedPool`] works on its shards in parallel, so it additionally requires the
+/// wrapper to be `Send` and `Sync`.
 pub trait ParkedOrd:
     Ord
     + Clone
@@ -520,7 +764,7 @@ impl<T: PoolTransaction> Ord for QueuedOrd<T> {
 mod tests {
     use super::*;
     use crate::test_utils::{MockTransaction, MockTransactionFactory, MockTransactionSet};
-    use reth_primitives::{address, TxType};
+    use reth_primitives::{address, Bytes, TxType};
     use std::collections::HashSet;
 
     #[test]
@@ -682,6 +926,61 @@ mod tests {
         assert_eq!(removed.len(), 1);
     }
 
+    #[test]
+    fn test_truncate_parked_by_encoded_size() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+        let limit = SubPoolLimit::new(usize::MAX, usize::MAX);
+        pool.set_max_encoded_size(1000);
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let a_txs = MockTransactionSet::dependent(a_sender, 0, 3, TxType::Eip1559)
+            .into_iter()
+            .map(|mut tx| {
+                tx.set_input(Bytes::from(vec![0; 400]));
+                tx
+            })
+            .collect::<Vec<_>>();
+
+        for tx in a_txs {
+            pool.add_transaction(f.validated_arc(tx));
+        }
+        assert_eq!(pool.encoded_size(), 1200);
+        assert!(pool.exceeds(&limit));
+
+        // only the highest nonce has to be removed to get under the encoded size limit
+        let removed = pool.truncate_pool(limit);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].nonce(), 2);
+        assert_eq!(pool.encoded_size(), 800);
+    }
+
+    #[test]
+    fn truncate_parked_sharded() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+
+        // enough senders to truncate the shards in parallel
+        let num_senders = PARALLEL_TRUNCATE_THRESHOLD * 2;
+        let txs = (0..num_senders)
+            .map(|_| f.validated_arc(MockTransaction::eip1559()))
+            .collect::<Vec<_>>();
+        for tx in &txs {
+            pool.add_transaction(tx.clone());
+        }
+        assert!(pool.shards.iter().all(|shard| !shard.by_id.is_empty()));
+
+        // the least recently submitted senders are removed first
+        let removed = pool.truncate_pool(SubPoolLimit::new(num_senders / 4, usize::MAX));
+        assert_eq!(
+            removed.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            txs[..num_senders * 3 / 4].iter().map(|tx| *tx.hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(pool.len(), num_senders / 4);
+        assert!(txs[num_senders * 3 / 4..].iter().all(|tx| pool.contains(tx.id())));
+        pool.assert_invariants();
+    }
+
     #[test]
     fn test_senders_by_submission_id() {
         // this test ensures that we evict from the pending pool by sender
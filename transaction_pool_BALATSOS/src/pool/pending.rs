@@ -4,12 +4,12 @@ use crate::{
         best::{BestTransactions, BestTransactionsWithFees},
         size::SizeTracker,
     },
-    Priority, SubPoolLimit, TransactionOrdering, ValidPoolTransaction,
+    LocalTransactionConfig, Priority, SubPoolLimit, TransactionOrdering, ValidPoolTransaction,
 };
 use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
-    ops::Bound::Unbounded,
+    ops::Bound::{Excluded, Unbounded},
     sync::Arc,
 };
 use tokio::sync::broadcast;
@@ -50,6 +50,12 @@ pub struct PendingPool<T: TransactionOrdering> {
     ///
     /// See also [`PoolTransaction::size`](crate::traits::PoolTransaction::size).
     size_of: SizeTracker,
+    /// Keeps track of the encoded size of this pool.
+    ///
+    /// See also [`PoolTransaction::encoded_length`](crate::traits::PoolTransaction).
+    encoded_size_of: SizeTracker,
+    /// Max encoded size of all transactions in the pool, unbounded by default.
+    max_encoded_size: usize,
     /// Used to broadcast new transactions that have been added to the `PendingPool` to existing
     /// `static_files` of this pool.
     new_transaction_notifier: broadcast::Sender<PendingTransaction<T>>,
@@ -69,10 +75,19 @@ impl<T: TransactionOrdering> PendingPool<T> {
             independent_transactions: Default::default(),
             highest_nonces: Default::default(),
             size_of: Default::default(),
+            encoded_size_of: Default::default(),
+            max_encoded_size: usize::MAX,
             new_transaction_notifier,
         }
     }
 
+    /// Updates the max encoded size of all transactions in the pool.
+    ///
+    /// If the limit was lowered, transactions are only removed once the pool is truncated.
+    pub(crate) fn set_max_encoded_size(&mut self, max_encoded_size: usize) {
+        self.max_encoded_size = max_encoded_size;
+    }
+
     /// Clear all transactions from the pool without resetting other values.
     /// Used for atomic reordering during basefee update.
     ///
@@ -84,6 +99,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.highest_nonces.clear();
         self.all.clear();
         self.size_of.reset();
+        self.encoded_size_of.reset();
         std::mem::take(&mut self.by_id)
     }
 
@@ -110,11 +126,26 @@ impl<T: TransactionOrdering> PendingPool<T> {
             all: self.by_id.clone(),
             independent: self.independent_transactions.clone(),
             invalid: Default::default(),
+            invalid_slots: Default::default(),
             new_transaction_receiver: Some(self.new_transaction_notifier.subscribe()),
             skip_blobs: false,
         }
     }
 
+    /// Returns a copy of the transactions that are _currently_ ready, from which iterators can be
+    /// created later without access to this pool, see [`PendingSnapshot::best`].
+    pub(crate) fn snapshot(&self) -> PendingSnapshot<T> {
+        PendingSnapshot {
+            by_id: self.by_id.clone(),
+            independent_transactions: self.independent_transactions.clone(),
+        }
+    }
+
+    /// Returns a receiver for the transactions that are added to the pool from now on.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<PendingTransaction<T>> {
+        self.new_transaction_notifier.subscribe()
+    }
+
     /// Same as `best` but only returns transactions that satisfy the given basefee and blobfee.
     pub(crate) fn best_with_basefee_and_blobfee(
         &self,
@@ -163,6 +194,20 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.by_id.values().map(|tx| tx.transaction.clone())
     }
 
+    /// Returns at most `limit` transactions ordered by their id, starting after the given id.
+    pub(crate) fn page(
+        &self,
+        after: Option<TransactionId>,
+        limit: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let start = after.map_or(Unbounded, Excluded);
+        self.by_id
+            .range((start, Unbounded))
+            .take(limit)
+            .map(|(_, tx)| tx.transaction.clone())
+            .collect()
+    }
+
     /// Updates the pool with the new blob fee. Removes
     /// from the subpool all transactions and their dependents that no longer satisfy the given
     /// blob fee (`tx.max_blob_fee < blob_fee`).
@@ -197,6 +242,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 }
             } else {
                 self.size_of += tx.transaction.size();
+                self.encoded_size_of += tx.transaction.encoded_length();
                 self.update_independents_and_highest_nonces(&tx, &id);
                 self.all.insert(tx.clone());
                 self.by_id.insert(id, tx);
@@ -243,6 +289,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
 
                 self.size_of += tx.transaction.size();
+                self.encoded_size_of += tx.transaction.encoded_length();
                 self.update_independents_and_highest_nonces(&tx, &id);
                 self.all.insert(tx.clone());
                 self.by_id.insert(id, tx);
@@ -298,6 +345,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.encoded_size_of += tx.encoded_length();
 
         let tx_id = *tx.id();
 
@@ -330,6 +378,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         }
         let tx = self.by_id.remove(id)?;
         self.size_of -= tx.transaction.size();
+        self.encoded_size_of -= tx.transaction.encoded_length();
         self.all.remove(&tx);
         self.independent_transactions.remove(&tx);
 
@@ -354,28 +403,29 @@ impl<T: TransactionOrdering> PendingPool<T> {
     /// This attempts to remove transactions by roughly the same amount for each sender. This is
     /// done by removing the highest-nonce transactions for each sender.
     ///
-    /// If the `remove_locals` flag is unset, transactions will be removed per-sender until a
-    /// local transaction is the highest nonce transaction for that sender. If all senders have a
-    /// local highest-nonce transaction, the pool will not be truncated further.
+    /// If `locals` is set, transactions will be removed per-sender until a transaction that is
+    /// exempt from eviction according to [`LocalTransactionConfig::is_exempt`] is the highest
+    /// nonce transaction for that sender. If all senders have an exempt highest-nonce
+    /// transaction, the pool will not be truncated further.
     ///
-    /// Otherwise, if the `remove_locals` flag is set, transactions will be removed per-sender
-    /// until the pool is under the given limits.
+    /// Otherwise, if `locals` is `None`, transactions will be removed per-sender until the pool is
+    /// under the given limits.
     ///
     /// Any removed transactions will be added to the `end_removed` vector.
     pub fn remove_to_limit(
         &mut self,
         limit: &SubPoolLimit,
-        remove_locals: bool,
+        locals: Option<&LocalTransactionConfig>,
         end_removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
     ) {
         // This serves as a termination condition for the loop - it represents the number of
         // _valid_ unique senders that might have descendants in the pool.
         //
-        // If `remove_locals` is false, a value of zero means that there are no non-local txs in the
-        // pool that can be removed.
+        // If `locals` is set, a value of zero means that there are no non-exempt txs in the pool
+        // that can be removed.
         //
-        // If `remove_locals` is true, a value of zero means that there are no txs in the pool that
-        // can be removed.
+        // If `locals` is `None`, a value of zero means that there are no txs in the pool that can
+        // be removed.
         let mut non_local_senders = self.highest_nonces.len();
 
         // keep track of unique senders from previous iterations, to understand how many unique
@@ -387,9 +437,11 @@ impl<T: TransactionOrdering> PendingPool<T> {
         let mut removed = Vec::new();
         let mut total_removed = 0;
 
-        // track total `size` of transactions to remove
+        // track total `size` and encoded size of transactions to remove
         let original_size = self.size();
         let mut total_size = 0;
+        let original_encoded_size = self.encoded_size();
+        let mut total_encoded_size = 0;
 
         loop {
             // check how many unique senders were removed last iteration
@@ -405,9 +457,10 @@ impl<T: TransactionOrdering> PendingPool<T> {
             // loop through the highest nonces set, removing transactions until we reach the limit
             for tx in &self.highest_nonces {
                 // return early if the pool is under limits
-                if !limit.is_exceeded(original_length - total_removed, original_size - total_size) ||
-                    non_local_senders == 0
-                {
+                let (len, size) = (original_length - total_removed, original_size - total_size);
+                let encoded_size = original_encoded_size - total_encoded_size;
+                let exceeded = limit.is_exceeded(len, size) || encoded_size > self.max_encoded_size;
+                if !exceeded || non_local_senders == 0 {
                     // need to remove remaining transactions before exiting
                     for id in &removed {
                         if let Some(tx) = self.remove_transaction(id) {
@@ -418,12 +471,13 @@ impl<T: TransactionOrdering> PendingPool<T> {
                     return
                 }
 
-                if !remove_locals && tx.transaction.is_local() {
+                if locals.is_some_and(|locals| locals.is_exempt(&tx.transaction)) {
                     non_local_senders -= 1;
                     continue
                 }
 
                 total_size += tx.transaction.size();
+                total_encoded_size += tx.transaction.encoded_length();
                 total_removed += 1;
                 removed.push(*tx.transaction.id());
             }
@@ -450,12 +504,25 @@ impl<T: TransactionOrdering> PendingPool<T> {
     /// information on this exact process see docs for
     /// [`remove_to_limit`](PendingPool::remove_to_limit).
     ///
-    /// This first truncates all of the non-local transactions in the pool. If the subpool is still
-    /// not under the limit, this truncates the entire pool, including non-local transactions. The
-    /// removed transactions are returned.
+    /// Local transactions are exempt from truncation with the default [`LocalTransactionConfig`],
+    /// see [`Self::truncate_pool_with_locals`]. The removed transactions are returned.
     pub fn truncate_pool(
         &mut self,
         limit: SubPoolLimit,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.truncate_pool_with_locals(limit, &LocalTransactionConfig::default())
+    }
+
+    /// Same as [`Self::truncate_pool`], but local transactions are exempt according to the given
+    /// config.
+    ///
+    /// This first truncates all of the non-local transactions in the pool. If the subpool is still
+    /// not under the limit and holds more than [`LocalTransactionConfig::max_exempt_transactions`]
+    /// local transactions, this truncates the entire pool, including local transactions.
+    pub(crate) fn truncate_pool_with_locals(
+        &mut self,
+        limit: SubPoolLimit,
+        locals: &LocalTransactionConfig,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let mut removed = Vec::new();
         // return early if the pool is already under the limits
@@ -463,23 +530,25 @@ impl<T: TransactionOrdering> PendingPool<T> {
             return removed
         }
 
-        // first truncate only non-local transactions, returning if the pool end up under the limit
-        self.remove_to_limit(&limit, false, &mut removed);
+        // first truncate only non-local transactions, returning if the pool ends up under the limit
+        self.remove_to_limit(&limit, Some(locals), &mut removed);
         if !self.exceeds(&limit) {
             return removed
         }
 
-        // now repeat for local transactions, since local transactions must be removed now for the
-        // pool to be under the limit
-        self.remove_to_limit(&limit, true, &mut removed);
+        // now repeat for local transactions if there are too many of them to keep
+        let exempt = self.by_id.values().filter(|tx| locals.is_exempt(&tx.transaction)).count();
+        if exempt > locals.max_exempt_transactions {
+            self.remove_to_limit(&limit, None, &mut removed);
+        }
 
         removed
     }
 
-    /// Returns true if the pool exceeds the given limit
+    /// Returns true if the pool exceeds the given limit, or holds more encoded bytes than allowed.
     #[inline]
     pub(crate) fn exceeds(&self, limit: &SubPoolLimit) -> bool {
-        limit.is_exceeded(self.len(), self.size())
+        limit.is_exceeded(self.len(), self.size()) || self.encoded_size() > self.max_encoded_size
     }
 
     /// The reported size of all transactions in this pool.
@@ -487,6 +556,11 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.size_of.into()
     }
 
+    /// The encoded size of all transactions in this pool, including blob sidecars.
+    pub(crate) fn encoded_size(&self) -> usize {
+        self.encoded_size_of.into()
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.by_id.len()
@@ -537,6 +611,36 @@ impl<T: TransactionOrdering> PendingPool<T> {
     }
 }
 
+/// A copy of the transactions of the [`PendingPool`] at some point in time.
+#[derive(Debug)]
+pub(crate) struct PendingSnapshot<T: TransactionOrdering> {
+    /// _All_ transactions of the pool at the time of the snapshot.
+    by_id: BTreeMap<TransactionId, PendingTransaction<T>>,
+    /// The independent transactions of the pool at the time of the snapshot.
+    independent_transactions: BTreeSet<PendingTransaction<T>>,
+}
+
+impl<T: TransactionOrdering> PendingSnapshot<T> {
+    /// Returns an iterator over the transactions of the snapshot, see [`PendingPool::best`].
+    ///
+    /// The iterator also yields the transactions received from the given receiver of
+    /// [`PendingPool::subscribe`], which must have subscribed while the pool was at the state of
+    /// the snapshot.
+    pub(crate) fn best(
+        &self,
+        new_transaction_receiver: broadcast::Receiver<PendingTransaction<T>>,
+    ) -> BestTransactions<T> {
+        BestTransactions {
+            all: self.by_id.clone(),
+            independent: self.independent_transactions.clone(),
+            invalid: Default::default(),
+            invalid_slots: Default::default(),
+            new_transaction_receiver: Some(new_transaction_receiver),
+            skip_blobs: false,
+        }
+    }
+}
+
 /// A transaction that is ready to be included in a block.
 #[derive(Debug)]
 pub(crate) struct PendingTransaction<T: TransactionOrdering> {
@@ -595,7 +699,7 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
-        PoolTransaction,
+        PoolTransaction, TransactionOrigin,
     };
     use reth_primitives::{address, TxType};
     use std::collections::HashSet;
@@ -681,6 +785,67 @@ mod tests {
         assert_eq!(removed[0].hash(), t.hash());
     }
 
+    #[test]
+    fn truncate_exempts_local_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+
+        let local = MockTransaction::eip1559();
+        pool.add_transaction(Arc::new(f.validated_with_origin(TransactionOrigin::Local, local)), 0);
+
+        let external = MockTransaction::eip1559().inc_price_by(10);
+        pool.add_transaction(f.validated_arc(external.clone()), 0);
+
+        // the local transaction is the worst one, but it can't be evicted
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 0, max_size: usize::MAX });
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].hash(), external.hash());
+        assert_eq!(pool.len(), 1);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn truncate_evicts_local_transactions_without_exemptions() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+
+        let local = MockTransaction::eip1559();
+        pool.add_transaction(
+            Arc::new(f.validated_with_origin(TransactionOrigin::Local, local.clone())),
+            0,
+        );
+
+        let locals = LocalTransactionConfig { no_exemptions: true, ..Default::default() };
+        let removed = pool.truncate_pool_with_locals(SubPoolLimit::new(0, usize::MAX), &locals);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].hash(), local.hash());
+        assert!(pool.is_empty());
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn truncate_evicts_local_transactions_above_exempt_cap() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+
+        for _ in 0..3 {
+            let tx = f.validated_with_origin(TransactionOrigin::Local, MockTransaction::eip1559());
+            pool.add_transaction(Arc::new(tx), 0);
+        }
+
+        // the locals are kept as long as there are at most `max_exempt_transactions` of them
+        let locals = LocalTransactionConfig { max_exempt_transactions: 3, ..Default::default() };
+        let removed = pool.truncate_pool_with_locals(SubPoolLimit::new(1, usize::MAX), &locals);
+        assert!(removed.is_empty());
+        assert_eq!(pool.len(), 3);
+
+        let locals = LocalTransactionConfig { max_exempt_transactions: 2, ..Default::default() };
+        let removed = pool.truncate_pool_with_locals(SubPoolLimit::new(1, usize::MAX), &locals);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(pool.len(), 1);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn correct_independent_descendants() {
         // this test ensures that we set the right highest nonces set for each sender
@@ -1,7 +1,7 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{LocalTransactionConfig, SubPoolLimit, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
@@ -9,14 +9,18 @@ use crate::{
         best::BestTransactions,
         blob::BlobTransactions,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
-        pending::PendingPool,
+        index::TransactionIndex,
+        pending::{PendingPool, PendingSnapshot, PendingTransaction},
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    traits::{
+        BestTransactionsAttributes, BlockInfo, PeerId, PoolSize, SenderState, SenderTransaction,
+        TransactionSource, TransactionsPage,
+    },
+    EthReplacementPolicy, PoolConfig, PoolResult, PoolTransaction, ReplacementFees,
+    ReplacementPolicy, TransactionOrdering, ValidPoolTransaction, U256,
 };
 use reth_primitives::{
     constants::{
@@ -27,12 +31,14 @@ use reth_primitives::{
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
-    cmp::Ordering,
+    cmp::{Ordering, Reverse},
     collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::{Duration, Instant},
 };
+use tokio::sync::broadcast;
 use tracing::trace;
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
@@ -80,16 +86,27 @@ pub struct TxPool<T: TransactionOrdering> {
 impl<T: TransactionOrdering> TxPool<T> {
     /// Create a new graph pool instance.
     pub fn new(ordering: T, config: PoolConfig) -> Self {
-        Self {
+        let mut pool = Self {
             sender_info: Default::default(),
             pending_pool: PendingPool::new(ordering),
             queued_pool: Default::default(),
             basefee_pool: Default::default(),
-            blob_pool: Default::default(),
+            blob_pool: BlobTransactions::new(config.max_blob_count),
             all_transactions: AllTransactions::new(&config),
             config,
             metrics: Default::default(),
-        }
+        };
+        pool.update_max_encoded_sizes();
+        pool
+    }
+
+    /// Applies the configured [`PoolConfig::max_encoded_size`] to the sub-pools.
+    fn update_max_encoded_sizes(&mut self) {
+        let max_encoded_size = self.config.max_encoded_size;
+        self.pending_pool.set_max_encoded_size(max_encoded_size.pending);
+        self.basefee_pool.set_max_encoded_size(max_encoded_size.basefee);
+        self.queued_pool.set_max_encoded_size(max_encoded_size.queued);
+        self.blob_pool.set_max_encoded_size(max_encoded_size.blob);
     }
 
     /// Retrieves the highest nonce for a specific sender from the transaction pool.
@@ -121,16 +138,42 @@ impl<T: TransactionOrdering> TxPool<T> {
         PoolSize {
             pending: self.pending_pool.len(),
             pending_size: self.pending_pool.size(),
+            pending_encoded_size: self.pending_pool.encoded_size(),
             basefee: self.basefee_pool.len(),
             basefee_size: self.basefee_pool.size(),
+            basefee_encoded_size: self.basefee_pool.encoded_size(),
             queued: self.queued_pool.len(),
             queued_size: self.queued_pool.size(),
+            queued_encoded_size: self.queued_pool.encoded_size(),
             blob: self.blob_pool.len(),
             blob_size: self.blob_pool.size(),
+            blob_encoded_size: self.blob_pool.encoded_size(),
+            blob_count: self.blob_pool.blob_count(),
             total: self.all_transactions.len(),
         }
     }
 
+    /// Returns the fullness of the pool with the currently enforced sub-pool limits, see
+    /// [`PoolConfig::fullness`].
+    pub(crate) fn fullness(&self) -> f64 {
+        self.config.fullness(self.size())
+    }
+
+    /// Returns the max number of transaction slots per sender that are currently enforced.
+    pub const fn max_account_slots(&self) -> usize {
+        self.all_transactions.max_account_slots
+    }
+
+    /// Updates the max number of transaction slots per sender.
+    ///
+    /// New transactions are checked against the new limit right away. If the limit was lowered,
+    /// the transactions of senders that exceed it are removed on the next canonical state change,
+    /// see [`Self::on_canonical_state_change`].
+    pub fn set_max_account_slots(&mut self, max_account_slots: usize) {
+        self.config.max_account_slots = max_account_slots;
+        self.all_transactions.max_account_slots = max_account_slots;
+    }
+
     /// Returns the currently tracked block values
     pub const fn block_info(&self) -> BlockInfo {
         BlockInfo {
@@ -142,45 +185,54 @@ impl<T: TransactionOrdering> TxPool<T> {
     }
 
     /// Updates the tracked blob fee
-    fn update_blob_fee(&mut self, mut pending_blob_fee: u128, base_fee_update: Ordering) {
+    ///
+    /// The blob fee is tracked separately from the base fee, so a blob fee increase demotes
+    /// pending blob transactions even if the base fee decreased at the same time.
+    ///
+    /// All transactions that are moved in or out of the pending pool are recorded in the
+    /// `outcome`.
+    fn update_blob_fee(
+        &mut self,
+        mut pending_blob_fee: u128,
+        base_fee_update: Ordering,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) {
         std::mem::swap(&mut self.all_transactions.pending_fees.blob_fee, &mut pending_blob_fee);
-        match (self.all_transactions.pending_fees.blob_fee.cmp(&pending_blob_fee), base_fee_update)
-        {
-            (Ordering::Equal, Ordering::Equal) | (Ordering::Equal, Ordering::Greater) => {
-                // fee unchanged, nothing to update
+        let blob_fee_update = self.all_transactions.pending_fees.blob_fee.cmp(&pending_blob_fee);
+
+        if blob_fee_update == Ordering::Greater {
+            // increased blob fee: recheck pending pool and remove all that are no longer valid
+            let removed =
+                self.pending_pool.update_blob_fee(self.all_transactions.pending_fees.blob_fee);
+            for tx in removed {
+                let to = {
+                    let tx = self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
+
+                    // the blob fee is too high now, unset the blob fee cap block flag
+                    tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+                    tx.subpool = tx.state.into();
+                    tx.subpool
+                };
+                self.metrics.inc_removed(SubPool::Pending);
+                outcome.record_move(SubPool::Pending, to, &tx, &self.metrics);
+                self.add_transaction_to_subpool(to, tx);
             }
-            (Ordering::Greater, Ordering::Equal) | (Ordering::Greater, Ordering::Greater) => {
-                // increased blob fee: recheck pending pool and remove all that are no longer valid
-                let removed =
-                    self.pending_pool.update_blob_fee(self.all_transactions.pending_fees.blob_fee);
-                for tx in removed {
-                    let to = {
-                        let tx =
-                            self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
+        }
 
-                        // the blob fee is too high now, unset the blob fee cap block flag
-                        tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
-                        tx.subpool
-                    };
-                    self.add_transaction_to_subpool(to, tx);
-                }
-            }
-            (Ordering::Less, _) | (_, Ordering::Less) => {
-                // decreased blob/base fee: recheck blob pool and promote all that are now valid
-                let removed =
-                    self.blob_pool.enforce_pending_fees(&self.all_transactions.pending_fees);
-                for tx in removed {
-                    let to = {
-                        let tx =
-                            self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
-                        tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
-                        tx.state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
-                        tx.subpool
-                    };
-                    self.add_transaction_to_subpool(to, tx);
-                }
+        if blob_fee_update == Ordering::Less || base_fee_update == Ordering::Less {
+            // decreased blob/base fee: recheck blob pool and promote all that are now valid
+            let removed = self.blob_pool.enforce_pending_fees(&self.all_transactions.pending_fees);
+            for tx in removed {
+                let to = {
+                    let tx = self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
+                    tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+                    tx.state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
+                    tx.subpool = tx.state.into();
+                    tx.subpool
+                };
+                self.metrics.inc_removed(SubPool::Blob);
+                outcome.record_move(SubPool::Blob, to, &tx, &self.metrics);
+                self.add_transaction_to_subpool(to, tx);
             }
         }
     }
@@ -188,8 +240,12 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Updates the tracked basefee
     ///
     /// Depending on the change in direction of the basefee, this will promote or demote
-    /// transactions from the basefee pool.
-    fn update_basefee(&mut self, mut pending_basefee: u64) -> Ordering {
+    /// transactions from the basefee pool, which are recorded in the `outcome`.
+    fn update_basefee(
+        &mut self,
+        mut pending_basefee: u64,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) -> Ordering {
         std::mem::swap(&mut self.all_transactions.pending_fees.base_fee, &mut pending_basefee);
         match self.all_transactions.pending_fees.base_fee.cmp(&pending_basefee) {
             Ordering::Equal => {
@@ -208,6 +264,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.metrics.inc_removed(SubPool::Pending);
+                    outcome.record_move(SubPool::Pending, to, &tx, &self.metrics);
                     self.add_transaction_to_subpool(to, tx);
                 }
 
@@ -225,6 +283,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.metrics.inc_removed(SubPool::BaseFee);
+                    outcome.record_move(SubPool::BaseFee, to, &tx, &self.metrics);
                     self.add_transaction_to_subpool(to, tx);
                 }
 
@@ -237,6 +297,12 @@ impl<T: TransactionOrdering> TxPool<T> {
     ///
     /// This will also apply updates to the pool based on the new base fee
     pub fn set_block_info(&mut self, info: BlockInfo) {
+        self.update_block_info(info);
+    }
+
+    /// Sets the current block info for the pool, like [`Self::set_block_info`], and returns the
+    /// transactions that were promoted or demoted because of the new fees.
+    pub(crate) fn update_block_info(&mut self, info: BlockInfo) -> UpdateOutcome<T::Transaction> {
         let BlockInfo {
             last_seen_block_hash,
             last_seen_block_number,
@@ -245,11 +311,13 @@ impl<T: TransactionOrdering> TxPool<T> {
         } = info;
         self.all_transactions.last_seen_block_hash = last_seen_block_hash;
         self.all_transactions.last_seen_block_number = last_seen_block_number;
-        let basefee_ordering = self.update_basefee(pending_basefee);
+        let mut outcome = UpdateOutcome::default();
+        let basefee_ordering = self.update_basefee(pending_basefee, &mut outcome);
 
         if let Some(blob_fee) = pending_blob_fee {
-            self.update_blob_fee(blob_fee, basefee_ordering)
+            self.update_blob_fee(blob_fee, basefee_ordering, &mut outcome)
         }
+        outcome
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -258,6 +326,23 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.best()
     }
 
+    /// Returns a copy of the transactions that are ready to be included in the block, see
+    /// [`PendingSnapshot`].
+    pub(crate) fn pending_snapshot(&self) -> PendingSnapshot<T> {
+        self.pending_pool.snapshot()
+    }
+
+    /// Returns a receiver for the transactions that become pending from now on, see
+    /// [`PendingSnapshot::best`].
+    pub(crate) fn subscribe_pending(&self) -> broadcast::Receiver<PendingTransaction<T>> {
+        self.pending_pool.subscribe()
+    }
+
+    /// Returns the index of all transactions in the pool, see [`TransactionIndex`].
+    pub(crate) fn index(&self) -> Arc<TransactionIndex<T::Transaction>> {
+        self.all_transactions.index()
+    }
+
     /// Returns an iterator that yields transactions that are ready to be included in the block with
     /// the given base fee and optional blob fee.
     ///
@@ -327,6 +412,40 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.all().chain(self.queued_pool.all()).collect()
     }
 
+    /// Returns at most `limit` transactions from the pending sub-pool, starting after the given
+    /// transaction id.
+    pub(crate) fn pending_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<T::Transaction> {
+        TransactionsPage::new(self.pending_pool.page(cursor, limit), limit)
+    }
+
+    /// Returns at most `limit` transactions from the parked pools, starting after the given
+    /// transaction id.
+    pub(crate) fn queued_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<T::Transaction> {
+        let start = cursor.map_or(Unbounded, Excluded);
+        let transactions = self
+            .all_transactions
+            .txs
+            .range((start, Unbounded))
+            .filter(|(_, tx)| matches!(tx.subpool, SubPool::Queued | SubPool::BaseFee))
+            .take(limit)
+            .map(|(_, tx)| Arc::clone(&tx.transaction))
+            .collect();
+        TransactionsPage::new(transactions, limit)
+    }
+
+    /// Returns all transactions from the blob pool
+    pub(crate) fn blob_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.blob_pool.all().collect()
+    }
+
     /// Returns queued and pending transactions for the specified sender
     pub fn queued_and_pending_txs_by_sender(
         &self,
@@ -362,23 +481,34 @@ impl<T: TransactionOrdering> TxPool<T> {
         &self,
         tx_hash: &TxHash,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.all_transactions.by_hash.get(tx_hash).cloned()
+        self.all_transactions.by_hash.get(tx_hash)
     }
 
-    /// Returns transactions for the multiple given hashes, if they exist.
-    pub(crate) fn get_all(
-        &self,
-        txs: Vec<TxHash>,
-    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
-        txs.into_iter().filter_map(|tx| self.get(&tx))
-    }
+    /// Returns the nonce state of the given sender.
+    pub(crate) fn sender_state(&self, sender: Address, sender_id: SenderId) -> SenderState {
+        let info = self.sender_info.get(&sender_id);
+        let mut state = SenderState {
+            sender,
+            on_chain_nonce: info.map(|info| info.state_nonce),
+            balance: info.map(|info| info.balance),
+            ..Default::default()
+        };
 
-    /// Returns all transactions sent from the given sender.
-    pub(crate) fn get_transactions_by_sender(
-        &self,
-        sender: SenderId,
-    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
+        let mut next_nonce = state.on_chain_nonce;
+        for (id, tx) in self.all_transactions.txs_iter(sender_id) {
+            let hash = *tx.transaction.hash();
+            let nonce = id.nonce;
+            if state.nonce_gap.is_none() {
+                state.nonce_gap = next_nonce.filter(|next| *next < nonce);
+            }
+            if state.nonce_gap.is_some() {
+                state.blocked.push(hash);
+            }
+            next_nonce = Some(nonce + 1);
+            state.transactions.push(SenderTransaction { hash, nonce, subpool: tx.subpool });
+        }
+
+        state
     }
 
     /// Updates the transactions for the changed senders.
@@ -399,8 +529,8 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Updates the entire pool after a new block was mined.
     ///
-    /// This removes all mined transactions, updates according to the new base fee and rechecks
-    /// sender allowance.
+    /// This removes all mined transactions, updates according to the new base fee, rechecks
+    /// sender allowance and enforces the max number of transaction slots per sender.
     pub(crate) fn on_canonical_state_change(
         &mut self,
         block_info: BlockInfo,
@@ -419,11 +549,174 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, demoted, discarded } = self.update_accounts(changed_senders);
+
+        // adjust the sub-pool limits to the current share of blob transactions
+        let size = self.size();
+        if self.config.rebalance_memory_budget(size) {
+            self.blob_pool.set_max_blob_count(self.config.max_blob_count);
+            self.update_max_encoded_sizes();
+        }
+
+        // remove the transactions that exceed a lowered slot limit
+        let evicted = self.enforce_max_account_slots();
+
+        // remove the transactions that were parked for too long, unless this is deferred to the
+        // maintenance task
+        let expired = self
+            .config
+            .transaction_lifetime
+            .filter(|_| !self.config.deferred_maintenance)
+            .map(|lifetime| self.remove_expired(lifetime, usize::MAX))
+            .unwrap_or_default();
 
         self.metrics.performed_state_updates.increment(1);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            demoted,
+            discarded,
+            evicted,
+            expired,
+        }
+    }
+
+    /// Removes all transactions that are parked in the queued or basefee sub-pool and were added
+    /// longer than `lifetime` ago. The descendants of removed transactions are removed as well.
+    ///
+    /// Local transactions are exempt.
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    ///
+    /// At most `budget` transactions are removed, including descendants.
+    pub(crate) fn remove_expired(
+        &mut self,
+        lifetime: Duration,
+        budget: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let expired = self
+            .all_transactions
+            .txs
+            .iter()
+            .filter(|(_, tx)| {
+                matches!(tx.subpool, SubPool::Queued | SubPool::BaseFee) &&
+                    !tx.transaction.is_local() &&
+                    tx.transaction.timestamp.elapsed() > lifetime
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let mut removed = Vec::new();
+        for id in expired {
+            if removed.len() >= budget {
+                break
+            }
+            // the transaction may have been removed as a descendant of an expired transaction
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+
+        if !removed.is_empty() {
+            self.update_size_metrics();
+        }
+        removed
+    }
+
+    /// Removes the transactions of all senders that occupy more than `max_account_slots` slots,
+    /// which is only possible if the limit was lowered with [`Self::set_max_account_slots`].
+    ///
+    /// The transactions with the highest nonces are removed. Local transactions are exempt, like
+    /// they are when they are inserted.
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    fn enforce_max_account_slots(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let max_account_slots = self.all_transactions.max_account_slots;
+        let exceeding = self
+            .all_transactions
+            .tx_counter
+            .iter()
+            .filter(|(_, count)| **count > max_account_slots)
+            .map(|(sender, _)| *sender)
+            .collect::<Vec<_>>();
+
+        let mut removed = Vec::new();
+        for sender in exceeding {
+            // the first transaction that exceeds the limit, all its descendants are removed as well
+            let Some(id) = self
+                .all_transactions
+                .txs_iter(sender)
+                .nth(max_account_slots)
+                .filter(|(_, tx)| {
+                    !self
+                        .all_transactions
+                        .local_transactions_config
+                        .is_local(tx.transaction.origin, tx.transaction.sender())
+                })
+                .map(|(id, _)| *id)
+            else {
+                continue
+            };
+
+            trace!(target: "txpool", ?sender, max_account_slots, "removing transactions exceeding the slot limit");
+
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+
+        removed
+    }
+
+    /// Removes the transactions of all peers that occupy more than
+    /// [`PoolConfig::max_transactions_per_peer`] slots, at most `budget` transactions in total.
+    ///
+    /// The parked transactions of a peer are removed before its pending transactions, the
+    /// transactions with the highest nonces first, each with all its descendants. Local
+    /// transactions are exempt.
+    fn enforce_peer_quota(
+        &mut self,
+        budget: usize,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        let quota = self.config.max_transactions_per_peer;
+        let mut by_peer = HashMap::<PeerId, Vec<(SubPool, TransactionId)>>::new();
+        for (id, tx) in &self.all_transactions.txs {
+            let Some(peer) = tx.transaction.source.peer() else { continue };
+            if !self
+                .all_transactions
+                .local_transactions_config
+                .is_local(tx.transaction.origin, tx.transaction.sender())
+            {
+                by_peer.entry(*peer).or_default().push((tx.subpool, *id));
+            }
+        }
+
+        for (peer, mut txs) in by_peer.into_iter().filter(|(_, txs)| txs.len() > quota) {
+            trace!(target: "txpool", ?peer, quota, count = txs.len(), "removing transactions exceeding the peer quota");
+
+            // worst first: parked before pending, the highest nonces first
+            txs.sort_unstable_by_key(|(subpool, id)| (*subpool, Reverse(id.nonce)));
+            let mut excess = txs.len() - quota;
+            for (_, id) in txs {
+                if excess == 0 || removed.len() >= budget {
+                    break
+                }
+                // the transaction may already be removed as a descendant
+                let Some(tx) = self.remove_transaction(&id) else { continue };
+                let from = removed.len();
+                removed.push(tx);
+                self.remove_descendants(&id, removed);
+
+                let source = TransactionSource::Peer(peer);
+                let removed_of_peer = removed[from..].iter().filter(|tx| tx.source == source);
+                excess = excess.saturating_sub(removed_of_peer.count());
+            }
+        }
     }
 
     /// Update sub-pools size metrics.
@@ -431,12 +724,17 @@ impl<T: TransactionOrdering> TxPool<T> {
         let stats = self.size();
         self.metrics.pending_pool_transactions.set(stats.pending as f64);
         self.metrics.pending_pool_size_bytes.set(stats.pending_size as f64);
+        self.metrics.pending_pool_encoded_size_bytes.set(stats.pending_encoded_size as f64);
         self.metrics.basefee_pool_transactions.set(stats.basefee as f64);
         self.metrics.basefee_pool_size_bytes.set(stats.basefee_size as f64);
+        self.metrics.basefee_pool_encoded_size_bytes.set(stats.basefee_encoded_size as f64);
         self.metrics.queued_pool_transactions.set(stats.queued as f64);
         self.metrics.queued_pool_size_bytes.set(stats.queued_size as f64);
+        self.metrics.queued_pool_encoded_size_bytes.set(stats.queued_encoded_size as f64);
         self.metrics.blob_pool_transactions.set(stats.blob as f64);
         self.metrics.blob_pool_size_bytes.set(stats.blob_size as f64);
+        self.metrics.blob_pool_encoded_size_bytes.set(stats.blob_encoded_size as f64);
+        self.metrics.blob_pool_blobs.set(stats.blob_count as f64);
         self.metrics.total_transactions.set(stats.total as f64);
     }
 
@@ -487,7 +785,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, demoted, discarded } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
 
@@ -496,11 +794,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                     AddedTransaction::Pending(AddedPendingTransaction {
                         transaction,
                         promoted,
+                        demoted,
                         discarded,
                         replaced,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to, replaced }
+                    AddedTransaction::Parked { transaction, subpool: move_to, replaced, demoted }
                 };
 
                 // Update size metrics after adding and potentially moving transactions.
@@ -558,6 +857,15 @@ impl<T: TransactionOrdering> TxPool<T> {
                             transaction.tx_type(),
                         ),
                     )),
+                    InsertErr::Eip7702Conflict { transaction, conflict } => {
+                        let kind = match conflict {
+                            Eip7702Conflict::InflightTxLimitReached => {
+                                PoolErrorKind::InflightTxLimitReached(transaction.sender())
+                            }
+                            Eip7702Conflict::AuthorityReserved => PoolErrorKind::AuthorityReserved,
+                        };
+                        Err(PoolError::new(*transaction.hash(), kind))
+                    }
                 }
             }
         }
@@ -579,11 +887,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                 }
                 Destination::Pool(move_to) => {
                     debug_assert_ne!(&move_to, &current, "destination must be different");
-                    let moved = self.move_transaction(current, move_to, &id);
-                    if matches!(move_to, SubPool::Pending) {
-                        if let Some(tx) = moved {
-                            outcome.promoted.push(tx);
-                        }
+                    if let Some(tx) = self.move_transaction(current, move_to, &id) {
+                        outcome.record_move(current, move_to, &tx, &self.metrics);
                     }
                 }
             }
@@ -663,12 +968,16 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        match pool {
+        let tx = match pool {
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::Pending => self.pending_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
             SubPool::Blob => self.blob_pool.remove_transaction(tx),
+        };
+        if tx.is_some() {
+            self.metrics.inc_removed(pool);
         }
+        tx
     }
 
     /// Removes the transaction from the given pool and advance sub-pool internal state, with the
@@ -678,12 +987,16 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        match pool {
+        let tx = match pool {
             SubPool::Pending => self.pending_pool.remove_transaction(tx),
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
             SubPool::Blob => self.blob_pool.remove_transaction(tx),
+        };
+        if tx.is_some() {
+            self.metrics.inc_removed(pool);
         }
+        tx
     }
 
     /// Removes _only_ the descendants of the given transaction from the __entire__ pool.
@@ -717,6 +1030,7 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: Arc<ValidPoolTransaction<T::Transaction>>,
     ) {
+        self.metrics.inc_added(pool);
         match pool {
             SubPool::Queued => {
                 self.queued_pool.add_transaction(tx);
@@ -752,17 +1066,37 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Ensures that the transactions in the sub-pools are within the given bounds.
     ///
     /// If the current size exceeds the given bounds, the worst transactions are evicted from the
-    /// pool and returned.
+    /// pool and returned. Local transactions are exempt, so a sub-pool can remain above its bounds
+    /// if it only contains local transactions.
     ///
     /// This returns all transactions that were removed from the entire pool.
     pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.discard_worst_bounded(usize::MAX)
+    }
+
+    /// Same as [`Self::discard_worst`], but stops once `budget` transactions were removed, which
+    /// can leave sub-pools above their bounds.
+    ///
+    /// Note: a sub-pool that exceeds its size bounds can overshoot the budget, because the number
+    /// of transactions to remove is only known by count.
+    pub(crate) fn discard_worst_bounded(
+        &mut self,
+        budget: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let start = Instant::now();
         let mut removed = Vec::new();
 
+        // first evict the transactions of peers above their quota, so that a single peer can't
+        // crowd out the transactions of others
+        if self.is_exceeded() {
+            self.enforce_peer_quota(budget, &mut removed);
+        }
+
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
             ($this:ident, $removed:ident, [$($limit:ident => $pool:ident),* $(,)*]) => {
                 $ (
-                while $this.$pool.exceeds(&$this.config.$limit)
+                while removed.len() < budget && $this.$pool.exceeds(&$this.config.$limit)
                     {
                         trace!(
                             target: "txpool",
@@ -773,8 +1107,19 @@ impl<T: TransactionOrdering> TxPool<T> {
                             $this.$pool.len(),
                         );
 
-                        // 1. first remove the worst transaction from the subpool
-                        let removed_from_subpool = $this.$pool.truncate_pool($this.config.$limit.clone());
+                        // 1. first remove the worst transaction from the subpool, at most the
+                        // remaining budget by count
+                        let limit = SubPoolLimit {
+                            max_txs: $this
+                                .config
+                                .$limit
+                                .max_txs
+                                .max($this.$pool.len().saturating_sub(budget - removed.len())),
+                            ..$this.config.$limit
+                        };
+                        let locals = &$this.config.local_transactions_config;
+                        let removed_from_subpool =
+                            $this.$pool.truncate_pool_with_locals(limit, locals);
 
                         trace!(
                             target: "txpool",
@@ -786,6 +1131,11 @@ impl<T: TransactionOrdering> TxPool<T> {
                             $this.$pool.len()
                         );
 
+                        // the remaining transactions are exempt from eviction
+                        if removed_from_subpool.is_empty() {
+                            break
+                        }
+
                         // 2. remove all transactions from the total set
                         for tx in removed_from_subpool {
                             $this.all_transactions.remove_transaction(tx.id());
@@ -813,6 +1163,10 @@ impl<T: TransactionOrdering> TxPool<T> {
             ]
         );
 
+        if !removed.is_empty() {
+            self.metrics.record_truncation(removed.len(), start.elapsed());
+        }
+
         removed
     }
 
@@ -876,6 +1230,10 @@ impl<T: TransactionOrdering> TxPool<T> {
     pub(crate) const fn queued(&self) -> &ParkedPool<QueuedOrd<T::Transaction>> {
         &self.queued_pool
     }
+
+    pub(crate) const fn config(&self) -> &PoolConfig {
+        &self.config
+    }
 }
 
 impl<T: TransactionOrdering> fmt::Debug for TxPool<T> {
@@ -897,20 +1255,25 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
-    /// _All_ transactions identified by their hash.
-    by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+    /// _All_ transactions identified by their hash, and by their sender.
+    ///
+    /// This is shared with the [`PoolInner`](crate::pool::PoolInner), see [`TransactionIndex`].
+    by_hash: Arc<TransactionIndex<T>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// Tracks the EIP-7702 transactions in the pool by the authorities of their authorization
+    /// lists.
+    auths: FxHashMap<SenderId, HashSet<TxHash>>,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
     last_seen_block_hash: B256,
     /// Expected blob and base fee for the pending block.
     pending_fees: PendingFees,
-    /// Configured price bump settings for replacements
-    price_bumps: PriceBumpConfig,
+    /// Configured policy for replacements
+    replacement_policy: Arc<dyn ReplacementPolicy>,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// All Transactions metrics
@@ -922,7 +1285,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
-            price_bumps: config.price_bumps,
+            replacement_policy: config.replacement_policy(),
             local_transactions_config: config.local_transactions_config.clone(),
             ..Default::default()
         }
@@ -931,19 +1294,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
     /// Returns an iterator over all _unique_ hashes in the pool
     #[allow(dead_code)]
     pub(crate) fn hashes_iter(&self) -> impl Iterator<Item = TxHash> + '_ {
-        self.by_hash.keys().copied()
-    }
-
-    /// Returns an iterator over all _unique_ hashes in the pool
-    pub(crate) fn transactions_iter(
-        &self,
-    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
-        self.by_hash.values().cloned()
+        self.by_hash.transactions().into_iter().map(|tx| *tx.hash())
     }
 
     /// Returns if the transaction for the given hash is already included in this pool
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
-        self.by_hash.contains_key(tx_hash)
+        self.by_hash.contains(tx_hash)
+    }
+
+    /// Returns the index of all transactions, which can be read without holding the pool lock.
+    pub(crate) fn index(&self) -> Arc<TransactionIndex<T>> {
+        Arc::clone(&self.by_hash)
     }
 
     /// Returns the internal transaction with additional metadata
@@ -972,6 +1333,31 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Tracks the authorities of the given EIP-7702 transaction.
+    fn add_auths(&mut self, tx: &ValidPoolTransaction<T>) {
+        for authority in tx.authority_ids().unwrap_or_default() {
+            self.auths.entry(*authority).or_default().insert(*tx.hash());
+        }
+    }
+
+    /// Removes the tracked authorities of the given EIP-7702 transaction.
+    fn remove_auths(&mut self, tx: &ValidPoolTransaction<T>) {
+        for authority in tx.authority_ids().unwrap_or_default() {
+            if let hash_map::Entry::Occupied(mut entry) = self.auths.entry(*authority) {
+                entry.get_mut().remove(tx.hash());
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+    }
+
+    /// Returns true if an EIP-7702 transaction in the pool carries an authorization of the given
+    /// authority.
+    pub(crate) fn has_pending_auth(&self, authority: SenderId) -> bool {
+        self.auths.contains_key(&authority)
+    }
+
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
         let BlockInfo {
@@ -1087,6 +1473,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
             // Update the first transaction of this sender.
             Self::update_tx_base_fee(self.pending_fees.base_fee, tx);
+            Self::update_tx_blob_fee(self.pending_fees.blob_fee, tx);
             // Track if the transaction's sub-pool changed.
             Self::record_subpool_update(&mut updates, tx);
 
@@ -1142,6 +1529,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
                 // Update and record sub-pool changes.
                 Self::update_tx_base_fee(self.pending_fees.base_fee, tx);
+                Self::update_tx_blob_fee(self.pending_fees.blob_fee, tx);
                 Self::record_subpool_update(&mut updates, tx);
 
                 // Advance iterator
@@ -1182,6 +1570,19 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Rechecks the blob fee condition of a blob transaction.
+    ///
+    /// The blob fee moves independently of the base fee, so blob transactions are moved between
+    /// the pending and blob sub-pools when the blob fee crosses their blob fee cap.
+    fn update_tx_blob_fee(pending_blob_fee: u128, tx: &mut PoolInternalTransaction<T>) {
+        let Some(blob_fee_cap) = tx.transaction.max_fee_per_blob_gas() else { return };
+        if blob_fee_cap >= pending_blob_fee {
+            tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+        } else {
+            tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+        }
+    }
+
     /// Returns an iterator over all transactions for the given sender, starting with the lowest
     /// nonce
     pub(crate) fn txs_iter(
@@ -1247,6 +1648,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(&tx.transaction_id)?;
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
+        self.remove_auths(&tx);
         self.update_size_metrics();
         Some((tx, internal.subpool))
     }
@@ -1264,6 +1666,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
+        self.remove_auths(&internal.transaction);
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1288,6 +1691,31 @@ impl<T: PoolTransaction> AllTransactions<T> {
         false
     }
 
+    /// Checks the EIP-7702 authorization constraints of the given transaction.
+    ///
+    /// A pending authorization can change the code of its authority, which invalidates the
+    /// in-flight transactions of the authority. Therefore:
+    ///   - a sender that is the authority of an authorization in the pool can only have a single
+    ///     in-flight transaction, which can still be replaced.
+    ///   - an authorization is rejected if its authority already has in-flight transactions, other
+    ///     than the transaction carrying the authorization itself.
+    fn ensure_valid_auths(&self, tx: &ValidPoolTransaction<T>) -> Result<(), Eip7702Conflict> {
+        let sender = tx.sender_id();
+        let sender_txs = self.tx_counter.get(&sender).copied().unwrap_or_default();
+        if self.has_pending_auth(sender) && sender_txs > 0 && !self.txs.contains_key(tx.id()) {
+            return Err(Eip7702Conflict::InflightTxLimitReached)
+        }
+
+        for authority in tx.authority_ids().unwrap_or_default() {
+            let count = self.tx_counter.get(authority).copied().unwrap_or_default();
+            if count > 1 || (count == 1 && *authority != sender) {
+                return Err(Eip7702Conflict::AuthorityReserved)
+            }
+        }
+
+        Ok(())
+    }
+
     /// Additional checks for a new transaction.
     ///
     /// This will enforce all additional rules in the context of this pool, such as:
@@ -1296,6 +1724,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
     ///   - Ensures transaction types are not conflicting for the sender: blob vs normal
     ///     transactions are mutually exclusive for the same sender.
+    ///   - EIP-7702 authorizations: see [`Self::ensure_valid_auths`].
     fn ensure_valid(
         &self,
         transaction: ValidPoolTransaction<T>,
@@ -1322,6 +1751,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
             return Err(InsertErr::TxTypeConflict { transaction: Arc::new(transaction) })
         }
 
+        if let Err(conflict) = self.ensure_valid_auths(&transaction) {
+            return Err(InsertErr::Eip7702Conflict { transaction: Arc::new(transaction), conflict })
+        }
+
         Ok(transaction)
     }
 
@@ -1386,49 +1819,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 
     /// Returns true if the replacement candidate is underpriced and can't replace the existing
-    /// transaction.
+    /// transaction, according to the configured [`ReplacementPolicy`].
     #[inline]
     fn is_underpriced(
+        &self,
         existing_transaction: &ValidPoolTransaction<T>,
         maybe_replacement: &ValidPoolTransaction<T>,
-        price_bumps: &PriceBumpConfig,
     ) -> bool {
-        let price_bump = price_bumps.price_bump(existing_transaction.tx_type());
-
-        if maybe_replacement.max_fee_per_gas() <=
-            existing_transaction.max_fee_per_gas() * (100 + price_bump) / 100
-        {
-            return true
-        }
-
-        let existing_max_priority_fee_per_gas =
-            existing_transaction.transaction.max_priority_fee_per_gas().unwrap_or(0);
-        let replacement_max_priority_fee_per_gas =
-            maybe_replacement.transaction.max_priority_fee_per_gas().unwrap_or(0);
-
-        if replacement_max_priority_fee_per_gas <=
-            existing_max_priority_fee_per_gas * (100 + price_bump) / 100 &&
-            existing_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas != 0
-        {
-            return true
-        }
-
-        // check max blob fee per gas
-        if let Some(existing_max_blob_fee_per_gas) =
-            existing_transaction.transaction.max_fee_per_blob_gas()
-        {
-            // this enforces that blob txs can only be replaced by blob txs
-            let replacement_max_blob_fee_per_gas =
-                maybe_replacement.transaction.max_fee_per_blob_gas().unwrap_or(0);
-            if replacement_max_blob_fee_per_gas <=
-                existing_max_blob_fee_per_gas * (100 + price_bump) / 100
-            {
-                return true
-            }
-        }
-
-        false
+        self.replacement_policy.is_underpriced(
+            &ReplacementFees::new(&existing_transaction.transaction),
+            &ReplacementFees::new(&maybe_replacement.transaction),
+            self.len(),
+        )
     }
 
     /// Inserts a new _valid_ transaction into the pool.
@@ -1536,7 +1938,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         match self.txs.entry(*transaction.id()) {
             Entry::Vacant(entry) => {
                 // Insert the transaction in both maps
-                self.by_hash.insert(*pool_tx.transaction.hash(), pool_tx.transaction.clone());
+                self.by_hash.insert(pool_tx.transaction.clone());
                 entry.insert(pool_tx);
             }
             Entry::Occupied(mut entry) => {
@@ -1545,23 +1947,27 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let maybe_replacement = transaction.as_ref();
 
                 // Ensure the new transaction is not underpriced
-                if Self::is_underpriced(existing_transaction, maybe_replacement, &self.price_bumps)
-                {
+                if self.is_underpriced(existing_transaction, maybe_replacement) {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
                     })
                 }
-                let new_hash = *pool_tx.transaction.hash();
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
                 self.by_hash.remove(replaced.transaction.hash());
-                self.by_hash.insert(new_hash, new_transaction);
+                self.by_hash.insert(new_transaction);
                 // also remove the hash
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
             }
         }
 
+        // track the authorities of the new transaction instead of the replaced one
+        if let Some((replaced, _)) = &replaced_tx {
+            self.remove_auths(replaced);
+        }
+        self.add_auths(&transaction);
+
         // The next transaction of this sender
         let on_chain_id = TransactionId::new(transaction.sender_id(), on_chain_nonce);
         {
@@ -1688,10 +2094,11 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            auths: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
-            price_bumps: Default::default(),
+            replacement_policy: Arc::new(EthReplacementPolicy::default()),
             local_transactions_config: Default::default(),
             metrics: Default::default(),
         }
@@ -1746,6 +2153,18 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     },
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     TxTypeConflict { transaction: Arc<ValidPoolTransaction<T>> },
+    /// Thrown if the transaction conflicts with a pending EIP-7702 authorization.
+    Eip7702Conflict { transaction: Arc<ValidPoolTransaction<T>>, conflict: Eip7702Conflict },
+}
+
+/// The EIP-7702 authorization constraints a new transaction can violate, see
+/// [`AllTransactions::ensure_valid_auths`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Eip7702Conflict {
+    /// The sender has a pending authorization and already has an in-flight transaction.
+    InflightTxLimitReached,
+    /// An authority of the transaction already has in-flight transactions.
+    AuthorityReserved,
 }
 
 /// Transaction was successfully inserted into the pool
@@ -1795,13 +2214,34 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
 pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     /// transactions promoted to the pending pool
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved from the pending pool to the given parked pool
+    pub(crate) demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
     /// transaction that failed and were discarded
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
+impl<T: PoolTransaction> UpdateOutcome<T> {
+    /// Records a transaction that was moved between two sub-pools if it was promoted or demoted.
+    fn record_move(
+        &mut self,
+        from: SubPool,
+        to: SubPool,
+        tx: &Arc<ValidPoolTransaction<T>>,
+        metrics: &TxPoolMetrics,
+    ) {
+        if to.is_pending() && !from.is_pending() {
+            metrics.record_promotion(tx.timestamp.elapsed());
+            self.promoted.push(Arc::clone(tx));
+        } else if from.is_pending() && !to.is_pending() {
+            metrics.demoted_transactions.increment(1);
+            self.demoted.push((Arc::clone(tx), to));
+        }
+    }
+}
+
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], demoted: vec![], discarded: vec![] }
     }
 }
 
@@ -1983,6 +2423,68 @@ mod tests {
         assert!(pool.blob_pool.is_empty());
     }
 
+    #[test]
+    fn test_demote_blob_tx_on_canonical_state_change() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let tx = MockTransaction::eip4844().inc_price().inc_limit();
+
+        let mut block_info = pool.block_info();
+        block_info.pending_blob_fee = Some(tx.max_fee_per_blob_gas().unwrap());
+        pool.set_block_info(block_info);
+
+        let validated = f.validated(tx.clone());
+        let id = *validated.id();
+        pool.add_transaction(validated, on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        // the blob fee of the next block exceeds the cap of the tx
+        block_info.pending_blob_fee = Some(tx.max_fee_per_blob_gas().unwrap() + 1);
+        let outcome = pool.on_canonical_state_change(block_info, vec![], Default::default());
+        assert_eq!(outcome.demoted.len(), 1);
+
+        let internal_tx = pool.all_transactions.txs.get(&id).unwrap();
+        assert!(!internal_tx.state.contains(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK));
+        assert_eq!(internal_tx.subpool, SubPool::Blob);
+        assert_eq!(pool.blob_pool.len(), 1);
+
+        // and drops back below it
+        block_info.pending_blob_fee = Some(tx.max_fee_per_blob_gas().unwrap());
+        let outcome = pool.on_canonical_state_change(block_info, vec![], Default::default());
+        assert_eq!(outcome.promoted.len(), 1);
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert!(pool.blob_pool.is_empty());
+    }
+
+    #[test]
+    fn test_demote_blob_tx_with_increasing_blob_fee_and_decreasing_base_fee() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let tx = MockTransaction::eip4844().inc_price().inc_limit();
+
+        let mut block_info = pool.block_info();
+        block_info.pending_basefee = 10;
+        block_info.pending_blob_fee = Some(tx.max_fee_per_blob_gas().unwrap());
+        pool.set_block_info(block_info);
+
+        let validated = f.validated(tx.clone());
+        let id = *validated.id();
+        pool.add_transaction(validated, on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        block_info.pending_basefee = 9;
+        block_info.pending_blob_fee = Some(tx.max_fee_per_blob_gas().unwrap() + 1);
+        pool.set_block_info(block_info);
+
+        let internal_tx = pool.all_transactions.txs.get(&id).unwrap();
+        assert_eq!(internal_tx.subpool, SubPool::Blob);
+        assert!(pool.pending_pool.is_empty());
+    }
+
     /// A struct representing a txpool promotion test instance
     #[derive(Debug, PartialEq, Eq, Clone, Hash)]
     struct PromotionTest {
@@ -2403,6 +2905,59 @@ mod tests {
         assert!(matches!(err, InsertErr::TxTypeConflict { .. }), "{err:?}");
     }
 
+    #[test]
+    fn insert_eip7702_authority_conflicts() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+
+        // the authority already has an in-flight transaction
+        let authority_tx = MockTransaction::eip1559().inc_price().inc_limit();
+        let authority = authority_tx.get_sender();
+        pool.insert_tx(f.validated(authority_tx.clone()), on_chain_balance, on_chain_nonce)
+            .unwrap();
+        let set_code = MockTransaction::eip7702_with_authorities(vec![authority]).inc_limit();
+        let err = pool
+            .insert_tx(f.validated(set_code.clone()), on_chain_balance, on_chain_nonce)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                InsertErr::Eip7702Conflict { conflict: Eip7702Conflict::AuthorityReserved, .. }
+            ),
+            "{err:?}"
+        );
+
+        // once the authority's transaction is gone, the authorization is accepted
+        pool.remove_transaction_by_hash(&authority_tx.get_hash()).unwrap();
+        pool.insert_tx(f.validated(set_code.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(pool.has_pending_auth(f.ids.sender_id(&authority).unwrap()));
+
+        // the authority can only have a single in-flight transaction, which can be replaced
+        let first = authority_tx.rng_hash();
+        pool.insert_tx(f.validated(first.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        let err = pool
+            .insert_tx(f.validated(first.next()), on_chain_balance, on_chain_nonce)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                InsertErr::Eip7702Conflict {
+                    conflict: Eip7702Conflict::InflightTxLimitReached,
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+        let replacement = first.inc_price_by(first.get_gas_price()).rng_hash();
+        pool.insert_tx(f.validated(replacement), on_chain_balance, on_chain_nonce).unwrap();
+
+        // removing the authorization lifts the limit
+        pool.remove_transaction_by_hash(&set_code.get_hash()).unwrap();
+        assert!(!pool.has_pending_auth(f.ids.sender_id(&authority).unwrap()));
+    }
+
     // insert nonce then nonce - 1
     #[test]
     fn insert_previous() {
@@ -2595,10 +3150,13 @@ mod tests {
 
         assert_eq!(pool.pending_pool.len(), 1);
 
-        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64);
+        let mut outcome = UpdateOutcome::default();
+        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64, &mut outcome);
 
         assert!(pool.pending_pool.is_empty());
         assert_eq!(pool.basefee_pool.len(), 1);
+        assert_eq!(outcome.demoted.len(), 1);
+        assert_eq!(outcome.demoted[0].1, SubPool::BaseFee);
 
         assert_eq!(pool.all_transactions.txs.get(&id).unwrap().subpool, SubPool::BaseFee)
     }
@@ -2684,6 +3242,131 @@ mod tests {
         assert_eq!(pool.pending_pool.len(), 1);
     }
 
+    #[test]
+    fn enforce_lowered_max_account_slots() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let mut tx = MockTransaction::eip1559().inc_price_by(10);
+        for _ in 0..4 {
+            pool.add_transaction(f.validated(tx.clone()), U256::from(1_000), 0).unwrap();
+            tx = tx.next();
+        }
+        assert_eq!(pool.pending_pool.len(), 4);
+
+        pool.set_max_account_slots(2);
+        assert_eq!(pool.max_account_slots(), 2);
+
+        // new transactions are rejected right away
+        let err = pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::SpammerExceededCapacity(_)));
+
+        // existing transactions are removed on the next canonical state change
+        let outcome =
+            pool.on_canonical_state_change(pool.block_info(), Vec::new(), HashMap::new());
+        assert_eq!(outcome.evicted.len(), 2);
+        assert!(outcome.discarded.is_empty());
+        assert_eq!(pool.pending_pool.len(), 2);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn evict_expired_parked_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            transaction_lifetime: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        let added_at = Instant::now() - Duration::from_secs(120);
+
+        // a pending transaction is never evicted
+        let mut pending = f.validated(MockTransaction::eip1559());
+        pending.timestamp = added_at;
+        pool.add_transaction(pending, U256::MAX, 0).unwrap();
+
+        // a queued transaction with a nonce gap and its descendant
+        let tx = MockTransaction::eip1559().inc_nonce();
+        let mut queued = f.validated(tx.clone());
+        queued.timestamp = added_at;
+        pool.add_transaction(queued, U256::MAX, 0).unwrap();
+        pool.add_transaction(f.validated(tx.next()), U256::MAX, 0).unwrap();
+
+        // local transactions are exempt
+        let mut local = f.validated_with_origin(
+            TransactionOrigin::Local,
+            MockTransaction::eip1559().inc_nonce(),
+        );
+        local.timestamp = added_at;
+        pool.add_transaction(local, U256::MAX, 0).unwrap();
+
+        let outcome =
+            pool.on_canonical_state_change(pool.block_info(), Vec::new(), HashMap::new());
+        assert_eq!(outcome.expired.len(), 2);
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 1);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn discard_worst_within_budget() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            queued_limit: SubPoolLimit::new(2, usize::MAX),
+            deferred_maintenance: true,
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        // queued transactions of different senders, each with a nonce gap
+        for _ in 0..6 {
+            let tx = f.validated(MockTransaction::eip1559().inc_nonce());
+            pool.add_transaction(tx, U256::MAX, 0).unwrap();
+        }
+        assert_eq!(pool.queued_pool.len(), 6);
+
+        assert_eq!(pool.discard_worst_bounded(3).len(), 3);
+        assert_eq!(pool.queued_pool.len(), 3);
+        assert_eq!(pool.discard_worst_bounded(3).len(), 1);
+        assert_eq!(pool.queued_pool.len(), 2);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn discard_transactions_of_peers_above_quota() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            queued_limit: SubPoolLimit::new(4, usize::MAX),
+            max_transactions_per_peer: 2,
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        let peer = PeerId::random();
+
+        // queued transactions of a single peer
+        let tx = MockTransaction::eip1559().inc_nonce();
+        let mut txs = vec![tx.clone(), tx.next(), tx.next().next()];
+        txs.extend((0..2).map(|_| MockTransaction::eip1559().inc_nonce()));
+        for tx in txs {
+            let mut tx = f.validated(tx);
+            tx.source = TransactionSource::Peer(peer);
+            pool.add_transaction(tx, U256::MAX, 0).unwrap();
+        }
+
+        // a queued transaction of an unknown source
+        let other = f.validated(MockTransaction::eip1559().inc_nonce());
+        pool.add_transaction(other.clone(), U256::MAX, 0).unwrap();
+        assert!(pool.is_exceeded());
+
+        // the transactions of the peer above its quota are evicted, not the other transaction
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 3);
+        assert!(removed.iter().all(|tx| tx.source == TransactionSource::Peer(peer)));
+        assert!(pool.contains(other.hash()));
+        assert_eq!(pool.queued_pool.len(), 3);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn discard_with_large_blob_txs() {
         // init tracing
@@ -2742,7 +3425,7 @@ mod tests {
 
         // set the base fee of the pool
         let pool_base_fee = 100;
-        pool.update_basefee(pool_base_fee);
+        pool.update_basefee(pool_base_fee, &mut UpdateOutcome::default());
 
         // 2 txs, that should put the pool over the size limit but not max txs
         let a_txs = MockTransactionSet::dependent(a_sender, 0, 2, TxType::Eip1559)
@@ -2958,4 +3641,71 @@ mod tests {
             vec![1, 2, 3]
         );
     }
+
+    #[test]
+    fn sender_state_nonce_gap() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx0 = MockTransaction::eip1559();
+        let tx1 = tx0.next();
+        let tx3 = tx1.next().next();
+        let tx4 = tx3.next();
+        for tx in [&tx0, &tx1, &tx3, &tx4] {
+            pool.add_transaction(f.validated(tx.clone()), on_chain_balance, on_chain_nonce)
+                .unwrap();
+        }
+
+        let sender = tx0.get_sender();
+        let state = pool.sender_state(sender, f.ids.sender_id(&sender).unwrap());
+        assert_eq!(state.on_chain_nonce, Some(0));
+        assert_eq!(state.pool_nonces().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+        assert_eq!(state.nonce_gap, Some(2));
+        assert_eq!(state.blocked, vec![tx3.get_hash(), tx4.get_hash()]);
+        assert_eq!(state.transactions[0].subpool, SubPool::Pending);
+        assert_eq!(state.transactions[2].subpool, SubPool::Queued);
+    }
+
+    #[test]
+    fn paginate_subpools() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        // three pending transactions and one queued transaction
+        let tx0 = MockTransaction::eip1559();
+        let tx1 = tx0.next();
+        let tx2 = tx1.next();
+        let gapped = tx2.next().next();
+        for tx in [&tx0, &tx1, &tx2, &gapped] {
+            pool.add_transaction(f.validated(tx.clone()), on_chain_balance, on_chain_nonce)
+                .unwrap();
+        }
+
+        let page = pool.pending_transactions_page(None, 2);
+        assert_eq!(
+            page.transactions.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![tx0.get_hash(), tx1.get_hash()]
+        );
+        let cursor = page.next_cursor.unwrap();
+
+        // removing a transaction of the previous page does not shift the next page
+        pool.prune_transaction_by_hash(&tx0.get_hash());
+        let page = pool.pending_transactions_page(Some(cursor), 2);
+        assert_eq!(
+            page.transactions.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![tx2.get_hash()]
+        );
+        assert!(page.next_cursor.is_none());
+
+        let page = pool.queued_transactions_page(None, 10);
+        assert_eq!(
+            page.transactions.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![gapped.get_hash()]
+        );
+        assert!(page.next_cursor.is_none());
+    }
 }
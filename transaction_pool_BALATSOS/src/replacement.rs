@@ -0,0 +1,197 @@
+use crate::{traits::PoolTransaction, PriceBumpConfig, DEFAULT_PRICE_BUMP};
+use std::fmt;
+
+/// The fees of a transaction that decide whether it can replace another transaction with the same
+/// sender and nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplacementFees {
+    /// The type of the transaction.
+    pub tx_type: u8,
+    /// The max fee per gas, or the gas price for legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// The max priority fee per gas, `None` for legacy transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The max fee per blob gas, `None` for non-EIP4844 transactions.
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+impl ReplacementFees {
+    /// Returns the replacement fees of the given transaction.
+    pub fn new<T: PoolTransaction>(transaction: &T) -> Self {
+        Self {
+            tx_type: transaction.tx_type(),
+            max_fee_per_gas: transaction.max_fee_per_gas(),
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas(),
+            max_fee_per_blob_gas: transaction.max_fee_per_blob_gas(),
+        }
+    }
+}
+
+/// Decides whether a transaction can replace an existing transaction with the same sender and
+/// nonce (replace-by-fee).
+///
+/// The policy of a pool is configured with
+/// [`PoolConfig::replacement_policy`](crate::PoolConfig::replacement_policy).
+pub trait ReplacementPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns true if the replacement candidate is underpriced and can't replace the existing
+    /// transaction.
+    ///
+    /// `pool_size` is the number of transactions currently in the pool, which allows policies to
+    /// require a higher bump when the pool is under pressure.
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        pool_size: usize,
+    ) -> bool;
+}
+
+/// The default replacement policy for Ethereum.
+///
+/// The max fee and the max priority fee must be bumped by the configured [`PriceBumpConfig`]. Blob
+/// transactions can only be replaced by blob transactions that also bump the max blob fee, and
+/// require [`PriceBumpConfig::replace_blob_tx_price_bump`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthReplacementPolicy {
+    /// The required price bumps.
+    price_bumps: PriceBumpConfig,
+}
+
+impl EthReplacementPolicy {
+    /// Creates a new policy with the given price bumps.
+    pub const fn new(price_bumps: PriceBumpConfig) -> Self {
+        Self { price_bumps }
+    }
+}
+
+impl ReplacementPolicy for EthReplacementPolicy {
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        _pool_size: usize,
+    ) -> bool {
+        let price_bump = self.price_bumps.price_bump(existing.tx_type);
+
+        if is_underpriced_fees(existing, replacement, price_bump) {
+            return true
+        }
+
+        // check max blob fee per gas
+        if let Some(existing_max_blob_fee_per_gas) = existing.max_fee_per_blob_gas {
+            // this enforces that blob txs can only be replaced by blob txs
+            let replacement_max_blob_fee_per_gas = replacement.max_fee_per_blob_gas.unwrap_or(0);
+            if replacement_max_blob_fee_per_gas <=
+                existing_max_blob_fee_per_gas * (100 + price_bump) / 100
+            {
+                return true
+            }
+        }
+
+        false
+    }
+}
+
+/// The default replacement policy for OP-stack chains.
+///
+/// OP-stack chains do not support blob transactions, so every transaction type requires the same
+/// flat bump of the max fee and the max priority fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpReplacementPolicy {
+    /// The required price bump (in %).
+    price_bump: u128,
+}
+
+impl OpReplacementPolicy {
+    /// Creates a new policy with the given price bump (in %).
+    pub const fn new(price_bump: u128) -> Self {
+        Self { price_bump }
+    }
+}
+
+impl Default for OpReplacementPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRICE_BUMP)
+    }
+}
+
+impl ReplacementPolicy for OpReplacementPolicy {
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        _pool_size: usize,
+    ) -> bool {
+        is_underpriced_fees(existing, replacement, self.price_bump)
+    }
+}
+
+/// Returns true if the max fee or the max priority fee of the replacement is not bumped by at least
+/// `price_bump` (in %).
+///
+/// The priority fee is only checked if both transactions have a non-zero one.
+#[inline]
+fn is_underpriced_fees(
+    existing: &ReplacementFees,
+    replacement: &ReplacementFees,
+    price_bump: u128,
+) -> bool {
+    if replacement.max_fee_per_gas <= existing.max_fee_per_gas * (100 + price_bump) / 100 {
+        return true
+    }
+
+    let existing_max_priority_fee_per_gas = existing.max_priority_fee_per_gas.unwrap_or(0);
+    let replacement_max_priority_fee_per_gas = replacement.max_priority_fee_per_gas.unwrap_or(0);
+
+    replacement_max_priority_fee_per_gas <=
+        existing_max_priority_fee_per_gas * (100 + price_bump) / 100 &&
+        existing_max_priority_fee_per_gas != 0 &&
+        replacement_max_priority_fee_per_gas != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn eth_blob_replacement() {
+        let policy = EthReplacementPolicy::default();
+        let existing = MockTransaction::eip4844();
+
+        // the default blob price bump is 100%
+        let replacement = existing.inc_price_by(existing.get_gas_price() * 2);
+        assert!(policy.is_underpriced(
+            &ReplacementFees::new(&existing),
+            &ReplacementFees::new(&replacement),
+            0
+        ));
+
+        let replacement = replacement.inc_blob_fee_by(existing.max_fee_per_blob_gas().unwrap() * 2);
+        assert!(!policy.is_underpriced(
+            &ReplacementFees::new(&existing),
+            &ReplacementFees::new(&replacement),
+            0
+        ));
+    }
+
+    #[test]
+    fn op_flat_replacement() {
+        let policy = OpReplacementPolicy::default();
+        let existing = MockTransaction::eip1559();
+
+        let replacement = existing.inc_price_by(existing.get_gas_price() / 20);
+        assert!(policy.is_underpriced(
+            &ReplacementFees::new(&existing),
+            &ReplacementFees::new(&replacement),
+            0
+        ));
+
+        let replacement = existing.inc_price_by(existing.get_gas_price() / 5);
+        assert!(!policy.is_underpriced(
+            &ReplacementFees::new(&existing),
+            &ReplacementFees::new(&replacement),
+            0
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+//! Reputation of transaction senders, based on their rejected submissions.
+
+use crate::error::{InvalidPoolTransactionError, PoolError, PoolErrorKind};
+use parking_lot::Mutex;
+use reth_primitives::Address;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The number of tracked senders above which negligible scores are pruned.
+const REPUTATION_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Scores below this are considered negligible and are pruned.
+const NEGLIGIBLE_SCORE: f64 = 0.01;
+
+/// Configuration of the [`SenderReputation`] tracker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReputationConfig {
+    /// The score added for a transaction that is invalid, see [`PoolError::is_bad_transaction`].
+    pub invalid_penalty: f64,
+    /// The score added for a transaction that is underpriced.
+    pub underpriced_penalty: f64,
+    /// The time after which a score has decayed to half its value.
+    pub half_life: Duration,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            invalid_penalty: 10.0,
+            underpriced_penalty: 1.0,
+            half_life: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// A rejected submission that hurts the reputation of its sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offense {
+    /// The transaction can never be valid.
+    Invalid,
+    /// The transaction, or the replacement, does not pay enough fees.
+    Underpriced,
+}
+
+impl Offense {
+    /// Returns the offense of the given error, if the sender is to blame for it.
+    pub fn from_error(err: &PoolError) -> Option<Self> {
+        match &err.kind {
+            PoolErrorKind::ReplacementUnderpriced |
+            PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) |
+            PoolErrorKind::InvalidTransaction(
+                InvalidPoolTransactionError::Underpriced |
+                InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. },
+            ) => Some(Self::Underpriced),
+            _ if err.is_bad_transaction() => Some(Self::Invalid),
+            _ => None,
+        }
+    }
+}
+
+/// Tracks a spam score per sender, which is raised by every [`Offense`] and decays over time.
+///
+/// A sender with a score of `0` has a clean record. The pool validates and gossips transactions of
+/// senders with lower scores first, so that nuisance senders can't crowd out other senders.
+#[derive(Debug, Default)]
+pub struct SenderReputation {
+    /// The configured penalties.
+    config: ReputationConfig,
+    /// The score of each sender and when it was last updated.
+    scores: Mutex<HashMap<Address, (f64, Instant)>>,
+}
+
+impl SenderReputation {
+    /// Creates a new tracker with the given config.
+    pub fn new(config: ReputationConfig) -> Self {
+        Self { config, scores: Default::default() }
+    }
+
+    /// Returns the given score decayed by the elapsed time.
+    fn decay(&self, score: f64, elapsed: Duration) -> f64 {
+        let half_lives = elapsed.as_secs_f64() / self.config.half_life.as_secs_f64();
+        score * 0.5f64.powf(half_lives)
+    }
+
+    /// Raises the score of the sender for the given offense.
+    pub fn record(&self, sender: Address, offense: Offense) {
+        let penalty = match offense {
+            Offense::Invalid => self.config.invalid_penalty,
+            Offense::Underpriced => self.config.underpriced_penalty,
+        };
+
+        let now = Instant::now();
+        let mut scores = self.scores.lock();
+        if scores.len() >= REPUTATION_PRUNE_THRESHOLD {
+            // forget senders whose score has decayed, so that the map does not grow unbounded
+            scores.retain(|_, (score, updated)| {
+                self.decay(*score, now.duration_since(*updated)) >= NEGLIGIBLE_SCORE
+            });
+        }
+
+        let (score, updated) = scores.entry(sender).or_insert((0.0, now));
+        *score = self.decay(*score, now.duration_since(*updated)) + penalty;
+        *updated = now;
+    }
+
+    /// Raises the score of the sender if it is to blame for the given error.
+    pub fn record_error(&self, sender: Address, err: &PoolError) {
+        if let Some(offense) = Offense::from_error(err) {
+            self.record(sender, offense);
+        }
+    }
+
+    /// Returns the current score of the sender.
+    pub fn score(&self, sender: &Address) -> f64 {
+        self.scores
+            .lock()
+            .get(sender)
+            .map_or(0.0, |(score, updated)| self.decay(*score, updated.elapsed()))
+    }
+
+    /// Sorts the items by the score of their sender, lowest first.
+    ///
+    /// The sort is stable, so items of senders with the same score keep their order.
+    pub fn sort_by_score<I>(&self, items: &mut [I], sender: impl Fn(&I) -> Address) {
+        let now = Instant::now();
+        let scores = self.scores.lock();
+        if scores.is_empty() {
+            return
+        }
+        let score = |item: &I| {
+            scores.get(&sender(item)).map_or(0.0, |(score, updated)| {
+                self.decay(*score, now.saturating_duration_since(*updated))
+            })
+        };
+        // scores are never negative, so their bits are ordered like the scores
+        items.sort_by_cached_key(|item| score(item).to_bits());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_nuisance_senders_last() {
+        let reputation = SenderReputation::default();
+        let good = Address::random();
+        let underpriced = Address::random();
+        let invalid = Address::random();
+
+        reputation.record(invalid, Offense::Invalid);
+        reputation.record(underpriced, Offense::Underpriced);
+        reputation.record(underpriced, Offense::Underpriced);
+        assert_eq!(reputation.score(&good), 0.0);
+        assert!(reputation.score(&underpriced) > 1.9);
+        assert!(reputation.score(&invalid) > reputation.score(&underpriced));
+
+        let mut senders = vec![invalid, good, underpriced, good];
+        reputation.sort_by_score(&mut senders, |sender| *sender);
+        assert_eq!(senders, vec![good, good, underpriced, invalid]);
+    }
+
+    #[test]
+    fn score_decays() {
+        let reputation = SenderReputation::new(ReputationConfig {
+            half_life: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let sender = Address::random();
+        reputation.record(sender, Offense::Invalid);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(reputation.score(&sender) < NEGLIGIBLE_SCORE);
+    }
+}
@@ -5,8 +5,9 @@ use crate::{
     pool::txpool::TxPool,
     traits::TransactionOrigin,
     CoinbaseTipOrdering, EthBlobTransactionSidecar, EthPoolTransaction, PoolTransaction,
-    ValidPoolTransaction,
+    ValidPoolTransaction, TXPOOL_BLOB_SIZE_BYTES,
 };
+use alloy_eips::eip7702::SignedAuthorization;
 use paste::paste;
 use rand::{
     distributions::{Uniform, WeightedIndex},
@@ -18,8 +19,9 @@ use reth_primitives::{
     AccessList, Address, BlobTransactionSidecar, BlobTransactionValidationError, Bytes, ChainId,
     FromRecoveredPooledTransaction, IntoRecoveredTransaction, PooledTransactionsElementEcRecovered,
     Signature, Transaction, TransactionSigned, TransactionSignedEcRecovered,
-    TryFromRecoveredTransaction, TxEip1559, TxEip2930, TxEip4844, TxHash, TxKind, TxLegacy, TxType,
-    B256, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
+    TryFromRecoveredTransaction, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxHash, TxKind,
+    TxLegacy, TxType, B256, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
 };
 use std::{ops::Range, sync::Arc, time::Instant, vec::IntoIter};
 
@@ -47,6 +49,7 @@ macro_rules! set_value {
             MockTransaction::Legacy { ref mut $field, .. } |
             MockTransaction::Eip1559 { ref mut $field, .. } |
             MockTransaction::Eip4844 { ref mut $field, .. } |
+            MockTransaction::Eip7702 { ref mut $field, .. } |
             MockTransaction::Eip2930 { ref mut $field, .. } => {
                 *$field = new_value;
             }
@@ -61,6 +64,7 @@ macro_rules! get_value {
             MockTransaction::Legacy { $field, .. } |
             MockTransaction::Eip1559 { $field, .. } |
             MockTransaction::Eip4844 { $field, .. } |
+            MockTransaction::Eip7702 { $field, .. } |
             MockTransaction::Eip2930 { $field, .. } => $field.clone(),
         }
     };
@@ -201,6 +205,41 @@ pub enum MockTransaction {
         /// The size of the transaction, returned in the implementation of [`PoolTransaction`].
         size: usize,
     },
+    /// EIP-7702 transaction type.
+    Eip7702 {
+        /// The chain id of the transaction.
+        chain_id: ChainId,
+        /// The hash of the transaction.
+        hash: B256,
+        /// The sender's address.
+        sender: Address,
+        /// The transaction nonce.
+        nonce: u64,
+        /// The maximum fee per gas for the transaction.
+        max_fee_per_gas: u128,
+        /// The maximum priority fee per gas for the transaction.
+        max_priority_fee_per_gas: u128,
+        /// The gas limit for the transaction.
+        gas_limit: u64,
+        /// The transaction's destination.
+        to: TxKind,
+        /// The value of the transaction.
+        value: U256,
+        /// The access list associated with the transaction.
+        access_list: AccessList,
+        /// The authorization list associated with the transaction.
+        authorization_list: Vec<SignedAuthorization>,
+        /// The authorities of the authorization list, returned in the implementation of
+        /// [`PoolTransaction`].
+        ///
+        /// Mock transactions are not signed, so these are set directly instead of being recovered
+        /// from the authorization list.
+        authorities: Vec<Address>,
+        /// The transaction input data.
+        input: Bytes,
+        /// The size of the transaction, returned in the implementation of [`PoolTransaction`].
+        size: usize,
+    },
 }
 
 // === impl MockTransaction ===
@@ -288,6 +327,31 @@ impl MockTransaction {
         }
     }
 
+    /// Returns a new EIP7702 transaction with random address and hash and empty values
+    pub fn eip7702() -> Self {
+        Self::Eip7702 {
+            chain_id: 1,
+            hash: B256::random(),
+            sender: Address::random(),
+            nonce: 0,
+            max_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            max_priority_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            gas_limit: 0,
+            to: Address::random().into(),
+            value: Default::default(),
+            input: Bytes::new(),
+            access_list: Default::default(),
+            authorization_list: Default::default(),
+            authorities: Default::default(),
+            size: Default::default(),
+        }
+    }
+
+    /// Returns a new EIP7702 transaction with the given authorities
+    pub fn eip7702_with_authorities(authorities: Vec<Address>) -> Self {
+        Self::eip7702().with_authorities(authorities)
+    }
+
     /// Returns a new EIP4844 transaction with a provided sidecar
     pub fn eip4844_with_sidecar(sidecar: BlobTransactionSidecar) -> Self {
         let mut transaction = Self::eip4844();
@@ -305,6 +369,7 @@ impl MockTransaction {
     /// * [`MockTransaction::eip2930`]
     /// * [`MockTransaction::eip1559`]
     /// * [`MockTransaction::eip4844`]
+    /// * [`MockTransaction::eip7702`]
     pub fn new_from_type(tx_type: TxType) -> Self {
         #[allow(unreachable_patterns)]
         match tx_type {
@@ -312,6 +377,7 @@ impl MockTransaction {
             TxType::Eip2930 => Self::eip2930(),
             TxType::Eip1559 => Self::eip1559(),
             TxType::Eip4844 => Self::eip4844(),
+            TxType::Eip7702 => Self::eip7702(),
 
             _ => unreachable!("Invalid transaction type"),
         }
@@ -331,10 +397,25 @@ impl MockTransaction {
         self
     }
 
+    /// Sets the authorities of the authorization list for EIP-7702 transactions
+    pub fn set_authorities(&mut self, val: Vec<Address>) -> &mut Self {
+        if let Self::Eip7702 { authorities, .. } = self {
+            *authorities = val;
+        }
+        self
+    }
+
+    /// Sets the authorities of the authorization list for EIP-7702 transactions
+    pub fn with_authorities(mut self, val: Vec<Address>) -> Self {
+        self.set_authorities(val);
+        self
+    }
+
     /// Sets the priority fee for dynamic fee transactions (EIP-1559 and EIP-4844)
     pub fn set_priority_fee(&mut self, val: u128) -> &mut Self {
         if let Self::Eip1559 { max_priority_fee_per_gas, .. } |
-        Self::Eip4844 { max_priority_fee_per_gas, .. } = self
+        Self::Eip4844 { max_priority_fee_per_gas, .. } |
+        Self::Eip7702 { max_priority_fee_per_gas, .. } = self
         {
             *max_priority_fee_per_gas = val;
         }
@@ -351,14 +432,17 @@ impl MockTransaction {
     pub const fn get_priority_fee(&self) -> Option<u128> {
         match self {
             Self::Eip1559 { max_priority_fee_per_gas, .. } |
-            Self::Eip4844 { max_priority_fee_per_gas, .. } => Some(*max_priority_fee_per_gas),
+            Self::Eip4844 { max_priority_fee_per_gas, .. } |
+            Self::Eip7702 { max_priority_fee_per_gas, .. } => Some(*max_priority_fee_per_gas),
             _ => None,
         }
     }
 
     /// Sets the max fee for dynamic fee transactions (EIP-1559 and EIP-4844)
     pub fn set_max_fee(&mut self, val: u128) -> &mut Self {
-        if let Self::Eip1559 { max_fee_per_gas, .. } | Self::Eip4844 { max_fee_per_gas, .. } = self
+        if let Self::Eip1559 { max_fee_per_gas, .. } |
+        Self::Eip4844 { max_fee_per_gas, .. } |
+        Self::Eip7702 { max_fee_per_gas, .. } = self
         {
             *max_fee_per_gas = val;
         }
@@ -374,7 +458,9 @@ impl MockTransaction {
     /// Gets the max fee for dynamic fee transactions (EIP-1559 and EIP-4844)
     pub const fn get_max_fee(&self) -> Option<u128> {
         match self {
-            Self::Eip1559 { max_fee_per_gas, .. } | Self::Eip4844 { max_fee_per_gas, .. } => {
+            Self::Eip1559 { max_fee_per_gas, .. } |
+            Self::Eip4844 { max_fee_per_gas, .. } |
+            Self::Eip7702 { max_fee_per_gas, .. } => {
                 Some(*max_fee_per_gas)
             }
             _ => None,
@@ -387,6 +473,7 @@ impl MockTransaction {
             Self::Legacy { .. } => {}
             Self::Eip1559 { access_list: accesslist, .. } |
             Self::Eip4844 { access_list: accesslist, .. } |
+            Self::Eip7702 { access_list: accesslist, .. } |
             Self::Eip2930 { access_list: accesslist, .. } => {
                 *accesslist = list;
             }
@@ -401,7 +488,8 @@ impl MockTransaction {
                 *gas_price = val;
             }
             Self::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, .. } |
-            Self::Eip4844 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
+            Self::Eip4844 { max_fee_per_gas, max_priority_fee_per_gas, .. } |
+            Self::Eip7702 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
                 *max_fee_per_gas = val;
                 *max_priority_fee_per_gas = val;
             }
@@ -416,7 +504,8 @@ impl MockTransaction {
                 *gas_price = val;
             }
             Self::Eip1559 { ref mut max_fee_per_gas, ref mut max_priority_fee_per_gas, .. } |
-            Self::Eip4844 { ref mut max_fee_per_gas, ref mut max_priority_fee_per_gas, .. } => {
+            Self::Eip4844 { ref mut max_fee_per_gas, ref mut max_priority_fee_per_gas, .. } |
+            Self::Eip7702 { ref mut max_fee_per_gas, ref mut max_priority_fee_per_gas, .. } => {
                 *max_fee_per_gas = val;
                 *max_priority_fee_per_gas = val;
             }
@@ -428,7 +517,9 @@ impl MockTransaction {
     pub const fn get_gas_price(&self) -> u128 {
         match self {
             Self::Legacy { gas_price, .. } | Self::Eip2930 { gas_price, .. } => *gas_price,
-            Self::Eip1559 { max_fee_per_gas, .. } | Self::Eip4844 { max_fee_per_gas, .. } => {
+            Self::Eip1559 { max_fee_per_gas, .. } |
+            Self::Eip4844 { max_fee_per_gas, .. } |
+            Self::Eip7702 { max_fee_per_gas, .. } => {
                 *max_fee_per_gas
             }
         }
@@ -532,6 +623,7 @@ impl MockTransaction {
             Self::Legacy { .. } => LEGACY_TX_TYPE_ID,
             Self::Eip1559 { .. } => EIP1559_TX_TYPE_ID,
             Self::Eip4844 { .. } => EIP4844_TX_TYPE_ID,
+            Self::Eip7702 { .. } => EIP7702_TX_TYPE_ID,
             Self::Eip2930 { .. } => EIP2930_TX_TYPE_ID,
         }
     }
@@ -551,6 +643,11 @@ impl MockTransaction {
         matches!(self, Self::Eip4844 { .. })
     }
 
+    /// Checks if the transaction is of the EIP-7702 type.
+    pub const fn is_eip7702(&self) -> bool {
+        matches!(self, Self::Eip7702 { .. })
+    }
+
     /// Checks if the transaction is of the EIP-2930 type.
     pub const fn is_eip2930(&self) -> bool {
         matches!(self, Self::Eip2930 { .. })
@@ -563,6 +660,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { hash, .. } |
             Self::Eip1559 { hash, .. } |
             Self::Eip4844 { hash, .. } |
+            Self::Eip7702 { hash, .. } |
             Self::Eip2930 { hash, .. } => hash,
         }
     }
@@ -572,6 +670,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { sender, .. } |
             Self::Eip1559 { sender, .. } |
             Self::Eip4844 { sender, .. } |
+            Self::Eip7702 { sender, .. } |
             Self::Eip2930 { sender, .. } => *sender,
         }
     }
@@ -581,6 +680,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { nonce, .. } |
             Self::Eip1559 { nonce, .. } |
             Self::Eip4844 { nonce, .. } |
+            Self::Eip7702 { nonce, .. } |
             Self::Eip2930 { nonce, .. } => *nonce,
         }
     }
@@ -592,12 +692,17 @@ impl PoolTransaction for MockTransaction {
                 U256::from(*gas_limit) * U256::from(*gas_price) + *value
             }
             Self::Eip1559 { max_fee_per_gas, value, gas_limit, .. } |
-            Self::Eip4844 { max_fee_per_gas, value, gas_limit, .. } => {
+            Self::Eip4844 { max_fee_per_gas, value, gas_limit, .. } |
+            Self::Eip7702 { max_fee_per_gas, value, gas_limit, .. } => {
                 U256::from(*gas_limit) * U256::from(*max_fee_per_gas) + *value
             }
         }
     }
 
+    fn value(&self) -> U256 {
+        self.get_value()
+    }
+
     fn gas_limit(&self) -> u64 {
         self.get_gas_limit()
     }
@@ -605,7 +710,9 @@ impl PoolTransaction for MockTransaction {
     fn max_fee_per_gas(&self) -> u128 {
         match self {
             Self::Legacy { gas_price, .. } | Self::Eip2930 { gas_price, .. } => *gas_price,
-            Self::Eip1559 { max_fee_per_gas, .. } | Self::Eip4844 { max_fee_per_gas, .. } => {
+            Self::Eip1559 { max_fee_per_gas, .. } |
+            Self::Eip4844 { max_fee_per_gas, .. } |
+            Self::Eip7702 { max_fee_per_gas, .. } => {
                 *max_fee_per_gas
             }
         }
@@ -616,6 +723,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { .. } => None,
             Self::Eip1559 { access_list: accesslist, .. } |
             Self::Eip4844 { access_list: accesslist, .. } |
+            Self::Eip7702 { access_list: accesslist, .. } |
             Self::Eip2930 { access_list: accesslist, .. } => Some(accesslist),
         }
     }
@@ -624,7 +732,8 @@ impl PoolTransaction for MockTransaction {
         match self {
             Self::Legacy { .. } | Self::Eip2930 { .. } => None,
             Self::Eip1559 { max_priority_fee_per_gas, .. } |
-            Self::Eip4844 { max_priority_fee_per_gas, .. } => Some(*max_priority_fee_per_gas),
+            Self::Eip4844 { max_priority_fee_per_gas, .. } |
+            Self::Eip7702 { max_priority_fee_per_gas, .. } => Some(*max_priority_fee_per_gas),
         }
     }
 
@@ -666,14 +775,18 @@ impl PoolTransaction for MockTransaction {
         match self {
             Self::Legacy { gas_price, .. } | Self::Eip2930 { gas_price, .. } => *gas_price,
             Self::Eip1559 { max_priority_fee_per_gas, .. } |
-            Self::Eip4844 { max_priority_fee_per_gas, .. } => *max_priority_fee_per_gas,
+            Self::Eip4844 { max_priority_fee_per_gas, .. } |
+            Self::Eip7702 { max_priority_fee_per_gas, .. } => *max_priority_fee_per_gas,
         }
     }
 
     /// Returns the transaction kind associated with the transaction.
     fn kind(&self) -> TxKind {
         match self {
-            Self::Legacy { to, .. } | Self::Eip1559 { to, .. } | Self::Eip2930 { to, .. } => *to,
+            Self::Legacy { to, .. } |
+            Self::Eip1559 { to, .. } |
+            Self::Eip7702 { to, .. } |
+            Self::Eip2930 { to, .. } => *to,
             Self::Eip4844 { to, .. } => TxKind::Call(*to),
         }
     }
@@ -684,6 +797,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { .. } => &[],
             Self::Eip1559 { input, .. } |
             Self::Eip4844 { input, .. } |
+            Self::Eip7702 { input, .. } |
             Self::Eip2930 { input, .. } => input,
         }
     }
@@ -694,6 +808,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { size, .. } |
             Self::Eip1559 { size, .. } |
             Self::Eip4844 { size, .. } |
+            Self::Eip7702 { size, .. } |
             Self::Eip2930 { size, .. } => *size,
         }
     }
@@ -704,13 +819,41 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { .. } => TxType::Legacy.into(),
             Self::Eip1559 { .. } => TxType::Eip1559.into(),
             Self::Eip4844 { .. } => TxType::Eip4844.into(),
+            Self::Eip7702 { .. } => TxType::Eip7702.into(),
             Self::Eip2930 { .. } => TxType::Eip2930.into(),
         }
     }
 
+    /// Returns the number of blobs in the sidecar of the transaction.
+    fn blob_count(&self) -> usize {
+        match self {
+            Self::Eip4844 { sidecar, .. } => sidecar.blobs.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of authorizations of the transaction.
+    fn authorization_count(&self) -> usize {
+        match self {
+            Self::Eip7702 { authorization_list, .. } => authorization_list.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the configured authorities of the transaction.
+    fn authorities(&self) -> Option<&[Address]> {
+        match self {
+            Self::Eip7702 { authorities, .. } => Some(authorities),
+            _ => None,
+        }
+    }
+
     /// Returns the encoded length of the transaction.
+    ///
+    /// Mock transactions are never encoded, so this is approximated by the length of the input
+    /// and the blobs of the sidecar.
     fn encoded_length(&self) -> usize {
-        0
+        self.input().len() + self.blob_count() * TXPOOL_BLOB_SIZE_BYTES
     }
 
     /// Returns the chain ID associated with the transaction.
@@ -719,6 +862,7 @@ impl PoolTransaction for MockTransaction {
             Self::Legacy { chain_id, .. } => *chain_id,
             Self::Eip1559 { chain_id, .. } |
             Self::Eip4844 { chain_id, .. } |
+            Self::Eip7702 { chain_id, .. } |
             Self::Eip2930 { chain_id, .. } => Some(*chain_id),
         }
     }
@@ -732,13 +876,6 @@ impl EthPoolTransaction for MockTransaction {
         }
     }
 
-    fn blob_count(&self) -> usize {
-        match self {
-            Self::Eip4844 { sidecar, .. } => sidecar.blobs.len(),
-            _ => 0,
-        }
-    }
-
     fn validate_blob(
         &self,
         _blob: &BlobTransactionSidecar,
@@ -860,6 +997,36 @@ impl TryFromRecoveredTransaction for MockTransaction {
                 sidecar: BlobTransactionSidecar::default(),
                 size,
             }),
+            Transaction::Eip7702(TxEip7702 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                authorization_list,
+                input,
+            }) => Ok(Self::Eip7702 {
+                chain_id,
+                hash,
+                sender,
+                nonce,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_limit,
+                to,
+                value,
+                access_list,
+                authorities: authorization_list
+                    .iter()
+                    .filter_map(|auth| auth.recover_authority().ok())
+                    .collect(),
+                authorization_list,
+                input,
+                size,
+            }),
             _ => unreachable!("Invalid transaction type"),
         }
     }
@@ -979,6 +1146,33 @@ impl From<MockTransaction> for Transaction {
                 max_fee_per_blob_gas,
                 input,
             }),
+            MockTransaction::Eip7702 {
+                chain_id,
+                hash: _,
+                sender: _,
+                nonce,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_limit,
+                to,
+                value,
+                access_list,
+                authorization_list,
+                authorities: _,
+                input,
+                size: _,
+            } => Self::Eip7702(TxEip7702 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                authorization_list,
+                input,
+            }),
         }
     }
 }
@@ -1059,7 +1253,12 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
                     access_list: access_list.clone(),
                     size: tx.size(),
                 },
-                Transaction::Eip4844(TxEip4844 {
+                // only generate a sidecar if it is a 4844 tx - also for the sake of
+                // performance just use a default sidecar
+                Transaction::Eip4844(tx) => {
+                    Self::from_eip4844(tx, sender, tx_hash, BlobTransactionSidecar::default())
+                }
+                Transaction::Eip7702(TxEip7702 {
                     chain_id,
                     nonce,
                     gas_limit,
@@ -1067,28 +1266,25 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
                     max_priority_fee_per_gas,
                     to,
                     value,
-                    input,
-                    max_fee_per_blob_gas,
                     access_list,
-                    blob_versioned_hashes: _,
-                    placeholder,
-                }) => Self::Eip4844 {
+                    authorization_list,
+                    input,
+                }) => Self::Eip7702 {
                     chain_id: *chain_id,
                     sender,
                     hash: tx_hash,
                     nonce: *nonce,
                     max_fee_per_gas: *max_fee_per_gas,
                     max_priority_fee_per_gas: *max_priority_fee_per_gas,
-                    max_fee_per_blob_gas: *max_fee_per_blob_gas,
                     gas_limit: *gas_limit,
-                    placeholder: *placeholder,
                     to: *to,
                     value: *value,
                     input: input.clone(),
                     access_list: access_list.clone(),
-                    // only generate a sidecar if it is a 4844 tx - also for the sake of
-                    // performance just use a default sidecar
-                    sidecar: BlobTransactionSidecar::default(),
+                    authorization_list: authorization_list.clone(),
+                    // arbitrary authorizations are not validly signed, so the authorities are
+                    // generated independently
+                    authorities: authorization_list.iter().map(|_| Address::random()).collect(),
                     size: tx.size(),
                 },
                 #[allow(unreachable_patterns)]
@@ -1100,6 +1296,87 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
     type Strategy = proptest::strategy::BoxedStrategy<Self>;
 }
 
+#[cfg(any(test, feature = "arbitrary"))]
+impl MockTransaction {
+    /// Returns a strategy that only generates EIP-4844 transactions, with arbitrary values and the
+    /// given sidecar.
+    ///
+    /// Generating valid sidecars is expensive, so all generated transactions share the given
+    /// sidecar, see [`random_blob_sidecar`].
+    pub fn arbitrary_eip4844(
+        sidecar: BlobTransactionSidecar,
+    ) -> proptest::strategy::BoxedStrategy<Self> {
+        use proptest::prelude::Strategy;
+        use proptest_arbitrary_interop::arb;
+
+        arb::<(TxEip4844, Address, B256)>()
+            .prop_map(move |(tx, sender, tx_hash)| {
+                Self::from_eip4844(&tx, sender, tx_hash, sidecar.clone())
+            })
+            .boxed()
+    }
+
+    /// Returns a new EIP4844 transaction with a sidecar of `num_blobs` random blobs, see
+    /// [`random_blob_sidecar`].
+    pub fn eip4844_with_blobs(num_blobs: usize) -> Self {
+        Self::eip4844_with_sidecar(random_blob_sidecar(num_blobs))
+    }
+
+    /// Converts the given EIP-4844 transaction into a [`MockTransaction`] with the given sidecar.
+    fn from_eip4844(
+        tx: &TxEip4844,
+        sender: Address,
+        tx_hash: B256,
+        sidecar: BlobTransactionSidecar,
+    ) -> Self {
+        Self::Eip4844 {
+            chain_id: tx.chain_id,
+            sender,
+            hash: tx_hash,
+            nonce: tx.nonce,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+            gas_limit: tx.gas_limit,
+            placeholder: tx.placeholder,
+            to: tx.to,
+            value: tx.value,
+            input: tx.input.clone(),
+            access_list: tx.access_list.clone(),
+            sidecar,
+            size: tx.size(),
+        }
+    }
+}
+
+/// Generates a sidecar of `num_blobs` random blobs with valid commitments and proofs.
+///
+/// Note: computing the commitments and proofs is expensive.
+#[cfg(any(test, feature = "arbitrary"))]
+pub fn random_blob_sidecar(num_blobs: usize) -> BlobTransactionSidecar {
+    use rand::Rng;
+    use reth_primitives::{
+        kzg::{Blob, BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT},
+        transaction::generate_blob_sidecar,
+    };
+
+    let mut rng = rand::thread_rng();
+    let blobs = (0..num_blobs)
+        .map(|_| {
+            let mut bytes = vec![0u8; BYTES_PER_BLOB];
+            rng.fill(bytes.as_mut_slice());
+            // clear the most significant byte of each field element, so that it is a valid
+            // (canonical) field element
+            for element in bytes.chunks_mut(BYTES_PER_FIELD_ELEMENT) {
+                element[0] = 0;
+            }
+            Blob::from_bytes(&bytes).expect("blob has the correct size")
+        })
+        .collect();
+
+    generate_blob_sidecar(blobs)
+}
+
 /// A factory for creating and managing various types of mock transactions.
 #[derive(Debug, Default)]
 pub struct MockTransactionFactory {
@@ -1131,12 +1408,17 @@ impl MockTransactionFactory {
         origin: TransactionOrigin,
         transaction: MockTransaction,
     ) -> MockValidTx {
+        let authority_ids = transaction.authorities().map(|authorities| {
+            authorities.iter().map(|authority| self.ids.sender_id_or_create(*authority)).collect()
+        });
         MockValidTx {
             propagate: false,
             transaction_id: self.tx_id(&transaction),
+            authority_ids,
             transaction,
             timestamp: Instant::now(),
             origin,
+            source: origin.into(),
         }
     }
 
@@ -1154,6 +1436,11 @@ impl MockTransactionFactory {
     pub fn create_eip4844(&mut self) -> MockValidTx {
         self.validated(MockTransaction::eip4844())
     }
+
+    /// Creates a validated EIP-7702 [`MockTransaction`] with the given authorities.
+    pub fn create_eip7702(&mut self, authorities: Vec<Address>) -> MockValidTx {
+        self.validated(MockTransaction::eip7702_with_authorities(authorities))
+    }
 }
 
 /// `MockOrdering` is just a `CoinbaseTipOrdering` with `MockTransaction`
@@ -30,15 +30,20 @@
 #![allow(dead_code)]
 
 use crate::{
-    pool::{txpool::TxPool, AddedTransaction},
-    test_utils::{MockOrdering, MockTransactionDistribution, MockTransactionFactory},
-    TransactionOrdering,
+    pool::{
+        txpool::{SenderInfo, TxPool},
+        AddedTransaction,
+    },
+    test_utils::{
+        MockOrdering, MockTransaction, MockTransactionDistribution, MockTransactionFactory,
+    },
+    BlockInfo, PoolConfig, TransactionOrdering,
 };
 use rand::Rng;
-use reth_primitives::{Address, U256};
+use reth_primitives::{Address, B256, U256};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
 };
 
@@ -49,6 +54,11 @@ pub(crate) struct MockPool<T: TransactionOrdering = MockOrdering> {
 }
 
 impl MockPool {
+    /// Creates a new pool with the given configuration.
+    fn with_config(config: PoolConfig) -> Self {
+        Self { pool: TxPool::new(MockOrdering::default(), config) }
+    }
+
     /// The total size of all subpools
     fn total_subpool_size(&self) -> usize {
         self.pool.pending().len() + self.pool.base_fee().len() + self.pool.queued().len()
@@ -174,6 +184,194 @@ impl<R: Rng> MockTransactionSimulator<R> {
     }
 }
 
+/// An operation applied to a [`MockPoolModel`].
+#[derive(Debug, Clone)]
+pub(crate) enum MockPoolOp {
+    /// Adds a transaction of the sender, `nonce_offset` above the sender's on-chain nonce.
+    Add { sender: usize, nonce_offset: u64, gas_price: u128 },
+    /// Replaces the transaction of the sender `nonce_offset` above the sender's on-chain nonce,
+    /// if there is one, with a higher priced transaction.
+    Replace { sender: usize, nonce_offset: u64 },
+    /// Mines at most `count` pending transactions of the sender in a new block.
+    Mine { sender: usize, count: usize },
+    /// Reverts the last mined block and reinjects its transactions.
+    Reorg,
+    /// Sets the pending base fee.
+    SetBaseFee(u64),
+}
+
+/// A block mined by a [`MockPoolModel`].
+#[derive(Debug, Clone)]
+struct MockBlock {
+    /// The sender of all transactions of the block.
+    sender: Address,
+    /// The mined transactions, ordered by their nonce.
+    transactions: Vec<MockTransaction>,
+}
+
+/// Applies [`MockPoolOp`]s to a [`MockPool`] while tracking the on-chain state of the senders, and
+/// checks the invariants that must hold across all sub-pools.
+///
+/// Like the [`PoolInner`](crate::pool::PoolInner), the model enforces the pool limits after every
+/// operation.
+pub(crate) struct MockPoolModel {
+    /// The pool under test.
+    pool: MockPool,
+    /// "Validates" generated transactions.
+    factory: MockTransactionFactory,
+    /// The senders of the transactions.
+    senders: Vec<Address>,
+    /// The on-chain nonce of each sender.
+    nonces: HashMap<Address, u64>,
+    /// All mined blocks that can be reverted, the latest last.
+    blocks: Vec<MockBlock>,
+}
+
+impl MockPoolModel {
+    /// The on-chain balance of all senders, which is enough to pay for any transaction.
+    const BALANCE: U256 = U256::MAX;
+
+    /// The gas limit of all transactions.
+    const GAS_LIMIT: u64 = 21_000;
+
+    /// Creates a new model of a pool with the given configuration and number of senders.
+    pub(crate) fn new(config: PoolConfig, num_senders: usize) -> Self {
+        let senders: Vec<_> = std::iter::repeat_with(Address::random).take(num_senders).collect();
+        Self {
+            pool: MockPool::with_config(config),
+            factory: Default::default(),
+            nonces: senders.iter().map(|sender| (*sender, 0)).collect(),
+            senders,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Applies the operation to the pool and enforces the pool limits afterwards.
+    ///
+    /// Operations the pool rejects, e.g. underpriced replacements, are no-ops.
+    pub(crate) fn apply(&mut self, op: MockPoolOp) {
+        match op {
+            MockPoolOp::Add { sender, nonce_offset, gas_price } => {
+                let sender = self.senders[sender % self.senders.len()];
+                let tx = MockTransaction::legacy()
+                    .with_sender(sender)
+                    .with_nonce(self.nonces[&sender] + nonce_offset)
+                    .with_gas_limit(Self::GAS_LIMIT)
+                    .with_gas_price(gas_price);
+                self.add(tx);
+            }
+            MockPoolOp::Replace { sender, nonce_offset } => {
+                let sender = self.senders[sender % self.senders.len()];
+                let nonce = self.nonces[&sender] + nonce_offset;
+                let sender_id = self.factory.ids.sender_id_or_create(sender);
+                let existing = self
+                    .pool
+                    .index()
+                    .by_sender(sender_id)
+                    .into_iter()
+                    .find(|tx| tx.nonce() == nonce);
+                if let Some(existing) = existing {
+                    // bump the price above the required price bump
+                    let price = existing.transaction.get_gas_price();
+                    self.add(existing.transaction.inc_price_by(price / 5 + 1).rng_hash());
+                }
+            }
+            MockPoolOp::Mine { sender, count } => {
+                let sender = self.senders[sender % self.senders.len()];
+                let mut pending: Vec<_> =
+                    self.pool.pending().all().filter(|tx| tx.sender() == sender).collect();
+                pending.sort_by_key(|tx| tx.nonce());
+                pending.truncate(count);
+                if pending.is_empty() {
+                    return
+                }
+
+                let mined = pending.iter().map(|tx| *tx.hash()).collect();
+                let transactions = pending.iter().map(|tx| tx.transaction.clone()).collect();
+                self.blocks.push(MockBlock { sender, transactions });
+                let nonce = self.nonces[&sender] + pending.len() as u64;
+                self.on_new_block(sender, nonce, mined);
+            }
+            MockPoolOp::Reorg => {
+                let Some(MockBlock { sender, transactions }) = self.blocks.pop() else { return };
+                let nonce = transactions[0].get_nonce();
+                self.on_new_block(sender, nonce, Vec::new());
+                for tx in transactions {
+                    self.add(tx);
+                }
+            }
+            MockPoolOp::SetBaseFee(pending_basefee) => {
+                let block_info = BlockInfo { pending_basefee, ..self.pool.block_info() };
+                self.pool.set_block_info(block_info);
+            }
+        }
+
+        self.pool.discard_worst();
+    }
+
+    /// Adds the transaction to the pool, ignoring rejections.
+    fn add(&mut self, tx: MockTransaction) {
+        let nonce = self.nonces[&tx.get_sender()];
+        let tx = self.factory.validated(tx);
+        let _ = self.pool.add_transaction(tx, Self::BALANCE, nonce);
+    }
+
+    /// Updates the pool for a new canonical block that changed the on-chain nonce of the sender.
+    fn on_new_block(&mut self, sender: Address, nonce: u64, mined: Vec<B256>) {
+        self.nonces.insert(sender, nonce);
+        let sender_id = self.factory.ids.sender_id_or_create(sender);
+        let changed_senders =
+            HashMap::from([(sender_id, SenderInfo { state_nonce: nonce, balance: Self::BALANCE })]);
+        let block_info = BlockInfo {
+            last_seen_block_hash: B256::random(),
+            last_seen_block_number: self.blocks.len() as u64,
+            ..self.pool.block_info()
+        };
+        self.pool.on_canonical_state_change(block_info, mined, changed_senders);
+    }
+
+    /// Checks that all invariants across the sub-pools hold:
+    ///
+    ///  - every transaction is in exactly one sub-pool and no hash is tracked twice
+    ///  - the pending transactions of each sender have contiguous nonces, starting at the sender's
+    ///    on-chain nonce
+    ///  - the pool limits and the max number of transactions per sender are respected
+    ///
+    /// # Panics
+    /// if any invariant is violated
+    pub(crate) fn assert_invariants(&self) {
+        self.pool.assert_invariants();
+        self.pool.enforce_invariants();
+
+        let mut hashes = HashSet::new();
+        let mut by_sender = HashMap::<Address, usize>::new();
+        let parked = self.pool.base_fee().all().chain(self.pool.queued().all());
+        for tx in self.pool.pending().all().chain(parked) {
+            assert!(hashes.insert(*tx.hash()), "duplicate transaction {}", tx.hash());
+            assert!(self.pool.contains(tx.hash()), "untracked transaction {}", tx.hash());
+            *by_sender.entry(tx.sender()).or_default() += 1;
+        }
+        assert_eq!(hashes.len(), self.pool.len(), "sub-pools and all transactions must match");
+
+        let mut pending = HashMap::<Address, Vec<u64>>::new();
+        for tx in self.pool.pending().all() {
+            pending.entry(tx.sender()).or_default().push(tx.nonce());
+        }
+        for (sender, mut nonces) in pending {
+            nonces.sort_unstable();
+            let on_chain_nonce = self.nonces[&sender];
+            let expected: Vec<_> = (on_chain_nonce..).take(nonces.len()).collect();
+            assert_eq!(nonces, expected, "pending nonces of {sender} must be contiguous");
+        }
+
+        assert!(!self.pool.is_exceeded(), "pool exceeds its limits: {:?}", self.pool.size());
+        let max_account_slots = self.pool.config().max_account_slots;
+        for (sender, count) in by_sender {
+            assert!(count <= max_account_slots, "{sender} exceeds the account slots: {count}");
+        }
+    }
+}
+
 /// How to configure a new mock transaction stream
 pub(crate) struct MockSimulatorConfig {
     /// How many senders to generate.
@@ -238,7 +436,11 @@ pub(crate) struct ExecutedScenarios {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{MockFeeRange, MockTransactionRatio};
+    use crate::{
+        test_utils::{MockFeeRange, MockTransactionRatio},
+        SubPoolLimit,
+    };
+    use proptest::prelude::*;
 
     #[test]
     fn test_on_chain_nonce_scenario() {
@@ -272,4 +474,48 @@ mod tests {
 
         simulator.next(&mut pool);
     }
+
+    /// The number of senders of the [`MockPoolModel`].
+    const NUM_SENDERS: usize = 8;
+
+    /// Returns a strategy for operations on a [`MockPoolModel`] with [`NUM_SENDERS`] senders.
+    fn pool_op() -> impl Strategy<Value = MockPoolOp> {
+        prop_oneof![
+            4 => (0..NUM_SENDERS, 0..4u64, 1..200u128).prop_map(
+                |(sender, nonce_offset, gas_price)| MockPoolOp::Add {
+                    sender,
+                    nonce_offset,
+                    gas_price
+                }
+            ),
+            1 => (0..NUM_SENDERS, 0..4u64)
+                .prop_map(|(sender, nonce_offset)| MockPoolOp::Replace { sender, nonce_offset }),
+            2 => (0..NUM_SENDERS, 1..4usize)
+                .prop_map(|(sender, count)| MockPoolOp::Mine { sender, count }),
+            1 => Just(MockPoolOp::Reorg),
+            1 => (1..200u64).prop_map(MockPoolOp::SetBaseFee),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn invariants_hold_under_churn(ops in proptest::collection::vec(pool_op(), 1..200)) {
+            // small limits, so that the pool is truncated frequently
+            let limit = SubPoolLimit { max_txs: 12, ..Default::default() };
+            let config = PoolConfig {
+                pending_limit: limit,
+                basefee_limit: limit,
+                queued_limit: limit,
+                max_account_slots: 4,
+                ..Default::default()
+            };
+            let mut model = MockPoolModel::new(config, NUM_SENDERS);
+            for op in ops {
+                model.apply(op);
+                model.assert_invariants();
+            }
+        }
+    }
 }
@@ -2,8 +2,11 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    conditional::TransactionConditional,
     error::PoolResult,
-    pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    fees::FeeForecast,
+    identifier::TransactionId,
+    pool::{state::SubPool, BestTransactionFilter, PoolLifecycleEvents, TransactionEvents},
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -14,7 +17,7 @@ use reth_primitives::{
     BlobTransactionSidecar, BlobTransactionValidationError, FromRecoveredPooledTransaction,
     IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
     SealedBlock, Transaction, TransactionSignedEcRecovered, TryFromRecoveredTransaction, TxHash,
-    TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, U256,
+    TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, U256,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -76,6 +79,24 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.add_transactions(TransactionOrigin::External, transactions)
     }
 
+    /// Imports all _external_ transactions received from the given peer.
+    ///
+    /// This is the same as [`TransactionPool::add_external_transactions`], but the transactions
+    /// count towards the quota of the peer, see [`TransactionSource::Peer`].
+    ///
+    /// Consumer: P2P
+    fn add_peer_transactions(
+        &self,
+        peer: PeerId,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send {
+        self.add_transactions_from(
+            TransactionSource::Peer(peer),
+            TransactionOrigin::External,
+            transactions,
+        )
+    }
+
     /// Adds an _unvalidated_ transaction into the pool and subscribe to state changes.
     ///
     /// This is the same as [TransactionPool::add_transaction] but returns an event stream for the
@@ -108,6 +129,57 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
 
+    /// Adds the given _unvalidated_ transactions into the pool and records who submitted them.
+    ///
+    /// [`TransactionPool::add_transactions`] derives the source from the origin, see
+    /// [`TransactionSource::from`].
+    ///
+    /// Consumer: RPC, P2P
+    fn add_transactions_from(
+        &self,
+        source: TransactionSource,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
+
+    /// Adds the given _unvalidated_ transactions into the pool as a batch.
+    ///
+    /// This is the same as [`TransactionPool::add_transactions`], but implementations can validate
+    /// and insert the batch at once, which is cheaper for large batches, e.g. transactions
+    /// announced by peers.
+    ///
+    /// Consumer: P2P
+    fn add_transactions_batch(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send {
+        self.add_transactions(origin, transactions)
+    }
+
+    /// Adds an _unvalidated_ transaction into the pool that may only be included while the given
+    /// conditions hold.
+    ///
+    /// The transaction is rejected if its block number range can't be satisfied on top of the
+    /// current head. The caller is expected to check the remaining conditions against the current
+    /// state before submitting the transaction. The pool re-checks them on each canonical state
+    /// change and drops the transaction once they can no longer be satisfied.
+    ///
+    /// Consumer: RPC (`eth_sendRawTransactionConditional`)
+    fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
+    /// Returns the conditions of the given transaction, if it was submitted with
+    /// [`TransactionPool::add_conditional_transaction`].
+    ///
+    /// Consumer: Block production, which must only include transactions whose conditions match the
+    /// block.
+    fn get_conditional(&self, tx_hash: &TxHash) -> Option<TransactionConditional>;
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -116,6 +188,13 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new stream of the lifecycle events of _all_ transactions in the pool.
+    ///
+    /// Unlike [`Self::all_transactions_event_listener`], the stream reports promotions and
+    /// demotions between the sub-pools and why a transaction was dropped, see
+    /// [`PoolLifecycleEvent`](crate::PoolLifecycleEvent).
+    fn lifecycle_event_listener(&self) -> PoolLifecycleEvents<Self::Transaction>;
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -244,6 +323,19 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Self::Transaction>>>>;
 
+    /// Returns a stream that yields transactions that are ready for block production.
+    ///
+    /// This first yields the same transactions as [`TransactionPool::best_transactions`], ordered
+    /// by the configured [`TransactionOrdering`](crate::TransactionOrdering). Afterwards, instead
+    /// of ending, it yields transactions as they are promoted to the pending sub-pool, so they can
+    /// be consumed incrementally.
+    ///
+    /// Like the iterator, the stream does not remove transactions that are mined or replaced after
+    /// it was created.
+    ///
+    /// Consumer: Block production
+    fn best_transactions_stream(&self) -> BestTransactionsStream<Self::Transaction>;
+
     /// Returns an iterator that yields transactions that are ready for block production with the
     /// given base fee.
     ///
@@ -281,6 +373,32 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: RPC
     fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns at most `limit` transactions of the pending sub-pool, ordered by sender and nonce,
+    /// starting after the given cursor.
+    ///
+    /// Pass the [`TransactionsPage::next_cursor`] of the previous page to get the next page, or
+    /// `None` to get the first page. The pagination is stable: transactions that are added or
+    /// removed in between do not shift the following pages.
+    ///
+    /// Consumer: RPC
+    fn pending_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<Self::Transaction>;
+
+    /// Returns at most `limit` transactions that can be included in _future_ blocks, ordered by
+    /// sender and nonce, starting after the given cursor.
+    ///
+    /// See also [`Self::pending_transactions_page`] and [`Self::queued_transactions`].
+    ///
+    /// Consumer: RPC
+    fn queued_transactions_page(
+        &self,
+        cursor: Option<TransactionId>,
+        limit: usize,
+    ) -> TransactionsPage<Self::Transaction>;
+
     /// Returns all transactions that are currently in the pool grouped by whether they are ready
     /// for inclusion in the next block or not.
     ///
@@ -345,6 +463,12 @@ pub trait TransactionPool: Send + Sync + Clone {
         origin: TransactionOrigin,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions that where submitted by the given [`TransactionSource`]
+    fn get_transactions_by_source(
+        &self,
+        source: TransactionSource,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns all transactions that where submitted as [TransactionOrigin::Local]
     fn get_local_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.get_transactions_by_origin(TransactionOrigin::Local)
@@ -363,6 +487,22 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
+    /// Returns the nonce state of the given sender, including the first nonce gap and the
+    /// transactions that are blocked by it.
+    fn sender_state(&self, sender: Address) -> SenderState;
+
+    /// Returns the spam score of the given sender, which is raised by rejected transactions of the
+    /// sender and decays over time.
+    ///
+    /// A score of `0` means the sender has a clean record, see
+    /// [`SenderReputation`](crate::SenderReputation).
+    fn sender_spam_score(&self, sender: Address) -> f64;
+
+    /// Returns fee suggestions for the next block: the base fee and blob fee of the next block and
+    /// a priority fee for each of the given percentiles (in `[0, 100]`), based on the pending
+    /// transactions and the recent blocks.
+    fn fee_forecast(&self, percentiles: &[f64]) -> FeeForecast;
+
     /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
     /// store.
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
@@ -387,6 +527,11 @@ pub trait TransactionPool: Send + Sync + Clone {
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 }
 
+/// A stream of transactions that are ready for block production, see
+/// [`TransactionPool::best_transactions_stream`].
+pub type BestTransactionsStream<T> =
+    Pin<Box<dyn Stream<Item = Arc<ValidPoolTransaction<T>>> + Send + 'static>>;
+
 /// Extension for [TransactionPool] trait that allows to set the current block info.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait TransactionPoolExt: TransactionPool {
@@ -421,6 +566,37 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Returns the max number of transaction slots per sender that are currently enforced.
+    ///
+    /// This is [`PoolConfig::max_account_slots`](crate::PoolConfig::max_account_slots) unless it
+    /// was changed with [`TransactionPoolExt::set_max_account_slots`].
+    fn max_account_slots(&self) -> usize;
+
+    /// Updates the max number of transaction slots per sender at runtime.
+    ///
+    /// New transactions are checked against the new limit right away. If the limit is lowered,
+    /// the non-local transactions of senders that exceed it are removed on the next canonical
+    /// state change, starting with the highest nonces.
+    fn set_max_account_slots(&self, max_account_slots: usize);
+
+    /// Emits the hashes of all pending local transactions to the pending transaction listeners
+    /// again, so that they are rebroadcast to peers until they are included.
+    ///
+    /// Local transactions that must not be propagated are skipped. Returns the hashes of the
+    /// rebroadcast transactions.
+    fn rebroadcast_local_transactions(&self) -> Vec<TxHash>;
+
+    /// Returns the conditional transactions in the pool and their conditions, see
+    /// [`TransactionPool::add_conditional_transaction`].
+    fn conditional_transactions(&self) -> Vec<(TxHash, TransactionConditional)>;
+
+    /// Removes the given conditional transactions, whose conditions can no longer be satisfied.
+    fn remove_violated_conditionals(&self, hashes: Vec<TxHash>);
+
+    /// Removes expired transactions and enforces the sub-pool limits, removing at most `budget`
+    /// transactions, see [`PoolConfig::deferred_maintenance`](crate::PoolConfig).
+    fn run_maintenance(&self, budget: usize) -> PoolMaintenanceStats;
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -453,6 +629,9 @@ pub struct AllPoolTransactions<T: PoolTransaction> {
     /// because they depend on other transactions that are not yet included in the pool (nonce gap)
     /// or otherwise blocked.
     pub queued: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Blob transactions that are parked, because their fee caps are below the current base fee
+    /// or blob fee.
+    pub blob: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 // === impl AllPoolTransactions ===
@@ -471,7 +650,39 @@ impl<T: PoolTransaction> AllPoolTransactions<T> {
 
 impl<T: PoolTransaction> Default for AllPoolTransactions<T> {
     fn default() -> Self {
-        Self { pending: Default::default(), queued: Default::default() }
+        Self { pending: Default::default(), queued: Default::default(), blob: Default::default() }
+    }
+}
+
+/// A page of the transactions of a sub-pool.
+///
+/// See [`TransactionPool::pending_transactions_page`].
+#[derive(Debug, Clone)]
+pub struct TransactionsPage<T: PoolTransaction> {
+    /// The transactions of this page, ordered by sender and nonce.
+    pub transactions: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// The cursor to request the next page with, or `None` if this is the last page.
+    ///
+    /// The next page can be empty, if the pool has no more transactions.
+    pub next_cursor: Option<TransactionId>,
+}
+
+// === impl TransactionsPage ===
+
+impl<T: PoolTransaction> TransactionsPage<T> {
+    /// Creates a page of the given transactions, which is not the last page if the transactions
+    /// reached the requested limit.
+    pub fn new(transactions: Vec<Arc<ValidPoolTransaction<T>>>, limit: usize) -> Self {
+        let next_cursor = (limit > 0 && transactions.len() >= limit)
+            .then(|| transactions.last().map(|tx| *tx.id()))
+            .flatten();
+        Self { transactions, next_cursor }
+    }
+}
+
+impl<T: PoolTransaction> Default for TransactionsPage<T> {
+    fn default() -> Self {
+        Self { transactions: Default::default(), next_cursor: None }
     }
 }
 
@@ -574,6 +785,45 @@ impl TransactionOrigin {
     }
 }
 
+/// Who submitted a transaction to the pool.
+///
+/// In contrast to the [`TransactionOrigin`], which determines how a transaction is handled, this
+/// records the submitter, so that the pool can enforce a quota per peer, see
+/// [`PoolConfig::max_transactions_per_peer`](crate::PoolConfig::max_transactions_per_peer).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransactionSource {
+    /// Transaction was submitted by the node itself, e.g. reinjected after a reorg.
+    Local,
+    /// Transaction was submitted via RPC.
+    Rpc,
+    /// Transaction was received from the peer with the given id.
+    Peer(PeerId),
+    /// Transaction was received from an external source that is not known.
+    Unknown,
+}
+
+// === impl TransactionSource ===
+
+impl TransactionSource {
+    /// Returns the id of the peer the transaction was received from, if any.
+    pub const fn peer(&self) -> Option<&PeerId> {
+        match self {
+            Self::Peer(peer) => Some(peer),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransactionOrigin> for TransactionSource {
+    fn from(origin: TransactionOrigin) -> Self {
+        match origin {
+            TransactionOrigin::Local | TransactionOrigin::Private => Self::Local,
+            TransactionOrigin::External => Self::Unknown,
+        }
+    }
+}
+
 /// Represents changes after a new canonical block or range of canonical blocks was added to the
 /// chain.
 ///
@@ -676,6 +926,14 @@ pub trait BestTransactions: Iterator + Send {
     /// depend on it.
     fn mark_invalid(&mut self, transaction: &Self::Item);
 
+    /// Marks all transactions in the given slots as invalid, see [`InvalidSlots`].
+    ///
+    /// In contrast to [`Self::mark_invalid`], this also skips descendants that were already
+    /// unlocked, e.g. because the builder only found out that a sender is out of funds after
+    /// executing one of its transactions. This way the builder can share the senders that failed
+    /// while building the block without restarting the iteration.
+    fn mark_invalid_slots(&mut self, slots: &InvalidSlots);
+
     /// An iterator may be able to receive additional pending transactions that weren't present it
     /// the pool when it was created.
     ///
@@ -717,6 +975,8 @@ pub trait BestTransactions: Iterator + Send {
 impl<T> BestTransactions for std::iter::Empty<T> {
     fn mark_invalid(&mut self, _tx: &T) {}
 
+    fn mark_invalid_slots(&mut self, _slots: &InvalidSlots) {}
+
     fn no_updates(&mut self) {}
 
     fn skip_blobs(&mut self) {}
@@ -724,6 +984,57 @@ impl<T> BestTransactions for std::iter::Empty<T> {
     fn set_skip_blobs(&mut self, _skip_blobs: bool) {}
 }
 
+/// Transaction slots, a sender and a nonce, that became invalid while building a block.
+///
+/// A slot covers the transaction with the sender and nonce and all its descendants, so marking
+/// the slot with the on chain nonce of a sender invalidates all its transactions. See
+/// [`BestTransactions::mark_invalid_slots`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvalidSlots {
+    /// The lowest invalid nonce of each sender.
+    by_sender: HashMap<Address, u64>,
+}
+
+// === impl InvalidSlots ===
+
+impl InvalidSlots {
+    /// Marks all transactions of the sender as invalid.
+    pub fn insert_sender(&mut self, sender: Address) {
+        self.insert(sender, 0)
+    }
+
+    /// Marks the transaction of the sender with the given nonce and all its descendants as
+    /// invalid.
+    pub fn insert(&mut self, sender: Address, nonce: u64) {
+        self.by_sender
+            .entry(sender)
+            .and_modify(|lowest| *lowest = (*lowest).min(nonce))
+            .or_insert(nonce);
+    }
+
+    /// Returns `true` if the transaction of the sender with the given nonce is invalid.
+    pub fn contains(&self, sender: &Address, nonce: u64) -> bool {
+        self.by_sender.get(sender).is_some_and(|lowest| nonce >= *lowest)
+    }
+
+    /// Adds all slots of `other`.
+    pub fn extend(&mut self, other: &Self) {
+        for (sender, nonce) in &other.by_sender {
+            self.insert(*sender, *nonce)
+        }
+    }
+
+    /// Returns the number of senders with invalid slots.
+    pub fn len(&self) -> usize {
+        self.by_sender.len()
+    }
+
+    /// Returns `true` if there are no invalid slots.
+    pub fn is_empty(&self) -> bool {
+        self.by_sender.is_empty()
+    }
+}
+
 /// A Helper type that bundles best transactions attributes together.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct BestTransactionsAttributes {
@@ -779,6 +1090,9 @@ pub trait PoolTransaction:
     /// max_blob_fee_per_gas * blob_gas_used`.
     fn cost(&self) -> U256;
 
+    /// Returns the value transferred by the transaction.
+    fn value(&self) -> U256;
+
     /// Amount of gas that should be used in executing this transaction. This is paid up-front.
     fn gas_limit(&self) -> u64;
 
@@ -842,8 +1156,41 @@ pub trait PoolTransaction:
         self.tx_type() == EIP4844_TX_TYPE_ID
     }
 
+    /// Returns true if the transaction is an EIP-7702 transaction.
+    fn is_eip7702(&self) -> bool {
+        self.tx_type() == EIP7702_TX_TYPE_ID
+    }
+
+    /// Returns true if the transaction is an OP deposit transaction.
+    ///
+    /// Deposit transactions are included by the sequencer and must never be pooled.
+    #[cfg(feature = "optimism")]
+    fn is_deposit(&self) -> bool {
+        self.tx_type() == reth_primitives::DEPOSIT_TX_TYPE_ID
+    }
+
+    /// Returns the number of blobs this transaction has.
+    ///
+    /// This is always zero for non-EIP4844 transactions.
+    fn blob_count(&self) -> usize;
+
+    /// Returns the number of authorizations in the authorization list of this transaction.
+    ///
+    /// This is always zero for non-EIP7702 transactions.
+    fn authorization_count(&self) -> usize;
+
+    /// Returns the authorities that signed the authorization list of this transaction.
+    ///
+    /// Authorizations with an invalid signature are skipped, since they are skipped during
+    /// execution as well. This will return `None` for non-EIP7702 transactions.
+    fn authorities(&self) -> Option<&[Address]>;
+
     /// Returns the length of the rlp encoded transaction object
     ///
+    /// For blob transactions, this includes the blob sidecar the transaction was received with.
+    /// This is what the encoded size limits of the pool are enforced on, see
+    /// [`PoolConfig::max_encoded_size`](crate::PoolConfig::max_encoded_size).
+    ///
     /// Note: Implementations should cache this value.
     fn encoded_length(&self) -> usize;
 
@@ -857,9 +1204,6 @@ pub trait EthPoolTransaction: PoolTransaction {
     /// Extracts the blob sidecar from the transaction.
     fn take_blob(&mut self) -> EthBlobTransactionSidecar;
 
-    /// Returns the number of blobs this transaction has.
-    fn blob_count(&self) -> usize;
-
     /// Validates the blob sidecar of the transaction with the given settings.
     fn validate_blob(
         &self,
@@ -889,6 +1233,9 @@ pub struct EthPooledTransaction {
 
     /// The blob side car for this transaction
     pub(crate) blob_sidecar: EthBlobTransactionSidecar,
+
+    /// The recovered authorities of the authorization list, for EIP-7702 transactions.
+    pub(crate) authorities: Option<Vec<Address>>,
 }
 
 /// Represents the blob sidecar of the [`EthPooledTransaction`].
@@ -922,6 +1269,7 @@ impl EthPooledTransaction {
     /// [`EthBlobTransactionSidecar::Missing`]
     pub fn new(transaction: TransactionSignedEcRecovered, encoded_length: usize) -> Self {
         let mut blob_sidecar = EthBlobTransactionSidecar::None;
+        let mut authorities = None;
 
         #[allow(unreachable_patterns)]
         let gas_cost = match &transaction.transaction {
@@ -938,6 +1286,15 @@ impl EthPooledTransaction {
                 blob_sidecar = EthBlobTransactionSidecar::Missing;
                 U256::from(t.max_fee_per_gas).saturating_mul(U256::from(t.gas_limit))
             }
+            Transaction::Eip7702(t) => {
+                authorities = Some(
+                    t.authorization_list
+                        .iter()
+                        .filter_map(|auth| auth.recover_authority().ok())
+                        .collect(),
+                );
+                U256::from(t.max_fee_per_gas).saturating_mul(U256::from(t.gas_limit))
+            }
             _ => U256::ZERO,
         };
         let mut cost = transaction.value();
@@ -950,7 +1307,7 @@ impl EthPooledTransaction {
             ));
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, authorities }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1007,6 +1364,11 @@ impl PoolTransaction for EthPooledTransaction {
         self.cost
     }
 
+    /// Returns the value transferred by the transaction.
+    fn value(&self) -> U256 {
+        self.transaction.value()
+    }
+
     /// Amount of gas that should be used in executing this transaction. This is paid up-front.
     fn gas_limit(&self) -> u64 {
         self.transaction.gas_limit()
@@ -1024,6 +1386,7 @@ impl PoolTransaction for EthPooledTransaction {
             Transaction::Eip2930(tx) => tx.gas_price,
             Transaction::Eip1559(tx) => tx.max_fee_per_gas,
             Transaction::Eip4844(tx) => tx.max_fee_per_gas,
+            Transaction::Eip7702(tx) => tx.max_fee_per_gas,
             _ => 0,
         }
     }
@@ -1041,6 +1404,7 @@ impl PoolTransaction for EthPooledTransaction {
             Transaction::Legacy(_) | Transaction::Eip2930(_) => None,
             Transaction::Eip1559(tx) => Some(tx.max_priority_fee_per_gas),
             Transaction::Eip4844(tx) => Some(tx.max_priority_fee_per_gas),
+            Transaction::Eip7702(tx) => Some(tx.max_priority_fee_per_gas),
             _ => None,
         }
     }
@@ -1083,6 +1447,27 @@ impl PoolTransaction for EthPooledTransaction {
         self.transaction.tx_type().into()
     }
 
+    /// Returns the number of blob versioned hashes of the transaction.
+    fn blob_count(&self) -> usize {
+        match &self.transaction.transaction {
+            Transaction::Eip4844(tx) => tx.blob_versioned_hashes.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of authorizations of the transaction.
+    fn authorization_count(&self) -> usize {
+        match &self.transaction.transaction {
+            Transaction::Eip7702(tx) => tx.authorization_list.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the authorities recovered when the transaction was created.
+    fn authorities(&self) -> Option<&[Address]> {
+        self.authorities.as_deref()
+    }
+
     /// Returns the length of the rlp encoded object
     fn encoded_length(&self) -> usize {
         self.encoded_length
@@ -1103,13 +1488,6 @@ impl EthPoolTransaction for EthPooledTransaction {
         }
     }
 
-    fn blob_count(&self) -> usize {
-        match &self.transaction.transaction {
-            Transaction::Eip4844(tx) => tx.blob_versioned_hashes.len(),
-            _ => 0,
-        }
-    }
-
     fn validate_blob(
         &self,
         sidecar: &BlobTransactionSidecar,
@@ -1130,7 +1508,7 @@ impl TryFromRecoveredTransaction for EthPooledTransaction {
     ) -> Result<Self, Self::Error> {
         // ensure we can handle the transaction type and its format
         match tx.tx_type() as u8 {
-            0..=EIP1559_TX_TYPE_ID => {
+            0..=EIP1559_TX_TYPE_ID | EIP7702_TX_TYPE_ID => {
                 // supported
             }
             EIP4844_TX_TYPE_ID => {
@@ -1170,18 +1548,28 @@ pub struct PoolSize {
     pub pending: usize,
     /// Reported size of transactions in the _pending_ sub-pool.
     pub pending_size: usize,
+    /// Encoded size of transactions in the _pending_ sub-pool.
+    pub pending_encoded_size: usize,
     /// Number of transactions in the _blob_ pool.
     pub blob: usize,
     /// Reported size of transactions in the _blob_ pool.
     pub blob_size: usize,
+    /// Encoded size of transactions in the _blob_ pool, including the blob sidecars.
+    pub blob_encoded_size: usize,
+    /// Number of blobs of the transactions in the _blob_ pool.
+    pub blob_count: usize,
     /// Number of transactions in the _basefee_ pool.
     pub basefee: usize,
     /// Reported size of transactions in the _basefee_ sub-pool.
     pub basefee_size: usize,
+    /// Encoded size of transactions in the _basefee_ sub-pool.
+    pub basefee_encoded_size: usize,
     /// Number of transactions in the _queued_ sub-pool.
     pub queued: usize,
     /// Reported size of transactions in the _queued_ sub-pool.
     pub queued_size: usize,
+    /// Encoded size of transactions in the _queued_ sub-pool.
+    pub queued_encoded_size: usize,
     /// Number of all transactions of all sub-pools
     ///
     /// Note: this is the sum of ```pending + basefee + queued```
@@ -1217,6 +1605,62 @@ pub struct BlockInfo {
     pub pending_blob_fee: Option<u128>,
 }
 
+/// The nonce state of a sender in the pool, see [`TransactionPool::sender_state`].
+///
+/// This explains why transactions of a sender are stuck: transactions after a nonce gap can't be
+/// executed until the missing nonce is filled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderState {
+    /// The sender.
+    pub sender: Address,
+    /// The on-chain nonce of the sender, as last seen by the pool.
+    ///
+    /// `None` if the pool never tracked the sender.
+    pub on_chain_nonce: Option<u64>,
+    /// The on-chain balance of the sender, as last seen by the pool.
+    pub balance: Option<U256>,
+    /// The transactions of the sender in the pool, ordered by nonce.
+    pub transactions: Vec<SenderTransaction>,
+    /// The first missing nonce before a transaction of the sender in the pool.
+    pub nonce_gap: Option<u64>,
+    /// The transactions that are blocked by the [`Self::nonce_gap`].
+    pub blocked: Vec<TxHash>,
+}
+
+impl SenderState {
+    /// Returns the nonces of the transactions of the sender in the pool.
+    pub fn pool_nonces(&self) -> impl Iterator<Item = u64> + '_ {
+        self.transactions.iter().map(|tx| tx.nonce)
+    }
+}
+
+/// The number of transactions removed by [`TransactionPoolExt::run_maintenance`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolMaintenanceStats {
+    /// Transactions that were removed because they exceeded the transaction lifetime.
+    pub expired: usize,
+    /// Transactions that were removed because the sub-pools exceeded their limits.
+    pub discarded: usize,
+}
+
+impl PoolMaintenanceStats {
+    /// Returns the total number of removed transactions.
+    pub const fn removed(&self) -> usize {
+        self.expired + self.discarded
+    }
+}
+
+/// A transaction of a [`SenderState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SenderTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The sub-pool the transaction is in.
+    pub subpool: SubPool,
+}
+
 /// The limit to enforce for [`TransactionPool::get_pooled_transaction_elements`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum GetPooledTransactionLimit {
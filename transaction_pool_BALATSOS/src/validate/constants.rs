@@ -16,3 +16,10 @@ pub const MAX_CODE_BYTE_SIZE: usize = 24576;
 
 /// Maximum initcode to permit in a creation transaction and create instructions.
 pub const MAX_INIT_CODE_BYTE_SIZE: usize = 2 * MAX_CODE_BYTE_SIZE;
+
+/// The code prefix of an account that delegated its code to another account with an EIP-7702
+/// authorization, followed by the address of the delegate.
+pub const EIP7702_DELEGATION_DESIGNATOR: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// The intrinsic gas charged for each authorization of an EIP-7702 transaction.
+pub const PER_EMPTY_ACCOUNT_COST: u64 = 25_000;
@@ -1,11 +1,19 @@
 //! Ethereum transaction validator.
 
-use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
+use super::constants::{
+    DEFAULT_MAX_TX_INPUT_BYTES, EIP7702_DELEGATION_DESIGNATOR, PER_EMPTY_ACCOUNT_COST,
+};
 use crate::{
     blobstore::BlobStore,
-    error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+    },
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        DynamicPriorityFee, IntrinsicGasCache, MinimumPriorityFee, PreInclusionSimulator,
+        SimulationBudget, SimulationExecutor, SimulationOutcome, ValidTransaction, ValidationTask,
+        DEFAULT_MAX_CONCURRENT_SIMULATIONS, MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -13,9 +21,9 @@ use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_primitives::{
     constants::{eip4844::MAX_BLOBS_PER_BLOCK, ETHEREUM_BLOCK_GAS_LIMIT},
     Address, GotExpected, InvalidTransactionError, SealedBlock, TxKind, EIP1559_TX_TYPE_ID,
-    EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
+    EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
 };
-use reth_provider::{AccountReader, BlockReaderIdExt, StateProviderFactory};
+use reth_provider::{AccountReader, BlockReaderIdExt, StateProviderBox, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use revm::{
     interpreter::gas::validate_initial_tx_gas,
@@ -26,6 +34,7 @@ use std::{
     sync::{atomic::AtomicBool, Arc},
 };
 use tokio::sync::Mutex;
+use tracing::trace;
 
 /// Validator for Ethereum transactions.
 #[derive(Debug, Clone)]
@@ -100,6 +109,12 @@ where
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.inner.on_new_head_block(new_tip_block)
     }
+
+    fn on_pool_fullness(&self, fullness: f64) {
+        if let Some(minimum_priority_fee) = &self.inner.minimum_priority_fee {
+            minimum_priority_fee.set_pool_fullness(fullness)
+        }
+    }
 }
 
 /// A [`TransactionValidator`] implementation that validates ethereum transaction.
@@ -119,10 +134,18 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     eip1559: bool,
     /// Fork indicator whether we are using EIP-4844 blob transactions.
     eip4844: bool,
+    /// Fork indicator whether we are using EIP-7702 set code transactions.
+    eip7702: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
-    minimum_priority_fee: Option<u128>,
+    minimum_priority_fee: Option<MinimumPriorityFee>,
+    /// Caches the intrinsic gas of resubmitted transactions, if enabled.
+    intrinsic_gas_cache: Option<IntrinsicGasCache>,
+    /// Simulates local transactions before they are admitted, if configured.
+    simulator: Option<SimulationExecutor>,
+    /// Resources a single simulation may use.
+    simulation_budget: SimulationBudget,
     /// Stores the setup and parameters needed for validating KZG proofs.
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
@@ -185,6 +208,15 @@ where
                     )
                 }
             }
+            EIP7702_TX_TYPE_ID => {
+                // Reject set code transactions.
+                if !self.eip7702 {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::TxTypeNotSupported.into(),
+                    )
+                }
+            }
 
             _ => {
                 return TransactionValidationOutcome::Invalid(
@@ -235,14 +267,17 @@ where
 
         // Drop non-local transactions with a fee lower than the configured fee for acceptance into
         // the pool.
-        if !self.local_transactions_config.is_local(origin, transaction.sender()) &&
-            transaction.is_eip1559() &&
-            transaction.max_priority_fee_per_gas() < self.minimum_priority_fee
-        {
-            return TransactionValidationOutcome::Invalid(
-                transaction,
-                InvalidPoolTransactionError::Underpriced,
-            )
+        if let Some(minimum_priority_fee) = &self.minimum_priority_fee {
+            let minimum_priority_fee = minimum_priority_fee.current();
+            if !self.local_transactions_config.is_local(origin, transaction.sender()) &&
+                (transaction.is_eip1559() || transaction.is_eip7702()) &&
+                transaction.max_priority_fee_per_gas() < Some(minimum_priority_fee)
+            {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::PriorityFeeBelowMinimum { minimum_priority_fee },
+                )
+            }
         }
 
         // Checks for chainid
@@ -257,8 +292,17 @@ where
 
         // intrinsic gas checks
         let is_shanghai = self.fork_tracker.is_shanghai_activated();
-        if let Err(err) = ensure_intrinsic_gas(&transaction, is_shanghai) {
-            return TransactionValidationOutcome::Invalid(transaction, err)
+        let intrinsic_gas = match &self.intrinsic_gas_cache {
+            Some(cache) => cache.get_or_insert_with(&transaction, || {
+                calculate_intrinsic_gas(&transaction, is_shanghai)
+            }),
+            None => calculate_intrinsic_gas(&transaction, is_shanghai),
+        };
+        if transaction.gas_limit() < intrinsic_gas {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::IntrinsicGasTooLow,
+            )
         }
 
         // light blob tx pre-checks
@@ -296,11 +340,43 @@ where
             }
         }
 
-        let account = match self
-            .client
-            .latest()
-            .and_then(|state| state.basic_account(transaction.sender()))
-        {
+        // light set code tx pre-checks
+        if transaction.is_eip7702() {
+            // Prague fork is required for set code txs
+            if !self.fork_tracker.is_prague_activated() {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidTransactionError::TxTypeNotSupported.into(),
+                )
+            }
+
+            if transaction.authorization_count() == 0 {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::Eip7702(
+                        Eip7702PoolTransactionError::MissingEip7702AuthorizationList,
+                    ),
+                )
+            }
+
+            if transaction.kind().is_create() {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::Eip7702(
+                        Eip7702PoolTransactionError::Eip7702Create,
+                    ),
+                )
+            }
+        }
+
+        let state = match self.client.latest() {
+            Ok(state) => state,
+            Err(err) => {
+                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+            }
+        };
+
+        let account = match state.basic_account(transaction.sender()) {
             Ok(account) => account.unwrap_or_default(),
             Err(err) => {
                 return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
@@ -308,12 +384,30 @@ where
         };
 
         // Signer account shouldn't have bytecode. Presence of bytecode means this is a
-        // smartcontract.
+        // smartcontract, unless the account delegated its code with an EIP-7702 authorization.
         if account.has_bytecode() {
-            return TransactionValidationOutcome::Invalid(
-                transaction,
-                InvalidTransactionError::SignerAccountHasBytecode.into(),
-            )
+            let is_delegated = if self.fork_tracker.is_prague_activated() {
+                match state.account_code(transaction.sender()) {
+                    Ok(code) => {
+                        code.map_or(false, |code| is_delegation_designator(&code.original_bytes()))
+                    }
+                    Err(err) => {
+                        return TransactionValidationOutcome::Error(
+                            *transaction.hash(),
+                            Box::new(err),
+                        )
+                    }
+                }
+            } else {
+                false
+            };
+
+            if !is_delegated {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidTransactionError::SignerAccountHasBytecode.into(),
+                )
+            }
         }
 
         // Checks for nonce
@@ -382,6 +476,16 @@ where
             }
         }
 
+        // reject local transactions that are guaranteed to revert
+        if origin.is_local() {
+            if let SimulationOutcome::Reverted { output } = self.simulate(state, &transaction) {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::SimulationReverted { output },
+                )
+            }
+        }
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
@@ -398,14 +502,45 @@ where
         }
     }
 
+    /// Simulates the transaction against the given state with the configured
+    /// [`PreInclusionSimulator`].
+    ///
+    /// The simulation runs on the simulation threads, so validation doesn't wait for it longer
+    /// than the time budget. Returns [`SimulationOutcome::Inconclusive`] if no simulator is
+    /// configured or the simulation didn't complete in time.
+    fn simulate(&self, state: StateProviderBox, transaction: &Tx) -> SimulationOutcome {
+        let Some(simulator) = &self.simulator else { return SimulationOutcome::Inconclusive };
+
+        let budget = SimulationBudget {
+            gas_limit: self.simulation_budget.gas_limit.min(transaction.gas_limit()),
+            ..self.simulation_budget
+        };
+        let outcome = simulator.simulate(state, transaction.to_recovered_transaction(), budget);
+        trace!(target: "txpool", hash = ?transaction.hash(), ?outcome, "simulated transaction");
+        outcome
+    }
+
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         // update all forks
+        let mut activated = false;
         if self.chain_spec.is_cancun_active_at_timestamp(new_tip_block.timestamp) {
-            self.fork_tracker.cancun.store(true, std::sync::atomic::Ordering::Relaxed);
+            activated |= !self.fork_tracker.cancun.swap(true, std::sync::atomic::Ordering::Relaxed);
         }
 
         if self.chain_spec.is_shanghai_active_at_timestamp(new_tip_block.timestamp) {
-            self.fork_tracker.shanghai.store(true, std::sync::atomic::Ordering::Relaxed);
+            activated |=
+                !self.fork_tracker.shanghai.swap(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        if self.chain_spec.is_prague_active_at_timestamp(new_tip_block.timestamp) {
+            activated |= !self.fork_tracker.prague.swap(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        // the intrinsic gas may change with the new hardfork
+        if activated {
+            if let Some(cache) = &self.intrinsic_gas_cache {
+                cache.clear();
+            }
         }
     }
 }
@@ -418,16 +553,28 @@ pub struct EthTransactionValidatorBuilder {
     shanghai: bool,
     /// Fork indicator whether we are in the Cancun hardfork.
     cancun: bool,
+    /// Fork indicator whether we are in the Prague hardfork.
+    prague: bool,
     /// Whether using EIP-2718 type transactions is allowed
     eip2718: bool,
     /// Whether using EIP-1559 type transactions is allowed
     eip1559: bool,
     /// Whether using EIP-4844 type transactions is allowed
     eip4844: bool,
+    /// Whether using EIP-7702 type transactions is allowed
+    eip7702: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
-    minimum_priority_fee: Option<u128>,
+    minimum_priority_fee: Option<MinimumPriorityFee>,
+    /// Max number of transactions to cache the intrinsic gas of, disabled if not set.
+    intrinsic_gas_cache_size: Option<u32>,
+    /// Simulates local transactions before they are admitted, disabled if not set.
+    simulator: Option<Arc<dyn PreInclusionSimulator>>,
+    /// Resources a single simulation may use.
+    simulation_budget: SimulationBudget,
+    /// Max number of simulations that run concurrently.
+    max_concurrent_simulations: usize,
     /// Determines how many additional tasks to spawn
     ///
     /// Default is 1
@@ -450,11 +597,16 @@ impl EthTransactionValidatorBuilder {
     ///  - EIP-2718
     ///  - EIP-1559
     ///  - EIP-4844
+    ///  - EIP-7702, once `Prague` is activated
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
         Self {
             chain_spec,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             minimum_priority_fee: None,
+            intrinsic_gas_cache_size: None,
+            simulator: None,
+            simulation_budget: Default::default(),
+            max_concurrent_simulations: DEFAULT_MAX_CONCURRENT_SIMULATIONS,
             additional_tasks: 1,
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
@@ -464,12 +616,16 @@ impl EthTransactionValidatorBuilder {
             eip2718: true,
             eip1559: true,
             eip4844: true,
+            eip7702: true,
 
             // shanghai is activated by default
             shanghai: true,
 
             // cancun is activated by default
             cancun: true,
+
+            // prague is not activated by default
+            prague: false,
         }
     }
 
@@ -493,6 +649,17 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Disables the Prague fork.
+    pub const fn no_prague(self) -> Self {
+        self.set_prague(false)
+    }
+
+    /// Set the Prague fork.
+    pub const fn set_prague(mut self, prague: bool) -> Self {
+        self.prague = prague;
+        self
+    }
+
     /// Disables the Shanghai fork.
     pub const fn no_shanghai(self) -> Self {
         self.set_shanghai(false)
@@ -537,6 +704,17 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Disables the support for EIP-7702 transactions.
+    pub const fn no_eip7702(self) -> Self {
+        self.set_eip7702(false)
+    }
+
+    /// Set the support for EIP-7702 transactions.
+    pub const fn set_eip7702(mut self, eip7702: bool) -> Self {
+        self.eip7702 = eip7702;
+        self
+    }
+
     /// Sets the [`EnvKzgSettings`] to use for validating KZG proofs.
     pub fn kzg_settings(mut self, kzg_settings: EnvKzgSettings) -> Self {
         self.kzg_settings = kzg_settings;
@@ -544,8 +722,51 @@ impl EthTransactionValidatorBuilder {
     }
 
     /// Sets a minimum priority fee that's enforced for acceptance into the pool.
-    pub const fn with_minimum_priority_fee(mut self, minimum_priority_fee: u128) -> Self {
-        self.minimum_priority_fee = Some(minimum_priority_fee);
+    pub fn with_minimum_priority_fee(mut self, minimum_priority_fee: u128) -> Self {
+        self.minimum_priority_fee = Some(MinimumPriorityFee::Static(minimum_priority_fee));
+        self
+    }
+
+    /// Sets a minimum priority fee that rises with the fullness of the pool, see
+    /// [`DynamicPriorityFee`].
+    pub fn with_dynamic_minimum_priority_fee(
+        mut self,
+        minimum_priority_fee: DynamicPriorityFee,
+    ) -> Self {
+        self.minimum_priority_fee = Some(MinimumPriorityFee::Dynamic(minimum_priority_fee));
+        self
+    }
+
+    /// Enables caching the intrinsic gas of at most `max_length` transactions, see
+    /// [`IntrinsicGasCache`].
+    pub const fn with_intrinsic_gas_cache(mut self, max_length: u32) -> Self {
+        self.intrinsic_gas_cache_size = Some(max_length);
+        self
+    }
+
+    /// Sets a [`PreInclusionSimulator`] that local transactions are executed with before they are
+    /// admitted.
+    ///
+    /// Local transactions that revert in the simulation are rejected.
+    pub fn with_pre_inclusion_simulator<S>(mut self, simulator: S) -> Self
+    where
+        S: PreInclusionSimulator + 'static,
+    {
+        self.simulator = Some(Arc::new(simulator));
+        self
+    }
+
+    /// Sets the resources a single simulation may use, see [`SimulationBudget`].
+    pub const fn with_simulation_budget(mut self, simulation_budget: SimulationBudget) -> Self {
+        self.simulation_budget = simulation_budget;
+        self
+    }
+
+    /// Sets the max number of simulations that run concurrently.
+    ///
+    /// Default is [`DEFAULT_MAX_CONCURRENT_SIMULATIONS`].
+    pub const fn with_max_concurrent_simulations(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent_simulations = max_concurrent;
         self
     }
 
@@ -557,10 +778,11 @@ impl EthTransactionValidatorBuilder {
 
     /// Configures validation rules based on the head block's timestamp.
     ///
-    /// For example, whether the Shanghai and Cancun hardfork is activated at launch.
+    /// For example, whether the Shanghai, Cancun and Prague hardforks are activated at launch.
     pub fn with_head_timestamp(mut self, timestamp: u64) -> Self {
         self.cancun = self.chain_spec.is_cancun_active_at_timestamp(timestamp);
         self.shanghai = self.chain_spec.is_shanghai_active_at_timestamp(timestamp);
+        self.prague = self.chain_spec.is_prague_active_at_timestamp(timestamp);
         self
     }
 
@@ -591,19 +813,28 @@ impl EthTransactionValidatorBuilder {
             chain_spec,
             shanghai,
             cancun,
+            prague,
             eip2718,
             eip1559,
             eip4844,
+            eip7702,
             block_gas_limit,
             minimum_priority_fee,
+            intrinsic_gas_cache_size,
+            simulator,
+            simulation_budget,
+            max_concurrent_simulations,
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
             ..
         } = self;
 
-        let fork_tracker =
-            ForkTracker { shanghai: AtomicBool::new(shanghai), cancun: AtomicBool::new(cancun) };
+        let fork_tracker = ForkTracker {
+            shanghai: AtomicBool::new(shanghai),
+            cancun: AtomicBool::new(cancun),
+            prague: AtomicBool::new(prague),
+        };
 
         let inner = EthTransactionValidatorInner {
             chain_spec,
@@ -612,8 +843,15 @@ impl EthTransactionValidatorBuilder {
             eip1559,
             fork_tracker,
             eip4844,
+            eip7702,
             block_gas_limit,
             minimum_priority_fee,
+            intrinsic_gas_cache: intrinsic_gas_cache_size.map(IntrinsicGasCache::new),
+            simulator: simulator.map(|simulator| {
+                SimulationExecutor::new(simulator, max_concurrent_simulations)
+                    .expect("failed to build simulation thread pool")
+            }),
+            simulation_budget,
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
@@ -675,6 +913,8 @@ pub(crate) struct ForkTracker {
     pub(crate) shanghai: AtomicBool,
     /// Tracks if cancun is activated at the block's timestamp.
     pub(crate) cancun: AtomicBool,
+    /// Tracks if prague is activated at the block's timestamp.
+    pub(crate) prague: AtomicBool,
 }
 
 impl ForkTracker {
@@ -687,6 +927,18 @@ impl ForkTracker {
     pub(crate) fn is_cancun_activated(&self) -> bool {
         self.cancun.load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Returns `true` if Prague fork is activated.
+    pub(crate) fn is_prague_activated(&self) -> bool {
+        self.prague.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+/// Returns true if the given code is an EIP-7702 delegation designator, `0xef0100 || address`.
+#[inline]
+pub fn is_delegation_designator(code: &[u8]) -> bool {
+    code.len() == EIP7702_DELEGATION_DESIGNATOR.len() + 20 &&
+        code.starts_with(&EIP7702_DELEGATION_DESIGNATOR)
 }
 
 /// Ensure that the code size is not greater than `max_init_code_size`.
@@ -712,21 +964,27 @@ pub fn ensure_intrinsic_gas<T: PoolTransaction>(
     transaction: &T,
     is_shanghai: bool,
 ) -> Result<(), InvalidPoolTransactionError> {
-    let access_list = transaction.access_list().map(|list| list.flattened()).unwrap_or_default();
-    if transaction.gas_limit() <
-        calculate_intrinsic_gas_after_merge(
-            transaction.input(),
-            &transaction.kind(),
-            &access_list,
-            is_shanghai,
-        )
-    {
+    if transaction.gas_limit() < calculate_intrinsic_gas(transaction, is_shanghai) {
         Err(InvalidPoolTransactionError::IntrinsicGasTooLow)
     } else {
         Ok(())
     }
 }
 
+/// Calculates the intrinsic gas of the transaction, including the authorizations of EIP-7702
+/// transactions.
+///
+/// See also [`calculate_intrinsic_gas_after_merge`]
+pub fn calculate_intrinsic_gas<T: PoolTransaction>(transaction: &T, is_shanghai: bool) -> u64 {
+    let access_list = transaction.access_list().map(|list| list.flattened()).unwrap_or_default();
+    calculate_intrinsic_gas_after_merge(
+        transaction.input(),
+        &transaction.kind(),
+        &access_list,
+        is_shanghai,
+    ) + transaction.authorization_count() as u64 * PER_EMPTY_ACCOUNT_COST
+}
+
 /// Calculates the Intrinsic Gas usage for a Transaction
 ///
 /// Caution: This only checks past the Merge hardfork.
@@ -745,12 +1003,22 @@ pub fn calculate_intrinsic_gas_after_merge(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, CoinbaseTipOrdering,
-        EthPooledTransaction, Pool, TransactionPool,
+        blobstore::InMemoryBlobStore, error::PoolErrorKind, validate::SimulationCancellation,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use reth_chainspec::MAINNET;
-    use reth_primitives::{hex, FromRecoveredPooledTransaction, PooledTransactionsElement, U256};
-    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_primitives::{
+        hex, Bytes, FromRecoveredPooledTransaction, PooledTransactionsElement,
+        TransactionSignedEcRecovered, U256,
+    };
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        StateProvider,
+    };
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
 
     fn get_transaction() -> EthPooledTransaction {
         let raw = "0x02f914950181ad84b2d05e0085117553845b830f7df88080b9143a6040608081523462000414576200133a803803806200001e8162000419565b9283398101608082820312620004145781516001600160401b03908181116200041457826200004f9185016200043f565b92602092838201519083821162000414576200006d9183016200043f565b8186015190946001600160a01b03821692909183900362000414576060015190805193808511620003145760038054956001938488811c9816801562000409575b89891014620003f3578190601f988981116200039d575b50899089831160011462000336576000926200032a575b505060001982841b1c191690841b1781555b8751918211620003145760049788548481811c9116801562000309575b89821014620002f457878111620002a9575b5087908784116001146200023e5793839491849260009562000232575b50501b92600019911b1c19161785555b6005556007805460ff60a01b19169055600880546001600160a01b0319169190911790553015620001f3575060025469d3c21bcecceda100000092838201809211620001de57506000917fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef9160025530835282815284832084815401905584519384523093a351610e889081620004b28239f35b601190634e487b7160e01b6000525260246000fd5b90606493519262461bcd60e51b845283015260248201527f45524332303a206d696e7420746f20746865207a65726f2061646472657373006044820152fd5b0151935038806200013a565b9190601f198416928a600052848a6000209460005b8c8983831062000291575050501062000276575b50505050811b0185556200014a565b01519060f884600019921b161c191690553880808062000267565b86860151895590970196948501948893500162000253565b89600052886000208880860160051c8201928b8710620002ea575b0160051c019085905b828110620002dd5750506200011d565b60008155018590620002cd565b92508192620002c4565b60228a634e487b7160e01b6000525260246000fd5b90607f16906200010b565b634e487b7160e01b600052604160045260246000fd5b015190503880620000dc565b90869350601f19831691856000528b6000209260005b8d8282106200038657505084116200036d575b505050811b018155620000ee565b015160001983861b60f8161c191690553880806200035f565b8385015186558a979095019493840193016200034c565b90915083600052896000208980850160051c8201928c8610620003e9575b918891869594930160051c01915b828110620003d9575050620000c5565b60008155859450889101620003c9565b92508192620003bb565b634e487b7160e01b600052602260045260246000fd5b97607f1697620000ae565b600080fd5b6040519190601f01601f191682016001600160401b038111838210176200031457604052565b919080601f84011215620004145782516001600160401b038111620003145760209062000475601f8201601f1916830162000419565b92818452828287010111620004145760005b8181106200049d57508260009394955001015290565b85810183015184820184015282016200048756fe608060408181526004918236101561001657600080fd5b600092833560e01c91826306fdde0314610a1c57508163095ea7b3146109f257816318160ddd146109d35781631b4c84d2146109ac57816323b872dd14610833578163313ce5671461081757816339509351146107c357816370a082311461078c578163715018a6146107685781638124f7ac146107495781638da5cb5b1461072057816395d89b411461061d578163a457c2d714610575578163a9059cbb146104e4578163c9567bf914610120575063dd62ed3e146100d557600080fd5b3461011c578060031936011261011c57806020926100f1610b5a565b6100f9610b75565b6001600160a01b0391821683526001865283832091168252845220549051908152f35b5080fd5b905082600319360112610338576008546001600160a01b039190821633036104975760079283549160ff8360a01c1661045557737a250d5630b4cf539739df2c5dacb4c659f2488d92836bffffffffffffffffffffffff60a01b8092161786553087526020938785528388205430156104065730895260018652848920828a52865280858a205584519081527f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925863092a38554835163c45a015560e01b815290861685828581845afa9182156103dd57849187918b946103e7575b5086516315ab88c960e31b815292839182905afa9081156103dd576044879289928c916103c0575b508b83895196879586946364e329cb60e11b8652308c870152166024850152165af19081156103b6579086918991610389575b50169060065416176006558385541660604730895288865260c4858a20548860085416928751958694859363f305d71960e01b8552308a86015260248501528d60448501528d606485015260848401524260a48401525af1801561037f579084929161034c575b50604485600654169587541691888551978894859363095ea7b360e01b855284015260001960248401525af1908115610343575061030c575b5050805460ff60a01b1916600160a01b17905580f35b81813d831161033c575b6103208183610b8b565b8101031261033857518015150361011c5738806102f6565b8280fd5b503d610316565b513d86823e3d90fd5b6060809293503d8111610378575b6103648183610b8b565b81010312610374578290386102bd565b8580fd5b503d61035a565b83513d89823e3d90fd5b6103a99150863d88116103af575b6103a18183610b8b565b810190610e33565b38610256565b503d610397565b84513d8a823e3d90fd5b6103d79150843d86116103af576103a18183610b8b565b38610223565b85513d8b823e3d90fd5b6103ff919450823d84116103af576103a18183610b8b565b92386101fb565b845162461bcd60e51b81528085018790526024808201527f45524332303a20617070726f76652066726f6d20746865207a65726f206164646044820152637265737360e01b6064820152608490fd5b6020606492519162461bcd60e51b8352820152601760248201527f74726164696e6720697320616c7265616479206f70656e0000000000000000006044820152fd5b608490602084519162461bcd60e51b8352820152602160248201527f4f6e6c79206f776e65722063616e2063616c6c20746869732066756e6374696f6044820152603760f91b6064820152fd5b9050346103385781600319360112610338576104fe610b5a565b9060243593303303610520575b602084610519878633610bc3565b5160018152f35b600594919454808302908382041483151715610562576127109004820391821161054f5750925080602061050b565b634e487b7160e01b815260118552602490fd5b634e487b7160e01b825260118652602482fd5b9050823461061a578260031936011261061a57610590610b5a565b918360243592338152600160205281812060018060a01b03861682526020522054908282106105c9576020856105198585038733610d31565b608490602086519162461bcd60e51b8352820152602560248201527f45524332303a2064656372656173656420616c6c6f77616e63652062656c6f77604482015264207a65726f60d81b6064820152fd5b80fd5b83833461011c578160031936011261011c57805191809380549160019083821c92828516948515610716575b6020958686108114610703578589529081156106df5750600114610687575b6106838787610679828c0383610b8b565b5191829182610b11565b0390f35b81529295507f8a35acfbc15ff81a39ae7d344fd709f28e8600b4aa8c65c6b64bfe7fe36bd19b5b8284106106cc57505050826106839461067992820101948680610668565b80548685018801529286019281016106ae565b60ff19168887015250505050151560051b8301019250610679826106838680610668565b634e487b7160e01b845260228352602484fd5b93607f1693610649565b50503461011c578160031936011261011c5760085490516001600160a01b039091168152602090f35b50503461011c578160031936011261011c576020906005549051908152f35b833461061a578060031936011261061a57600880546001600160a01b031916905580f35b50503461011c57602036600319011261011c5760209181906001600160a01b036107b4610b5a565b16815280845220549051908152f35b82843461061a578160031936011261061a576107dd610b5a565b338252600160209081528383206001600160a01b038316845290528282205460243581019290831061054f57602084610519858533610d31565b50503461011c578160031936011261011c576020905160128152f35b83833461011c57606036600319011261011c5761084e610b5a565b610856610b75565b6044359160018060a01b0381169485815260209560018752858220338352875285822054976000198903610893575b505050906105199291610bc3565b85891061096957811561091a5733156108cc5750948481979861051997845260018a528284203385528a52039120558594938780610885565b865162461bcd60e51b8152908101889052602260248201527f45524332303a20617070726f766520746f20746865207a65726f206164647265604482015261737360f01b6064820152608490fd5b865162461bcd60e51b81529081018890526024808201527f45524332303a20617070726f76652066726f6d20746865207a65726f206164646044820152637265737360e01b6064820152608490fd5b865162461bcd60e51b8152908101889052601d60248201527f45524332303a20696e73756666696369656e7420616c6c6f77616e63650000006044820152606490fd5b50503461011c578160031936011261011c5760209060ff60075460a01c1690519015158152f35b50503461011c578160031936011261011c576020906002549051908152f35b50503461011c578060031936011261011c57602090610519610a12610b5a565b6024359033610d31565b92915034610b0d5783600319360112610b0d57600354600181811c9186908281168015610b03575b6020958686108214610af05750848852908115610ace5750600114610a75575b6106838686610679828b0383610b8b565b929550600383527fc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b5b828410610abb575050508261068394610679928201019438610a64565b8054868501880152928601928101610a9e565b60ff191687860152505050151560051b83010192506106798261068338610a64565b634e487b7160e01b845260229052602483fd5b93607f1693610a44565b8380fd5b6020808252825181830181905290939260005b828110610b4657505060409293506000838284010152601f8019910116010190565b818101860151848201604001528501610b24565b600435906001600160a01b0382168203610b7057565b600080fd5b602435906001600160a01b0382168203610b7057565b90601f8019910116810190811067ffffffffffffffff821117610bad57604052565b634e487b7160e01b600052604160045260246000fd5b6001600160a01b03908116918215610cde5716918215610c8d57600082815280602052604081205491808310610c3957604082827fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef958760209652828652038282205586815220818154019055604051908152a3565b60405162461bcd60e51b815260206004820152602660248201527f45524332303a207472616e7366657220616d6f756e7420657863656564732062604482015265616c616e636560d01b6064820152608490fd5b60405162461bcd60e51b815260206004820152602360248201527f45524332303a207472616e7366657220746f20746865207a65726f206164647260448201526265737360e81b6064820152608490fd5b60405162461bcd60e51b815260206004820152602560248201527f45524332303a207472616e736665722066726f6d20746865207a65726f206164604482015264647265737360d81b6064820152608490fd5b6001600160a01b03908116918215610de25716918215610d925760207f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925918360005260018252604060002085600052825280604060002055604051908152a3565b60405162461bcd60e51b815260206004820152602260248201527f45524332303a20617070726f766520746f20746865207a65726f206164647265604482015261737360f01b6064820152608490fd5b60405162461bcd60e51b8152602060048201526024808201527f45524332303a20617070726f76652066726f6d20746865207a65726f206164646044820152637265737360e01b6064820152608490fd5b90816020910312610b7057516001600160a01b0381168103610b70579056fea2646970667358221220285c200b3978b10818ff576bb83f2dc4a2a7c98dfb6a36ea01170de792aa652764736f6c63430008140033000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000d3fd4f95820a9aa848ce716d6c200eaefb9a2e4900000000000000000000000000000000000000000000000000000000000000640000000000000000000000000000000000000000000000000000000000000003543131000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000035431310000000000000000000000000000000000000000000000000000000000c001a04e551c75810ffdfe6caff57da9f5a8732449f42f0f4c57f935b05250a76db3b6a046cd47e6d01914270c1ec0d9ac7fae7dfb240ec9a8b6ec7898c4d6aa174388f2";
@@ -827,4 +1095,141 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn invalid_below_dynamic_minimum_priority_fee() {
+        // tx priority fee is 3 gwei
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let minimum_priority_fee = DynamicPriorityFee::new(1_000_000_000, 11_000_000_000);
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_dynamic_minimum_priority_fee(minimum_priority_fee)
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        // the minimum rises once the pool fills up
+        validator.on_pool_fullness(1.0);
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::PriorityFeeBelowMinimum {
+                    minimum_priority_fee: 11_000_000_000
+                }
+            )
+        ));
+
+        // local transactions are exempt
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction);
+        assert!(outcome.is_valid());
+    }
+
+    #[derive(Debug)]
+    struct RevertingSimulator;
+
+    impl PreInclusionSimulator for RevertingSimulator {
+        fn simulate(
+            &self,
+            _state: &dyn StateProvider,
+            transaction: &TransactionSignedEcRecovered,
+            budget: SimulationBudget,
+            _cancellation: &SimulationCancellation,
+        ) -> SimulationOutcome {
+            // the budget is capped at the gas limit of the transaction
+            assert_eq!(budget.gas_limit, transaction.gas_limit());
+            SimulationOutcome::Reverted { output: Bytes::from_static(&[0xde, 0xad]) }
+        }
+    }
+
+    #[test]
+    fn invalid_local_on_simulated_revert() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_pre_inclusion_simulator(RevertingSimulator)
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::SimulationReverted { .. }
+            )
+        ));
+
+        // only local transactions are simulated
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(outcome.is_valid());
+    }
+
+    /// Runs until the simulation is cancelled.
+    #[derive(Debug, Default)]
+    struct SlowSimulator {
+        cancelled: Arc<AtomicBool>,
+    }
+
+    impl PreInclusionSimulator for SlowSimulator {
+        fn simulate(
+            &self,
+            _state: &dyn StateProvider,
+            _transaction: &TransactionSignedEcRecovered,
+            _budget: SimulationBudget,
+            cancellation: &SimulationCancellation,
+        ) -> SimulationOutcome {
+            while !cancellation.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            self.cancelled.store(true, Ordering::Relaxed);
+            SimulationOutcome::Inconclusive
+        }
+    }
+
+    #[test]
+    fn valid_local_on_simulation_timeout() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let simulator = SlowSimulator::default();
+        let cancelled = Arc::clone(&simulator.cancelled);
+        let budget = SimulationBudget { timeout: Duration::from_millis(10), ..Default::default() };
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_pre_inclusion_simulator(simulator)
+            .with_simulation_budget(budget)
+            .with_max_concurrent_simulations(1)
+            .build(provider, InMemoryBlobStore::default());
+
+        // the validator doesn't wait for the simulation to complete
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction.clone());
+        assert!(outcome.is_valid());
+
+        // the timed out simulation is cancelled, so it doesn't block the next one
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !cancelled.load(Ordering::Relaxed) {
+            assert!(Instant::now() < deadline, "simulation wasn't cancelled");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction);
+        assert!(outcome.is_valid());
+    }
 }
@@ -0,0 +1,99 @@
+//! Cache of the intrinsic gas of validated transactions.
+
+use crate::PoolTransaction;
+use parking_lot::Mutex;
+use reth_primitives::TxHash;
+use schnellru::{ByLength, LruMap};
+use std::fmt;
+
+/// The default number of transactions a [`IntrinsicGasCache`] keeps the intrinsic gas of.
+pub const DEFAULT_INTRINSIC_GAS_CACHE_SIZE: u32 = 10_000;
+
+/// Caches the intrinsic gas of transactions by their hash.
+///
+/// The same transaction is often validated more than once, e.g. when it is reinjected after a
+/// reorg or resubmitted by its sender, in which case the intrinsic gas only has to be computed
+/// once.
+///
+/// The intrinsic gas depends on the active hardforks, so the cache must be cleared once a new
+/// hardfork activates, see [`Self::clear`].
+pub struct IntrinsicGasCache {
+    /// The intrinsic gas of the recently validated transactions.
+    entries: Mutex<LruMap<TxHash, u64, ByLength>>,
+}
+
+impl IntrinsicGasCache {
+    /// Creates a new cache that keeps the intrinsic gas of at most `max_length` transactions.
+    pub fn new(max_length: u32) -> Self {
+        Self { entries: Mutex::new(LruMap::new(ByLength::new(max_length))) }
+    }
+
+    /// Returns the cached intrinsic gas of the transaction, or computes and caches it with the
+    /// given function.
+    pub fn get_or_insert_with<T: PoolTransaction>(
+        &self,
+        transaction: &T,
+        intrinsic_gas: impl FnOnce() -> u64,
+    ) -> u64 {
+        let hash = *transaction.hash();
+        if let Some(gas) = self.entries.lock().get(&hash) {
+            return *gas
+        }
+
+        let gas = intrinsic_gas();
+        self.entries.lock().insert(hash, gas);
+        gas
+    }
+
+    /// Returns the number of cached transactions.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Returns `true` if no transactions are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached transactions, e.g. once a new hardfork activates.
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+}
+
+impl Default for IntrinsicGasCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_INTRINSIC_GAS_CACHE_SIZE)
+    }
+}
+
+impl fmt::Debug for IntrinsicGasCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntrinsicGasCache").field("len", &self.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn cache_intrinsic_gas_of_revalidated_transactions() {
+        let cache = IntrinsicGasCache::new(10);
+        let tx = MockTransaction::eip1559();
+        assert_eq!(cache.get_or_insert_with(&tx, || 21_000), 21_000);
+
+        // a reinjected transaction shares the intrinsic gas
+        assert_eq!(cache.get_or_insert_with(&tx.clone(), || unreachable!()), 21_000);
+
+        // the intrinsic gas of a replacement is computed separately
+        let replacement = tx.inc_price().rng_hash();
+        assert_eq!(cache.get_or_insert_with(&replacement, || 42_000), 42_000);
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get_or_insert_with(&tx, || 53_000), 53_000);
+    }
+}
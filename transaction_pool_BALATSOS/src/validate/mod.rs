@@ -3,7 +3,7 @@
 use crate::{
     error::InvalidPoolTransactionError,
     identifier::{SenderId, TransactionId},
-    traits::{PoolTransaction, TransactionOrigin},
+    traits::{PoolTransaction, TransactionOrigin, TransactionSource},
 };
 use reth_primitives::{
     Address, BlobTransactionSidecar, IntoRecoveredTransaction, SealedBlock,
@@ -13,17 +13,42 @@ use std::{fmt, future::Future, time::Instant};
 
 mod constants;
 mod eth;
+mod gas_cache;
+#[cfg(feature = "optimism")]
+mod op;
+mod priority_fee;
+mod simulate;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Cache of the intrinsic gas of validated transactions.
+pub use gas_cache::{IntrinsicGasCache, DEFAULT_INTRINSIC_GAS_CACHE_SIZE};
+
+/// A `TransactionValidator` implementation that validates transactions of OP-stack chains.
+#[cfg(feature = "optimism")]
+pub use op::OpTransactionValidator;
+
+/// Minimum priority fee for acceptance into the pool.
+pub use priority_fee::{
+    DynamicPriorityFee, MinimumPriorityFee, DEFAULT_PRIORITY_FEE_FULLNESS_THRESHOLD,
+};
+
+/// Simulation of local transactions before they are admitted to the pool.
+pub use simulate::{
+    PreInclusionSimulator, SimulationBudget, SimulationCancellation, SimulationOutcome,
+    DEFAULT_MAX_CONCURRENT_SIMULATIONS, DEFAULT_SIMULATION_GAS_LIMIT, DEFAULT_SIMULATION_TIMEOUT,
+};
+pub(crate) use simulate::SimulationExecutor;
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
 /// Validation constants.
 pub use constants::{
-    DEFAULT_MAX_TX_INPUT_BYTES, MAX_CODE_BYTE_SIZE, MAX_INIT_CODE_BYTE_SIZE, TX_SLOT_BYTE_SIZE,
+    DEFAULT_MAX_TX_INPUT_BYTES, EIP7702_DELEGATION_DESIGNATOR, MAX_CODE_BYTE_SIZE,
+    MAX_INIT_CODE_BYTE_SIZE, PER_EMPTY_ACCOUNT_COST, TX_SLOT_BYTE_SIZE,
 };
 
 /// A Result type returned after checking a transaction's validity.
@@ -206,6 +231,13 @@ pub trait TransactionValidator: Send + Sync {
     ///
     /// This can be used to update fork specific values (timestamp).
     fn on_new_head_block(&self, _new_tip_block: &SealedBlock) {}
+
+    /// Invoked when the fullness of the pool changes, between `0.0` for an empty and `1.0` for a
+    /// full pool.
+    ///
+    /// This can be used to raise the bar for acceptance into the pool while it fills up, see
+    /// [`MinimumPriorityFee`].
+    fn on_pool_fullness(&self, _fullness: f64) {}
 }
 
 /// A valid transaction in the pool.
@@ -219,12 +251,17 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub transaction: T,
     /// The identifier for this transaction.
     pub transaction_id: TransactionId,
+    /// The identifiers of the authorities of an EIP-7702 transaction, see
+    /// [`PoolTransaction::authorities`].
+    pub authority_ids: Option<Vec<SenderId>>,
     /// Whether it is allowed to propagate the transaction.
     pub propagate: bool,
     /// Timestamp when this was added to the pool.
     pub timestamp: Instant,
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
+    /// Who submitted this transaction.
+    pub source: TransactionSource,
 }
 
 // === impl ValidPoolTransaction ===
@@ -255,6 +292,11 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction_id.sender
     }
 
+    /// Returns the internal identifiers of the authorities of an EIP-7702 transaction.
+    pub(crate) fn authority_ids(&self) -> Option<&[SenderId]> {
+        self.authority_ids.as_deref()
+    }
+
     /// Returns the internal identifier for this transaction.
     pub(crate) const fn id(&self) -> &TransactionId {
         &self.transaction_id
@@ -323,6 +365,12 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.is_eip4844()
     }
 
+    /// Whether the transaction is an EIP-7702 set code transaction.
+    #[inline]
+    pub fn is_eip7702(&self) -> bool {
+        self.transaction.is_eip7702()
+    }
+
     /// The heap allocated size of this transaction.
     pub(crate) fn size(&self) -> usize {
         self.transaction.size()
@@ -351,9 +399,11 @@ impl<T: PoolTransaction + Clone> Clone for ValidPoolTransaction<T> {
         Self {
             transaction: self.transaction.clone(),
             transaction_id: self.transaction_id,
+            authority_ids: self.authority_ids.clone(),
             propagate: self.propagate,
             timestamp: self.timestamp,
             origin: self.origin,
+            source: self.source,
         }
     }
 }
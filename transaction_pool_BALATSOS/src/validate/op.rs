@@ -0,0 +1,284 @@
+//! Transaction validator for OP-stack chains.
+//!
+//! OP-stack chains have two differences that matter for the pool:
+//!
+//! - Deposit transactions (type `0x7E`) are derived from L1 and force-included by the sequencer.
+//!   They are never gossiped, so they are never pooled. Their senders are still tracked like any
+//!   other account: the nonce and balance changes of deposits are part of the state changes of the
+//!   block, which update the sender state of the pool on every canonical state change.
+//! - Every non-deposit transaction is charged an L1 data fee on top of its L2 execution cost, which
+//!   depends on the L1 block info that is set by the first transaction of every L2 block.
+
+use crate::{
+    EthPoolTransaction, EthTransactionValidator, PoolTransaction, TransactionOrigin,
+    TransactionValidationOutcome, TransactionValidator,
+};
+use parking_lot::RwLock;
+use reth_chainspec::OptimismHardfork;
+use reth_primitives::{
+    BlockNumberOrTag, GotExpected, InvalidTransactionError, SealedBlock, TransactionSigned, U256,
+};
+use reth_provider::{BlockReaderIdExt, StateProviderFactory};
+use revm::{primitives::SpecId, L1BlockInfo};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use tracing::trace;
+
+/// Function selector of `setL1BlockValuesEcotone()`, the L1 info transaction since Ecotone.
+const L1_INFO_ECOTONE_SELECTOR: [u8; 4] = [0x44, 0x0a, 0x5e, 0x20];
+
+/// Validator for transactions of OP-stack chains.
+///
+/// This wraps an [`EthTransactionValidator`], rejects deposit transactions and additionally
+/// ensures that the sender can pay for the L1 data fee of the transaction.
+#[derive(Debug, Clone)]
+pub struct OpTransactionValidator<Client, Tx> {
+    /// The type that performs the actual validation.
+    inner: EthTransactionValidator<Client, Tx>,
+    /// The L1 block info of the current head block.
+    block_info: Arc<OpL1BlockInfo>,
+}
+
+/// The L1 block info of the head block, which determines the L1 data fee of transactions.
+#[derive(Debug, Default)]
+struct OpL1BlockInfo {
+    /// The L1 block info set by the L1 info transaction of the head block.
+    l1_block_info: RwLock<L1BlockInfo>,
+    /// Timestamp of the head block.
+    timestamp: AtomicU64,
+}
+
+impl<Client, Tx> OpTransactionValidator<Client, Tx> {
+    /// Returns the configured client
+    pub fn client(&self) -> &Client {
+        self.inner.client()
+    }
+
+    /// Returns the L1 block info of the current head block.
+    pub fn l1_block_info(&self) -> L1BlockInfo {
+        self.block_info.l1_block_info.read().clone()
+    }
+
+    /// Updates the L1 block info from the L1 info transaction of the given block.
+    ///
+    /// The L1 info transaction is always the first transaction of an L2 block. If the block has
+    /// no transactions or the calldata can't be decoded, the previous info is kept.
+    fn update_l1_block_info(&self, timestamp: u64, transactions: &[TransactionSigned]) {
+        self.block_info.timestamp.store(timestamp, Ordering::Relaxed);
+        let Some(info_tx) = transactions.first() else { return };
+        match parse_l1_block_info(info_tx.input()) {
+            Some(info) => *self.block_info.l1_block_info.write() = info,
+            None => {
+                trace!(target: "txpool", hash = ?info_tx.hash(), "failed to decode L1 block info")
+            }
+        }
+    }
+}
+
+impl<Client, Tx> OpTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Tx: EthPoolTransaction,
+{
+    /// Creates a new validator that wraps the given [`EthTransactionValidator`].
+    ///
+    /// The L1 block info is loaded from the latest block.
+    pub fn new(inner: EthTransactionValidator<Client, Tx>) -> Self {
+        let this = Self { inner, block_info: Default::default() };
+        if let Ok(Some(block)) = this.client().block_by_number_or_tag(BlockNumberOrTag::Latest) {
+            this.update_l1_block_info(block.timestamp, &block.body);
+        }
+        this
+    }
+
+    /// Validates a single transaction.
+    ///
+    /// See also [`TransactionValidator::validate_transaction`]
+    pub fn validate_one(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        if transaction.is_deposit() {
+            // deposits are only ever included by the sequencer
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidTransactionError::TxTypeNotSupported.into(),
+            )
+        }
+
+        let outcome = self.inner.validate_one(origin, transaction);
+        self.apply_l1_data_fee(outcome)
+    }
+
+    /// Validates all given transactions.
+    ///
+    /// Returns all outcomes for the given transactions in the same order.
+    ///
+    /// See also [`Self::validate_one`]
+    pub fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        transactions.into_iter().map(|(origin, tx)| self.validate_one(origin, tx)).collect()
+    }
+
+    /// Ensures that the sender of a valid transaction can also pay for its L1 data fee.
+    fn apply_l1_data_fee(
+        &self,
+        outcome: TransactionValidationOutcome<Tx>,
+    ) -> TransactionValidationOutcome<Tx> {
+        let TransactionValidationOutcome::Valid { balance, state_nonce, transaction, propagate } =
+            outcome
+        else {
+            return outcome
+        };
+
+        let l1_data_fee = self.l1_data_fee(transaction.transaction());
+        let cost = transaction.transaction().cost().saturating_add(l1_data_fee);
+        if cost > balance {
+            return TransactionValidationOutcome::Invalid(
+                transaction.into_transaction(),
+                InvalidTransactionError::InsufficientFunds(
+                    GotExpected { got: balance, expected: cost }.into(),
+                )
+                .into(),
+            )
+        }
+
+        TransactionValidationOutcome::Valid { balance, state_nonce, transaction, propagate }
+    }
+
+    /// Returns the L1 data fee of the transaction, based on the L1 block info of the head block.
+    fn l1_data_fee(&self, transaction: &Tx) -> U256 {
+        let encoded = transaction.to_recovered_transaction().envelope_encoded();
+        let spec_id = self.l1_spec_id(self.block_info.timestamp.load(Ordering::Relaxed));
+        self.block_info.l1_block_info.read().calculate_tx_l1_cost(&encoded, spec_id)
+    }
+
+    /// Returns the [`SpecId`] that determines the L1 data fee function at the given timestamp.
+    fn l1_spec_id(&self, timestamp: u64) -> SpecId {
+        let chain_spec = self.inner.chain_spec();
+        if chain_spec.is_fork_active_at_timestamp(OptimismHardfork::Fjord, timestamp) {
+            SpecId::FJORD
+        } else if chain_spec.is_fork_active_at_timestamp(OptimismHardfork::Ecotone, timestamp) {
+            SpecId::ECOTONE
+        } else if chain_spec.is_fork_active_at_timestamp(OptimismHardfork::Regolith, timestamp) {
+            SpecId::REGOLITH
+        } else {
+            SpecId::BEDROCK
+        }
+    }
+}
+
+impl<Client, Tx> TransactionValidator for OpTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Tx: EthPoolTransaction,
+{
+    type Transaction = Tx;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_one(origin, transaction)
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        self.validate_all(transactions)
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.inner.on_new_head_block(new_tip_block);
+        self.update_l1_block_info(new_tip_block.timestamp, &new_tip_block.body);
+    }
+
+    fn on_pool_fullness(&self, fullness: f64) {
+        self.inner.on_pool_fullness(fullness)
+    }
+}
+
+/// Decodes the [`L1BlockInfo`] from the calldata of an L1 info transaction.
+///
+/// Supports both the Bedrock `setL1BlockValues` and the Ecotone `setL1BlockValuesEcotone` format.
+fn parse_l1_block_info(input: &[u8]) -> Option<L1BlockInfo> {
+    let (selector, data) = (input.get(..4)?, input.get(4..)?);
+
+    if selector == L1_INFO_ECOTONE_SELECTOR {
+        // packed: baseFeeScalar (u32), blobBaseFeeScalar (u32), sequenceNumber (u64),
+        // timestamp (u64), number (u64), basefee (u256), blobBaseFee (u256), ...
+        if data.len() < 96 {
+            return None
+        }
+        let base_fee_scalar = U256::from_be_slice(&data[..4]);
+        let blob_base_fee_scalar = U256::from_be_slice(&data[4..8]);
+        return Some(L1BlockInfo {
+            l1_base_fee: U256::from_be_slice(&data[32..64]),
+            l1_base_fee_scalar: base_fee_scalar,
+            l1_blob_base_fee: Some(U256::from_be_slice(&data[64..96])),
+            l1_blob_base_fee_scalar: Some(blob_base_fee_scalar),
+            l1_fee_overhead: None,
+            empty_scalars: base_fee_scalar.is_zero() && blob_base_fee_scalar.is_zero(),
+        })
+    }
+
+    // abi encoded: number, timestamp, basefee, hash, sequenceNumber, batcherHash, l1FeeOverhead,
+    // l1FeeScalar
+    if data.len() < 256 {
+        return None
+    }
+    Some(L1BlockInfo {
+        l1_base_fee: U256::from_be_slice(&data[64..96]),
+        l1_fee_overhead: Some(U256::from_be_slice(&data[192..224])),
+        l1_base_fee_scalar: U256::from_be_slice(&data[224..256]),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bedrock_l1_block_info() {
+        let mut input = vec![0x01, 0x5d, 0x8e, 0xb9];
+        let words = [1u64, 2, 30, 4, 5, 6, 188, 684_000];
+        for word in words {
+            input.extend_from_slice(&U256::from(word).to_be_bytes::<32>());
+        }
+
+        let info = parse_l1_block_info(&input).unwrap();
+        assert_eq!(info.l1_base_fee, U256::from(30));
+        assert_eq!(info.l1_fee_overhead, Some(U256::from(188)));
+        assert_eq!(info.l1_base_fee_scalar, U256::from(684_000));
+        assert_eq!(info.l1_blob_base_fee, None);
+
+        // truncated calldata can't be decoded
+        assert!(parse_l1_block_info(&input[..input.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn parse_ecotone_l1_block_info() {
+        let mut input = L1_INFO_ECOTONE_SELECTOR.to_vec();
+        input.extend_from_slice(&1_368u32.to_be_bytes());
+        input.extend_from_slice(&810_949u32.to_be_bytes());
+        input.extend_from_slice(&[0; 24]);
+        input.extend_from_slice(&U256::from(7).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        input.extend_from_slice(&[0; 64]);
+
+        let info = parse_l1_block_info(&input).unwrap();
+        assert_eq!(info.l1_base_fee, U256::from(7));
+        assert_eq!(info.l1_base_fee_scalar, U256::from(1_368));
+        assert_eq!(info.l1_blob_base_fee, Some(U256::from(1)));
+        assert_eq!(info.l1_blob_base_fee_scalar, Some(U256::from(810_949)));
+        assert!(!info.empty_scalars);
+    }
+}
@@ -0,0 +1,143 @@
+//! Minimum priority fee for acceptance into the pool.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// The default fullness of the pool above which a [`DynamicPriorityFee`] starts to rise.
+pub const DEFAULT_PRIORITY_FEE_FULLNESS_THRESHOLD: f64 = 0.5;
+
+/// The minimum priority fee non-local transactions must pay to be accepted into the pool.
+///
+/// Transactions below the minimum are rejected with
+/// [`PriorityFeeBelowMinimum`](crate::error::InvalidPoolTransactionError::PriorityFeeBelowMinimum).
+#[derive(Debug, Clone)]
+pub enum MinimumPriorityFee {
+    /// A fixed minimum priority fee.
+    Static(u128),
+    /// A minimum priority fee that rises with the fullness of the pool.
+    Dynamic(DynamicPriorityFee),
+}
+
+impl MinimumPriorityFee {
+    /// Returns the currently enforced minimum priority fee.
+    pub fn current(&self) -> u128 {
+        match self {
+            Self::Static(fee) => *fee,
+            Self::Dynamic(fee) => fee.current(),
+        }
+    }
+
+    /// Updates the fullness of the pool, this is a no-op for a static minimum.
+    ///
+    /// See [`DynamicPriorityFee::set_pool_fullness`].
+    pub fn set_pool_fullness(&self, fullness: f64) {
+        if let Self::Dynamic(fee) = self {
+            fee.set_pool_fullness(fullness)
+        }
+    }
+}
+
+impl From<u128> for MinimumPriorityFee {
+    fn from(fee: u128) -> Self {
+        Self::Static(fee)
+    }
+}
+
+impl From<DynamicPriorityFee> for MinimumPriorityFee {
+    fn from(fee: DynamicPriorityFee) -> Self {
+        Self::Dynamic(fee)
+    }
+}
+
+/// A minimum priority fee that is computed from the fullness of the pool.
+///
+/// While the pool is at most filled up to the threshold, the minimum is the `base` fee. Above the
+/// threshold, the minimum rises linearly up to the `max` fee once the pool is full.
+///
+/// Clones share the tracked fullness of the pool.
+#[derive(Debug, Clone)]
+pub struct DynamicPriorityFee {
+    /// The minimum priority fee while the pool is not filled above the threshold.
+    base: u128,
+    /// The minimum priority fee when the pool is full.
+    max: u128,
+    /// The fullness of the pool above which the minimum starts to rise.
+    threshold: f64,
+    /// The bits of the last reported fullness of the pool.
+    fullness: Arc<AtomicU64>,
+}
+
+impl DynamicPriorityFee {
+    /// Creates a new minimum that rises from `base` to `max` once the pool is filled above
+    /// [`DEFAULT_PRIORITY_FEE_FULLNESS_THRESHOLD`].
+    pub fn new(base: u128, max: u128) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            threshold: DEFAULT_PRIORITY_FEE_FULLNESS_THRESHOLD,
+            fullness: Arc::new(AtomicU64::new(0f64.to_bits())),
+        }
+    }
+
+    /// Sets the fullness of the pool, between `0.0` and `1.0`, above which the minimum starts to
+    /// rise.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the last reported fullness of the pool.
+    pub fn pool_fullness(&self) -> f64 {
+        f64::from_bits(self.fullness.load(Ordering::Relaxed))
+    }
+
+    /// Updates the fullness of the pool, between `0.0` for an empty and `1.0` for a full pool.
+    ///
+    /// See [`PoolConfig::fullness`](crate::PoolConfig::fullness).
+    pub fn set_pool_fullness(&self, fullness: f64) {
+        self.fullness.store(fullness.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns the minimum priority fee for the last reported fullness of the pool.
+    pub fn current(&self) -> u128 {
+        let fullness = self.pool_fullness();
+        if fullness <= self.threshold || self.threshold >= 1.0 {
+            return self.base
+        }
+        let ratio = (fullness - self.threshold) / (1.0 - self.threshold);
+        self.base + ((self.max - self.base) as f64 * ratio) as u128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic_minimum_rises_with_fullness() {
+        let fee = DynamicPriorityFee::new(1_000, 11_000);
+        let minimum = MinimumPriorityFee::from(fee.clone());
+        assert_eq!(minimum.current(), 1_000);
+
+        minimum.set_pool_fullness(0.5);
+        assert_eq!(fee.current(), 1_000);
+
+        minimum.set_pool_fullness(0.75);
+        assert_eq!(fee.current(), 6_000);
+
+        minimum.set_pool_fullness(2.0);
+        assert_eq!(fee.current(), 11_000);
+
+        minimum.set_pool_fullness(0.1);
+        assert_eq!(minimum.current(), 1_000);
+    }
+
+    #[test]
+    fn static_minimum_ignores_fullness() {
+        let minimum = MinimumPriorityFee::from(5u128);
+        minimum.set_pool_fullness(1.0);
+        assert_eq!(minimum.current(), 5);
+    }
+}
@@ -0,0 +1,154 @@
+//! Simulation of transactions before they are admitted to the pool.
+
+use reth_primitives::{Bytes, TransactionSignedEcRecovered};
+use reth_provider::{StateProvider, StateProviderBox};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    time::Duration,
+};
+use tracing::trace;
+
+/// The default amount of gas a single simulation may consume.
+pub const DEFAULT_SIMULATION_GAS_LIMIT: u64 = 30_000_000;
+
+/// The default amount of time a single simulation may take.
+pub const DEFAULT_SIMULATION_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The default number of simulations that may run concurrently.
+pub const DEFAULT_MAX_CONCURRENT_SIMULATIONS: usize = 4;
+
+/// Executes transactions against the latest state before they are accepted into the pool.
+///
+/// If configured on the [`EthTransactionValidator`](crate::EthTransactionValidator), all local
+/// transactions are simulated after they passed validation and rejected if they revert, so users
+/// don't burn fees on transactions that are guaranteed to fail.
+///
+/// A simulation is best effort: if it can't be completed within the given [`SimulationBudget`],
+/// the transaction is admitted. Simulations run on a bounded pool of threads, so the validator
+/// stops waiting once the time budget is exhausted and cancels the simulation.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PreInclusionSimulator: fmt::Debug + Send + Sync {
+    /// Executes the transaction on top of the given state.
+    ///
+    /// Implementations should abort with [`SimulationOutcome::Inconclusive`] once the budget is
+    /// exhausted or the simulation is cancelled, so that the state is released.
+    fn simulate(
+        &self,
+        state: &dyn StateProvider,
+        transaction: &TransactionSignedEcRecovered,
+        budget: SimulationBudget,
+        cancellation: &SimulationCancellation,
+    ) -> SimulationOutcome;
+}
+
+/// Signals a running simulation that the validator stopped waiting for its outcome.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationCancellation(Arc<AtomicBool>);
+
+impl SimulationCancellation {
+    /// Returns `true` if the simulation was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Cancels the simulation.
+    pub(crate) fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+}
+
+/// Runs a [`PreInclusionSimulator`] on a bounded number of threads.
+#[derive(Debug)]
+pub(crate) struct SimulationExecutor {
+    simulator: Arc<dyn PreInclusionSimulator>,
+    /// The threads simulations run on, at most one simulation per thread.
+    pool: rayon::ThreadPool,
+}
+
+impl SimulationExecutor {
+    /// Creates a new executor that runs at most `max_concurrent` simulations at a time.
+    pub(crate) fn new(
+        simulator: Arc<dyn PreInclusionSimulator>,
+        max_concurrent: usize,
+    ) -> Result<Self, rayon::ThreadPoolBuildError> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(max_concurrent.max(1))
+            .thread_name(|idx| format!("txpool-simulation-{idx}"))
+            .build()?;
+        Ok(Self { simulator, pool })
+    }
+
+    /// Simulates the transaction against the given state and waits at most for the time budget.
+    ///
+    /// Returns [`SimulationOutcome::Inconclusive`] and cancels the simulation if it didn't complete
+    /// in time.
+    pub(crate) fn simulate(
+        &self,
+        state: StateProviderBox,
+        transaction: TransactionSignedEcRecovered,
+        budget: SimulationBudget,
+    ) -> SimulationOutcome {
+        let cancellation = SimulationCancellation::default();
+        let (tx, rx) = mpsc::sync_channel(1);
+        let simulator = Arc::clone(&self.simulator);
+        let cancelled = cancellation.clone();
+        self.pool.spawn(move || {
+            // the validator may have stopped waiting while the simulation was queued
+            if cancelled.is_cancelled() {
+                return
+            }
+            let _ = tx.send(simulator.simulate(&*state, &transaction, budget, &cancelled));
+        });
+
+        rx.recv_timeout(budget.timeout).unwrap_or_else(|_| {
+            cancellation.cancel();
+            trace!(target: "txpool", "simulation timed out");
+            SimulationOutcome::Inconclusive
+        })
+    }
+}
+
+/// Bounds the resources a single simulation may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationBudget {
+    /// Max amount of gas the simulation may consume.
+    ///
+    /// The validator caps this at the gas limit of the simulated transaction.
+    pub gas_limit: u64,
+    /// Max amount of time the validator waits for the simulation.
+    pub timeout: Duration,
+}
+
+impl Default for SimulationBudget {
+    fn default() -> Self {
+        Self { gas_limit: DEFAULT_SIMULATION_GAS_LIMIT, timeout: DEFAULT_SIMULATION_TIMEOUT }
+    }
+}
+
+/// The outcome of a [`PreInclusionSimulator::simulate`] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationOutcome {
+    /// The transaction executed successfully.
+    Success {
+        /// The gas used by the transaction.
+        gas_used: u64,
+    },
+    /// The transaction reverted or halted.
+    Reverted {
+        /// The revert data returned by the transaction.
+        output: Bytes,
+    },
+    /// The simulation couldn't be completed, e.g. because the budget was exhausted.
+    Inconclusive,
+}
+
+impl SimulationOutcome {
+    /// Returns `true` if the transaction reverted.
+    pub const fn is_reverted(&self) -> bool {
+        matches!(self, Self::Reverted { .. })
+    }
+}
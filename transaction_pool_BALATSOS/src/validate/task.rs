@@ -223,4 +223,8 @@ where
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.validator.on_new_head_block(new_tip_block)
     }
+
+    fn on_pool_fullness(&self, fullness: f64) {
+        self.validator.on_pool_fullness(fullness)
+    }
 }
@@ -28,6 +28,10 @@
 //      - Creates a mock transaction and subscribes to new transaction events.
 //      - Adds the transaction to the pool.
 //      - Verifies that the transaction is added to the pool but not propagated.
+// 5) txpool_lifecycle_listeners:
+//      - Creates a transaction pool and subscribes two listeners to the lifecycle events.
+//      - Adds a transaction to the pool and removes it again.
+//      - Verifies that both listeners receive the added and the dropped event with its reason.
 //
 
 
@@ -35,8 +39,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    DropReason, FullTransactionEvent, PoolLifecycleEvent, SubPool, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -122,3 +126,31 @@ async fn txpool_listener_new_propagate_only() {
     })
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_lifecycle_listeners() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = transaction.transaction.get_hash();
+
+    let mut listeners = [txpool.lifecycle_event_listener(), txpool.lifecycle_event_listener()];
+
+    let result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert_matches!(result, Ok(_));
+    assert_eq!(txpool.remove_transactions(vec![hash]).len(), 1);
+
+    for listener in &mut listeners {
+        assert_matches!(
+            listener.next().await,
+            Some(PoolLifecycleEvent::Added { transaction, subpool: SubPool::Pending })
+                if *transaction.hash() == hash
+        );
+        assert_matches!(
+            listener.next().await,
+            Some(PoolLifecycleEvent::Dropped { tx_hash, reason: DropReason::Removed })
+                if tx_hash == hash
+        );
+    }
+}
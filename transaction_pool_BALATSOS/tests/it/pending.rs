@@ -17,10 +17,15 @@
 // 5. Adds another mock EIP-1559 transaction and performs the same checks.
 // 
 // The assertions ensure that the transaction pool correctly handles the addition and retrieval of transactions.
+//
+// A second test verifies that a batch of transactions is added with `add_transactions_batch` and
+// that the results are returned in the order of the batch.
 
 
 use assert_matches::assert_matches;
+use futures_util::StreamExt;
 use reth_transaction_pool::{
+    error::PoolErrorKind,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
     TransactionOrigin, TransactionPool,
 };
@@ -58,3 +63,53 @@ async fn txpool_new_pending_txs() {
     assert_matches!(added_result, Ok(hash) if hash == transaction.transaction.get_hash());
     assert_matches!(best_txns.next(), Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
 }
+
+// This test verifies that the best transactions stream yields the pending transactions and then
+// waits for new transactions that are promoted to the pending pool.
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_best_transactions_stream() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let transaction = mock_tx_factory.create_eip1559();
+    txpool
+        .add_transaction(TransactionOrigin::External, transaction.transaction.clone())
+        .await
+        .unwrap();
+
+    // the stream first yields the transaction that is already pending
+    let mut stream = txpool.best_transactions_stream();
+    assert_matches!(stream.next().await, Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
+
+    // the stream does not end, but yields the next promoted transaction
+    let transaction = mock_tx_factory.create_eip1559();
+    txpool
+        .add_transaction(TransactionOrigin::External, transaction.transaction.clone())
+        .await
+        .unwrap();
+    assert_matches!(stream.next().await, Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
+}
+
+// This test verifies that a batch of transactions is inserted at once and that the results are
+// returned in the order of the batch.
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_add_transactions_batch() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let first = mock_tx_factory.create_eip1559().transaction;
+    let second = mock_tx_factory.create_eip1559().transaction;
+
+    let results = txpool
+        .add_transactions_batch(
+            TransactionOrigin::External,
+            vec![first.clone(), second.clone(), first.clone()],
+        )
+        .await;
+    assert_eq!(results.len(), 3);
+    assert_matches!(results[0], Ok(hash) if hash == first.get_hash());
+    assert_matches!(results[1], Ok(hash) if hash == second.get_hash());
+    assert_matches!(&results[2], Err(err) if matches!(err.kind, PoolErrorKind::AlreadyImported));
+
+    assert_eq!(txpool.pending_transactions().len(), 2);
+}